@@ -0,0 +1,63 @@
+use crate::ast::{Ast, NodeId};
+use proc_macro2::{Ident, Span};
+use syn::ext::IdentExt;
+
+/// Name of the placeholder alias (`_`) - it discards the value it is bound to.
+pub const PLACEHOLDER_ALIAS: &str = "_";
+
+/// Alias declaration.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Alias {
+    id: NodeId,
+    ident: Ident,
+    docs: Option<String>,
+}
+
+impl Ast for Alias {
+    fn id(&self) -> NodeId {
+        self.id
+    }
+    fn span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
+impl Alias {
+    /// Creates a new [`Alias`] with the given identifier.
+    pub fn new(id: NodeId, ident: Ident) -> Self {
+        Self {
+            id,
+            ident,
+            docs: None,
+        }
+    }
+
+    /// Attaches the text of the doc-comments preceding the alias definition.
+    pub fn with_docs(mut self, docs: Option<String>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    /// Reads the identifier.
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    /// Reads the name of the alias.
+    ///
+    /// Raw identifiers are named without the `r#` prefix - `r#foo` and `foo` are the same
+    /// identifier.
+    pub fn name(&self) -> String {
+        self.ident.unraw().to_string()
+    }
+
+    /// Whether the alias is a placeholder (`_`) discarding the value it is bound to.
+    pub fn is_placeholder(&self) -> bool {
+        self.ident == PLACEHOLDER_ALIAS
+    }
+
+    /// Reads the text of the doc-comments of the alias (if any).
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+}
@@ -0,0 +1,151 @@
+use crate::ast::{AliasSpecItem, Ast, Expr, NodeId, TupleAliasSpecItem};
+use crate::core::Options;
+use proc_macro2::Span;
+use std::rc::Rc;
+use syn::Attribute;
+
+/// An entry of [`AliasSpec`] - a single alias or a tuple destructuring assignment.
+#[derive(Debug, Clone)]
+pub enum AliasSpecEntry {
+    Item(Rc<AliasSpecItem>),
+    Tuple(Rc<TupleAliasSpecItem>),
+}
+
+impl Ast for AliasSpecEntry {
+    fn id(&self) -> NodeId {
+        match self {
+            AliasSpecEntry::Item(item) => item.id(),
+            AliasSpecEntry::Tuple(item) => item.id(),
+        }
+    }
+    fn span(&self) -> Span {
+        match self {
+            AliasSpecEntry::Item(item) => item.span(),
+            AliasSpecEntry::Tuple(item) => item.span(),
+        }
+    }
+}
+
+/// Specification of aliases provided to the [`compose_idents`] macro.
+#[derive(Debug, Clone)]
+pub struct AliasSpec {
+    id: NodeId,
+    entries: Vec<AliasSpecEntry>,
+    is_comma_used: Option<bool>,
+    semicolon_span: Option<Span>,
+    options: Options,
+    apply_attrs: Vec<Attribute>,
+    when: Option<Rc<Expr>>,
+}
+
+impl Ast for AliasSpec {
+    fn id(&self) -> NodeId {
+        self.id
+    }
+    fn span(&self) -> Span {
+        self.entries
+            .first()
+            .map(|entry| entry.span())
+            .unwrap_or_else(Span::call_site)
+    }
+}
+
+impl AliasSpec {
+    /// Creates a new [`AliasSpec`] with the given items, separator information and options.
+    pub fn new(
+        id: NodeId,
+        items: Vec<Rc<AliasSpecItem>>,
+        is_comma_used: Option<bool>,
+        options: Options,
+    ) -> Self {
+        let entries = items.into_iter().map(AliasSpecEntry::Item).collect();
+        Self::from_entries(id, entries, is_comma_used, options)
+    }
+
+    /// Creates a new [`AliasSpec`] with the given entries, separator information and options.
+    pub fn from_entries(
+        id: NodeId,
+        entries: Vec<AliasSpecEntry>,
+        is_comma_used: Option<bool>,
+        options: Options,
+    ) -> Self {
+        Self {
+            id,
+            entries,
+            is_comma_used,
+            semicolon_span: None,
+            options,
+            apply_attrs: vec![],
+            when: None,
+        }
+    }
+
+    /// Records the span of the first (deprecated) semicolon separator.
+    pub fn with_semicolon_span(mut self, span: Option<Span>) -> Self {
+        self.semicolon_span = span;
+        self
+    }
+
+    /// Sets the attributes specified with `apply_attrs(...)` directive.
+    pub fn with_apply_attrs(mut self, attrs: Vec<Attribute>) -> Self {
+        self.apply_attrs = attrs;
+        self
+    }
+
+    /// Sets the condition specified with `when(...)` directive.
+    pub fn with_when(mut self, when: Option<Rc<Expr>>) -> Self {
+        self.when = when;
+        self
+    }
+
+    /// Appends the entries of another alias specification - the aliases could be interleaved with
+    /// the loops, so they are parsed in chunks.
+    pub fn append(&mut self, other: AliasSpec) {
+        self.entries.extend(other.entries);
+        self.is_comma_used = self.is_comma_used.or(other.is_comma_used);
+        self.semicolon_span = self.semicolon_span.or(other.semicolon_span);
+    }
+
+    /// Reads the entries of the alias specification as they were written.
+    pub fn entries(&self) -> &[AliasSpecEntry] {
+        &self.entries
+    }
+
+    /// Reads the individual items in the alias specification.
+    ///
+    /// Tuple destructurings are desugared into items during the expand phase - they are not
+    /// included.
+    pub fn items(&self) -> impl DoubleEndedIterator<Item = &Rc<AliasSpecItem>> {
+        self.entries.iter().filter_map(|entry| match entry {
+            AliasSpecEntry::Item(item) => Some(item),
+            AliasSpecEntry::Tuple(_) => None,
+        })
+    }
+
+    /// Whether a comma is used as a separator.
+    pub fn is_comma_used(&self) -> Option<bool> {
+        self.is_comma_used
+    }
+
+    /// Span of the first semicolon separator if semicolons are used.
+    pub fn semicolon_span(&self) -> Option<Span> {
+        self.semicolon_span
+    }
+
+    /// Options specified with `options(...)` directive.
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Attributes specified with `apply_attrs(...)` directive - they are stamped onto each
+    /// top-level item of the generated code.
+    pub fn apply_attrs(&self) -> &[Attribute] {
+        &self.apply_attrs
+    }
+
+    /// Condition specified with `when(...)` directive - the code is generated only for the
+    /// combinations of loop values it evaluates to `true` for.
+    pub fn when(&self) -> Option<&Rc<Expr>> {
+        self.when.as_ref()
+    }
+}
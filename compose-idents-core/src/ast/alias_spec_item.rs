@@ -0,0 +1,104 @@
+use crate::ast::{Alias, AliasValue, Ast, LoopSourceValue, NodeId, Tuple};
+use crate::core::Type;
+use proc_macro2::Span;
+use std::rc::Rc;
+
+/// A single alias specification.
+#[derive(Debug)]
+pub struct AliasSpecItem {
+    id: NodeId,
+    alias: Rc<Alias>,
+    type_annotation: Option<Type>,
+    value: Rc<AliasValue>,
+    is_private: bool,
+}
+
+impl Ast for AliasSpecItem {
+    fn id(&self) -> NodeId {
+        self.id
+    }
+    fn span(&self) -> Span {
+        self.alias.span()
+    }
+}
+
+impl AliasSpecItem {
+    /// Creates a new [`AliasSpecItem`] with the given alias, optional type annotation and value.
+    pub fn new(
+        id: NodeId,
+        alias: Rc<Alias>,
+        type_annotation: Option<Type>,
+        value: Rc<AliasValue>,
+    ) -> Self {
+        Self {
+            id,
+            alias,
+            type_annotation,
+            value,
+            is_private: false,
+        }
+    }
+
+    /// Marks the alias as private - available to other aliases, but not substituted into the
+    /// code block.
+    pub fn with_private(mut self, is_private: bool) -> Self {
+        self.is_private = is_private;
+        self
+    }
+
+    /// Reads the alias identifier.
+    pub fn alias(&self) -> Rc<Alias> {
+        self.alias.clone()
+    }
+
+    /// Reads the explicit type annotation of the alias (if any).
+    pub fn type_annotation(&self) -> Option<&Type> {
+        self.type_annotation.as_ref()
+    }
+
+    /// Reads the alias value.
+    pub fn value(&self) -> Rc<AliasValue> {
+        self.value.clone()
+    }
+
+    /// Whether the alias is private (defined with `let`).
+    pub fn is_private(&self) -> bool {
+        self.is_private
+    }
+}
+
+/// A tuple destructuring assignment - `(a, b) = (foo, bar)`.
+///
+/// It is desugared into an [`AliasSpecItem`] per alias of the tuple during the expand phase.
+#[derive(Debug)]
+pub struct TupleAliasSpecItem {
+    id: NodeId,
+    aliases: Tuple<Alias>,
+    value: LoopSourceValue,
+}
+
+impl Ast for TupleAliasSpecItem {
+    fn id(&self) -> NodeId {
+        self.id
+    }
+    fn span(&self) -> Span {
+        self.aliases.span()
+    }
+}
+
+impl TupleAliasSpecItem {
+    /// Creates a new [`TupleAliasSpecItem`] with the given tuple of aliases and value.
+    pub fn new(id: NodeId, aliases: Tuple<Alias>, value: LoopSourceValue) -> Self {
+        Self { id, aliases, value }
+    }
+
+    /// Reads the tuple of aliases.
+    pub fn aliases(&self) -> &Tuple<Alias> {
+        &self.aliases
+    }
+
+    /// Reads the destructured value.
+    pub fn value(&self) -> &LoopSourceValue {
+        &self.value
+    }
+}
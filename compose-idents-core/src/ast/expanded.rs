@@ -23,21 +23,27 @@ impl BlockRewrite {
 }
 
 /// Simplified AST.
+///
+/// Holds a block-rewrite per combination of loop values and block - the block-rewrites of the same
+/// combination go in a row and share the same alias-spec.
 #[derive(Debug, Clone)]
 pub struct ExpandedAST {
     id: NodeId,
     blocks: Vec<BlockRewrite>,
+    num_blocks: usize,
 }
 
 impl ExpandedAST {
-    pub fn new(id: NodeId, invocations: Vec<BlockRewrite>) -> Self {
+    pub fn new(id: NodeId, invocations: Vec<BlockRewrite>, num_blocks: usize) -> Self {
         Self {
             id,
             blocks: invocations,
+            num_blocks,
         }
     }
-    pub fn block_rewrite_items(&self) -> &[BlockRewrite] {
-        &self.blocks
+    /// Iterates over the block-rewrites grouped by combinations of loop values.
+    pub fn combinations(&self) -> std::slice::Chunks<'_, BlockRewrite> {
+        self.blocks.chunks(self.num_blocks)
     }
 }
 
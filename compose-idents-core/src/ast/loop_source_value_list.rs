@@ -0,0 +1,218 @@
+//! Provides [`LoopSourceValueList`] - a list of source values of a loop.
+use crate::ast::{Ast, Expr, NodeId, Tuple};
+use proc_macro2::{Ident, Span};
+use std::rc::Rc;
+use syn::LitStr;
+
+/// A list of source values of a loop.
+#[derive(Debug, Clone)]
+pub enum LoopSourceValue {
+    Value(Rc<Expr>),
+    Tuple(Tuple<Expr>),
+}
+
+impl LoopSourceValue {
+    /// Creates a new [`LoopSourceValue`] from a singular value.
+    pub fn from_value(expr: Expr) -> Self {
+        Self::Value(Rc::new(expr))
+    }
+    /// Creates a new [`LoopSourceValue`] from a tuple.
+    pub fn from_tuple(tuple: Tuple<Expr>) -> Self {
+        Self::Tuple(tuple)
+    }
+}
+
+impl Ast for LoopSourceValue {
+    fn id(&self) -> NodeId {
+        match self {
+            LoopSourceValue::Value(expr) => expr.id(),
+            LoopSourceValue::Tuple(tuple) => tuple.id(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            LoopSourceValue::Value(expr) => expr.span(),
+            LoopSourceValue::Tuple(tuple) => tuple.span(),
+        }
+    }
+}
+
+/// A list of source values for a loop.
+///
+/// In an expression like `for (a, b) in [(1, 2), (3, 4)]`, the value list
+/// would be `[(1, 2), (3, 4)]`. In an expression like `for w in widths` the list is given by
+/// a reference to an alias bound to a list - it is resolved during the expand phase. In an
+/// expression like `for (a, b) in zip([x, y], [1, 2])` the values are tuples of the elements of
+/// the zipped lists - they are paired during the expand phase as well. In an expression like
+/// `for part in split("a,b", ",")` the values are the fragments of the string. In an expression
+/// like `for (i, name) in enumerate([foo, bar])` the values are the elements of the enumerated
+/// list paired with their indices. In an expression like `for t in [u8, u16] + [f32, f64]` the
+/// values are the ones of the concatenated lists. In an expression like `for t in unique([...])`
+/// the values are the ones of the wrapped list without duplicates.
+#[derive(Debug, Clone)]
+pub struct LoopSourceValueList {
+    id: NodeId,
+    values: Vec<LoopSourceValue>,
+    alias: Option<Ident>,
+    zipped: Vec<LoopSourceValueList>,
+    split: Option<(LitStr, LitStr)>,
+    enumerated: Option<Box<LoopSourceValueList>>,
+    chained: Vec<LoopSourceValueList>,
+    deduplicated: Option<Box<LoopSourceValueList>>,
+    span: Span,
+}
+
+impl LoopSourceValueList {
+    /// Creates a new [`LoopSourceValueList`] with the given values.
+    pub fn new(id: NodeId, values: Vec<LoopSourceValue>, span: Span) -> Self {
+        Self {
+            id,
+            values,
+            alias: None,
+            zipped: vec![],
+            split: None,
+            enumerated: None,
+            chained: vec![],
+            deduplicated: None,
+            span,
+        }
+    }
+
+    /// Creates a new [`LoopSourceValueList`] referencing an alias bound to a list.
+    pub fn from_alias(id: NodeId, alias: Ident) -> Self {
+        Self {
+            id,
+            values: vec![],
+            span: alias.span(),
+            alias: Some(alias),
+            zipped: vec![],
+            split: None,
+            enumerated: None,
+            chained: vec![],
+            deduplicated: None,
+        }
+    }
+
+    /// Creates a new [`LoopSourceValueList`] pairing the elements of the given lists positionally.
+    pub fn from_zip(id: NodeId, lists: Vec<LoopSourceValueList>, span: Span) -> Self {
+        Self {
+            id,
+            values: vec![],
+            alias: None,
+            zipped: lists,
+            split: None,
+            enumerated: None,
+            chained: vec![],
+            deduplicated: None,
+            span,
+        }
+    }
+
+    /// Creates a new [`LoopSourceValueList`] of the fragments of the string split by the separator.
+    pub fn from_split(id: NodeId, string: LitStr, separator: LitStr, span: Span) -> Self {
+        Self {
+            id,
+            values: vec![],
+            alias: None,
+            zipped: vec![],
+            split: Some((string, separator)),
+            enumerated: None,
+            chained: vec![],
+            deduplicated: None,
+            span,
+        }
+    }
+
+    /// Creates a new [`LoopSourceValueList`] pairing the elements of the given list with their
+    /// indices.
+    pub fn from_enumerate(id: NodeId, list: LoopSourceValueList, span: Span) -> Self {
+        Self {
+            id,
+            values: vec![],
+            alias: None,
+            zipped: vec![],
+            split: None,
+            enumerated: Some(Box::new(list)),
+            chained: vec![],
+            deduplicated: None,
+            span,
+        }
+    }
+
+    /// Creates a new [`LoopSourceValueList`] concatenating the given lists - `[...] + [...]`.
+    pub fn from_chain(id: NodeId, lists: Vec<LoopSourceValueList>, span: Span) -> Self {
+        Self {
+            id,
+            values: vec![],
+            alias: None,
+            zipped: vec![],
+            split: None,
+            enumerated: None,
+            chained: lists,
+            deduplicated: None,
+            span,
+        }
+    }
+
+    /// Creates a new [`LoopSourceValueList`] removing duplicate values of the given list.
+    pub fn from_unique(id: NodeId, list: LoopSourceValueList, span: Span) -> Self {
+        Self {
+            id,
+            values: vec![],
+            alias: None,
+            zipped: vec![],
+            split: None,
+            enumerated: None,
+            chained: vec![],
+            deduplicated: Some(Box::new(list)),
+            span,
+        }
+    }
+
+    /// Reads the source values - empty if the list is given by an alias, by `zip(...)`,
+    /// `split(...)`, `enumerate(...)`, `unique(...)` or by concatenation of lists.
+    pub fn values(&self) -> &[LoopSourceValue] {
+        &self.values
+    }
+
+    /// Reads the alias the list is given by if any.
+    pub fn alias(&self) -> Option<&Ident> {
+        self.alias.as_ref()
+    }
+
+    /// Reads the lists zipped by `zip(...)` - empty for the other kinds of lists.
+    pub fn zipped(&self) -> &[LoopSourceValueList] {
+        &self.zipped
+    }
+
+    /// Reads the string and the separator of `split(...)` if the list is given by it.
+    pub fn split(&self) -> Option<&(LitStr, LitStr)> {
+        self.split.as_ref()
+    }
+
+    /// Reads the list enumerated by `enumerate(...)` if the list is given by it.
+    pub fn enumerated(&self) -> Option<&LoopSourceValueList> {
+        self.enumerated.as_deref()
+    }
+
+    /// Reads the list deduplicated by `unique(...)` if the list is given by it.
+    pub fn deduplicated(&self) -> Option<&LoopSourceValueList> {
+        self.deduplicated.as_deref()
+    }
+
+    /// Reads the concatenated lists - empty for the other kinds of lists.
+    pub fn chained(&self) -> &[LoopSourceValueList] {
+        &self.chained
+    }
+}
+
+impl Ast for LoopSourceValueList {
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
@@ -1,6 +1,6 @@
 //! Provides [`LoopSpecItem`] - a single for-loop.
 
-use crate::ast::{Ast, LoopAlias, LoopSourceValueList, NodeId};
+use crate::ast::{Ast, Expr, LoopAlias, LoopSourceValueList, NodeId};
 use proc_macro2::Span;
 use std::rc::Rc;
 
@@ -10,6 +10,7 @@ pub struct LoopSpecItem {
     id: NodeId,
     alias: Rc<LoopAlias>,
     list: Rc<LoopSourceValueList>,
+    filter: Option<Rc<Expr>>,
     span: Span,
 }
 
@@ -24,10 +25,17 @@ impl LoopSpecItem {
             id,
             alias,
             list,
+            filter: None,
             span,
         }
     }
 
+    /// Sets the filter condition of the loop - `for x in [...] if <expr>`.
+    pub fn with_filter(mut self, filter: Option<Rc<Expr>>) -> Self {
+        self.filter = filter;
+        self
+    }
+
     /// Reads the loop's alias.
     pub fn alias(&self) -> Rc<LoopAlias> {
         self.alias.clone()
@@ -37,6 +45,11 @@ impl LoopSpecItem {
     pub fn list(&self) -> Rc<LoopSourceValueList> {
         self.list.clone()
     }
+
+    /// Reads the filter condition of the loop if any.
+    pub fn filter(&self) -> Option<Rc<Expr>> {
+        self.filter.clone()
+    }
 }
 
 impl Ast for LoopSpecItem {
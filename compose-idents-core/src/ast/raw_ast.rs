@@ -2,15 +2,17 @@ use crate::ast::{AliasSpec, Ast, ComposeItemSpec, LoopSpec, NodeId};
 use proc_macro2::Span;
 use std::rc::Rc;
 use syn::spanned::Spanned;
-use syn::Block;
+use syn::{Attribute, Block};
 
-/// Root AST produced by the parse phase, containing optional loops, optional alias spec, and a user block.
+/// Root AST produced by the parse phase, containing outer attributes of the invocation, optional
+/// loops, optional alias spec, and one or more user blocks.
 #[derive(Debug)]
 pub struct RawAST {
     id: NodeId,
+    attrs: Vec<Attribute>,
     loops: Option<Rc<LoopSpec>>,
     spec: Option<Rc<AliasSpec>>,
-    block: Block,
+    blocks: Vec<Block>,
 }
 
 impl Ast for RawAST {
@@ -23,7 +25,7 @@ impl Ast for RawAST {
         } else if let Some(spec) = &self.spec {
             spec.span()
         } else {
-            self.block.span()
+            self.blocks[0].span()
         }
     }
 }
@@ -34,26 +36,39 @@ impl RawAST {
         id: NodeId,
         loops: Option<Rc<LoopSpec>>,
         spec: Option<Rc<AliasSpec>>,
-        block: Block,
+        blocks: Vec<Block>,
     ) -> Self {
         Self {
             id,
+            attrs: vec![],
             loops,
             spec,
-            block,
+            blocks,
         }
     }
 
+    /// Sets the outer attributes of the invocation - they are applied to every generated item.
+    pub fn with_attrs(mut self, attrs: Vec<Attribute>) -> Self {
+        self.attrs = attrs;
+        self
+    }
+
     /// Created new RawAST from a [`ComposeItemSpec`] and a code block.
     pub fn from_compose_item_spec(spec: &ComposeItemSpec, block: Block) -> Self {
         Self {
             id: spec.id(),
+            attrs: vec![],
             loops: spec.loops(),
             spec: spec.spec(),
-            block,
+            blocks: vec![block],
         }
     }
 
+    /// Reads the outer attributes of the invocation.
+    pub fn attrs(&self) -> &[Attribute] {
+        &self.attrs
+    }
+
     /// Reads the loop specification if any.
     pub fn loops(&self) -> Option<Rc<LoopSpec>> {
         self.loops.clone()
@@ -64,8 +79,8 @@ impl RawAST {
         self.spec.clone()
     }
 
-    /// Reads an immutable reference to the code block.
-    pub fn block(&self) -> &Block {
-        &self.block
+    /// Reads the code blocks - the spec is applied to each of them.
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
     }
 }
@@ -26,9 +26,6 @@ impl<V> Tuple<V> {
     pub fn iter_recursive(&self) -> TupleValuesRecursiveIterator<'_, V> {
         TupleValuesRecursiveIterator::new(self)
     }
-    pub fn iter_destructuring(&self) -> TupleValuesDestructuringIterator<'_, V> {
-        TupleValuesDestructuringIterator::new(self)
-    }
 }
 
 /// Recursively iterates over the elements of the tuple and nested tuples.
@@ -138,36 +135,3 @@ where
         }
     }
 }
-
-/// Recursively iterates over [`TupleValueKind`] elements - both tuples or values.
-///
-/// This allows to compare structures of multiple nested tuples without writing recursive code and
-/// instead operating with flat iterators.
-#[derive(Debug, Clone)]
-pub struct TupleValuesDestructuringIterator<'a, V> {
-    stack: VecDeque<&'a TupleValue<V>>,
-}
-
-impl<'a, V> TupleValuesDestructuringIterator<'a, V> {
-    pub fn new(tuple: &'a Tuple<V>) -> Self {
-        Self {
-            stack: VecDeque::from_iter(tuple.values().iter()),
-        }
-    }
-}
-
-impl<'a, V> Iterator for TupleValuesDestructuringIterator<'a, V> {
-    type Item = &'a TupleValueKind<V>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let item = self.stack.pop_front().map(|v| v.kind());
-        match item {
-            Some(value @ TupleValueKind::Value(_)) => Some(value),
-            Some(tuple @ TupleValueKind::Tuple(values)) => {
-                self.stack.extend(values.values().iter());
-                Some(tuple)
-            }
-            None => None,
-        }
-    }
-}
@@ -0,0 +1,878 @@
+use crate::ast::{Ast, NodeId};
+use crate::core::Type;
+use crate::error::Error;
+use crate::strings::escape_keyword;
+use crate::util::token_stream::token_streams_eq;
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, Parser};
+use syn::spanned::Spanned;
+use syn::LitStr;
+
+/// Argument to the [`compose_idents`] macro.
+///
+/// Accepted inputs:
+/// - Literal strings (enclosed in double quotes) are recognized and their content is used.
+/// - Identifiers, literal numbers, underscores are used as is.
+/// - Arbitrary sequences of tokens that do not include `,`.
+/// - Lists of values enclosed in square brackets.
+/// - Outer attributes.
+#[derive(Debug, Clone)]
+pub struct Value {
+    id: NodeId,
+    kind: ValueKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum ValueKind {
+    Ident(Ident),
+    Path(syn::Path),
+    Type(syn::Type),
+    Expr(syn::Expr),
+    LitStr(syn::LitStr),
+    LitByteStr(syn::LitByteStr),
+    LitInt(syn::LitInt),
+    LitFloat(syn::LitFloat),
+    LitChar(syn::LitChar),
+    LitBool(syn::LitBool),
+    Lifetime(syn::Lifetime),
+    Tokens(TokenStream),
+    Raw(TokenStream),
+    Attr(syn::Attribute),
+    List(Vec<Rc<Value>>),
+}
+
+impl Value {
+    pub fn new(id: NodeId, kind: ValueKind) -> Self {
+        Self { id, kind }
+    }
+    pub fn from_ident(ident: Ident) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Ident(ident))
+    }
+    pub fn from_path(path: syn::Path) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Path(path))
+    }
+    pub fn from_type(type_: syn::Type) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Type(type_))
+    }
+    pub fn from_expr(expr: syn::Expr) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Expr(expr))
+    }
+    pub fn from_lit_str(lit_str: syn::LitStr) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::LitStr(lit_str))
+    }
+    pub fn from_lit_byte_str(lit_byte_str: syn::LitByteStr) -> Self {
+        Self::new(
+            next_unique_id() as NodeId,
+            ValueKind::LitByteStr(lit_byte_str),
+        )
+    }
+    pub fn from_lit_int(lit_int: syn::LitInt) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::LitInt(lit_int))
+    }
+    pub fn from_lit_float(lit_float: syn::LitFloat) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::LitFloat(lit_float))
+    }
+    pub fn from_lit_char(lit_char: syn::LitChar) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::LitChar(lit_char))
+    }
+    pub fn from_lit_bool(lit_bool: syn::LitBool) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::LitBool(lit_bool))
+    }
+    pub fn from_lifetime(lifetime: syn::Lifetime) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Lifetime(lifetime))
+    }
+    pub fn from_tokens(tokens: TokenStream) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Tokens(tokens))
+    }
+    pub fn from_raw(tokens: TokenStream) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Raw(tokens))
+    }
+    pub fn from_attr(attr: syn::Attribute) -> Self {
+        Self::new(next_unique_id() as NodeId, ValueKind::Attr(attr))
+    }
+    /// Creates a list value, casting the items to their common type.
+    pub fn from_list(items: Vec<Rc<Value>>) -> Result<Self, Error> {
+        let mut item_type = Option::<Type>::None;
+        for item in &items {
+            let type_ = item.type_();
+            item_type = match item_type {
+                None => Some(type_),
+                Some(prev_type) => match Type::common(&prev_type, &type_) {
+                    Some(common_type) => Some(common_type),
+                    None => {
+                        return Err(Error::TypeError(
+                            format!(
+                                "List items of types {} and {} can't be mixed",
+                                prev_type, type_
+                            ),
+                            item.span(),
+                        ))
+                    }
+                },
+            };
+        }
+        let items = match item_type {
+            Some(item_type) => items
+                .into_iter()
+                .map(|item| match item.type_() == item_type {
+                    true => Ok(item),
+                    false => item.try_cast(&item_type).map(Rc::new),
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+            None => items,
+        };
+        Ok(Self::new(
+            next_unique_id() as NodeId,
+            ValueKind::List(items),
+        ))
+    }
+    pub fn kind(&self) -> &ValueKind {
+        &self.kind
+    }
+    pub fn type_(&self) -> Type {
+        match self.kind() {
+            ValueKind::Ident(_) => Type::Ident,
+            ValueKind::Path(_) => Type::Path,
+            ValueKind::Type(_) => Type::Type,
+            ValueKind::Expr(_) => Type::Expr,
+            ValueKind::LitStr(_) => Type::LitStr,
+            ValueKind::LitByteStr(_) => Type::LitByteStr,
+            ValueKind::LitInt(_) => Type::LitInt,
+            ValueKind::LitFloat(_) => Type::LitFloat,
+            ValueKind::LitChar(_) => Type::LitChar,
+            ValueKind::LitBool(_) => Type::LitBool,
+            ValueKind::Lifetime(_) => Type::Lifetime,
+            ValueKind::Tokens(_) => Type::Tokens,
+            ValueKind::Raw(_) => Type::Raw,
+            ValueKind::Attr(_) => Type::Attr,
+            // List items always share the same type, which for empty lists is unknown, so the
+            // most general one is assumed.
+            ValueKind::List(items) => Type::List(Box::new(
+                items.first().map_or(Type::Tokens, |item| item.type_()),
+            )),
+        }
+    }
+    fn make_cast_error(
+        error: Option<syn::Error>,
+        error_str: Option<&str>,
+        from_type: Type,
+        to_type: Type,
+    ) -> Error {
+        if let Some(error) = error {
+            Error::TypeError(
+                format!("Unable to cast {:?} to {:?}: {}", from_type, to_type, error),
+                error.span(),
+            )
+        } else if let Some(error_str) = error_str {
+            Error::TypeError(
+                format!(
+                    "Unable to cast {:?} to {:?}: {}",
+                    from_type, to_type, error_str
+                ),
+                error_str.span(),
+            )
+        } else {
+            panic!("make_error called without an error provided");
+        }
+    }
+
+    /// Whether the value is a token-sequence consisting of a single string literal.
+    fn is_lit_str_tokens(&self) -> bool {
+        match &self.kind {
+            ValueKind::Tokens(tokens) => syn::parse2::<LitStr>(tokens.clone()).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Prefixes the string with `_` if it starts with a digit (or another character that could
+    /// only continue an identifier), so numbers (`42`) and strings like `123abc` could become
+    /// identifiers.
+    fn prefix_leading_digit(string: String) -> String {
+        match string.chars().next() {
+            Some(c)
+                if c != '_'
+                    && !unicode_ident::is_xid_start(c)
+                    && unicode_ident::is_xid_continue(c) =>
+            {
+                format!("_{}", string)
+            }
+            _ => string,
+        }
+    }
+
+    /// Makes an error for a keyword that can't be escaped as a raw identifier.
+    fn make_keyword_error(keyword: &str, span: Span) -> Error {
+        Error::EvalError(
+            format!("Keyword {:?} can't be used as a raw identifier", keyword),
+            span,
+        )
+    }
+
+    fn from_lit_str_as_ident(lit_str: LitStr) -> Result<Value, Error> {
+        let string = Self::prefix_leading_digit(lit_str.value());
+        let Some(string) = escape_keyword(&string) else {
+            return Err(Self::make_keyword_error(&string, lit_str.span()));
+        };
+        match syn::parse_str::<Ident>(string.as_str()) {
+            Err(error) => Err(Self::make_cast_error(
+                Some(error),
+                None,
+                Type::LitStr,
+                Type::Ident,
+            )),
+            Ok(ident) => Ok(Value::from_ident(ident)),
+        }
+    }
+
+    /// Re-parses a token-sequence consisting of a single invisible group (a `macro_rules!`
+    /// fragment) as a value of its own.
+    fn unwrap_none_group(&self) -> Option<Value> {
+        let ValueKind::Tokens(tokens) = &self.kind else {
+            return None;
+        };
+        let mut trees = tokens.clone().into_iter();
+        let (Some(TokenTree::Group(group)), None) = (trees.next(), trees.next()) else {
+            return None;
+        };
+        if group.delimiter() != Delimiter::None {
+            return None;
+        }
+        syn::parse2::<TerminatedValue<syn::Token![,]>>(group.stream())
+            .ok()
+            .map(TerminatedValue::into_value)
+    }
+
+    /// Re-interprets a bracketed value - `[x]` is parsed as a slice type - as a list literal with
+    /// the contents of the brackets as its items.
+    ///
+    /// Returns `None` if the value isn't enclosed in square brackets or its contents aren't
+    /// comma-separated values.
+    pub fn bracketed_list(&self) -> Option<Value> {
+        let mut trees = self.to_token_stream().into_iter();
+        let (Some(TokenTree::Group(group)), None) = (trees.next(), trees.next()) else {
+            return None;
+        };
+        if group.delimiter() != Delimiter::Bracket {
+            return None;
+        }
+        let (items, _) = TerminatedValue::<syn::Token![,]>::parse_list_items
+            .parse2(group.to_token_stream())
+            .ok()?;
+        if items.is_empty() {
+            return None;
+        }
+        Value::from_list(items).ok()
+    }
+
+    /// Tries to cast the value to the specified type.
+    pub fn try_cast(&self, type_: &Type) -> Result<Value, Error> {
+        if let Some(value) = self.unwrap_none_group() {
+            return value.try_cast(type_);
+        }
+        match (&self.type_(), type_) {
+            (from_type, to_type) if from_type == to_type => Ok(self.clone()),
+            (Type::List(_), Type::List(item_type)) => {
+                let ValueKind::List(items) = &self.kind else {
+                    unreachable!()
+                };
+                let items = items
+                    .iter()
+                    .map(|item| item.try_cast(item_type).map(Rc::new))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                Ok(Value::new(
+                    next_unique_id() as NodeId,
+                    ValueKind::List(items),
+                ))
+            }
+            (from_type, to_type @ Type::List(_)) => match self.bracketed_list() {
+                Some(list) => list.try_cast(to_type),
+                None => Err(Self::make_cast_error(
+                    None,
+                    Some("impossible cast"),
+                    from_type.clone(),
+                    to_type.clone(),
+                )),
+            },
+            (Type::Ident, Type::Path) => {
+                let ValueKind::Ident(ident) = self.kind.clone() else {
+                    unreachable!()
+                };
+                let path = syn::Path::from(ident.clone());
+                Ok(Value::from_path(path))
+            }
+            (Type::Ident, Type::Type) => {
+                let ValueKind::Ident(ident) = self.kind.clone() else {
+                    unreachable!()
+                };
+                match syn::parse2::<syn::Type>(ident.clone().to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        Type::Ident,
+                        Type::Type,
+                    )),
+                    Ok(type_) => Ok(Value::from_type(type_)),
+                }
+            }
+            (Type::Ident, Type::Expr) => {
+                let ValueKind::Ident(ref ident) = self.kind else {
+                    unreachable!()
+                };
+                match syn::parse2::<syn::Expr>(ident.clone().to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        Type::Ident,
+                        Type::Expr,
+                    )),
+                    Ok(expr) => Ok(Value::from_expr(expr)),
+                }
+            }
+            (Type::Ident, Type::LitStr) => {
+                let ValueKind::Ident(ref ident) = self.kind else {
+                    unreachable!()
+                };
+                match syn::parse_str::<syn::LitStr>(format!("\"{}\"", ident).as_str()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        Type::Ident,
+                        Type::LitStr,
+                    )),
+                    Ok(mut lit_str) => {
+                        lit_str.set_span(Ast::span(self));
+                        Ok(Value::from_lit_str(lit_str))
+                    }
+                }
+            }
+            // Keywords `true` and `false` can't be parsed as identifiers, but could be made into ones.
+            (Type::LitBool, Type::Ident) => {
+                let ValueKind::LitBool(lit_bool) = &self.kind else {
+                    unreachable!()
+                };
+                let ident = Ident::new(&lit_bool.value().to_string(), lit_bool.span());
+                Ok(Value::from_ident(ident))
+            }
+            (Type::LitStr, Type::Ident) => {
+                let ValueKind::LitStr(lit_str) = self.kind.clone() else {
+                    unreachable!()
+                };
+
+                Self::from_lit_str_as_ident(lit_str)
+            }
+            (Type::LitStr, to_type @ (Type::Path | Type::Type | Type::Expr)) => {
+                let ValueKind::LitStr(lit_str) = &self.kind else {
+                    unreachable!()
+                };
+                // Parsing the content of the literal, the resulting tokens inherit its span.
+                let result = match to_type {
+                    Type::Path => lit_str.parse::<syn::Path>().map(Value::from_path),
+                    Type::Type => lit_str.parse::<syn::Type>().map(Value::from_type),
+                    _ => lit_str.parse::<syn::Expr>().map(Value::from_expr),
+                };
+                result.map_err(|error| {
+                    Self::make_cast_error(Some(error), None, Type::LitStr, to_type.clone())
+                })
+            }
+            (from_type, Type::Ident) => {
+                let tokens = self.to_token_stream();
+                if let Ok(lit_str) = syn::parse2::<LitStr>(tokens.clone()) {
+                    return Self::from_lit_str_as_ident(lit_str);
+                };
+                if let Ok(lit_int) = syn::parse2::<syn::LitInt>(tokens.clone()) {
+                    // A negative literal (`-1`) still isn't a valid identifier after prefixing.
+                    let string = Self::prefix_leading_digit(lit_int.to_string());
+                    return match syn::parse_str::<Ident>(&string) {
+                        Err(error) => Err(Self::make_cast_error(
+                            Some(syn::Error::new(lit_int.span(), error)),
+                            None,
+                            from_type.clone(),
+                            Type::Ident,
+                        )),
+                        Ok(mut ident) => {
+                            ident.set_span(lit_int.span());
+                            Ok(Value::from_ident(ident))
+                        }
+                    };
+                };
+                // A keyword is escaped as a raw identifier.
+                if let Ok(ident) = Ident::parse_any.parse2(tokens) {
+                    let name = ident.to_string();
+                    match escape_keyword(&name) {
+                        None => return Err(Self::make_keyword_error(&name, ident.span())),
+                        Some(escaped) if escaped != name => {
+                            return Ok(Value::from_ident(Ident::new_raw(&name, ident.span())));
+                        }
+                        Some(_) => {}
+                    }
+                };
+
+                match syn::parse2::<Ident>(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::Ident,
+                    )),
+                    Ok(ident) => Ok(Value::from_ident(ident)),
+                }
+            }
+            // A string literal in form of tokens (e.g. an argument of `to_type()`) is treated
+            // just like a string literal value.
+            (_, to_type @ (Type::Path | Type::Type | Type::Expr)) if self.is_lit_str_tokens() => {
+                let lit_str = syn::parse2::<LitStr>(self.to_token_stream()).unwrap();
+                Value::from_lit_str(lit_str).try_cast(to_type)
+            }
+            (from_type, Type::Path) => match syn::parse2::<syn::Path>(self.to_token_stream()) {
+                Err(error) => Err(Self::make_cast_error(
+                    Some(error),
+                    None,
+                    from_type.clone(),
+                    Type::Path,
+                )),
+                Ok(path) => Ok(Value::from_path(path)),
+            },
+            (from_type, Type::Type) => match syn::parse2::<syn::Type>(self.to_token_stream()) {
+                Err(error) => Err(Self::make_cast_error(
+                    Some(error),
+                    None,
+                    from_type.clone(),
+                    Type::Type,
+                )),
+                Ok(type_) => Ok(Value::from_type(type_)),
+            },
+            (from_type, Type::Expr) => match syn::parse2::<syn::Expr>(self.to_token_stream()) {
+                Err(error) => Err(Self::make_cast_error(
+                    Some(error),
+                    None,
+                    from_type.clone(),
+                    Type::Expr,
+                )),
+                Ok(expr) => Ok(Value::from_expr(expr)),
+            },
+            (from_type, Type::LitStr) => {
+                if let Ok(ident) = syn::parse2::<Ident>(self.to_token_stream()) {
+                    let lit_str = LitStr::new(&ident.to_string(), ident.span());
+                    return Ok(Value::from_lit_str(lit_str));
+                }
+                if let Ok(lit_char) = syn::parse2::<syn::LitChar>(self.to_token_stream()) {
+                    let lit_str = LitStr::new(&lit_char.value().to_string(), lit_char.span());
+                    return Ok(Value::from_lit_str(lit_str));
+                }
+                match syn::parse2::<syn::LitStr>(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::LitStr,
+                    )),
+                    Ok(lit_str) => Ok(Value::from_lit_str(lit_str)),
+                }
+            }
+            (Type::LitStr, Type::LitByteStr) => {
+                let ValueKind::LitStr(lit_str) = &self.kind else {
+                    unreachable!()
+                };
+                let lit_byte_str = syn::LitByteStr::new(lit_str.value().as_bytes(), lit_str.span());
+                Ok(Value::from_lit_byte_str(lit_byte_str))
+            }
+            (from_type, Type::LitByteStr) => {
+                match syn::parse2::<syn::LitByteStr>(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::LitByteStr,
+                    )),
+                    Ok(lit_byte_str) => Ok(Value::from_lit_byte_str(lit_byte_str)),
+                }
+            }
+            (from_type, Type::LitInt) => match syn::parse2::<syn::LitInt>(self.to_token_stream()) {
+                Err(error) => Err(Self::make_cast_error(
+                    Some(error),
+                    None,
+                    from_type.clone(),
+                    Type::LitInt,
+                )),
+                Ok(lit_int) => Ok(Value::from_lit_int(lit_int)),
+            },
+            (from_type, Type::LitFloat) => {
+                // An integer without a suffix becomes a float with a zero fractional part.
+                if let Ok(lit_int) = syn::parse2::<syn::LitInt>(self.to_token_stream()) {
+                    if lit_int.suffix().is_empty() {
+                        let repr = format!("{}.0", lit_int.base10_digits());
+                        let lit_float = syn::LitFloat::new(&repr, lit_int.span());
+                        return Ok(Value::from_lit_float(lit_float));
+                    }
+                }
+                match syn::parse2::<syn::LitFloat>(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::LitFloat,
+                    )),
+                    Ok(lit_float) => Ok(Value::from_lit_float(lit_float)),
+                }
+            }
+            (from_type, Type::LitChar) => {
+                match syn::parse2::<syn::LitChar>(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::LitChar,
+                    )),
+                    Ok(lit_char) => Ok(Value::from_lit_char(lit_char)),
+                }
+            }
+            (from_type, Type::LitBool) => {
+                match syn::parse2::<syn::LitBool>(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::LitBool,
+                    )),
+                    Ok(lit_bool) => Ok(Value::from_lit_bool(lit_bool)),
+                }
+            }
+            // An identifier is turned into a lifetime with the same name.
+            (from_type, Type::Lifetime) => {
+                let tokens = self.to_token_stream();
+                if let Ok(ident) = syn::parse2::<Ident>(tokens.clone()) {
+                    let name = format!("'{}", ident.unraw());
+                    return Ok(Value::from_lifetime(syn::Lifetime::new(
+                        &name,
+                        ident.span(),
+                    )));
+                }
+                match syn::parse2::<syn::Lifetime>(tokens) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::Lifetime,
+                    )),
+                    Ok(lifetime) => Ok(Value::from_lifetime(lifetime)),
+                }
+            }
+            (from_type, Type::Attr) => {
+                match syn::Attribute::parse_outer.parse2(self.to_token_stream()) {
+                    Err(error) => Err(Self::make_cast_error(
+                        Some(error),
+                        None,
+                        from_type.clone(),
+                        Type::Attr,
+                    )),
+                    Ok(attrs) if attrs.len() != 1 => Err(Self::make_cast_error(
+                        None,
+                        Some("expected exactly one outer attribute"),
+                        from_type.clone(),
+                        Type::Attr,
+                    )),
+                    Ok(mut attrs) => Ok(Value::from_attr(attrs.remove(0))),
+                }
+            }
+            (_, Type::Tokens) => Ok(Value::from_tokens(self.to_token_stream())),
+            (_, Type::Raw) => Ok(Value::from_raw(self.to_token_stream())),
+            (from_type, to_type) => Err(Self::make_cast_error(
+                None,
+                Some("impossible cast"),
+                from_type.clone(),
+                to_type.clone(),
+            )),
+        }
+    }
+}
+
+impl Ast for Value {
+    fn id(&self) -> NodeId {
+        self.id
+    }
+
+    fn span(&self) -> Span {
+        match &self.kind() {
+            ValueKind::Ident(ident) => ident.span(),
+            ValueKind::Path(path) => path.span(),
+            ValueKind::Type(type_) => type_.span(),
+            ValueKind::Expr(expr) => expr.span(),
+            ValueKind::LitStr(lit_str) => lit_str.span(),
+            ValueKind::LitByteStr(lit_byte_str) => lit_byte_str.span(),
+            ValueKind::LitInt(lit_int) => lit_int.span(),
+            ValueKind::LitFloat(lit_float) => lit_float.span(),
+            ValueKind::LitChar(lit_char) => lit_char.span(),
+            ValueKind::LitBool(lit_bool) => lit_bool.span(),
+            ValueKind::Lifetime(lifetime) => lifetime.span(),
+            ValueKind::Tokens(tokens) => tokens.span(),
+            ValueKind::Raw(tokens) => tokens.span(),
+            ValueKind::Attr(attr) => attr.span(),
+            ValueKind::List(items) => items
+                .first()
+                .map_or_else(Span::call_site, |item| item.span()),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind() {
+            ValueKind::Ident(ident) => write!(f, "{}", ident),
+            ValueKind::Path(path) => write!(f, "{}", path.to_token_stream()),
+            ValueKind::Type(type_) => write!(f, "{}", type_.to_token_stream()),
+            ValueKind::Expr(expr) => write!(f, "{}", expr.to_token_stream()),
+            ValueKind::LitStr(value) => write!(f, "\"{}\"", value.value()),
+            ValueKind::LitByteStr(value) => write!(f, "{}", value.to_token_stream()),
+            ValueKind::LitInt(value) => write!(f, "{}", value.base10_digits()),
+            ValueKind::LitFloat(value) => write!(f, "{}", value),
+            ValueKind::LitChar(value) => write!(f, "'{}'", value.value()),
+            ValueKind::LitBool(value) => write!(f, "{}", value.value()),
+            ValueKind::Lifetime(lifetime) => write!(f, "{}", lifetime),
+            ValueKind::Tokens(tokens) => write!(f, "{}", tokens),
+            ValueKind::Raw(tokens) => write!(f, "{}", tokens),
+            ValueKind::Attr(attr) => write!(f, "{}", attr.to_token_stream()),
+            ValueKind::List(items) => write!(
+                f,
+                "[{}]",
+                items
+                    .iter()
+                    .map(|item| item.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl ToTokens for Value {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match &self.kind {
+            ValueKind::Ident(ident) => tokens.extend(ident.to_token_stream()),
+            ValueKind::Path(path) => tokens.extend(path.to_token_stream()),
+            ValueKind::Type(type_) => tokens.extend(type_.to_token_stream()),
+            ValueKind::Expr(expr) => tokens.extend(expr.to_token_stream()),
+            ValueKind::LitStr(lit_str) => tokens.extend(lit_str.to_token_stream()),
+            ValueKind::LitByteStr(lit_byte_str) => tokens.extend(lit_byte_str.to_token_stream()),
+            ValueKind::LitInt(lit_int) => tokens.extend(lit_int.to_token_stream()),
+            ValueKind::LitFloat(lit_float) => tokens.extend(lit_float.to_token_stream()),
+            ValueKind::LitChar(lit_char) => tokens.extend(lit_char.to_token_stream()),
+            ValueKind::LitBool(lit_bool) => tokens.extend(lit_bool.to_token_stream()),
+            ValueKind::Lifetime(lifetime) => tokens.extend(lifetime.to_token_stream()),
+            ValueKind::Tokens(tokens_) => tokens.extend(tokens_.clone()),
+            ValueKind::Raw(tokens_) => tokens.extend(tokens_.clone()),
+            ValueKind::Attr(attr) => tokens.extend(attr.to_token_stream()),
+            ValueKind::List(items) => tokens.extend(quote!([#(#items),*])),
+        }
+    }
+}
+
+/// Values are equal when they are of the same type and consist of the same tokens regardless of
+/// spacing and provenance of the tokens.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_() == other.type_()
+            && token_streams_eq(&self.to_token_stream(), &other.to_token_stream())
+    }
+}
+
+/// Metadata for [`Value`] AST elements.
+#[derive(Debug, Clone)]
+pub struct ValueMetadata {
+    /// The target coercion type.
+    pub target_type: Type,
+    /// The cost of coercing to the target type.
+    pub coercion_cost: u32,
+}
+
+/// Auxiliary type that represents an [`Value`] terminated by a generic terminator-token.
+#[derive(Debug, Clone)]
+pub struct TerminatedValue<Term>
+where
+    Term: Parse,
+{
+    value: Value,
+    terminator_type: PhantomData<Term>,
+}
+
+impl<Term: Parse> TerminatedValue<Term> {
+    pub fn new(arg: Value) -> Self {
+        Self {
+            value: arg,
+            terminator_type: PhantomData,
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+}
+
+impl<Term: Parse> Ast for TerminatedValue<Term> {
+    fn id(&self) -> NodeId {
+        self.value.id()
+    }
+    fn span(&self) -> Span {
+        Ast::span(&self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Value;
+    use crate::core::Type;
+    use crate::error::ErrorType;
+    use proc_macro2::{Delimiter, Group, Span, TokenTree};
+    use quote::ToTokens;
+    use rstest::rstest;
+    use syn::LitStr;
+
+    #[rstest]
+    #[case::type_("Result<T, E>", Type::Type, "Result < T , E >")]
+    #[case::type_reference("&'static str", Type::Type, "& 'static str")]
+    #[case::path("std::vec::Vec", Type::Path, "std :: vec :: Vec")]
+    #[case::path_with_generics("Vec::<u32>", Type::Path, "Vec :: < u32 >")]
+    #[case::expr("1 + 2", Type::Expr, "1 + 2")]
+    #[case::expr_call("foo(bar)", Type::Expr, "foo (bar)")]
+    #[case::ident("foo", Type::Ident, "foo")]
+    fn lit_str_content_cast(#[case] content: &str, #[case] to_type: Type, #[case] expected: &str) {
+        let value = Value::from_lit_str(LitStr::new(content, Span::call_site()));
+        let casted = value.try_cast(&to_type).unwrap();
+
+        assert_eq!(casted.type_(), to_type);
+        assert_eq!(casted.to_token_stream().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::type_("Result<", Type::Type)]
+    #[case::path("foo bar", Type::Path)]
+    #[case::expr("1 +", Type::Expr)]
+    fn lit_str_content_cast_failure(#[case] content: &str, #[case] to_type: Type) {
+        let value = Value::from_lit_str(LitStr::new(content, Span::call_site()));
+        assert!(value.try_cast(&to_type).is_err());
+    }
+
+    #[rstest]
+    #[case::type_("Result<T, E>", Type::Type, "Result < T , E >")]
+    #[case::path("Result<T, E>", Type::Path, "Result < T , E >")]
+    #[case::expr("1 + 2", Type::Expr, "1 + 2")]
+    fn lit_str_tokens_cast(#[case] content: &str, #[case] to_type: Type, #[case] expected: &str) {
+        let tokens = LitStr::new(content, Span::call_site()).to_token_stream();
+        let casted = Value::from_tokens(tokens).try_cast(&to_type).unwrap();
+
+        assert_eq!(casted.type_(), to_type);
+        assert_eq!(casted.to_token_stream().to_string(), expected);
+    }
+
+    /// Tokens of a `macro_rules!` fragment are cast the same way as the fragment content.
+    #[rstest]
+    #[case::ident("foo", Type::Ident, "foo")]
+    #[case::int_to_ident("7", Type::Ident, "_7")]
+    #[case::str_to_ident("\"foo\"", Type::Ident, "foo")]
+    #[case::str_to_type("\"Vec<u8>\"", Type::Type, "Vec < u8 >")]
+    #[case::type_("Vec<u8>", Type::Type, "Vec < u8 >")]
+    #[case::nested_groups("(Vec<u8>)", Type::Type, "(Vec < u8 >)")]
+    fn none_group_tokens_cast(
+        #[case] content: &str,
+        #[case] to_type: Type,
+        #[case] expected: &str,
+    ) {
+        let group = Group::new(Delimiter::None, content.parse().unwrap());
+        let value = Value::from_tokens(group.to_token_stream());
+        let casted = value.try_cast(&to_type).unwrap();
+
+        assert!(!matches!(
+            casted.to_token_stream().into_iter().next(),
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None,
+        ));
+        assert_eq!(casted.type_(), to_type);
+        assert_eq!(casted.to_token_stream().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::same_tokens("Vec<u8>", "Vec < u8 >", true)]
+    #[case::different_tokens("Vec<u8>", "Vec<u16>", false)]
+    fn tokens_equality(#[case] a: &str, #[case] b: &str, #[case] expected: bool) {
+        let a = Value::from_tokens(a.parse().unwrap());
+        let b = Value::from_tokens(b.parse().unwrap());
+        assert_eq!(a == b, expected);
+    }
+
+    #[test]
+    fn different_types_are_not_equal() {
+        let ident = Value::from_ident(syn::parse_str("foo").unwrap());
+        let tokens = Value::from_tokens(ident.to_token_stream());
+        assert_ne!(ident, tokens);
+        assert_eq!(ident, ident.clone());
+    }
+
+    #[rstest]
+    #[case::int(Value::from_lit_int(syn::parse_str("42").unwrap()), "_42")]
+    #[case::int_with_suffix(Value::from_lit_int(syn::parse_str("42u8").unwrap()), "_42u8")]
+    #[case::int_tokens(Value::from_tokens(quote::quote!(7)), "_7")]
+    #[case::str_leading_digit(
+        Value::from_lit_str(LitStr::new("123abc", Span::call_site())),
+        "_123abc"
+    )]
+    #[case::str(Value::from_lit_str(LitStr::new("abc", Span::call_site())), "abc")]
+    fn leading_digit_ident_cast(#[case] value: Value, #[case] expected: &str) {
+        let casted = value.try_cast(&Type::Ident).unwrap();
+
+        assert_eq!(casted.type_(), Type::Ident);
+        assert_eq!(casted.to_token_stream().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::negative_int(Value::from_lit_int(syn::parse_str("-1").unwrap()))]
+    #[case::negative_int_tokens(Value::from_tokens(quote::quote!(-1)))]
+    fn leading_digit_ident_cast_failure(#[case] value: Value) {
+        let err = value.try_cast(&Type::Ident).unwrap_err();
+
+        assert_eq!(err.type_(), ErrorType::TypeError);
+        assert!(err.to_string().contains("to Ident"), "{}", err);
+    }
+
+    #[rstest]
+    #[case::slice_type(Value::from_type(syn::parse_str("[u8]").unwrap()), Some("[u8]"))]
+    #[case::tokens(Value::from_tokens(quote::quote!([a, b])), Some("[a , b]"))]
+    #[case::nested(Value::from_tokens(quote::quote!([[a]])), Some("[[a]]"))]
+    #[case::array_type(Value::from_type(syn::parse_str("[u8; 4]").unwrap()), None)]
+    #[case::empty(Value::from_tokens(quote::quote!([])), None)]
+    #[case::not_bracketed(Value::from_tokens(quote::quote!((a, b))), None)]
+    fn bracketed_list(#[case] value: Value, #[case] expected: Option<&str>) {
+        let list = value.bracketed_list();
+
+        assert_eq!(
+            list.as_ref().map(|list| list.to_token_stream().to_string()),
+            expected.map(str::to_string)
+        );
+        assert!(list.map_or(true, |list| matches!(list.type_(), Type::List(_))));
+    }
+
+    #[rstest]
+    #[case::int(Value::from_lit_int(syn::parse_str("3").unwrap()), "3.0")]
+    #[case::negative_int(Value::from_lit_int(syn::parse_str("-3").unwrap()), "- 3.0")]
+    #[case::hex_int(Value::from_lit_int(syn::parse_str("0x10").unwrap()), "16.0")]
+    #[case::int_tokens(Value::from_tokens(quote::quote!(3)), "3.0")]
+    #[case::float(Value::from_lit_float(syn::parse_str("2.5").unwrap()), "2.5")]
+    fn float_cast(#[case] value: Value, #[case] expected: &str) {
+        let casted = value.try_cast(&Type::LitFloat).unwrap();
+
+        assert_eq!(casted.type_(), Type::LitFloat);
+        assert_eq!(casted.to_token_stream().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::int_with_suffix(Value::from_lit_int(syn::parse_str("3u8").unwrap()))]
+    #[case::ident(Value::from_ident(syn::parse_str("foo").unwrap()))]
+    fn float_cast_failure(#[case] value: Value) {
+        let err = value.try_cast(&Type::LitFloat).unwrap_err();
+
+        assert_eq!(err.type_(), ErrorType::TypeError);
+    }
+}
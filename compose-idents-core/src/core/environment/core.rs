@@ -0,0 +1,262 @@
+use crate::core::{Func, Options};
+use crate::error::Error;
+use crate::util::stable_hash::StableHasher;
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::Span;
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+/// Name of the environment variable that pins the seed of all macro invocations.
+pub const SEED_ENV_VAR: &str = "COMPOSE_IDENTS_SEED";
+
+/// Name of the environment variable that overrides [`DEFAULT_MAX_DEPTH`].
+pub const MAX_DEPTH_ENV_VAR: &str = "COMPOSE_IDENTS_MAX_DEPTH";
+
+/// Default limit of the invocation depth (see [`Environment::enter_invocation`]).
+///
+/// Legitimate nesting rarely goes more than a few levels deep, so the limit is mostly reached by
+/// the expansions re-entering themselves.
+pub const DEFAULT_MAX_DEPTH: u64 = 32;
+
+/// Global execution environment a particular macro invocation.
+///
+/// Contains data useful for internal components and exists within the scope of a single macro
+/// invocation.
+#[derive(Debug)]
+pub struct Environment {
+    /// Unique identifier of the macro invocation.
+    invocation_id: u64,
+    /// Random seed.
+    seed: u64,
+    /// Available function-types.
+    funcs: BTreeMap<String, Vec<Rc<Func>>>,
+    /// Options of the macro invocation.
+    options: RefCell<Options>,
+}
+
+thread_local! {
+    static GLOBAL_ENVIRONMENT: RefCell<Option<Rc<Environment>>> = const { RefCell::new(None) };
+    /// Counters shared between all macro invocations within the compiler process.
+    static GLOBAL_COUNTERS: RefCell<HashMap<String, u64>> = RefCell::new(HashMap::new());
+    /// Number of macro invocations being expanded on the current thread.
+    static INVOCATION_DEPTH: Cell<u64> = const { Cell::new(0) };
+}
+
+impl Environment {
+    pub fn new(funcs: BTreeMap<String, Vec<Rc<Func>>>, seed: u64) -> Self {
+        Self {
+            invocation_id: next_unique_id(),
+            funcs,
+            seed,
+            options: RefCell::new(Options::default()),
+        }
+    }
+
+    pub fn new_initialized(seed: u64) -> Self {
+        Self::new(Self::init_funcs(), seed)
+    }
+
+    /// Reads the pinned seed from [`SEED_ENV_VAR`] environment variable if it is set.
+    pub fn pinned_seed_from_env() -> Result<Option<u64>, Error> {
+        let Ok(value) = std::env::var(SEED_ENV_VAR) else {
+            return Ok(None);
+        };
+        match value.trim().parse::<u64>() {
+            Ok(seed) => Ok(Some(seed)),
+            Err(_) => Err(Error::EvalError(
+                format!(
+                    "{} environment variable must be an unsigned integer, got: {:?}",
+                    SEED_ENV_VAR, value,
+                ),
+                Span::call_site(),
+            )),
+        }
+    }
+
+    /// Reads the limit of the invocation depth from [`MAX_DEPTH_ENV_VAR`] environment variable
+    /// falling back to [`DEFAULT_MAX_DEPTH`].
+    pub fn max_depth_from_env() -> Result<u64, Error> {
+        let Ok(value) = std::env::var(MAX_DEPTH_ENV_VAR) else {
+            return Ok(DEFAULT_MAX_DEPTH);
+        };
+        match value.trim().parse::<u64>() {
+            Ok(max_depth) if max_depth > 0 => Ok(max_depth),
+            _ => Err(Error::EvalError(
+                format!(
+                    "{} environment variable must be a positive integer, got: {:?}",
+                    MAX_DEPTH_ENV_VAR, value,
+                ),
+                Span::call_site(),
+            )),
+        }
+    }
+
+    /// Makes a seed for a macro invocation.
+    ///
+    /// With a pinned seed the result depends only on it and on the input of the invocation, so it
+    /// is reproducible across builds. Otherwise a unique value is generated for each invocation.
+    pub fn make_seed(pinned_seed: Option<u64>, input: &str) -> u64 {
+        let Some(pinned_seed) = pinned_seed else {
+            return next_unique_id();
+        };
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(pinned_seed);
+        hasher.write_str(input);
+        hasher.finish()
+    }
+
+    /// Returns function variants for the function with given name.
+    pub fn get_func_variants(&self, name: &str) -> Option<&[Rc<Func>]> {
+        self.funcs.get(name).map(|funcs| funcs.as_slice())
+    }
+
+    pub fn has_func(&self, name: &str) -> bool {
+        self.funcs.contains_key(name)
+    }
+
+    pub fn get_global() -> Option<Rc<Environment>> {
+        GLOBAL_ENVIRONMENT.with(|cell| cell.borrow().clone())
+    }
+
+    /// Sets the global environment unless it is already set.
+    pub fn maybe_set_global(environment: Rc<Environment>) {
+        GLOBAL_ENVIRONMENT.with(|cell| {
+            cell.borrow_mut().get_or_insert(environment);
+        });
+    }
+
+    /// Replaces the global environment returning the previous one.
+    pub fn replace_global(environment: Option<Rc<Environment>>) -> Option<Rc<Environment>> {
+        GLOBAL_ENVIRONMENT.with(|cell| cell.replace(environment))
+    }
+
+    /// Pretty-prints the signatures of all variants of the function with the given name.
+    pub fn make_pretty_func_sig(&self, name: &str) -> Option<String> {
+        self.funcs.get(name).map(|funcs| {
+            funcs
+                .iter()
+                .map(|f| f.signature())
+                .fold(String::new(), |acc, sig| {
+                    if acc.is_empty() {
+                        sig
+                    } else {
+                        format!("{} | {}", acc, sig)
+                    }
+                })
+        })
+    }
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Unique identifier of the macro invocation the environment was created for.
+    pub fn invocation_id(&self) -> u64 {
+        self.invocation_id
+    }
+
+    /// Options of the macro invocation.
+    pub fn options(&self) -> Options {
+        self.options.borrow().clone()
+    }
+
+    pub fn set_options(&self, options: Options) {
+        *self.options.borrow_mut() = options;
+    }
+
+    /// Returns the next value of the counter with the given key shared between all macro
+    /// invocations within the compiler process.
+    pub fn next_global_counter(key: &str) -> u64 {
+        GLOBAL_COUNTERS.with(|counters| {
+            let mut counters = counters.borrow_mut();
+            let counter = counters.entry(key.to_string()).or_insert(0);
+            let value = *counter;
+            *counter += 1;
+            value
+        })
+    }
+
+    /// Enters a macro invocation - the returned guard holds its depth and leaves the invocation
+    /// when dropped.
+    ///
+    /// The depth counts only the invocations being expanded on the current thread, so it is the
+    /// nesting of the expansions within each other (e.g. a procedural macro expanding a
+    /// specification while handling its own), rather than the number of invocations expanded
+    /// before. Invocations the compiler finds in the expanded code are expanded separately, so
+    /// each of them starts from depth 1 - recursion through them isn't tracked.
+    pub fn enter_invocation() -> InvocationGuard {
+        let depth = INVOCATION_DEPTH.with(|depth| {
+            depth.set(depth.get() + 1);
+            depth.get()
+        });
+        InvocationGuard { depth }
+    }
+}
+
+/// Guard of a macro invocation entered with [`Environment::enter_invocation`].
+#[derive(Debug)]
+pub struct InvocationGuard {
+    depth: u64,
+}
+
+impl InvocationGuard {
+    /// Depth of the invocation - starts from 1.
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+}
+
+impl Drop for InvocationGuard {
+    fn drop(&mut self) {
+        INVOCATION_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Environment, DEFAULT_MAX_DEPTH};
+
+    #[test]
+    fn pinned_seed() {
+        let seed = Environment::make_seed(Some(12345), "a = foo, { fn a() {} }");
+
+        assert_eq!(
+            seed,
+            Environment::make_seed(Some(12345), "a = foo, { fn a() {} }")
+        );
+        assert_ne!(
+            seed,
+            Environment::make_seed(Some(12345), "a = bar, { fn a() {} }")
+        );
+        assert_ne!(
+            seed,
+            Environment::make_seed(Some(54321), "a = foo, { fn a() {} }")
+        );
+    }
+
+    #[test]
+    fn invocation_depth() {
+        let outer = Environment::enter_invocation();
+        {
+            let inner = Environment::enter_invocation();
+            assert_eq!(inner.depth(), outer.depth() + 1);
+        }
+        assert_eq!(Environment::enter_invocation().depth(), outer.depth() + 1);
+    }
+
+    #[test]
+    fn invocation_sequential() {
+        let depth = Environment::enter_invocation().depth();
+        for _ in 0..(DEFAULT_MAX_DEPTH * 2) {
+            assert_eq!(Environment::enter_invocation().depth(), depth);
+        }
+    }
+
+    #[test]
+    fn unpinned_seed() {
+        assert_ne!(
+            Environment::make_seed(None, "a = foo, { fn a() {} }"),
+            Environment::make_seed(None, "a = foo, { fn a() {} }"),
+        );
+    }
+}
@@ -0,0 +1,1541 @@
+//! Provides [`Environment::init_funcs`] method that initializes all the func-types.
+
+use crate::ast::{Expr, ExprKind, Value, ValueKind};
+use crate::core::{Environment, Func, Type};
+use crate::error::Error;
+use crate::funcs::{
+    bool_value, capitalize, char_count, concat, concat_piece, decapitalize, env, escape_keyword,
+    format_radix, from_bool, global_counter, hash, hash_ident, index, join, last_segment,
+    last_segment_of_type, len, lower, normalize, nul_terminated, repeat_count, seed_from_int,
+    seed_from_str, short_hash, short_hash_ident, strip_generics, strip_generics_of_path, strip_ref,
+    to_attr, to_bool, to_byte_str, to_camel_case, to_char, to_expr, to_float, to_ident, to_int,
+    to_kebab_case, to_lifetime, to_list, to_pascal_case, to_path, to_shouty_snake_case,
+    to_snake_case, to_str, to_title_case, to_type, tokens_char_count, tokens_eq, unraw, upper,
+};
+use crate::strings::is_ident_part;
+use crate::util::token_stream::canonical_string;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+use syn::parse::Parser;
+use syn::{LitInt, LitStr};
+
+macro_rules! arg_type_err {
+    ($func:expr, $arg_type:expr) => {
+        panic!(
+            "Func {}(...) received a value of incompatible type: {:?}",
+            $func.signature(),
+            $arg_type,
+        )
+    };
+}
+
+/// Makes an identifier out of the result of a function.
+///
+/// Unlike [`Ident::new`] it doesn't panic - e.g. the case of some characters can't be changed
+/// without making the identifier invalid. Keywords and raw identifiers are accepted.
+fn make_ident(func: &Func, string: &str, span: Span) -> Result<Ident, Error> {
+    let Some(string) = escape_keyword(string) else {
+        return Err(keyword_err(func, string, span));
+    };
+    match Ident::parse_any.parse_str(&string) {
+        Ok(mut ident) => {
+            ident.set_span(span);
+            Ok(ident)
+        }
+        Err(_) => Err(Error::EvalError(
+            format!(
+                "Function {}(...) produced an invalid identifier: {:?}",
+                func.name(),
+                string,
+            ),
+            span,
+        )),
+    }
+}
+
+/// Checks that the separator of a `join(...)` call could be put inside an identifier - a literal
+/// one like `"::"` makes the call join into a string instead.
+fn is_ident_separator(args: &[Rc<Expr>]) -> bool {
+    match args.first().map(|arg| arg.kind()) {
+        Some(ExprKind::ValueExpr(value)) => match value.kind() {
+            ValueKind::LitStr(separator) => is_ident_part(separator.value().as_str()),
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+/// Makes an error for a keyword produced in place of an identifier that can't be escaped as a raw
+/// identifier.
+fn keyword_err(func: &Func, string: &str, span: Span) -> Error {
+    Error::EvalError(
+        format!(
+            "Function {}(...) produced a keyword that can't be a raw identifier: {:?}",
+            func.name(),
+            string,
+        ),
+        span,
+    )
+}
+
+/// Generates func-types ([`Func`] instances) for string manipulation functions.
+macro_rules! make_str_funcs {
+    ($name:expr, $func:expr) => {
+        vec![
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::LitStr],
+                Type::LitStr,
+                move |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::LitStr(lit_str)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    let string = $func(lit_str.value().as_str());
+                    let result = LitStr::new(string.as_str(), lit_str.span());
+                    Ok(Value::from_lit_str(result))
+                },
+            )),
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::Ident],
+                Type::Ident,
+                move |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Ident(ident)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    let ident = make_ident(func, &$func(ident.to_string().as_str()), ident.span())?;
+                    Ok(Value::from_ident(ident))
+                },
+            )),
+            // Transforms only the last segment of the path.
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::Path],
+                Type::Path,
+                move |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Path(path)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    let mut path = path.clone();
+                    if let Some(segment) = path.segments.last_mut() {
+                        let ident = &segment.ident;
+                        segment.ident =
+                            make_ident(func, &$func(ident.to_string().as_str()), ident.span())?;
+                    }
+                    Ok(Value::from_path(path))
+                },
+            )),
+        ]
+    };
+}
+
+/// Generates func-types ([`Func`] instances) for string manipulation functions whose results
+/// aren't necessarily valid identifiers - a string is produced for an ident argument as well.
+macro_rules! make_str_only_funcs {
+    ($name:expr, $func:expr) => {
+        vec![
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::LitStr],
+                Type::LitStr,
+                move |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::LitStr(lit_str)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    let string = $func(lit_str.value().as_str());
+                    let result = LitStr::new(string.as_str(), lit_str.span());
+                    Ok(Value::from_lit_str(result))
+                },
+            )),
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::Ident],
+                Type::LitStr,
+                move |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Ident(ident)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    let string = $func(ident.unraw().to_string().as_str());
+                    let result = LitStr::new(string.as_str(), ident.span());
+                    Ok(Value::from_lit_str(result))
+                },
+            )),
+        ]
+    };
+}
+
+/// Generates func-types ([`Func`] instances) for the functions stripping an affix off strings
+/// and identifiers - the input is returned unchanged if it doesn't have the affix.
+macro_rules! make_strip_funcs {
+    ($name:expr, $strip:expr) => {
+        vec![
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::LitStr, Type::LitStr],
+                Type::LitStr,
+                move |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::LitStr(lit_str), ValueKind::LitStr(affix)] = kind.as_slice()
+                    else {
+                        arg_type_err!(func, values);
+                    };
+                    let (string, affix) = (lit_str.value(), affix.value());
+                    let string = $strip(string.as_str(), affix.as_str()).unwrap_or(&string);
+                    Ok(Value::from_lit_str(LitStr::new(string, lit_str.span())))
+                },
+            )),
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::Ident, Type::LitStr],
+                Type::Ident,
+                move |func, _, span, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Ident(ident), ValueKind::LitStr(affix)] = kind.as_slice()
+                    else {
+                        arg_type_err!(func, values);
+                    };
+                    let (string, affix) = (ident.to_string(), affix.value());
+                    let string = $strip(string.as_str(), affix.as_str()).unwrap_or(&string);
+                    if string.is_empty() {
+                        return Err(Error::EvalError(
+                            format!(
+                                "Function {}(...) produced an empty identifier out of: {}",
+                                func.name(),
+                                ident,
+                            ),
+                            *span,
+                        ));
+                    }
+                    let ident = make_ident(func, string, ident.span())?;
+                    Ok(Value::from_ident(ident))
+                },
+            )),
+        ]
+    };
+}
+
+/// Generates func-types ([`Func`] instances) for the predicates checking strings and identifiers
+/// against a string - the result is a boolean literal.
+macro_rules! make_predicate_funcs {
+    ($name:expr, $predicate:expr) => {
+        vec![
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::LitStr, Type::LitStr],
+                Type::LitBool,
+                move |func, _, span, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::LitStr(lit_str), ValueKind::LitStr(needle)] = kind.as_slice()
+                    else {
+                        arg_type_err!(func, values);
+                    };
+                    let result = $predicate(lit_str.value().as_str(), needle.value().as_str());
+                    Ok(from_bool(result, *span))
+                },
+            )),
+            Rc::new(Func::new(
+                ($name.to_string()),
+                vec![Type::Ident, Type::LitStr],
+                Type::LitBool,
+                move |func, _, span, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Ident(ident), ValueKind::LitStr(needle)] = kind.as_slice()
+                    else {
+                        arg_type_err!(func, values);
+                    };
+                    let string = ident.unraw().to_string();
+                    let result = $predicate(string.as_str(), needle.value().as_str());
+                    Ok(from_bool(result, *span))
+                },
+            )),
+        ]
+    };
+}
+
+/// Generates a func-type ([`Func`] instance) for a function formatting integers in a radix.
+///
+/// Only a string is produced - the result could start with a digit, so it's not necessarily a
+/// valid identifier, but it could still be concatenated to one (`concat(REG_, hex(255))`).
+macro_rules! make_radix_funcs {
+    ($name:expr, $radix:expr) => {
+        vec![Rc::new(Func::new(
+            ($name.to_string()),
+            vec![Type::LitInt],
+            Type::LitStr,
+            move |func, _, span, values| {
+                let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                let [ValueKind::LitInt(int)] = kind.as_slice() else {
+                    arg_type_err!(func, values);
+                };
+                let string = format_radix(int, $radix)?;
+                Ok(Value::from_lit_str(LitStr::new(&string, *span)))
+            },
+        ))]
+    };
+}
+
+/// Item types of lists supported by the list manipulation functions - all the scalar types and
+/// lists of them.
+///
+/// Also used for the functions returning one of their arguments unchanged.
+fn list_item_types() -> Vec<Type> {
+    let scalar_types = vec![
+        Type::Ident,
+        Type::Path,
+        Type::Type,
+        Type::Expr,
+        Type::LitStr,
+        Type::LitByteStr,
+        Type::LitInt,
+        Type::LitFloat,
+        Type::LitChar,
+        Type::LitBool,
+        Type::Lifetime,
+        Type::Tokens,
+        Type::Attr,
+    ];
+    let list_types = scalar_types
+        .iter()
+        .map(|type_| Type::List(Box::new(type_.clone())))
+        .collect::<Vec<_>>();
+    scalar_types.into_iter().chain(list_types).collect()
+}
+
+impl Environment {
+    /// Initializes the function types.
+    pub fn init_funcs() -> BTreeMap<String, Vec<Rc<Func>>> {
+        let mut funcs = BTreeMap::new();
+        funcs.insert(
+            "upper".to_string(),
+            make_str_funcs!("upper".to_string(), upper),
+        );
+        funcs.insert(
+            "lower".to_string(),
+            make_str_funcs!("lower".to_string(), lower),
+        );
+        funcs.insert(
+            "snake_case".to_string(),
+            make_str_funcs!("snake_case".to_string(), to_snake_case),
+        );
+        funcs.insert(
+            "shouty_snake_case".to_string(),
+            make_str_funcs!("shouty_snake_case".to_string(), to_shouty_snake_case),
+        );
+        funcs.insert(
+            "kebab_case".to_string(),
+            make_str_only_funcs!("kebab_case".to_string(), to_kebab_case),
+        );
+        funcs.insert(
+            "title_case".to_string(),
+            make_str_only_funcs!("title_case".to_string(), to_title_case),
+        );
+        funcs.insert(
+            "camel_case".to_string(),
+            make_str_funcs!("camel_case".to_string(), to_camel_case),
+        );
+        funcs.insert(
+            "pascal_case".to_string(),
+            make_str_funcs!("pascal_case".to_string(), to_pascal_case),
+        );
+        funcs.insert(
+            "capitalize".to_string(),
+            make_str_funcs!("capitalize".to_string(), capitalize),
+        );
+        funcs.insert(
+            "decapitalize".to_string(),
+            make_str_funcs!("decapitalize".to_string(), decapitalize),
+        );
+        funcs.insert(
+            "replace".to_string(),
+            vec![
+                // replace(str, from, to)
+                Rc::new(Func::new(
+                    "replace".to_string(),
+                    vec![Type::LitStr, Type::LitStr, Type::LitStr],
+                    Type::LitStr,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(lit_str), ValueKind::LitStr(from), ValueKind::LitStr(to)] =
+                            kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let string = lit_str.value().replace(&from.value(), &to.value());
+                        Ok(Value::from_lit_str(LitStr::new(&string, lit_str.span())))
+                    },
+                )),
+                // replace(ident, from, to)
+                Rc::new(Func::new(
+                    "replace".to_string(),
+                    vec![Type::Ident, Type::LitStr, Type::LitStr],
+                    Type::Ident,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Ident(ident), ValueKind::LitStr(from), ValueKind::LitStr(to)] =
+                            kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let string = ident.to_string().replace(&from.value(), &to.value());
+                        let ident = make_ident(func, &string, ident.span())?;
+                        Ok(Value::from_ident(ident))
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "strip_prefix".to_string(),
+            make_strip_funcs!("strip_prefix".to_string(), str::strip_prefix),
+        );
+        funcs.insert(
+            "strip_suffix".to_string(),
+            make_strip_funcs!("strip_suffix".to_string(), str::strip_suffix),
+        );
+        funcs.insert(
+            "starts_with".to_string(),
+            make_predicate_funcs!("starts_with".to_string(), str::starts_with::<&str>),
+        );
+        funcs.insert(
+            "ends_with".to_string(),
+            make_predicate_funcs!("ends_with".to_string(), str::ends_with::<&str>),
+        );
+        funcs.insert(
+            "contains".to_string(),
+            make_predicate_funcs!("contains".to_string(), str::contains::<&str>),
+        );
+        funcs.insert(
+            "eq".to_string(),
+            vec![
+                // eq(tokens, tokens) -> bool
+                Rc::new(Func::new(
+                    "eq".to_string(),
+                    vec![Type::Tokens, Type::Tokens],
+                    Type::LitBool,
+                    |func, _, span, values| {
+                        let [value, other] = values else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(from_bool(tokens_eq(value, other), *span))
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "ne".to_string(),
+            vec![
+                // ne(tokens, tokens) -> bool
+                Rc::new(Func::new(
+                    "ne".to_string(),
+                    vec![Type::Tokens, Type::Tokens],
+                    Type::LitBool,
+                    |func, _, span, values| {
+                        let [value, other] = values else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(from_bool(!tokens_eq(value, other), *span))
+                    },
+                )),
+            ],
+        );
+        funcs.insert("hex".to_string(), make_radix_funcs!("hex".to_string(), 16));
+        funcs.insert("oct".to_string(), make_radix_funcs!("oct".to_string(), 8));
+        funcs.insert("bin".to_string(), make_radix_funcs!("bin".to_string(), 2));
+        funcs.insert(
+            "repeat".to_string(),
+            vec![
+                // repeat(str, int)
+                Rc::new(Func::new(
+                    "repeat".to_string(),
+                    vec![Type::LitStr, Type::LitInt],
+                    Type::LitStr,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(lit_str), ValueKind::LitInt(n)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let string = lit_str.value().repeat(repeat_count(n)?);
+                        Ok(Value::from_lit_str(LitStr::new(&string, lit_str.span())))
+                    },
+                )),
+                // repeat(ident, int)
+                Rc::new(Func::new(
+                    "repeat".to_string(),
+                    vec![Type::Ident, Type::LitInt],
+                    Type::Ident,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Ident(ident), ValueKind::LitInt(n)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let string = ident.unraw().to_string().repeat(repeat_count(n)?);
+                        let ident = make_ident(func, &string, ident.span())?;
+                        Ok(Value::from_ident(ident))
+                    },
+                )),
+                // repeat(tokens, int)
+                Rc::new(Func::new(
+                    "repeat".to_string(),
+                    vec![Type::Tokens, Type::LitInt],
+                    Type::Tokens,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(tokens), ValueKind::LitInt(n)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let mut repeated = TokenStream::new();
+                        for _ in 0..repeat_count(n)? {
+                            repeated.extend(tokens.clone());
+                        }
+                        Ok(Value::from_tokens(repeated))
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "unraw".to_string(),
+            vec![Rc::new(Func::new(
+                "unraw".to_string(),
+                vec![Type::Ident],
+                Type::Ident,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Ident(ident)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    Ok(unraw(ident))
+                },
+            ))],
+        );
+        funcs.insert(
+            "normalize".to_string(),
+            vec![Rc::new(Func::new(
+                "normalize".to_string(),
+                vec![Type::Raw],
+                Type::Ident,
+                |func, _, span, values| {
+                    let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Raw(tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    let ident = make_ident(func, &normalize(tokens.to_string().as_str()), *span)?;
+                    Ok(Value::from_ident(ident))
+                },
+            ))],
+        );
+        funcs.insert(
+            "normalize2".to_string(),
+            vec![
+                // normalize2(ident)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::Ident],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Ident(ident)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(ident.to_string().as_str());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+                // normalize2(str)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::LitStr],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(s)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(s.value().as_str());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+                // normalize2(int)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::LitInt],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitInt(i)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(i.base10_digits());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+                // normalize2(path)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::Path],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Path(path)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(path.to_token_stream().to_string().as_str());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+                // normalize2(type)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::Type],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Type(ty)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(ty.to_token_stream().to_string().as_str());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+                // normalize2(expr)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::Expr],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Expr(expr)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(expr.to_token_stream().to_string().as_str());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+                // normalize2(tokens)
+                Rc::new(Func::new(
+                    "normalize2".to_string(),
+                    vec![Type::Tokens],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(tokens)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let out = normalize(tokens.to_string().as_str());
+                        Ok(Value::from_ident(make_ident(func, &out, *span)?))
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "env".to_string(),
+            vec![
+                // env(str) -> str
+                Rc::new(Func::new(
+                    "env".to_string(),
+                    vec![Type::LitStr],
+                    Type::LitStr,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(name)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        env(name, None, *span)
+                    },
+                )),
+                // env(str, str) -> str
+                Rc::new(Func::new(
+                    "env".to_string(),
+                    vec![Type::LitStr, Type::LitStr],
+                    Type::LitStr,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(name), ValueKind::LitStr(default)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        env(name, Some(default), *span)
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "global_counter".to_string(),
+            vec![Rc::new(
+                Func::new(
+                    "global_counter".to_string(),
+                    vec![Type::Tokens],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(tokens)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(global_counter(concat_piece(tokens).as_str(), *span))
+                    },
+                )
+                .with_global_state(),
+            )],
+        );
+        funcs.insert(
+            "hash".to_string(),
+            vec![
+                // hash(str)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::LitStr],
+                    Type::LitStr,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(lit_str)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let result = hash(lit_str.value().as_str(), state.seed());
+                        let lit_str = LitStr::new(result.as_str(), *span);
+
+                        Ok(Value::from_lit_str(lit_str))
+                    },
+                )),
+                // hash(ident)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Ident],
+                    Type::Ident,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Ident(ident)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let prefix = state.options().hash_prefix().to_string();
+                        hash_ident(&ident.to_string(), &prefix, state.seed(), *span)
+                    },
+                )),
+                // hash(ident, prefix)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Ident, Type::LitStr],
+                    Type::Ident,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Ident(ident), ValueKind::LitStr(prefix)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        hash_ident(&ident.to_string(), &prefix.value(), state.seed(), *span)
+                    },
+                )),
+                // hash(tokens)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Tokens],
+                    Type::Ident,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(stream)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        let prefix = state.options().hash_prefix().to_string();
+                        hash_ident(&stream.to_string(), &prefix, state.seed(), *span)
+                    },
+                )),
+                // hash(tokens, prefix)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Tokens, Type::LitStr],
+                    Type::Ident,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(stream), ValueKind::LitStr(prefix)] =
+                            kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        hash_ident(&stream.to_string(), &prefix.value(), state.seed(), *span)
+                    },
+                )),
+                // hash(str, seed)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::LitStr, Type::LitInt],
+                    Type::LitStr,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(lit_str), ValueKind::LitInt(seed)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let result = hash(lit_str.value().as_str(), seed_from_int(seed)?);
+                        let lit_str = LitStr::new(result.as_str(), *span);
+
+                        Ok(Value::from_lit_str(lit_str))
+                    },
+                )),
+                // hash(tokens, seed)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Tokens, Type::LitInt],
+                    Type::Ident,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(stream), ValueKind::LitInt(seed)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let prefix = state.options().hash_prefix().to_string();
+                        let seed = seed_from_int(seed)?;
+                        hash_ident(&canonical_string(stream), &prefix, seed, *span)
+                    },
+                )),
+                // hash(tokens, prefix, seed)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Tokens, Type::LitStr, Type::LitInt],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(stream), ValueKind::LitStr(prefix), ValueKind::LitInt(seed)] =
+                            kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let seed = seed_from_int(seed)?;
+                        hash_ident(&canonical_string(stream), &prefix.value(), seed, *span)
+                    },
+                )),
+                // hash(tokens, prefix, seed)
+                Rc::new(Func::new(
+                    "hash".to_string(),
+                    vec![Type::Tokens, Type::LitStr, Type::LitStr],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(stream), ValueKind::LitStr(prefix), ValueKind::LitStr(seed)] =
+                            kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let seed = seed_from_str(seed.value().as_str());
+                        hash_ident(&canonical_string(stream), &prefix.value(), seed, *span)
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "short_hash".to_string(),
+            vec![
+                // short_hash(str, len)
+                Rc::new(Func::new(
+                    "short_hash".to_string(),
+                    vec![Type::LitStr, Type::LitInt],
+                    Type::LitStr,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(lit_str), ValueKind::LitInt(len)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let result = short_hash(lit_str.value().as_str(), state.seed(), len)?;
+                        let lit_str = LitStr::new(result.as_str(), *span);
+
+                        Ok(Value::from_lit_str(lit_str))
+                    },
+                )),
+                // short_hash(tokens, len)
+                Rc::new(Func::new(
+                    "short_hash".to_string(),
+                    vec![Type::Tokens, Type::LitInt],
+                    Type::Ident,
+                    |func, state, span, values| {
+                        let kind = values.iter().map(|item| item.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(stream), ValueKind::LitInt(len)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let prefix = state.options().hash_prefix().to_string();
+                        short_hash_ident(&stream.to_string(), &prefix, state.seed(), len, *span)
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "concat".to_string(),
+            vec![
+                // concat(ident1, ident2, ...)
+                Rc::new(Func::new(
+                    "concat".to_string(),
+                    vec![Type::Variadic(Box::new(Type::Ident), 1)],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let mut strings = Vec::new();
+                        for value in values {
+                            let ValueKind::Ident(ident) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            strings.push(ident.to_string());
+                        }
+                        let strs = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                        let result = concat(strs.as_slice());
+                        let ident = make_ident(func, &result, *span)?;
+
+                        Ok(Value::from_ident(ident))
+                    },
+                )),
+                // concat(ident, tokens...)
+                Rc::new(Func::new(
+                    "concat".to_string(),
+                    vec![Type::Ident, Type::Variadic(Box::new(Type::Tokens), 1)],
+                    Type::Ident,
+                    |func, _, span, values| {
+                        let mut strings = Vec::new();
+                        let Some(first) = values.first() else {
+                            arg_type_err!(func, values);
+                        };
+                        strings.push(first.to_token_stream().to_string());
+                        for value in values.iter().skip(1) {
+                            let ValueKind::Tokens(tokens) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            strings.push(concat_piece(tokens));
+                        }
+                        let strs = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                        let result = concat(strs.as_slice());
+                        let Some(escaped) = escape_keyword(&result) else {
+                            return Err(keyword_err(func, &result, *span));
+                        };
+
+                        let Ok(ident) = syn::parse_str::<Ident>(escaped.as_str()) else {
+                            return Err(Error::EvalError(
+                                format!(
+                                    "Failed to produce a valid identifier \
+                                    from concatenated arguments: {}",
+                                    values
+                                        .iter()
+                                        .map(|v| v.to_token_stream().to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                ),
+                                *span,
+                            ));
+                        };
+
+                        Ok(Value::from_ident(ident))
+                    },
+                )),
+                // concat(str...)
+                Rc::new(Func::new(
+                    "concat".to_string(),
+                    vec![Type::Variadic(Box::new(Type::LitStr), 1)],
+                    Type::LitStr,
+                    |func, _, span, values| {
+                        let mut strings = Vec::new();
+                        for value in values {
+                            let ValueKind::LitStr(lit_str) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            strings.push(lit_str.value());
+                        }
+                        let strs = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                        let result = concat(strs.as_slice());
+                        let lit_str = LitStr::new(result.as_str(), *span);
+
+                        Ok(Value::from_lit_str(lit_str))
+                    },
+                )),
+                // concat(str, tokens...)
+                Rc::new(Func::new(
+                    "concat".to_string(),
+                    vec![Type::LitStr, Type::Variadic(Box::new(Type::Tokens), 1)],
+                    Type::LitStr,
+                    |func, _, span, values| {
+                        let mut strings = Vec::new();
+                        let Some(ValueKind::LitStr(first)) = values.first().map(|v| v.kind())
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        strings.push(first.value());
+                        for value in values.iter().skip(1) {
+                            let ValueKind::Tokens(tokens) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            strings.push(concat_piece(tokens));
+                        }
+                        let strs = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                        let result = concat(strs.as_slice());
+                        let lit_str = LitStr::new(result.as_str(), *span);
+
+                        Ok(Value::from_lit_str(lit_str))
+                    },
+                )),
+                // concat(int...)
+                Rc::new(Func::new(
+                    "concat".to_string(),
+                    vec![Type::Variadic(Box::new(Type::LitInt), 1)],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let mut digits = Vec::new();
+                        for value in values {
+                            let ValueKind::LitInt(lit_int) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            digits.push(lit_int.base10_digits());
+                        }
+                        let result = concat(digits.as_slice());
+                        // A sign of a negative integer is only valid at the beginning.
+                        let Ok(mut lit_int) = syn::parse_str::<LitInt>(result.as_str()) else {
+                            return Err(Error::EvalError(
+                                format!(
+                                    "Failed to produce a valid integer from concatenated \
+                                    arguments: {}",
+                                    digits.join(", "),
+                                ),
+                                *span,
+                            ));
+                        };
+                        lit_int.set_span(*span);
+
+                        Ok(Value::from_lit_int(lit_int))
+                    },
+                )),
+                // concat(tokens1, tokens2, ...)
+                Rc::new(Func::new(
+                    "concat".to_string(),
+                    vec![Type::Variadic(Box::new(Type::Tokens), 1)],
+                    Type::Tokens,
+                    |func, _, _, values| {
+                        let mut tokens = TokenStream::new();
+                        for value in values {
+                            let ValueKind::Tokens(stream) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            tokens.extend(stream.clone().into_iter());
+                        }
+
+                        Ok(Value::from_tokens(tokens))
+                    },
+                )),
+            ],
+        );
+        // Casting functions
+        funcs.insert(
+            "join".to_string(),
+            vec![
+                // join(str, ident...)
+                Rc::new(
+                    Func::new(
+                        "join".to_string(),
+                        vec![Type::LitStr, Type::Variadic(Box::new(Type::Ident), 1)],
+                        Type::Ident,
+                        |func, _, span, values| {
+                            let Some(ValueKind::LitStr(separator)) =
+                                values.first().map(|v| v.kind())
+                            else {
+                                arg_type_err!(func, values);
+                            };
+                            let mut strings = Vec::new();
+                            for value in values.iter().skip(1) {
+                                let ValueKind::Ident(ident) = value.kind() else {
+                                    arg_type_err!(func, values);
+                                };
+                                strings.push(ident.to_string());
+                            }
+                            let strs = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                            let result = join(separator.value().as_str(), strs.as_slice());
+                            let ident = make_ident(func, &result, *span)?;
+
+                            Ok(Value::from_ident(ident))
+                        },
+                    )
+                    .with_args_check(is_ident_separator),
+                ),
+                // join(str, tokens...)
+                Rc::new(Func::new(
+                    "join".to_string(),
+                    vec![Type::LitStr, Type::Variadic(Box::new(Type::Tokens), 1)],
+                    Type::LitStr,
+                    |func, _, span, values| {
+                        let Some(ValueKind::LitStr(separator)) = values.first().map(|v| v.kind())
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let mut strings = Vec::new();
+                        for value in values.iter().skip(1) {
+                            let ValueKind::Tokens(tokens) = value.kind() else {
+                                arg_type_err!(func, values);
+                            };
+                            strings.push(concat_piece(tokens));
+                        }
+                        let strs = strings.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                        let result = join(separator.value().as_str(), strs.as_slice());
+                        let lit_str = LitStr::new(result.as_str(), *span);
+
+                        Ok(Value::from_lit_str(lit_str))
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "to_ident".to_string(),
+            vec![Rc::new(Func::new(
+                "to_ident".to_string(),
+                vec![Type::Tokens],
+                Type::Ident,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_ident(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_path".to_string(),
+            vec![Rc::new(Func::new(
+                "to_path".to_string(),
+                vec![Type::Tokens],
+                Type::Path,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_path(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_type".to_string(),
+            vec![Rc::new(Func::new(
+                "to_type".to_string(),
+                vec![Type::Tokens],
+                Type::Type,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_type(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_expr".to_string(),
+            vec![Rc::new(Func::new(
+                "to_expr".to_string(),
+                vec![Type::Tokens],
+                Type::Expr,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_expr(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_str".to_string(),
+            vec![Rc::new(Func::new(
+                "to_str".to_string(),
+                vec![Type::Tokens],
+                Type::LitStr,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_str(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_byte_str".to_string(),
+            vec![
+                // to_byte_str(str) -> byte_str
+                Rc::new(Func::new(
+                    "to_byte_str".to_string(),
+                    vec![Type::LitStr],
+                    Type::LitByteStr,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(_)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        to_byte_str(values[0].as_ref())
+                    },
+                )),
+                // to_byte_str(str, bool) -> byte_str
+                Rc::new(Func::new(
+                    "to_byte_str".to_string(),
+                    vec![Type::LitStr, Type::LitBool],
+                    Type::LitByteStr,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(_), ValueKind::LitBool(nul)] = kind.as_slice()
+                        else {
+                            arg_type_err!(func, values);
+                        };
+                        let value = to_byte_str(values[0].as_ref())?;
+                        let ValueKind::LitByteStr(lit_byte_str) = value.kind() else {
+                            unreachable!()
+                        };
+                        if !nul.value() {
+                            return Ok(value);
+                        }
+                        Ok(nul_terminated(lit_byte_str))
+                    },
+                )),
+                // to_byte_str(tokens) -> byte_str
+                Rc::new(Func::new(
+                    "to_byte_str".to_string(),
+                    vec![Type::Tokens],
+                    Type::LitByteStr,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        to_byte_str(values[0].as_ref())
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "to_int".to_string(),
+            vec![Rc::new(Func::new(
+                "to_int".to_string(),
+                vec![Type::Tokens],
+                Type::LitInt,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_int(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_list".to_string(),
+            vec![Rc::new(Func::new(
+                "to_list".to_string(),
+                vec![Type::Expr],
+                Type::List(Box::new(Type::LitInt)),
+                |func, _, span, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Expr(expr)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_list(expr, *span)
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_float".to_string(),
+            vec![Rc::new(Func::new(
+                "to_float".to_string(),
+                vec![Type::Tokens],
+                Type::LitFloat,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_float(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_char".to_string(),
+            vec![Rc::new(Func::new(
+                "to_char".to_string(),
+                vec![Type::Tokens],
+                Type::LitChar,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_char(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_bool".to_string(),
+            vec![Rc::new(Func::new(
+                "to_bool".to_string(),
+                vec![Type::Tokens],
+                Type::LitBool,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_bool(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_lifetime".to_string(),
+            vec![Rc::new(Func::new(
+                "to_lifetime".to_string(),
+                vec![Type::Tokens],
+                Type::Lifetime,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_lifetime(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_attr".to_string(),
+            vec![Rc::new(Func::new(
+                "to_attr".to_string(),
+                vec![Type::Tokens],
+                Type::Attr,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    to_attr(values[0].as_ref())
+                },
+            ))],
+        );
+        funcs.insert(
+            "to_tokens".to_string(),
+            vec![Rc::new(Func::new(
+                "to_tokens".to_string(),
+                vec![Type::Tokens],
+                Type::Tokens,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Tokens(_tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    Ok(values[0].deref().clone())
+                },
+            ))],
+        );
+        funcs.insert(
+            "raw".to_string(),
+            vec![Rc::new(Func::new(
+                "raw".to_string(),
+                vec![Type::Raw],
+                Type::Tokens,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Raw(tokens)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    Ok(Value::from_tokens(tokens.clone()))
+                },
+            ))],
+        );
+        // List manipulation functions
+        funcs.insert(
+            "index".to_string(),
+            // index([T], int) -> T
+            list_item_types()
+                .into_iter()
+                .map(|item_type| {
+                    Rc::new(Func::new(
+                        "index".to_string(),
+                        vec![Type::List(Box::new(item_type.clone())), Type::LitInt],
+                        item_type,
+                        |func, _, span, values| {
+                            let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                            let [ValueKind::List(items), ValueKind::LitInt(i)] = kind.as_slice()
+                            else {
+                                arg_type_err!(func, values);
+                            };
+                            index(items, i, *span)
+                        },
+                    ))
+                })
+                .collect(),
+        );
+        funcs.insert(
+            "last_segment".to_string(),
+            vec![
+                // last_segment(path)
+                Rc::new(Func::new(
+                    "last_segment".to_string(),
+                    vec![Type::Path],
+                    Type::Ident,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Path(path)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        last_segment(path)
+                    },
+                )),
+                // last_segment(type)
+                Rc::new(Func::new(
+                    "last_segment".to_string(),
+                    vec![Type::Type],
+                    Type::Ident,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Type(type_)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        last_segment_of_type(type_)
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "strip_generics".to_string(),
+            vec![
+                // strip_generics(path)
+                Rc::new(Func::new(
+                    "strip_generics".to_string(),
+                    vec![Type::Path],
+                    Type::Path,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Path(path)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(strip_generics_of_path(path))
+                    },
+                )),
+                // strip_generics(type)
+                Rc::new(Func::new(
+                    "strip_generics".to_string(),
+                    vec![Type::Type],
+                    Type::Type,
+                    |func, _, _, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Type(type_)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(strip_generics(type_))
+                    },
+                )),
+            ],
+        );
+        funcs.insert(
+            "strip_ref".to_string(),
+            vec![Rc::new(Func::new(
+                "strip_ref".to_string(),
+                vec![Type::Type],
+                Type::Type,
+                |func, _, _, values| {
+                    let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                    let [ValueKind::Type(type_)] = kind.as_slice() else {
+                        arg_type_err!(func, values);
+                    };
+                    Ok(strip_ref(type_))
+                },
+            ))],
+        );
+        funcs.insert(
+            "select".to_string(),
+            // select(tokens, T, T) -> T
+            list_item_types()
+                .into_iter()
+                .map(|type_| {
+                    Rc::new(Func::new(
+                        "select".to_string(),
+                        vec![Type::Tokens, type_.clone(), type_.clone()],
+                        type_,
+                        |func, _, _, values| {
+                            let [cond, then, otherwise] = values else {
+                                arg_type_err!(func, values);
+                            };
+                            let value = if bool_value(cond)? { then } else { otherwise };
+                            Ok(value.as_ref().clone())
+                        },
+                    ))
+                })
+                .collect(),
+        );
+        funcs.insert(
+            "len".to_string(),
+            vec![
+                // len([tokens])
+                Rc::new(Func::new(
+                    "len".to_string(),
+                    vec![Type::List(Box::new(Type::Tokens))],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::List(items)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(len(items, *span))
+                    },
+                )),
+                // len([[tokens]])
+                Rc::new(Func::new(
+                    "len".to_string(),
+                    vec![Type::List(Box::new(Type::List(Box::new(Type::Tokens))))],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::List(items)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(len(items, *span))
+                    },
+                )),
+                // len(str)
+                Rc::new(Func::new(
+                    "len".to_string(),
+                    vec![Type::LitStr],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::LitStr(lit_str)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(char_count(lit_str.value().as_str(), *span))
+                    },
+                )),
+                // len(ident)
+                Rc::new(Func::new(
+                    "len".to_string(),
+                    vec![Type::Ident],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Ident(ident)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        Ok(char_count(ident.unraw().to_string().as_str(), *span))
+                    },
+                )),
+                // len(tokens)
+                Rc::new(Func::new(
+                    "len".to_string(),
+                    vec![Type::Tokens],
+                    Type::LitInt,
+                    |func, _, span, values| {
+                        let kind = values.iter().map(|v| v.kind()).collect::<Vec<_>>();
+                        let [ValueKind::Tokens(tokens)] = kind.as_slice() else {
+                            arg_type_err!(func, values);
+                        };
+                        tokens_char_count(tokens, *span)
+                    },
+                )),
+            ],
+        );
+        funcs
+    }
+}
@@ -5,3 +5,6 @@ pub use type_::*;
 
 mod environment;
 pub use environment::*;
+
+mod options;
+pub use options::*;
@@ -0,0 +1,144 @@
+use proc_macro2::Span;
+
+/// Names of the options that could be specified with `options(...)` directive.
+pub const OPTION_NAMES: &[&str] = &[
+    "allow_global_state",
+    "strict",
+    "max_combinations",
+    "deny_deprecated",
+    "allow_deprecated",
+    "trace",
+    "pretty",
+    "expand",
+    "hash_prefix",
+    "scoped",
+];
+
+/// Maximum number of loop combinations unless it is specified with `max_combinations` option.
+pub const DEFAULT_MAX_COMBINATIONS: usize = 10_000;
+
+/// Prefix of the identifiers generated by `hash()` unless it is specified.
+pub const DEFAULT_HASH_PREFIX: &str = "__";
+
+/// Order of the expansion of `#[compose_item]` relative to the other attribute macros of the item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExpansionOrder {
+    /// Expands before the attributes following it - they see the code with the aliases substituted.
+    #[default]
+    Before,
+    /// Expands after the attributes following it - they see the code as it was written.
+    After,
+}
+
+/// Options of a single macro invocation specified with `options(...)` directive at the start of
+/// the alias spec.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Acknowledges the caveats of functions relying on state shared between macro invocations.
+    allow_global_state: bool,
+    /// Evaluates all aliases including the ones not used by the code block.
+    strict: bool,
+    /// Maximum number of loop combinations the code block could be expanded to.
+    max_combinations: Option<usize>,
+    /// Turns deprecation warnings into hard errors.
+    deny_deprecated: bool,
+    /// Identifiers of the deprecation warnings suppressed for the invocation.
+    allow_deprecated: Vec<String>,
+    /// Writes the expansion trace even if the trace directory isn't configured.
+    trace: bool,
+    /// Formats the expanded code.
+    pretty: bool,
+    /// Order of the expansion relative to the other attribute macros of the item.
+    expand: ExpansionOrder,
+    /// Prefix of the identifiers generated by `hash()`.
+    hash_prefix: Option<String>,
+    /// Wraps the generated items into an anonymous const scope.
+    scoped: bool,
+    span: Option<Span>,
+}
+
+impl Options {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        allow_global_state: bool,
+        strict: bool,
+        max_combinations: Option<usize>,
+        deny_deprecated: bool,
+        allow_deprecated: Vec<String>,
+        trace: bool,
+        pretty: bool,
+        expand: ExpansionOrder,
+        hash_prefix: Option<String>,
+        scoped: bool,
+        span: Option<Span>,
+    ) -> Self {
+        Self {
+            allow_global_state,
+            strict,
+            max_combinations,
+            deny_deprecated,
+            allow_deprecated,
+            trace,
+            pretty,
+            expand,
+            hash_prefix,
+            scoped,
+            span,
+        }
+    }
+
+    /// Whether functions relying on state shared between invocations are allowed.
+    pub fn allow_global_state(&self) -> bool {
+        self.allow_global_state
+    }
+
+    /// Whether all aliases are evaluated regardless of their usage.
+    pub fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Maximum number of loop combinations if it is limited.
+    pub fn max_combinations(&self) -> Option<usize> {
+        self.max_combinations
+    }
+
+    /// Whether usage of deprecated syntax is an error.
+    pub fn deny_deprecated(&self) -> bool {
+        self.deny_deprecated
+    }
+
+    /// Identifiers of the suppressed deprecation warnings.
+    pub fn allow_deprecated(&self) -> &[String] {
+        &self.allow_deprecated
+    }
+
+    /// Whether writing of the expansion trace is forced.
+    pub fn trace(&self) -> bool {
+        self.trace
+    }
+
+    /// Whether the expanded code is formatted.
+    pub fn pretty(&self) -> bool {
+        self.pretty
+    }
+
+    /// Order of the expansion relative to the other attribute macros of the item.
+    pub fn expand(&self) -> ExpansionOrder {
+        self.expand
+    }
+
+    /// Prefix of the identifiers generated by `hash()` - [`DEFAULT_HASH_PREFIX`] unless specified.
+    pub fn hash_prefix(&self) -> &str {
+        self.hash_prefix.as_deref().unwrap_or(DEFAULT_HASH_PREFIX)
+    }
+
+    /// Whether the generated items are wrapped into an anonymous const scope.
+    pub fn scoped(&self) -> bool {
+        self.scoped
+    }
+
+    /// Span of the `options(...)` directive if it was specified.
+    pub fn span(&self) -> Option<Span> {
+        self.span
+    }
+}
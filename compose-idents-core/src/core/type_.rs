@@ -0,0 +1,327 @@
+//! Contains implementation of the little type system used by the library.
+
+use crate::ast::{Expr, Value};
+use crate::core::Environment;
+use crate::error::Error;
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::Span;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// Type of value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Type {
+    Ident,
+    Path,
+    Type,
+    Expr,
+    LitStr,
+    LitByteStr,
+    LitInt,
+    LitFloat,
+    LitChar,
+    LitBool,
+    Lifetime,
+    Tokens,
+    Raw,
+    Attr,
+    /// Variadic parameter accepting the specified minimum number of arguments or more.
+    Variadic(Box<Type>, usize),
+    List(Box<Type>),
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Ident => write!(f, "ident"),
+            Type::Path => write!(f, "path"),
+            Type::Type => write!(f, "type"),
+            Type::Expr => write!(f, "expr"),
+            Type::LitStr => write!(f, "str"),
+            Type::LitByteStr => write!(f, "byte_str"),
+            Type::LitInt => write!(f, "int"),
+            Type::LitFloat => write!(f, "float"),
+            Type::LitChar => write!(f, "char"),
+            Type::LitBool => write!(f, "bool"),
+            Type::Lifetime => write!(f, "lifetime"),
+            Type::Tokens => write!(f, "tokens"),
+            Type::Raw => write!(f, "raw"),
+            Type::Attr => write!(f, "attr"),
+            Type::Variadic(type_, 0) => write!(f, "{}...", type_),
+            Type::Variadic(type_, 1) => write!(f, "{}...+", type_),
+            Type::Variadic(type_, min) => write!(f, "{}...+{}", type_, min),
+            Type::List(type_) => write!(f, "[{}]", type_),
+        }
+    }
+}
+
+impl Type {
+    pub fn coercion_cost_basic(from: &Type, to: &Type) -> Option<u32> {
+        match (from, to) {
+            (_, _) if from == to => Some(0),
+            (Type::List(from), Type::List(to)) => Self::coercion_cost_basic(from, to),
+            (Type::List(_), _) | (_, Type::List(_)) => None,
+            (Type::Ident, Type::Path) => Some(1),
+            (Type::Ident, Type::Type) => Some(2),
+            (Type::Ident, Type::Expr) => Some(3),
+            (Type::LitFloat, Type::Expr) => Some(3),
+            (Type::LitBool, Type::Ident) => Some(3),
+            (Type::LitChar, Type::LitStr) => Some(3),
+            (_, Type::Tokens) => Some(4),
+            (_, Type::Raw) => Some(5),
+            _ => None,
+        }
+    }
+
+    /// Finds the type both of the types could be coerced to.
+    ///
+    /// Scalar types that aren't coercible to each other have [`Type::Tokens`] as their common
+    /// type. Returns `None` if a list type is mixed with a scalar type.
+    pub fn common(a: &Type, b: &Type) -> Option<Type> {
+        match (a, b) {
+            (_, _) if a == b => Some(a.clone()),
+            (Type::List(a), Type::List(b)) => Some(Type::List(Box::new(Self::common(a, b)?))),
+            (Type::List(_), _) | (_, Type::List(_)) => None,
+            (_, _) if Self::coercion_cost(a, b).is_some() => Some(b.clone()),
+            (_, _) if Self::coercion_cost(b, a).is_some() => Some(a.clone()),
+            (_, _) => Some(Type::Tokens),
+        }
+    }
+
+    /// Cost of the coercion from one type to another.
+    ///
+    /// Returns `None` if the casting is not possible.
+    pub fn coercion_cost(from: &Type, to: &Type) -> Option<u32> {
+        match (from, to) {
+            (Type::Variadic(boxed_from, _), Type::Variadic(boxed_to, _)) => {
+                Self::coercion_cost_basic(boxed_from.as_ref(), boxed_to.as_ref())
+            }
+            (from, to) => Self::coercion_cost_basic(from, to),
+        }
+    }
+}
+
+pub type FuncImpl = fn(&Func, &Environment, &Span, &[Rc<Value>]) -> Result<Value, Error>;
+
+/// Checks the raw arguments of a call during resolution - the overload is skipped if it rejects
+/// them.
+pub type ArgsCheck = fn(&[Rc<Expr>]) -> bool;
+
+/// Function type.
+///
+/// Describes function's name, signature, output type, stores the pointer to implementation.
+#[derive(Debug, Clone)]
+pub struct Func {
+    id: u64,
+    name: String,
+    arg_types: Vec<Type>,
+    out_type: Type,
+    func_impl: FuncImpl,
+    /// Whether the function relies on state shared between macro invocations.
+    global_state: bool,
+    args_check: Option<ArgsCheck>,
+}
+
+impl Func {
+    pub fn new(name: String, arg_types: Vec<Type>, out_type: Type, func_impl: FuncImpl) -> Self {
+        debug_assert!(
+            arg_types
+                .iter()
+                .filter(|x| matches!(x, Type::Variadic(..)))
+                .count()
+                <= 1,
+            "Function {} cannot have more than one variadic parameter",
+            name,
+        );
+        debug_assert!(
+            arg_types
+                .iter()
+                .enumerate()
+                .find(|(_, x)| matches!(x, Type::Variadic(..)))
+                .map_or(true, |(i, _)| i == arg_types.len() - 1),
+            "Variadic parameter must be the last one in the function {}",
+            name,
+        );
+        debug_assert!(
+            if arg_types.iter().any(|a| a == &Type::Raw) {
+                arg_types.len() == 1
+            } else {
+                true
+            },
+            "Function {} can have Raw type only if it is the only argument",
+            name,
+        );
+
+        Self {
+            id: next_unique_id(),
+            name,
+            arg_types,
+            out_type,
+            func_impl,
+            global_state: false,
+            args_check: None,
+        }
+    }
+
+    /// Marks the function as relying on state shared between macro invocations.
+    pub fn with_global_state(mut self) -> Self {
+        self.global_state = true;
+        self
+    }
+
+    /// Whether the function relies on state shared between macro invocations.
+    pub fn uses_global_state(&self) -> bool {
+        self.global_state
+    }
+
+    /// Makes the overload applicable only to the calls whose raw arguments pass the check.
+    pub fn with_args_check(mut self, args_check: ArgsCheck) -> Self {
+        self.args_check = Some(args_check);
+        self
+    }
+
+    /// Whether the overload is applicable to the raw arguments of a call.
+    pub fn accepts_args(&self, args: &[Rc<Expr>]) -> bool {
+        self.args_check.map_or(true, |check| check(args))
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The function's signature.
+    pub fn signature(&self) -> String {
+        let args = self
+            .arg_types
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({}) -> {}", self.name, args, self.out_type)
+    }
+
+    /// Creates an error for incorrect types of provided arguments.
+    pub fn arg_type_error(&self, args: &[Value], span: &Span) -> Error {
+        Error::TypeError(
+            format!(
+                "Incorrect arguments for {}: {}",
+                self.signature(),
+                args.iter()
+                    .map(|arg| format!("{:?}", arg.type_()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            *span,
+        )
+    }
+
+    /// Argument types of the function.
+    pub fn arg_types(&self) -> &[Type] {
+        &self.arg_types
+    }
+
+    /// Output type of the function.
+    pub fn out_type(&self) -> &Type {
+        &self.out_type
+    }
+
+    /// Whether the function has at least one variadic parameter or not.
+    pub fn is_variadic(&self) -> bool {
+        self.arg_types
+            .iter()
+            .any(|t| matches!(t, Type::Variadic(..)))
+    }
+
+    pub fn non_variadic_arg_types(&self) -> Vec<Type> {
+        self.arg_types
+            .iter()
+            .filter(|t| !matches!(t, Type::Variadic(..)))
+            .cloned()
+            .collect()
+    }
+
+    pub fn variadic_arg_type(&self) -> Option<Type> {
+        self.arg_types
+            .iter()
+            .find(|t| matches!(t, Type::Variadic(..)))
+            .map(|t| {
+                if let Type::Variadic(boxed_type, _) = t {
+                    *boxed_type.clone()
+                } else {
+                    unreachable!()
+                }
+            })
+    }
+
+    /// The minimum number of arguments the function accepts.
+    pub fn min_num_args(&self) -> usize {
+        self.arg_types
+            .iter()
+            .map(|t| match t {
+                Type::Variadic(_, min) => *min,
+                _ => 1,
+            })
+            .sum()
+    }
+
+    pub fn num_args(&self) -> usize {
+        self.arg_types.len()
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Calls the function with the provided arguments.
+    pub fn call(
+        &self,
+        args: &[Rc<Value>],
+        environment: &Environment,
+        span: Option<Span>,
+    ) -> Result<Value, Error> {
+        let span = span.unwrap_or_else(Span::call_site);
+        (self.func_impl)(self, environment, &span, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Type;
+    use rstest::rstest;
+
+    fn list(item_type: Type) -> Type {
+        Type::List(Box::new(item_type))
+    }
+
+    #[rstest]
+    #[case::list_to_list(list(Type::Ident), list(Type::Path), Some(1))]
+    #[case::nested_list_to_list(list(list(Type::Ident)), list(list(Type::Tokens)), Some(4))]
+    #[case::list_to_incompatible_list(list(Type::LitStr), list(Type::Ident), None)]
+    #[case::list_to_tokens(list(Type::Ident), Type::Tokens, None)]
+    #[case::list_to_scalar(list(Type::Ident), Type::Ident, None)]
+    #[case::scalar_to_list(Type::Ident, list(Type::Ident), None)]
+    #[case::nested_list_to_flat_list(list(list(Type::Ident)), list(Type::Tokens), None)]
+    fn list_coercion_cost(#[case] from: Type, #[case] to: Type, #[case] expected: Option<u32>) {
+        assert_eq!(Type::coercion_cost(&from, &to), expected);
+    }
+
+    #[rstest]
+    #[case::same(Type::Ident, Type::Ident, Some(Type::Ident))]
+    #[case::coercible(Type::Path, Type::Ident, Some(Type::Path))]
+    #[case::incompatible(Type::LitStr, Type::Ident, Some(Type::Tokens))]
+    #[case::lists(list(Type::Ident), list(Type::Type), Some(list(Type::Type)))]
+    #[case::list_and_scalar(list(Type::Ident), Type::Ident, None)]
+    fn common_type(#[case] a: Type, #[case] b: Type, #[case] expected: Option<Type>) {
+        assert_eq!(Type::common(&a, &b), expected);
+    }
+
+    #[rstest]
+    #[case::zero_or_more(Type::Variadic(Box::new(Type::Ident), 0), "ident...")]
+    #[case::one_or_more(Type::Variadic(Box::new(Type::Ident), 1), "ident...+")]
+    #[case::two_or_more(Type::Variadic(Box::new(Type::Ident), 2), "ident...+2")]
+    fn variadic_display(#[case] type_: Type, #[case] expected: &str) {
+        assert_eq!(type_.to_string(), expected);
+    }
+}
@@ -1,5 +1,4 @@
-use crate::ast::{Ast, Call};
-use crate::core::{Func, Type};
+use crate::core::Type;
 use proc_macro2::Span;
 use syn::Error as SynError;
 use thiserror::Error as ThisError;
@@ -13,12 +12,18 @@ pub enum Error {
     EvalError(String, Span),
     #[error("RedefinedNameError: name {0} has already been defined")]
     RedefinedNameError(String, Span),
+    #[error("UndefinedNameError: name {0} is undefined")]
+    UndefinedNameError(String, Span),
+    #[error("CyclicReferenceError: aliases reference each other in a cycle: {0}")]
+    CyclicReferenceError(String, Span),
     #[error("SignatureError: function {0} has been called with incompatible arguments: {1}")]
     SignatureError(String, String, Span),
     #[error(r#"UndefinedFunctionError: function "{0}(...)" is undefined"#)]
     UndefinedFunctionError(String, Span),
     #[error("SubstitutionError: failed to substitute:\n\n  {0}\n\nwith:\n\n  {1}\n\nEncountered an error:\n\n  {2}")]
     SubstitutionError(String, String, #[source] syn::Error, Span),
+    #[error("{0}")]
+    ParseError(#[source] syn::Error),
     #[error("InternalError: {0}")]
     InternalError(String),
 }
@@ -29,17 +34,16 @@ impl Error {
             Error::TypeError(_, span) => *span,
             Error::EvalError(_, span) => *span,
             Error::RedefinedNameError(_, span) => *span,
+            Error::UndefinedNameError(_, span) => *span,
+            Error::CyclicReferenceError(_, span) => *span,
             Error::SignatureError(_, _, span) => *span,
             Error::UndefinedFunctionError(_, span) => *span,
             Error::SubstitutionError(_, _, _, span) => *span,
+            Error::ParseError(err) => err.span(),
             Error::InternalError(_) => Span::call_site(),
         }
     }
 
-    pub fn make_sig_error(func: &Func, call: &Call) -> Error {
-        Error::SignatureError(func.signature(), call.to_string(), call.span())
-    }
-
     pub fn make_coercion_error(from: &Type, to: &Type) -> Error {
         Error::TypeError(
             format!("impossible to coerce from {} to {}", from, to),
@@ -56,9 +60,12 @@ impl Error {
             Error::TypeError(_, _) => ErrorType::TypeError,
             Error::EvalError(_, _) => ErrorType::EvalError,
             Error::RedefinedNameError(_, _) => ErrorType::RedefinedNameError,
+            Error::UndefinedNameError(_, _) => ErrorType::UndefinedNameError,
+            Error::CyclicReferenceError(_, _) => ErrorType::CyclicReferenceError,
             Error::SignatureError(_, _, _) => ErrorType::SignatureError,
             Error::UndefinedFunctionError(_, _) => ErrorType::UndefinedFunctionError,
             Error::SubstitutionError(_, _, _, _) => ErrorType::SubstitutionError,
+            Error::ParseError(_) => ErrorType::ParseError,
             Error::InternalError(_) => ErrorType::InternalError,
         }
     }
@@ -71,8 +78,17 @@ macro_rules! internal_error {
 }
 pub(crate) use internal_error;
 
+impl From<SynError> for Error {
+    fn from(value: SynError) -> Self {
+        Error::ParseError(value)
+    }
+}
+
 impl From<Error> for SynError {
     fn from(value: Error) -> Self {
+        if let Error::ParseError(err) = value {
+            return err;
+        }
         let message = value.to_string();
         SynError::new(value.span(), message)
     }
@@ -83,9 +99,12 @@ pub enum ErrorType {
     TypeError,
     EvalError,
     RedefinedNameError,
+    UndefinedNameError,
+    CyclicReferenceError,
     SignatureError,
     UndefinedFunctionError,
     SubstitutionError,
+    ParseError,
     InternalError,
 }
 
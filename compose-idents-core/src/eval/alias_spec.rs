@@ -2,11 +2,11 @@ use crate::ast::AliasSpec;
 use crate::core::Environment;
 use crate::error::Error;
 use crate::eval::{Context, Eval, Evaluated};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 impl Eval for AliasSpec {
     fn eval(&self, environment: &Environment, context: &mut Context) -> Result<Evaluated, Error> {
-        let mut bindings = HashMap::new();
+        let mut bindings = BTreeMap::new();
 
         for item in self.items() {
             let Evaluated::Bindings(evaluated_value) = item.eval(environment, context)? else {
@@ -3,8 +3,9 @@ use crate::core::Environment;
 use crate::error::Error;
 use proc_macro2::Ident;
 use std::cell::{Ref, RefCell};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::rc::Rc;
+use syn::ext::IdentExt;
 
 /// Result of evaluating a statement.
 #[derive(Clone)]
@@ -12,13 +13,13 @@ pub enum Evaluated {
     /// A singular value
     Value(Rc<Value>),
     /// A set of variable bindings
-    Bindings(HashMap<Rc<Alias>, Evaluated>),
+    Bindings(BTreeMap<Rc<Alias>, Evaluated>),
 }
 
 /// Runtime context of evaluation.
 #[derive(Default, Clone)]
 pub struct Context {
-    context: HashMap<Ident, Evaluated>,
+    context: BTreeMap<Ident, Evaluated>,
     metadata: Rc<RefCell<AstMetadata>>,
 }
 
@@ -26,22 +27,25 @@ impl Context {
     /// Creates a new `Context` with the given metadata.
     pub fn new(metadata: Rc<RefCell<AstMetadata>>) -> Self {
         Self {
-            context: HashMap::new(),
+            context: BTreeMap::new(),
             metadata,
         }
     }
 
     /// Adds a variable to the evaluation context.
+    ///
+    /// Raw identifiers are stored without the `r#` prefix, so `r#foo` and `foo` refer to the same
+    /// variable.
     pub fn add_variable(&mut self, name: &Ident, value: Evaluated) {
         if !matches!(&value, Evaluated::Value(_)) {
             panic!("Only Value can be added to the context");
         }
-        self.context.insert(name.clone(), value);
+        self.context.insert(name.unraw(), value);
     }
 
     /// Gets a variable reference from the evaluation context.
     pub fn get_variable(&self, name: &Ident) -> Option<&Evaluated> {
-        self.context.get(name)
+        self.context.get(&name.unraw())
     }
 
     /// Returns a reference to the metadata associated with the current scope.
@@ -0,0 +1,159 @@
+//! Orders alias definitions so that every alias goes after the aliases it references.
+
+use crate::ast::{AliasSpecItem, Ast, Expr, ExprKind, ValueKind};
+use crate::error::Error;
+use std::collections::HashMap;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+
+/// State of an alias during the depth-first traversal of the dependency graph.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Unvisited,
+    Visiting,
+    Visited,
+}
+
+/// Collects names of the identifier values of an expression - the only values that could
+/// reference other aliases.
+fn collect_references(expr: &Expr, names: &mut Vec<String>) {
+    match expr.kind() {
+        ExprKind::ValueExpr(value) => {
+            if let ValueKind::Ident(ident) = value.kind() {
+                names.push(ident.unraw().to_string());
+            }
+        }
+        ExprKind::FuncCallExpr(call) => {
+            for arg in call.raw_args() {
+                collect_references(arg, names);
+            }
+        }
+    }
+}
+
+/// Orders the alias definitions topologically by their references to each other.
+///
+/// # Notes
+///
+/// - The order of definitions is kept as is unless an alias references an alias defined after it.
+/// - A reference of an alias to its own name isn't a dependency - the name is used literally, e.g.
+///   `Wrapper = concat(Wrapper, Suffix)`.
+/// - In case of re-definition of an alias the references are bound to its first definition - the
+///   re-definition itself is reported by the resolve phase.
+pub fn order_alias_spec_items(
+    items: Vec<Rc<AliasSpecItem>>,
+) -> Result<Vec<Rc<AliasSpecItem>>, Error> {
+    let mut indices = HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        indices.entry(item.alias().name()).or_insert(index);
+    }
+    let dependencies = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mut names = Vec::new();
+            collect_references(&item.value().expr(), &mut names);
+            names
+                .iter()
+                .filter_map(|name| indices.get(name).copied())
+                .filter(|dependency| *dependency != index)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut marks = vec![Mark::Unvisited; items.len()];
+    let mut order = Vec::with_capacity(items.len());
+    for index in 0..items.len() {
+        visit(
+            index,
+            &items,
+            &dependencies,
+            &mut marks,
+            &mut Vec::new(),
+            &mut order,
+        )?;
+    }
+    Ok(order
+        .into_iter()
+        .map(|index| items[index].clone())
+        .collect())
+}
+
+/// Visits an alias emitting the aliases it depends on before it and reporting cycles.
+fn visit(
+    index: usize,
+    items: &[Rc<AliasSpecItem>],
+    dependencies: &[Vec<usize>],
+    marks: &mut [Mark],
+    path: &mut Vec<usize>,
+    order: &mut Vec<usize>,
+) -> Result<(), Error> {
+    match marks[index] {
+        Mark::Visited => return Ok(()),
+        Mark::Visiting => {
+            let start = path.iter().position(|i| *i == index).unwrap_or_default();
+            let cycle = path[start..]
+                .iter()
+                .chain([&index])
+                .map(|i| items[*i].alias().name())
+                .collect::<Vec<_>>();
+            return Err(Error::CyclicReferenceError(
+                cycle.join(" -> "),
+                items[index].span(),
+            ));
+        }
+        Mark::Unvisited => {}
+    }
+    marks[index] = Mark::Visiting;
+    path.push(index);
+    for dependency in dependencies[index].iter() {
+        visit(*dependency, items, dependencies, marks, path, order)?;
+    }
+    path.pop();
+    marks[index] = Mark::Visited;
+    order.push(index);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::order_alias_spec_items;
+    use crate::ast::AliasSpec;
+    use crate::core::Environment;
+    use crate::error::ErrorType;
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    fn order(input: &str) -> Result<Vec<String>, crate::error::Error> {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(input).unwrap();
+        let items = order_alias_spec_items(spec.items().cloned().collect())?;
+        Ok(items.iter().map(|item| item.alias().name()).collect())
+    }
+
+    #[rstest]
+    #[case::backward("a = foo, b = concat(a, _bar)", &["a", "b"])]
+    #[case::forward("b = concat(a, _bar), a = foo", &["a", "b"])]
+    #[case::forward_transitive(
+        "c = upper(b), b = concat(a, _bar), a = foo, d = baz",
+        &["a", "b", "c", "d"],
+    )]
+    #[case::independent("c = foo, a = bar, b = baz", &["c", "a", "b"])]
+    #[case::self_reference("a = concat(a, _bar)", &["a"])]
+    #[case::raw_ident("b = concat(r#a, _bar), a = foo", &["a", "b"])]
+    fn ordering(#[case] input: &str, #[case] expected: &[&str]) {
+        assert_eq!(order(input).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::direct("a = concat(b, _x), b = concat(a, _y)", "a -> b -> a")]
+    #[case::transitive(
+        "d = foo, a = upper(b), b = lower(c), c = concat(a, _x)",
+        "a -> b -> c -> a"
+    )]
+    fn cycles(#[case] input: &str, #[case] expected: &str) {
+        let err = order(input).unwrap_err();
+        assert_eq!(err.type_(), ErrorType::CyclicReferenceError);
+        assert!(err.to_string().ends_with(expected), "{}", err);
+    }
+}
@@ -3,4 +3,6 @@
 mod core;
 pub use core::*;
 
+mod alias_order;
+
 mod raw_ast;
@@ -0,0 +1,598 @@
+use crate::ast::{
+    Alias, AliasSpec, AliasSpecEntry, AliasSpecItem, AliasValue, Ast, Expr, ExprKind, LoopAlias,
+    LoopSourceValue, LoopSourceValueList, LoopSpec, LoopSpecItem, RawAST, Tuple, TupleValue,
+    TupleValueKind, Value, ValueKind,
+};
+use crate::ast::{BlockRewrite, ExpandedAST};
+use crate::core::{Environment, Options, Type, DEFAULT_MAX_COMBINATIONS};
+use crate::error::{internal_error, Error};
+use crate::eval::{Context, Eval, Evaluated};
+use crate::expand::alias_order::order_alias_spec_items;
+use crate::expand::Expand;
+use crate::funcs::range_items;
+use crate::interpreter::{INDEX1_ALIAS, INDEX_ALIAS};
+use crate::resolve::{Resolve, Scope};
+use crate::util::attrs::ApplyAttrsVisitor;
+use crate::util::cross_product::cross_product;
+use crate::util::deprecation::DeprecationService;
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Ident, Span};
+use std::collections::HashSet;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+use syn::visit_mut::VisitMut;
+use syn::{LitInt, LitStr};
+
+/// Pairs the aliases of the tuple with the values validating that the shapes of the tuples are
+/// compatible for destructuring.
+///
+/// Placeholder aliases (`_`) match any value - including a nested tuple - and are not paired.
+fn destructure_tuple(
+    alias_tuple: &Tuple<Alias>,
+    expr_tuple: &Tuple<Expr>,
+    pairs: &mut Vec<(Rc<Alias>, Rc<Expr>)>,
+) -> Result<(), Error> {
+    if alias_tuple.values().len() != expr_tuple.values().len() {
+        return Err(Error::TypeError(
+            "Mismatched number of elements in the tuple".to_string(),
+            expr_tuple.span(),
+        ));
+    }
+    for (alias_value, expr_value) in alias_tuple.values().iter().zip(expr_tuple.values()) {
+        match (alias_value.kind(), expr_value.kind()) {
+            (TupleValueKind::Value(alias), _) if alias.is_placeholder() => {}
+            (TupleValueKind::Value(alias), TupleValueKind::Value(expr)) => {
+                pairs.push((alias.clone(), expr.clone()));
+            }
+            (TupleValueKind::Tuple(aliases), TupleValueKind::Tuple(exprs)) => {
+                destructure_tuple(aliases, exprs, pairs)?;
+            }
+            _ => {
+                return Err(Error::TypeError(
+                    "Shape of the value tuple doesn't match the shape of the alias tuple"
+                        .to_string(),
+                    expr_tuple.span(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Builds a sequence of [`AliasSpecItem`] loop-aliases and loop-values.
+fn make_spec_items(
+    alias: &LoopAlias,
+    source_value: &LoopSourceValue,
+) -> Result<Vec<Rc<AliasSpecItem>>, Error> {
+    let items = match (alias, source_value) {
+        (LoopAlias::Simple(alias), _) if alias.is_placeholder() => vec![],
+        (LoopAlias::Simple(alias), LoopSourceValue::Value(expr)) => {
+            let val = Rc::new(AliasValue::new(next_unique_id(), expr.clone(), expr.span()));
+            vec![Rc::new(AliasSpecItem::new(
+                next_unique_id(),
+                alias.clone(),
+                None,
+                val,
+            ))]
+        }
+        (LoopAlias::Tuple(alias_tuple), LoopSourceValue::Tuple(expr_tuple)) => {
+            let mut pairs = Vec::new();
+            destructure_tuple(alias_tuple, expr_tuple, &mut pairs)?;
+            pairs
+                .into_iter()
+                .map(|(a, e)| {
+                    let span = e.span();
+                    let value = Rc::new(AliasValue::new(next_unique_id(), e, span));
+                    Rc::new(AliasSpecItem::new(next_unique_id(), a, None, value))
+                })
+                .collect()
+        }
+        _ => {
+            return Err(Error::TypeError(
+                "Mismatched alias and value types".to_string(),
+                source_value.span(),
+            ))
+        }
+    };
+    Ok(items)
+}
+
+/// Builds [`AliasSpecItem`]s binding the loop index aliases (`__index__` and `__index1__`) to the
+/// position of the combination - except the ones shadowed by the user-defined aliases.
+fn make_index_spec_items<'a>(
+    index: usize,
+    user_items: impl Iterator<Item = &'a Rc<AliasSpecItem>>,
+    span: Span,
+) -> Vec<Rc<AliasSpecItem>> {
+    let user_names = user_items
+        .map(|item| item.alias().name())
+        .collect::<HashSet<_>>();
+    [(INDEX_ALIAS, index), (INDEX1_ALIAS, index + 1)]
+        .iter()
+        .filter(|(name, _)| !user_names.contains(*name))
+        .map(|&(name, index)| {
+            let alias = Rc::new(Alias::new(next_unique_id(), Ident::new(name, span)));
+            let lit_int = LitInt::new(index.to_string().as_str(), span);
+            let expr = Rc::new(Expr::from_value(Value::from_lit_int(lit_int)));
+            let value = Rc::new(AliasValue::new(next_unique_id(), expr, span));
+            Rc::new(AliasSpecItem::new(next_unique_id(), alias, None, value))
+        })
+        .collect()
+}
+
+/// Evaluates the filter conditions of the loops (`for x in [...] if <expr>`) against the loop
+/// aliases of a single combination - the combination is selected only if all of them are `true`.
+fn is_combination_selected(
+    loops: &LoopSpec,
+    spec_items: &[Rc<AliasSpecItem>],
+) -> Result<bool, Error> {
+    let filters = loops
+        .loops()
+        .iter()
+        .filter_map(|item| item.filter())
+        .collect::<Vec<_>>();
+    if filters.is_empty() {
+        return Ok(true);
+    }
+    let Some(environment) = Environment::get_global() else {
+        return Err(internal_error!(
+            "Environment is not set, cannot evaluate loop filters"
+        ));
+    };
+    let spec = AliasSpec::new(
+        next_unique_id(),
+        spec_items.to_vec(),
+        None,
+        Options::default(),
+    );
+    let mut scope = Scope::default();
+    spec.resolve(&environment, &mut scope, None)?;
+    for filter in filters.iter() {
+        filter.resolve(&environment, &mut scope, Some(&Type::LitBool))?;
+    }
+
+    let mut context = Context::new(scope.metadata_rc());
+    spec.eval(&environment, &mut context)?;
+    for filter in filters {
+        let Evaluated::Value(value) = filter.eval(&environment, &mut context)? else {
+            unreachable!()
+        };
+        match value.kind() {
+            ValueKind::LitBool(lit_bool) if !lit_bool.value => return Ok(false),
+            ValueKind::LitBool(_) => {}
+            _ => {
+                return Err(Error::TypeError(
+                    format!(
+                        "Loop filter must evaluate to {}, got {}",
+                        Type::LitBool,
+                        value.type_()
+                    ),
+                    filter.span(),
+                ))
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Resolves the values of a loop source given by an alias (`for w in widths`).
+///
+/// The alias has to be bound to a list literal or to a range cast to a list (`1..=8 as list`) -
+/// the loops are expanded before the aliases are evaluated, so its items are read statically.
+fn resolve_loop_source_alias(
+    alias: &Ident,
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    let name = alias.unraw().to_string();
+    let Some(item) = spec.and_then(|spec| spec.items().find(|item| item.alias().name() == name))
+    else {
+        return Err(Error::UndefinedNameError(name, alias.span()));
+    };
+    let expr = item.value().expr();
+    let items = match expr.kind() {
+        ExprKind::ValueExpr(value) => match value.kind() {
+            ValueKind::List(items) => Some(
+                items
+                    .iter()
+                    .map(|item| item.as_ref().clone())
+                    .collect::<Vec<_>>(),
+            ),
+            _ => None,
+        },
+        ExprKind::FuncCallExpr(call) if call.name() == "to_list" => match call.raw_args() {
+            [arg] => match arg.kind() {
+                ExprKind::ValueExpr(value) => match value.kind() {
+                    ValueKind::Expr(range) => Some(
+                        range_items(range, alias.span())?
+                            .into_iter()
+                            .map(Value::from_lit_int)
+                            .collect(),
+                    ),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(items) = items else {
+        return Err(Error::TypeError(
+            format!(
+                "Alias {} used as a loop source must be bound to a list literal or to a range \
+                cast to a list (like 1..=8 as list)",
+                name
+            ),
+            alias.span(),
+        ));
+    };
+    Ok(items
+        .into_iter()
+        .map(|item| LoopSourceValue::from_value(Expr::from_value(item)))
+        .collect())
+}
+
+/// Converts a loop source value into an element of a tuple.
+fn make_tuple_value(value: &LoopSourceValue) -> TupleValue<Expr> {
+    match value {
+        LoopSourceValue::Value(expr) => {
+            TupleValue::from_value(next_unique_id(), expr.clone(), expr.span())
+        }
+        LoopSourceValue::Tuple(tuple) => {
+            TupleValue::from_tuple(next_unique_id(), tuple.clone(), tuple.span())
+        }
+    }
+}
+
+/// Pairs the elements of the zipped lists positionally into tuples.
+///
+/// The lists must be of the same length - otherwise an error is reported at the shorter one.
+fn zip_loop_sources(
+    lists: &[LoopSourceValueList],
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    let resolved = lists
+        .iter()
+        .map(|list| resolve_loop_source(list, spec))
+        .collect::<Result<Vec<_>, _>>()?;
+    let len = resolved[0].len();
+    for (list, values) in lists.iter().zip(resolved.iter()).skip(1) {
+        if values.len() != len {
+            let span = if values.len() < len {
+                list.span()
+            } else {
+                lists[0].span()
+            };
+            return Err(Error::TypeError(
+                format!(
+                    "Mismatched lengths of the zipped lists: {} and {}",
+                    len,
+                    values.len()
+                ),
+                span,
+            ));
+        }
+    }
+    let values = (0..len)
+        .map(|i| {
+            let tuple_values = resolved
+                .iter()
+                .map(|values| make_tuple_value(&values[i]))
+                .collect();
+            let span = resolved[0][i].span();
+            LoopSourceValue::from_tuple(Tuple::new(next_unique_id(), tuple_values, span))
+        })
+        .collect();
+    Ok(values)
+}
+
+/// Pairs the elements of the enumerated list with their 0-based indices - `(i, value)`.
+fn enumerate_loop_source(
+    list: &LoopSourceValueList,
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    let values = resolve_loop_source(list, spec)?
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            let span = value.span();
+            let lit_int = LitInt::new(i.to_string().as_str(), span);
+            let index = Rc::new(Expr::from_value(Value::from_lit_int(lit_int)));
+            let tuple_values = vec![
+                TupleValue::from_value(next_unique_id(), index, span),
+                make_tuple_value(value),
+            ];
+            LoopSourceValue::from_tuple(Tuple::new(next_unique_id(), tuple_values, span))
+        })
+        .collect();
+    Ok(values)
+}
+
+/// Builds the canonical token-string representation of a loop source value - values consisting
+/// of the same tokens have the same representation regardless of the spacing.
+fn loop_source_value_key(value: &LoopSourceValue) -> String {
+    fn tuple_key(tuple: &Tuple<Expr>) -> String {
+        let values = tuple
+            .values()
+            .iter()
+            .map(|value| match value.kind() {
+                TupleValueKind::Value(expr) => expr.to_string(),
+                TupleValueKind::Tuple(tuple) => tuple_key(tuple),
+            })
+            .collect::<Vec<_>>();
+        format!("({})", values.join(", "))
+    }
+    match value {
+        LoopSourceValue::Value(expr) => expr.to_string(),
+        LoopSourceValue::Tuple(tuple) => tuple_key(tuple),
+    }
+}
+
+/// Removes the duplicate values of the list of `unique(...)` loop source preserving the order of
+/// their first occurrences.
+///
+/// The removed duplicates are reported with a warning, so that the source of the list could be
+/// cleaned up.
+fn unique_loop_source(
+    list: &LoopSourceValueList,
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    let mut values = Vec::new();
+    for value in resolve_loop_source(list, spec)? {
+        let key = loop_source_value_key(&value);
+        if seen.contains(&key) {
+            duplicates.push(key);
+        } else {
+            seen.insert(key);
+            values.push(value);
+        }
+    }
+    if !duplicates.is_empty() {
+        DeprecationService::scoped().add_duplicate_loop_values_warning(&duplicates);
+    }
+    Ok(values)
+}
+
+/// Splits the string of `split(...)` loop source into string fragments.
+///
+/// Empty fragments are preserved, an empty separator splits the string into characters.
+fn split_loop_source(string: &LitStr, separator: &LitStr) -> Vec<LoopSourceValue> {
+    let value = string.value();
+    let separator = separator.value();
+    let fragments = if separator.is_empty() {
+        value.chars().map(String::from).collect::<Vec<_>>()
+    } else {
+        value.split(separator.as_str()).map(String::from).collect()
+    };
+    fragments
+        .iter()
+        .map(|fragment| {
+            let lit_str = LitStr::new(fragment, string.span());
+            LoopSourceValue::from_value(Expr::from_value(Value::from_lit_str(lit_str)))
+        })
+        .collect()
+}
+
+/// Resolves the values of a loop source - a list literal, an alias bound to a list, lists zipped
+/// by `zip(...)` or a string split by `split(...)`.
+fn resolve_loop_source(
+    list: &LoopSourceValueList,
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    if let Some(alias) = list.alias() {
+        return resolve_loop_source_alias(alias, spec);
+    }
+    if let Some((string, separator)) = list.split() {
+        return Ok(split_loop_source(string, separator));
+    }
+    if !list.zipped().is_empty() {
+        return zip_loop_sources(list.zipped(), spec);
+    }
+    if let Some(enumerated) = list.enumerated() {
+        return enumerate_loop_source(enumerated, spec);
+    }
+    if let Some(deduplicated) = list.deduplicated() {
+        return unique_loop_source(deduplicated, spec);
+    }
+    Ok(list.values().to_vec())
+}
+
+/// Concatenates the values of the lists of a loop source - `[...] + [...]`.
+///
+/// Values of the lists could be of different shapes if the loop alias is a simple one, otherwise
+/// they must be destructurable by the alias - a mismatch is reported at the offending list.
+fn chain_loop_sources(
+    alias: &LoopAlias,
+    lists: &[LoopSourceValueList],
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    let mut values = Vec::new();
+    for list in lists {
+        let list_values = resolve_loop_source(list, spec)?;
+        if let LoopAlias::Tuple(_) = alias {
+            if list_values
+                .iter()
+                .any(|value| make_spec_items(alias, value).is_err())
+            {
+                return Err(Error::TypeError(
+                    "Shape of the values of the concatenated list doesn't match the loop alias"
+                        .to_string(),
+                    list.span(),
+                ));
+            }
+        }
+        values.extend(list_values);
+    }
+    Ok(values)
+}
+
+/// Resolves the values of the loop source of a loop.
+fn resolve_loop_spec_item(
+    item: &LoopSpecItem,
+    spec: Option<&AliasSpec>,
+) -> Result<Vec<LoopSourceValue>, Error> {
+    let list = item.list();
+    if !list.chained().is_empty() {
+        return chain_loop_sources(&item.alias(), list.chained(), spec);
+    }
+    resolve_loop_source(&list, spec)
+}
+
+/// Builds a sequence of [`AliasSpecItem`]s of the spec desugaring its tuple destructurings and
+/// ordering the aliases by their references to each other.
+fn make_alias_spec_items(spec: &AliasSpec) -> Result<Vec<Rc<AliasSpecItem>>, Error> {
+    let mut items = Vec::new();
+    for entry in spec.entries() {
+        match entry {
+            AliasSpecEntry::Item(item) => items.push(item.clone()),
+            AliasSpecEntry::Tuple(item) => {
+                let alias = LoopAlias::from_tuple(item.aliases().clone());
+                items.append(&mut make_spec_items(&alias, item.value())?);
+            }
+        }
+    }
+    order_alias_spec_items(items)
+}
+
+impl Expand for RawAST {
+    type Expanded = ExpandedAST;
+
+    fn expand(&self) -> Result<Self::Expanded, Error> {
+        let loops = match self.loops() {
+            // No loops
+            None => {
+                let spec = match self.spec() {
+                    Some(spec) => Rc::new(
+                        AliasSpec::new(
+                            next_unique_id(),
+                            make_alias_spec_items(&spec)?,
+                            spec.is_comma_used(),
+                            spec.options().clone(),
+                        )
+                        .with_when(spec.when().cloned()),
+                    ),
+                    None => Rc::new(AliasSpec::new(
+                        next_unique_id(),
+                        vec![],
+                        None,
+                        Options::default(),
+                    )),
+                };
+                return Ok(ExpandedAST::new(
+                    next_unique_id(),
+                    self.make_block_rewrites(spec),
+                    self.blocks().len(),
+                ));
+            }
+            Some(loops) => loops,
+        };
+
+        // Gather per-loop lists as owned clones to use the cross-product utility
+        let per_loop_values: Vec<Vec<LoopSourceValue>> = loops
+            .loops()
+            .iter()
+            .map(|item| resolve_loop_spec_item(item, self.spec().as_deref()))
+            .collect::<Result<_, _>>()?;
+
+        let loop_sizes = per_loop_values
+            .iter()
+            .map(|values| values.len().to_string())
+            .collect::<Vec<_>>()
+            .join(" x ");
+        let combinations = cross_product(per_loop_values);
+        let max_combinations = self
+            .spec()
+            .and_then(|spec| spec.options().max_combinations())
+            .unwrap_or(DEFAULT_MAX_COMBINATIONS);
+        let num_combinations = combinations.total_combinations();
+        if num_combinations.map_or(true, |n| n > max_combinations) {
+            return Err(Error::EvalError(
+                format!(
+                    "Loops expand to {} combinations ({}) which exceeds max_combinations = {}, \
+                     raise the limit with options(max_combinations = <n>) if it is intended",
+                    num_combinations.map_or("too many".to_string(), |n| n.to_string()),
+                    loop_sizes,
+                    max_combinations,
+                ),
+                loops.span(),
+            ));
+        }
+
+        let alias_spec_items = match self.spec() {
+            Some(spec) => make_alias_spec_items(&spec)?,
+            None => vec![],
+        };
+        let mut block_rewrite_items: Vec<BlockRewrite> = Vec::new();
+
+        let mut index = 0;
+        for loop_values in combinations {
+            let mut spec_items: Vec<Rc<AliasSpecItem>> = Vec::new();
+            for (item, value) in loops.loops().iter().zip(loop_values.iter()) {
+                let alias = item.alias();
+                let mut new_spec_items = make_spec_items(alias.as_ref(), value)?;
+                spec_items.append(&mut new_spec_items);
+            }
+            if !is_combination_selected(&loops, &spec_items)? {
+                continue;
+            }
+            let mut index_items = make_index_spec_items(
+                index,
+                spec_items.iter().chain(alias_spec_items.iter()),
+                loops.span(),
+            );
+            spec_items.append(&mut index_items);
+            index += 1;
+
+            if let Some(spec) = self.spec() {
+                spec_items.extend(alias_spec_items.iter().cloned());
+                let spec = Rc::new(
+                    AliasSpec::new(
+                        next_unique_id(),
+                        spec_items,
+                        spec.is_comma_used(),
+                        spec.options().clone(),
+                    )
+                    .with_when(spec.when().cloned()),
+                );
+                block_rewrite_items.extend(self.make_block_rewrites(spec));
+            } else {
+                let spec = Rc::new(AliasSpec::new(
+                    next_unique_id(),
+                    spec_items,
+                    None,
+                    Options::default(),
+                ));
+                block_rewrite_items.extend(self.make_block_rewrites(spec));
+            }
+        }
+
+        Ok(ExpandedAST::new(
+            next_unique_id(),
+            block_rewrite_items,
+            self.blocks().len(),
+        ))
+    }
+}
+
+impl RawAST {
+    /// Makes a block-rewrite per block - all of them share the spec of a single combination.
+    ///
+    /// The attributes of `apply_attrs(...)` are stamped onto the items before the substitution, so
+    /// their placeholders are expanded per combination.
+    fn make_block_rewrites(&self, spec: Rc<AliasSpec>) -> Vec<BlockRewrite> {
+        let raw_spec = self.spec();
+        let apply_attrs = raw_spec
+            .as_deref()
+            .map_or(&[][..], |spec| spec.apply_attrs());
+        self.blocks()
+            .iter()
+            .map(|block| {
+                let mut block = block.clone();
+                ApplyAttrsVisitor::new(apply_attrs).visit_block_mut(&mut block);
+                BlockRewrite::new(spec.clone(), block)
+            })
+            .collect()
+    }
+}
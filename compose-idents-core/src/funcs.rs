@@ -0,0 +1,568 @@
+//! Provides implementations of the functions that can be used by the user in alias specifications.
+use crate::ast::Value;
+use crate::core::{Environment, Type};
+use crate::error::Error;
+use crate::strings::is_valid_ident_start;
+pub use crate::strings::{
+    capitalize, concat, decapitalize, escape_keyword, join, lower, normalize, to_camel_case,
+    to_kebab_case, to_pascal_case, to_shouty_snake_case, to_snake_case, to_title_case, upper,
+};
+use crate::util::stable_hash::StableHasher;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::ToTokens;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::visit_mut::{self, VisitMut};
+use syn::{LitBool, LitByteStr, LitInt, LitStr};
+
+/// Generates an identifier from a provided seed deterministically within a single macro invocation.
+///
+/// `hash(1)` called within a single macro invocation will always return the same
+/// value but different in another macro invocation - unless the same explicit seed is used
+/// instead of [`Environment::seed`].
+pub fn hash(input: &str, seed: u64) -> String {
+    hash_value(input, seed).to_string()
+}
+
+fn hash_value(input: &str, seed: u64) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_u64(seed);
+    hasher.write_str(input);
+    hasher.finish()
+}
+
+/// Maximum length of a hash produced by [`short_hash`].
+pub const MAX_SHORT_HASH_LEN: usize = 16;
+
+/// Same as [`hash`], but the hash is hex-encoded and truncated to the given length.
+pub fn short_hash(input: &str, seed: u64, len: &LitInt) -> Result<String, Error> {
+    let len = match len.base10_parse::<usize>() {
+        Ok(len) if (1..=MAX_SHORT_HASH_LEN).contains(&len) => len,
+        _ => {
+            return Err(Error::TypeError(
+                format!(
+                    "Length of short_hash() must be within 1..={}, got: {}",
+                    MAX_SHORT_HASH_LEN, len
+                ),
+                len.span(),
+            ));
+        }
+    };
+    let mut result = format!("{:016x}", hash_value(input, seed));
+    result.truncate(len);
+    Ok(result)
+}
+
+/// Makes an explicit seed of [`hash`] out of a string.
+pub fn seed_from_str(seed: &str) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_str(seed);
+    hasher.finish()
+}
+
+/// Makes an explicit seed of [`hash`] out of an integer literal.
+pub fn seed_from_int(seed: &LitInt) -> Result<u64, Error> {
+    seed.base10_parse::<u64>().map_err(|_| {
+        Error::EvalError(
+            format!("Seed of hash() must fit into u64, got: {}", seed),
+            seed.span(),
+        )
+    })
+}
+
+/// Generates an identifier out of [`hash`] of the input - the hash is preceded by `prefix` to make
+/// it a valid identifier.
+pub fn hash_ident(input: &str, prefix: &str, seed: u64, span: Span) -> Result<Value, Error> {
+    prefixed_hash_ident(prefix, &hash(input, seed), span)
+}
+
+/// Generates an identifier out of [`short_hash`] of the input preceded by `prefix`.
+pub fn short_hash_ident(
+    input: &str,
+    prefix: &str,
+    seed: u64,
+    len: &LitInt,
+    span: Span,
+) -> Result<Value, Error> {
+    prefixed_hash_ident(prefix, &short_hash(input, seed, len)?, span)
+}
+
+fn prefixed_hash_ident(prefix: &str, hash: &str, span: Span) -> Result<Value, Error> {
+    if !is_valid_ident_start(prefix) {
+        return Err(Error::EvalError(
+            format!(
+                "Prefix of hash() must be a valid start of an identifier, got: {:?}",
+                prefix
+            ),
+            span,
+        ));
+    }
+    let ident = Ident::new(&format!("{}{}", prefix, hash), span);
+    Ok(Value::from_ident(ident))
+}
+
+/// Returns the next value of the counter with the given key shared between all macro invocations
+/// within the compiler process.
+pub fn global_counter(key: &str, span: Span) -> Value {
+    let value = Environment::next_global_counter(key);
+    Value::from_lit_int(LitInt::new(value.to_string().as_str(), span))
+}
+
+/// Reads an environment variable of the compiler process at expansion time.
+///
+/// Falls back to `default` if the variable isn't set - otherwise a missing variable is an error.
+pub fn env(name: &LitStr, default: Option<&LitStr>, span: Span) -> Result<Value, Error> {
+    let value = match (std::env::var(name.value()), default) {
+        (Ok(value), _) => value,
+        (Err(std::env::VarError::NotPresent), Some(default)) => default.value(),
+        (Err(error), _) => {
+            return Err(Error::EvalError(
+                format!(
+                    "Failed to read environment variable {:?}: {}",
+                    name.value(),
+                    error
+                ),
+                name.span(),
+            ));
+        }
+    };
+    Ok(Value::from_lit_str(LitStr::new(&value, span)))
+}
+
+/// Renders a token-stream argument as a piece of a concatenated string.
+///
+/// String literals are rendered by their content, everything else - by its textual form.
+pub fn concat_piece(tokens: &TokenStream) -> String {
+    match syn::parse2::<LitStr>(tokens.clone()) {
+        Ok(lit_str) => lit_str.value(),
+        Err(_) => tokens.to_string(),
+    }
+}
+
+/// Strips the `r#` prefix off a raw identifier.
+pub fn unraw(ident: &Ident) -> Value {
+    Value::from_ident(ident.unraw())
+}
+
+/// Converts a `Value` to an identifier.
+pub fn to_ident(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::Ident)
+}
+
+/// Converts a `Value` to a path.
+pub fn to_path(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::Path)
+}
+
+/// Converts a `Value` to a type.
+pub fn to_type(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::Type)
+}
+
+/// Converts a `Value` to an expr.
+pub fn to_expr(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::Expr)
+}
+
+/// Converts a `Value` to a string literal.
+pub fn to_str(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::LitStr)
+}
+
+/// Converts a `Value` to a byte string literal - a string literal is converted by its content.
+pub fn to_byte_str(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::LitByteStr)
+}
+
+/// Appends a trailing NUL byte to a byte string literal.
+pub fn nul_terminated(lit_byte_str: &LitByteStr) -> Value {
+    let mut bytes = lit_byte_str.value();
+    bytes.push(0);
+    Value::from_lit_byte_str(LitByteStr::new(&bytes, lit_byte_str.span()))
+}
+
+/// Converts a `Value` to an integer literal.
+pub fn to_int(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::LitInt)
+}
+
+/// Converts a `Value` to a float literal.
+pub fn to_float(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::LitFloat)
+}
+
+/// Converts a `Value` to a char literal.
+pub fn to_char(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::LitChar)
+}
+
+/// Converts a `Value` to a lifetime - an identifier becomes a lifetime with the same name.
+pub fn to_lifetime(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::Lifetime)
+}
+
+/// Converts a `Value` to a boolean literal.
+pub fn to_bool(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::LitBool)
+}
+
+/// Converts a `Value` to an attribute.
+pub fn to_attr(value: &Value) -> Result<Value, Error> {
+    value.try_cast(&Type::Attr)
+}
+
+/// Maximum number of items a range could expand to.
+pub const MAX_RANGE_LEN: i128 = 1 << 16;
+
+/// Reads an integer bound of a range - an integer literal optionally preceded by a minus.
+fn range_bound(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => range_bound(expr).map(|bound| -bound),
+        syn::Expr::Group(group) => range_bound(&group.expr),
+        _ => None,
+    }
+}
+
+/// Expands a range with integer literal bounds (`1..=8`, `0..4`) into its items.
+pub fn range_items(expr: &syn::Expr, span: Span) -> Result<Vec<LitInt>, Error> {
+    let expr = match expr {
+        syn::Expr::Group(group) => group.expr.as_ref(),
+        expr => expr,
+    };
+    let bounds = match expr {
+        syn::Expr::Range(range) => range
+            .start
+            .as_deref()
+            .and_then(range_bound)
+            .zip(range.end.as_deref().and_then(range_bound))
+            .map(|(start, end)| match range.limits {
+                syn::RangeLimits::HalfOpen(_) => (start, end),
+                syn::RangeLimits::Closed(_) => (start, end + 1),
+            }),
+        _ => None,
+    };
+    let Some((start, end)) = bounds else {
+        return Err(Error::EvalError(
+            format!(
+                "Expected a range with integer literal bounds (like 1..=8), got: {}",
+                quote::ToTokens::to_token_stream(expr)
+            ),
+            span,
+        ));
+    };
+    if end - start > MAX_RANGE_LEN {
+        return Err(Error::EvalError(
+            format!(
+                "The range expands to {} items which exceeds the maximum of {}",
+                end - start,
+                MAX_RANGE_LEN
+            ),
+            span,
+        ));
+    }
+    Ok((start..end)
+        .map(|i| LitInt::new(i.to_string().as_str(), span))
+        .collect())
+}
+
+/// Converts a range expression with integer literal bounds to a list of integers.
+pub fn to_list(expr: &syn::Expr, span: Span) -> Result<Value, Error> {
+    let items = range_items(expr, span)?
+        .into_iter()
+        .map(|item| Rc::new(Value::from_lit_int(item)))
+        .collect();
+    Value::from_list(items)
+}
+
+/// Reads a boolean out of a value - the value has to be either `true` or `false`.
+pub fn bool_value(value: &Value) -> Result<bool, Error> {
+    let tokens = value.to_token_stream();
+    match tokens.to_string().as_str() {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(Error::TypeError(
+            format!("Expected either true or false, got: {}", other),
+            tokens.span(),
+        )),
+    }
+}
+
+/// Makes a boolean literal out of a boolean.
+pub fn from_bool(value: bool, span: Span) -> Value {
+    Value::from_lit_bool(LitBool::new(value, span))
+}
+
+/// Checks whether the values consist of the same tokens.
+pub fn tokens_eq(value: &Value, other: &Value) -> bool {
+    value.to_token_stream().to_string() == other.to_token_stream().to_string()
+}
+
+/// Reads the identifier of the last segment of a path - its generic arguments are dropped.
+pub fn last_segment(path: &syn::Path) -> Result<Value, Error> {
+    match path.segments.last() {
+        Some(segment) => Ok(Value::from_ident(segment.ident.clone())),
+        None => Err(Error::TypeError(
+            "Expected a path with at least one segment".to_string(),
+            path.span(),
+        )),
+    }
+}
+
+/// Same as [`last_segment`], but for a type - only path types are accepted.
+pub fn last_segment_of_type(type_: &syn::Type) -> Result<Value, Error> {
+    match type_ {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => last_segment(&type_path.path),
+        _ => Err(Error::TypeError(
+            format!(
+                "Function last_segment(...) expects a path, got: {}",
+                type_.to_token_stream(),
+            ),
+            type_.span(),
+        )),
+    }
+}
+
+/// Removes angle-bracketed generic arguments everywhere in the visited syntax tree.
+struct StripGenerics;
+
+impl VisitMut for StripGenerics {
+    fn visit_path_arguments_mut(&mut self, arguments: &mut syn::PathArguments) {
+        if let syn::PathArguments::AngleBracketed(_) = arguments {
+            *arguments = syn::PathArguments::None;
+        }
+        visit_mut::visit_path_arguments_mut(self, arguments);
+    }
+}
+
+/// Removes generic arguments from a type - `HashMap<K, Vec<V>>` becomes `HashMap`.
+pub fn strip_generics(type_: &syn::Type) -> Value {
+    let mut type_ = type_.clone();
+    StripGenerics.visit_type_mut(&mut type_);
+    Value::from_type(type_)
+}
+
+/// Removes generic arguments from a path - `std::vec::Vec<u8>` becomes `std::vec::Vec`.
+pub fn strip_generics_of_path(path: &syn::Path) -> Value {
+    let mut path = path.clone();
+    StripGenerics.visit_path_mut(&mut path);
+    Value::from_path(path)
+}
+
+/// Peels references (along with their lifetimes and mutability) off a type - `&&'a mut T` becomes
+/// `T`. Non-reference types are returned unchanged.
+pub fn strip_ref(type_: &syn::Type) -> Value {
+    let mut type_ = type_;
+    while let syn::Type::Reference(reference) = type_ {
+        type_ = reference.elem.as_ref();
+    }
+    Value::from_type(type_.clone())
+}
+
+/// Reads the item of a list at the given index.
+pub fn index(items: &[Rc<Value>], index: &LitInt, span: Span) -> Result<Value, Error> {
+    let Ok(i) = index.base10_parse::<usize>() else {
+        return Err(Error::EvalError(
+            format!("Invalid list index: {}", index),
+            index.span(),
+        ));
+    };
+    match items.get(i) {
+        Some(item) => Ok(item.as_ref().clone()),
+        None => Err(Error::EvalError(
+            format!(
+                "List index {} is out of bounds for a list of length {}",
+                i,
+                items.len()
+            ),
+            span,
+        )),
+    }
+}
+
+/// Computes the length of a list.
+pub fn len(items: &[Rc<Value>], span: Span) -> Value {
+    Value::from_lit_int(LitInt::new(items.len().to_string().as_str(), span))
+}
+
+/// Computes the number of characters in a string.
+pub fn char_count(string: &str, span: Span) -> Value {
+    Value::from_lit_int(LitInt::new(
+        string.chars().count().to_string().as_str(),
+        span,
+    ))
+}
+
+/// Counts the characters of the string representation of the tokens.
+///
+/// Ranges are rejected - they are `expr` values unless cast to a list, and the number of their
+/// characters is hardly ever the intended length.
+pub fn tokens_char_count(tokens: &TokenStream, span: Span) -> Result<Value, Error> {
+    if syn::parse2::<syn::ExprRange>(tokens.clone()).is_ok() {
+        return Err(Error::TypeError(
+            format!(
+                "Expected a list or a value to count the characters of, got a range: {} - cast it \
+                 to a list to get the number of its items: {} as list",
+                tokens, tokens,
+            ),
+            span,
+        ));
+    }
+    Ok(char_count(tokens.to_string().as_str(), span))
+}
+
+/// Formats an integer literal in the given radix (2, 8 or 16) without a prefix.
+///
+/// Values up to `u128::MAX` are supported.
+pub fn format_radix(int: &LitInt, radix: u32) -> Result<String, Error> {
+    let Ok(value) = int.base10_parse::<u128>() else {
+        return Err(Error::EvalError(
+            format!("Integer {} doesn't fit into u128", int.base10_digits()),
+            int.span(),
+        ));
+    };
+    Ok(match radix {
+        2 => format!("{:b}", value),
+        8 => format!("{:o}", value),
+        16 => format!("{:x}", value),
+        _ => unreachable!("Unsupported radix: {}", radix),
+    })
+}
+
+/// Maximum number of repetitions [`repeat_count`] allows.
+pub const MAX_REPEAT_COUNT: usize = 1 << 16;
+
+/// Reads the number of repetitions of a value for `repeat(...)`.
+pub fn repeat_count(n: &LitInt) -> Result<usize, Error> {
+    let Ok(count) = n.base10_parse::<usize>() else {
+        return Err(Error::EvalError(
+            format!("Invalid number of repetitions: {}", n),
+            n.span(),
+        ));
+    };
+    if count > MAX_REPEAT_COUNT {
+        return Err(Error::EvalError(
+            format!(
+                "The number of repetitions {} exceeds the maximum of {}",
+                count, MAX_REPEAT_COUNT
+            ),
+            n.span(),
+        ));
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::unique_id::next_unique_id;
+    use rstest::rstest;
+
+    #[rstest]
+    fn test_random_determinism() {
+        let environment = Environment::new(Default::default(), next_unique_id());
+        let expected = hash("1", environment.seed());
+        let actual = hash("1", environment.seed());
+
+        assert_eq!(actual, expected);
+        assert_ne!(hash("2", environment.seed()), expected);
+    }
+
+    /// Identifier validation of [`crate::strings::is_valid_ident`] has to agree with the parser.
+    #[rstest]
+    #[case("foo")]
+    #[case("_foo")]
+    #[case("Foo123")]
+    #[case("привет")]
+    #[case("union")]
+    #[case("r#type")]
+    #[case("")]
+    #[case("_")]
+    #[case("1foo")]
+    #[case("foo-bar")]
+    #[case("type")]
+    #[case("Self")]
+    #[case("r#self")]
+    fn is_valid_ident_matches_parser(#[case] input: &str) {
+        let expected = syn::parse_str::<syn::Ident>(input).is_ok();
+        assert_eq!(crate::strings::is_valid_ident(input), expected);
+    }
+
+    #[rstest]
+    #[case::min("1", 1)]
+    #[case::mid("6", 6)]
+    #[case::max("16", 16)]
+    fn short_hash_length(#[case] len: &str, #[case] expected: usize) {
+        let len = syn::parse_str::<LitInt>(len).unwrap();
+        let actual = short_hash("foo", 1, &len).unwrap();
+
+        assert_eq!(actual.len(), expected);
+        assert!(actual.chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(format!("{:016x}", hash_value("foo", 1)).starts_with(&actual));
+    }
+
+    #[rstest]
+    #[case::zero("0")]
+    #[case::too_long("17")]
+    #[case::overflow("18446744073709551616")]
+    fn short_hash_invalid_length(#[case] len: &str) {
+        let len = syn::parse_str::<LitInt>(len).unwrap();
+        let err = short_hash("foo", 1, &len).unwrap_err();
+
+        assert!(matches!(err, Error::TypeError(..)), "Error: {:?}", err);
+    }
+
+    #[rstest]
+    #[case::hex("255", 16, "ff")]
+    #[case::oct("8", 8, "10")]
+    #[case::bin("5", 2, "101")]
+    #[case::zero("0", 16, "0")]
+    #[case::prefixed_with_underscores("0xFF_00", 16, "ff00")]
+    #[case::suffixed("255u8", 2, "11111111")]
+    #[case::u128_max(
+        "340282366920938463463374607431768211455",
+        16,
+        "ffffffffffffffffffffffffffffffff"
+    )]
+    fn radix_formatting(#[case] input: &str, #[case] radix: u32, #[case] expected: &str) {
+        let int = syn::parse_str::<LitInt>(input).unwrap();
+        assert_eq!(format_radix(&int, radix).unwrap(), expected);
+    }
+
+    #[test]
+    fn radix_formatting_overflow() {
+        let int = syn::parse_str::<LitInt>("340282366920938463463374607431768211456").unwrap();
+        let err = format_radix(&int, 16).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "EvalError: Integer 340282366920938463463374607431768211456 doesn't fit into u128",
+        );
+    }
+
+    #[rstest]
+    #[case::tokens(quote::quote!(Vec<u8>), "10")]
+    #[case::expr(quote::quote!(a + b), "5")]
+    fn tokens_char_count_of(#[case] tokens: TokenStream, #[case] expected: &str) {
+        let value = tokens_char_count(&tokens, Span::call_site()).unwrap();
+
+        assert_eq!(value.to_token_stream().to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::closed(quote::quote!(1..=3))]
+    #[case::half_open(quote::quote!(0..4))]
+    fn tokens_char_count_of_range(#[case] tokens: TokenStream) {
+        let err = tokens_char_count(&tokens, Span::call_site()).unwrap_err();
+
+        assert!(matches!(err, Error::TypeError(..)), "Error: {:?}", err);
+        assert!(err.to_string().contains("as list"), "{}", err);
+    }
+}
@@ -0,0 +1,500 @@
+//! Implements the [`Interpreter`] type and the core logic of the library.
+
+use crate::ast::{AliasSpec, Ast, BlockRewrite, RawAST, Value, ValueKind};
+use crate::core::{Environment, Type};
+use crate::error::Error;
+use crate::eval::{Context, Eval, Evaluated};
+use crate::expand::Expand;
+use crate::interpreter::usage::{expr_used_aliases, used_aliases};
+use crate::resolve::{Resolve, Scope};
+use crate::substitution::AliasSubstitutionVisitor;
+use crate::util::attrs::{stmt_attrs_mut, take_export_marker};
+use crate::util::deprecation::DeprecationServiceScope;
+use crate::util::log::debug;
+#[cfg(feature = "pretty")]
+use crate::util::pretty::{make_separator, unparse_items};
+#[cfg(feature = "trace")]
+use crate::util::{
+    cfg::env_lookup,
+    trace::{trace_dir, TraceRecord},
+};
+#[cfg(feature = "pretty")]
+use crate::{ast::LoopAlias, error::internal_error};
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::{BTreeMap, HashSet};
+#[cfg(feature = "trace")]
+use std::path::PathBuf;
+use std::rc::Rc;
+use syn::spanned::Spanned;
+use syn::{Attribute, LitInt};
+
+/// Name of the builtin alias holding the unique identifier of the macro invocation.
+pub const INVOCATION_ID_ALIAS: &str = "__invocation_id";
+/// Name of the builtin alias holding the random seed of the macro invocation.
+pub const SEED_ALIAS: &str = "__seed";
+/// Name of the builtin alias holding the 0-based index of the loop combination.
+pub const INDEX_ALIAS: &str = "__index__";
+/// Name of the builtin alias holding the 1-based index of the loop combination.
+pub const INDEX1_ALIAS: &str = "__index1__";
+
+/// Values of all the aliases of a combination recorded for the trace, along with the flags of the
+/// private ones.
+type TracedAliases = BTreeMap<String, (Rc<Value>, bool)>;
+
+/// Returns the identifier of the item if it declares a type or a trait.
+fn type_item_ident(item: &syn::Item) -> Option<&syn::Ident> {
+    match item {
+        syn::Item::Struct(item) => Some(&item.ident),
+        syn::Item::Enum(item) => Some(&item.ident),
+        syn::Item::Union(item) => Some(&item.ident),
+        syn::Item::Type(item) => Some(&item.ident),
+        syn::Item::Trait(item) => Some(&item.ident),
+        syn::Item::TraitAlias(item) => Some(&item.ident),
+        _ => None,
+    }
+}
+
+/// Checks whether the self type or the trait of the impl block is one of the given items.
+fn impl_refers_to(item: &syn::ItemImpl, names: &HashSet<syn::Ident>) -> bool {
+    let is_named = |path: &syn::Path| {
+        path.leading_colon.is_none()
+            && path
+                .segments
+                .first()
+                .is_some_and(|segment| names.contains(&segment.ident))
+    };
+    let self_type = match item.self_ty.as_ref() {
+        syn::Type::Path(type_path) => type_path.qself.is_none() && is_named(&type_path.path),
+        _ => false,
+    };
+    self_type
+        || item
+            .trait_
+            .as_ref()
+            .is_some_and(|(_, path, _)| is_named(path))
+}
+
+/// Executes the lifecycle of the macro starting from analyzing the AST down to generating the
+/// final output.
+///
+/// # Notes
+///
+/// Workflow of the interpreter consists of three main phases:
+///
+/// 1. Expand – desugaring, conversion of higher-level AST elements to more primitive ones.
+///     - [`Expand::expand`] is called on relevant AST nodes to obtain the new expanded form,
+///       represented as new AST elements, chosen by the implementation.
+///     - Aliases are ordered so that each of them goes after the aliases it references.
+/// 2. Resolve – static analysis, fills the scope and catches duplicate aliases.
+///     - [`Resolve::resolve`] is called on relevant AST nodes.
+///     - Since the syntax is simple - there is no need for full AST traversal - instead
+///       ad-hoc traversal of relevant nodes is enough.
+///     - Simplified bidirectional typing is used at this phase.
+///     - The scope is currently single and global due to the simplicity of requirements.
+/// 3. Evaluate – runs functions and concatenations to obtain final identifier strings.
+///    - [`Eval::eval`] is called on relevant AST nodes to retrieve execution results.
+///    - Global execution context is maintained. It is singular and global due to simplicity of
+///      requirements at this point.
+/// 4. Code-gen – rewrites the user block with `ComposeIdentsVisitor` and returns a TokenStream.
+pub struct Interpreter {
+    /// Global execution environment.
+    environment: Rc<Environment>,
+    deprecation_service: DeprecationServiceScope,
+    /// Directory the expansion trace is written to regardless of the environment.
+    #[cfg(feature = "trace")]
+    trace_dir: Option<PathBuf>,
+}
+
+impl Interpreter {
+    /// Creates a new interpreter instance with the AST as an input.
+    pub fn new(environment: Rc<Environment>, deprecation_service: DeprecationServiceScope) -> Self {
+        Interpreter {
+            environment,
+            deprecation_service,
+            #[cfg(feature = "trace")]
+            trace_dir: None,
+        }
+    }
+    /// Makes the interpreter write the expansion trace into `dir`.
+    #[cfg(feature = "trace")]
+    pub fn with_trace_dir(mut self, dir: PathBuf) -> Self {
+        self.trace_dir = Some(dir);
+        self
+    }
+    /// Directory the expansion trace is written to - `None` if tracing is disabled.
+    #[cfg(feature = "trace")]
+    fn trace_dir(&self) -> Option<PathBuf> {
+        self.trace_dir
+            .clone()
+            .or_else(|| trace_dir(&env_lookup, self.environment.options().trace()))
+    }
+    #[cfg(not(feature = "trace"))]
+    fn trace_dir(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+    /// Appends a record of a single block-rewrite expansion to the trace.
+    ///
+    /// The record includes the doc-comments of the traced aliases of the spec.
+    #[cfg(feature = "trace")]
+    fn trace(
+        &self,
+        combination: usize,
+        combinations: usize,
+        spec: &AliasSpec,
+        aliases: &TracedAliases,
+    ) -> Result<(), Error> {
+        let Some(dir) = self.trace_dir() else {
+            return Ok(());
+        };
+        let docs = spec
+            .items()
+            .filter(|item| aliases.contains_key(&item.alias().name()))
+            .filter_map(|item| Some((item.alias().name(), item.alias().docs()?.to_string())))
+            .collect();
+        let record = TraceRecord {
+            invocation_id: self.environment.invocation_id(),
+            combination,
+            combinations,
+            aliases,
+            docs,
+        };
+        record.write(&dir)
+    }
+    #[cfg(not(feature = "trace"))]
+    fn trace(
+        &self,
+        _combination: usize,
+        _combinations: usize,
+        _spec: &AliasSpec,
+        _aliases: &TracedAliases,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+    /// Takes the [`BlockRewrite`]s of a single combination of loop values and turns them into a
+    /// substitutions-map after evaluating their alias-spec.
+    ///
+    /// The block-rewrites share the alias-spec, so it is evaluated once and the substitutions are
+    /// the same for all the blocks (e.g. `hash()` and `counter()` values agree between them).
+    ///
+    /// The whole alias-spec is resolved, but only aliases used by the blocks (directly or through
+    /// other used aliases) are evaluated - unless `strict` option is set. Private aliases are
+    /// evaluated, but excluded from the substitutions. Builtin aliases (`__invocation_id`,
+    /// `__seed`) are always included unless shadowed by user-defined ones.
+    ///
+    /// Returns `None` if the condition of `when(...)` directive evaluates to `false` - the
+    /// combination is not generated.
+    pub fn make_substitutions(
+        &self,
+        block_rewrites: &[BlockRewrite],
+    ) -> Result<Option<BTreeMap<String, Rc<Value>>>, Error> {
+        self.eval_spec(block_rewrites, None)
+    }
+    /// Implements [`Interpreter::make_substitutions`] recording the values of all the aliases
+    /// into `traced` if it is given.
+    ///
+    /// For the trace the aliases unused by the blocks are evaluated as well, after the used ones.
+    /// The ones failing to evaluate are left out of the trace instead of failing the expansion.
+    fn eval_spec(
+        &self,
+        block_rewrites: &[BlockRewrite],
+        mut traced: Option<&mut TracedAliases>,
+    ) -> Result<Option<BTreeMap<String, Rc<Value>>>, Error> {
+        let spec = block_rewrites[0].spec();
+        let mut scope = Scope::default();
+        spec.resolve(self.environment.as_ref(), &mut scope, None)?;
+        if let Some(condition) = spec.when() {
+            condition.resolve(self.environment.as_ref(), &mut scope, Some(&Type::LitBool))?;
+        }
+
+        // Only the aliases the blocks (and the condition) actually depend on are evaluated.
+        let mut used = block_rewrites
+            .iter()
+            .flat_map(|block_rewrite| used_aliases(spec, block_rewrite.block()))
+            .collect::<HashSet<_>>();
+        if let Some(condition) = spec.when() {
+            used.extend(expr_used_aliases(spec, condition));
+        }
+        let strict = self.environment.options().strict();
+
+        let mut context = Context::new(scope.metadata_rc());
+        let mut substitutions = self.make_builtin_substitutions();
+        if let Some(traced) = traced.as_deref_mut() {
+            traced.extend(
+                substitutions
+                    .iter()
+                    .map(|(alias, value)| (alias.clone(), (value.clone(), false))),
+            );
+        }
+        let mut unused = Vec::new();
+        for item in spec.items() {
+            let alias = item.alias();
+            if !strict && !used.contains(&alias.name()) {
+                unused.push(item);
+                continue;
+            }
+            let Evaluated::Bindings(bindings_map) = item.eval(&self.environment, &mut context)?
+            else {
+                unreachable!()
+            };
+            for (alias, value) in bindings_map.iter() {
+                let Evaluated::Value(value) = value else {
+                    unreachable!()
+                };
+                if let Some(traced) = traced.as_deref_mut() {
+                    traced.insert(alias.name(), (value.clone(), item.is_private()));
+                }
+                // Private aliases are only available to other aliases.
+                if !item.is_private() {
+                    substitutions.insert(alias.name(), value.clone());
+                }
+            }
+        }
+        if let Some(condition) = spec.when() {
+            let Evaluated::Value(value) = condition.eval(&self.environment, &mut context)? else {
+                unreachable!()
+            };
+            match value.kind() {
+                ValueKind::LitBool(lit_bool) if !lit_bool.value => return Ok(None),
+                ValueKind::LitBool(_) => {}
+                _ => {
+                    return Err(Error::TypeError(
+                        format!(
+                            "Condition of when(...) must evaluate to {}, got {}",
+                            Type::LitBool,
+                            value.type_()
+                        ),
+                        condition.span(),
+                    ))
+                }
+            }
+        }
+        if let Some(traced) = traced {
+            for item in unused {
+                let Ok(Evaluated::Bindings(bindings_map)) =
+                    item.eval(&self.environment, &mut context)
+                else {
+                    continue;
+                };
+                for (alias, value) in bindings_map.iter() {
+                    if let Evaluated::Value(value) = value {
+                        traced.insert(alias.name(), (value.clone(), item.is_private()));
+                    }
+                }
+            }
+        }
+        Ok(Some(substitutions))
+    }
+    /// Makes substitutions of the builtin aliases - they could be shadowed by user-defined aliases.
+    fn make_builtin_substitutions(&self) -> BTreeMap<String, Rc<Value>> {
+        [
+            (INVOCATION_ID_ALIAS, self.environment.invocation_id()),
+            (SEED_ALIAS, self.environment.seed()),
+        ]
+        .iter()
+        .map(|(alias, value)| {
+            let lit_int = LitInt::new(value.to_string().as_str(), Span::call_site());
+            (alias.to_string(), Rc::new(Value::from_lit_int(lit_int)))
+        })
+        .collect()
+    }
+    /// Performs alias substitutions in the given block.
+    pub fn substitute(
+        &self,
+        block: &mut syn::Block,
+        substitutions: BTreeMap<String, Rc<Value>>,
+    ) -> Result<(), Error> {
+        let mut visitor = AliasSubstitutionVisitor::new(substitutions);
+        visitor.substitute(block);
+        if let Some(err) = visitor.error() {
+            return Err(err.clone());
+        }
+
+        self.deprecation_service.emit(block);
+        Ok(())
+    }
+    /// Executes the interpreter within the context of a single block-rewrite AST node using the
+    /// substitutions made by [`Interpreter::make_substitutions`].
+    ///
+    /// The outer attributes of the invocation are prepended to every top-level item of the block.
+    /// With `options(scoped)` the items are wrapped into an anonymous const scope.
+    pub fn execute_block_rewrite(
+        &self,
+        block_rewrite: &BlockRewrite,
+        substitutions: BTreeMap<String, Rc<Value>>,
+        attrs: &[Attribute],
+    ) -> Result<TokenStream, Error> {
+        let mut block = block_rewrite.block().clone();
+        self.substitute(&mut block, substitutions)?;
+        let exported = block
+            .stmts
+            .iter_mut()
+            .map(take_export_marker)
+            .collect::<Vec<_>>();
+        Self::attach_attrs(&mut block, attrs)?;
+
+        if self.environment.options().scoped() {
+            return Self::scope_items(block.stmts, &exported);
+        }
+        let content = &block.stmts;
+        Ok(quote! { #(#content)* })
+    }
+    /// Wraps the top-level items into an anonymous `const _: () = { ... };` scope, so that they
+    /// don't leak into the surrounding namespace.
+    ///
+    /// Impl blocks and the items marked with `#[compose(export)]` are left outside of the scope -
+    /// except for the impl blocks of the scoped types and traits, which have to stay next to them
+    /// to be able to reference them. Impls are effective regardless of the scope anyway.
+    fn scope_items(stmts: Vec<syn::Stmt>, exported: &[bool]) -> Result<TokenStream, Error> {
+        let scoped_names = stmts
+            .iter()
+            .zip(exported)
+            .filter(|(_, is_exported)| !**is_exported)
+            .filter_map(|(stmt, _)| match stmt {
+                syn::Stmt::Item(item) => type_item_ident(item).cloned(),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let mut scoped = Vec::new();
+        let mut outside = Vec::new();
+        for (stmt, is_exported) in stmts.into_iter().zip(exported) {
+            match &stmt {
+                syn::Stmt::Item(syn::Item::Impl(item)) if !impl_refers_to(item, &scoped_names) => {
+                    outside.push(stmt)
+                }
+                _ if *is_exported => outside.push(stmt),
+                syn::Stmt::Item(_) | syn::Stmt::Macro(_) => scoped.push(stmt),
+                _ => {
+                    return Err(Error::EvalError(
+                        "Option scoped can only be applied to blocks consisting of items"
+                            .to_string(),
+                        stmt.span(),
+                    ))
+                }
+            }
+        }
+        if scoped.is_empty() {
+            return Ok(quote! { #(#outside)* });
+        }
+        Ok(quote! {
+            const _: () = { #(#scoped)* };
+            #(#outside)*
+        })
+    }
+    /// Prepends the attributes to every top-level item and statement of the block.
+    ///
+    /// The attributes are attached to each of them rather than wrapping the whole output, so that
+    /// `#[cfg(...)]` applies to the items themselves.
+    fn attach_attrs(block: &mut syn::Block, attrs: &[Attribute]) -> Result<(), Error> {
+        if attrs.is_empty() {
+            return Ok(());
+        }
+        for stmt in block.stmts.iter_mut() {
+            let Some(stmt_attrs) = stmt_attrs_mut(stmt) else {
+                return Err(Error::EvalError(
+                    "Attributes of the invocation can only be applied to items and statements, \
+                    not to expressions"
+                        .to_string(),
+                    stmt.span(),
+                ));
+            };
+            stmt_attrs.splice(0..0, attrs.iter().cloned());
+        }
+        Ok(())
+    }
+    /// Expands the loops and executes the resulting block-rewrites passing the substitutions and
+    /// the output of each combination of loop values to `on_rewrite`.
+    ///
+    /// The output of a combination consists of the outputs of all the blocks in order.
+    fn execute_block_rewrites(
+        &self,
+        args: &RawAST,
+        mut on_rewrite: impl FnMut(&BTreeMap<String, Rc<Value>>, TokenStream) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        if let Some(spec) = args.spec() {
+            self.environment.set_options(spec.options().clone());
+        }
+        let expanded = args.expand()?;
+
+        let combinations = expanded.combinations().len();
+        for (combination, block_rewrites) in expanded.combinations().enumerate() {
+            let mut traced = self.trace_dir().map(|_| TracedAliases::new());
+            let Some(substitutions) = self.eval_spec(block_rewrites, traced.as_mut())? else {
+                continue;
+            };
+            if let Some(traced) = traced {
+                self.trace(combination, combinations, block_rewrites[0].spec(), &traced)?;
+            }
+            let mut stream = TokenStream::new();
+            for block_rewrite in block_rewrites {
+                stream.extend(self.execute_block_rewrite(
+                    block_rewrite,
+                    substitutions.clone(),
+                    args.attrs(),
+                )?);
+            }
+            on_rewrite(&substitutions, stream)?;
+        }
+        Ok(())
+    }
+    /// Executes the interpreter and formats the output - each block-rewrite is formatted
+    /// separately and preceded by a comment with the values of the loop variables.
+    ///
+    /// The output of each block-rewrite must consist of items.
+    #[cfg(feature = "pretty")]
+    pub fn execute_pretty(self, args: RawAST) -> Result<String, Error> {
+        let loop_aliases = args
+            .loops()
+            .iter()
+            .flat_map(|loops| loops.loops().iter().map(|item| item.alias()))
+            .flat_map(|alias| match alias.as_ref() {
+                LoopAlias::Simple(alias) => vec![alias.name()],
+                LoopAlias::Tuple(tuple) => {
+                    tuple.iter_recursive().map(|alias| alias.name()).collect()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = Vec::new();
+        let mut combination = 0;
+        self.execute_block_rewrites(&args, |substitutions, stream| {
+            if !loop_aliases.is_empty() {
+                let variables = loop_aliases
+                    .iter()
+                    .filter_map(|alias| {
+                        let value = substitutions.get(alias)?;
+                        Some((alias.clone(), value.to_string()))
+                    })
+                    .collect::<Vec<_>>();
+                result.push(make_separator(combination, &variables));
+            }
+            combination += 1;
+            result.push(unparse_items(stream)?);
+            Ok(())
+        })?;
+        Ok(result.concat())
+    }
+    /// Executes the interpreter - main entry-point of the library.
+    ///
+    /// With `options(pretty)` the output is produced from the formatted code.
+    pub fn execute(self, args: RawAST) -> Result<TokenStream, Error> {
+        debug!("Executing interpreter with arguments: {:?}", args);
+
+        #[cfg(feature = "pretty")]
+        if args.spec().is_some_and(|spec| spec.options().pretty()) {
+            let code = self.execute_pretty(args)?;
+            return code
+                .parse::<TokenStream>()
+                .map_err(|err| internal_error!("Failed to tokenize formatted code: {}", err));
+        }
+
+        let mut result = vec![];
+        self.execute_block_rewrites(&args, |_, stream| {
+            result.push(stream);
+            Ok(())
+        })?;
+
+        Ok(quote! { #(#result)* })
+    }
+}
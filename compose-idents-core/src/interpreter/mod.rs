@@ -4,3 +4,4 @@ pub use core::*;
 mod test;
 #[cfg(test)]
 mod tests;
+mod usage;
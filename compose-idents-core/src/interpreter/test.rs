@@ -57,7 +57,7 @@ macro_rules! make_interpreter_test {
                 $crate::util::unique_id::next_unique_id(),
                 loops_spec.map(Rc::new),
                 alias_spec.map(Rc::new),
-                block,
+                vec![block],
             );
 
             let result = interpreter.execute(args);
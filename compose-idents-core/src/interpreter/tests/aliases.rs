@@ -0,0 +1,353 @@
+//! Tests for alias definition functionality.
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    aliases,
+
+    // Alias definition with a value expression.
+    (
+        value_expr,
+        { alias = foo },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    // Alias definition with a func-call expression.
+    (
+        call_expr,
+        { alias = concat(foo, _, bar) },
+        { fn alias() -> u32 { 1 } },
+        { fn foo_bar() -> u32 { 1 } },
+        None,
+    ),
+    // Multiple aliases.
+    (
+        multiple_aliases,
+        { alias1 = foo, alias2 = concat(foo, _, bar) },
+        { fn alias1() -> u32 { alias2 } },
+        { fn foo() -> u32 { foo_bar } },
+        None,
+    ),
+    // Alias reuse.
+    (
+        alias_reuse,
+        { alias1 = foo, alias2 = concat(alias1, _, bar) },
+        { fn alias1() -> u32 { alias2 } },
+        { fn foo() -> u32 { foo_bar } },
+        None,
+    ),
+    // Aliases could reference the ones defined after them.
+    (
+        forward_reference,
+        { alias2 = concat(alias1, _impl), alias1 = foo },
+        { fn alias2() -> u32 { alias1 } },
+        { fn foo_impl() -> u32 { foo } },
+        None,
+    ),
+    (
+        forward_reference_transitive,
+        { alias3 = upper(alias2), alias2 = concat(alias1, _bar), alias1 = foo },
+        { fn alias3() -> u32 { 1 } },
+        { fn FOO_BAR() -> u32 { 1 } },
+        None,
+    ),
+    // A reference of an alias to its own name is literal.
+    (
+        self_reference,
+        { Wrapper = concat(Wrapper, Int) },
+        { struct Wrapper; },
+        { struct WrapperInt; },
+        None,
+    ),
+    // Aliases referencing each other in a cycle.
+    (
+        cyclic_reference,
+        { alias1 = concat(alias2, _x), alias2 = concat(alias1, _y) },
+        { fn alias1() -> u32 { 1 } },
+        { },
+        Some(ErrorType::CyclicReferenceError),
+    ),
+    (
+        cyclic_reference_unused,
+        { alias = foo, unused1 = upper(unused2), unused2 = lower(unused1) },
+        { fn alias() -> u32 { 1 } },
+        { },
+        Some(ErrorType::CyclicReferenceError),
+    ),
+    // Juxtaposition of atoms is a shorthand for `concat(...)`.
+    (
+        juxtaposition,
+        { prefix = get, alias = prefix _ foo _bar, other = baz },
+        { fn alias() -> u32 { other } },
+        { fn get_foo_bar() -> u32 { baz } },
+        None,
+    ),
+    (
+        juxtaposition_int,
+        { for i in [1, 2] alias = foo _ i },
+        { fn alias() {} },
+        { fn foo_1() {} fn foo_2() {} },
+        None,
+    ),
+    (
+        juxtaposition_leading_underscore,
+        { alias = _ foo },
+        { fn alias() {} },
+        { fn _foo() {} },
+        None,
+    ),
+    // Keywords aren't concatenated.
+    (
+        juxtaposition_keywords,
+        { alias = pub async },
+        { const X: &str = stringify!(alias); },
+        { const X: &str = stringify!(pub async); },
+        None,
+    ),
+    // Doc-comments of alias definitions don't affect the expansion.
+    (
+        documented_aliases,
+        {
+            /// Wire-format name.
+            wire = snake_case(MyType),
+            #[doc = "Getter of the wire-format name."]
+            let getter = concat(get_, wire)
+        },
+        { fn wire() {} },
+        { fn my_type() {} },
+        None,
+    ),
+    // Duplicate aliases.
+    (
+        duplicate_aliases,
+        { alias = foo, alias = bar },
+        { fn alias() -> u32 { alias2 } },
+        { },
+        Some(ErrorType::RedefinedNameError),
+    ),
+    // Raw identifiers as alias names - `r#type` and `type` are the same identifier.
+    (
+        raw_ident_alias,
+        { r#type = to_type("u8"), r#fn = concat(get_, r#type) },
+        { fn r#fn() -> r#type { 1 } },
+        { fn get_u8() -> u8 { 1 } },
+        None,
+    ),
+    (
+        raw_ident_alias_unraw_occurrence,
+        { r#foo = bar },
+        { fn foo() -> u32 { 1 } },
+        { fn bar() -> u32 { 1 } },
+        None,
+    ),
+    (
+        duplicate_raw_ident_aliases,
+        { r#foo = bar, foo = baz },
+        { fn foo() -> u32 { 1 } },
+        { },
+        Some(ErrorType::RedefinedNameError),
+    ),
+    // Alias re-use coercion.
+    //
+    // This is a very subtle case:
+    //  - `s` in isolation has type `ident`, while `"foo bar"` has type `str`.
+    //  - When `s` is re-used, but its target type is still set to `ident` (which would be a bug)
+    //    then the coercion during the eval-phase would fail, because coercion from `str` to `ident`
+    //    is not possible.
+    //  - But if the resolve-phase correctly accounts for alias re-use - it should set the target
+    //    type of `s` to `str`, and then the coercion during the eval-phase should succeed.
+    (
+        reuse_coercion,
+        { s = "foo bar", alias = s },
+        { fn my_fn() -> &str { alias } },
+        { fn my_fn() -> &str { "foo bar" } },
+        None,
+    ),
+    // An unused alias is not evaluated, so its evaluation error is tolerated.
+    (
+        unused_alias_not_evaluated,
+        { alias = foo, unused = concat(foo, _, &'static str) },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    // A used alias is evaluated.
+    (
+        used_alias_evaluated,
+        { alias = foo, used = concat(foo, _, &'static str) },
+        { fn alias() -> u32 { used } },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    // An alias used only through another used alias is evaluated.
+    (
+        transitively_used_alias_evaluated,
+        { used = concat(foo, _, &'static str), alias = upper(used) },
+        { fn alias() -> u32 { 1 } },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    // An alias used only in a string placeholder is evaluated.
+    (
+        alias_used_in_placeholder_evaluated,
+        { used = concat(foo, _, &'static str) },
+        { #[doc = "% used %"] fn f() -> u32 { 1 } },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    // In strict mode all aliases are evaluated regardless of their usage.
+    (
+        strict_unused_alias_evaluated,
+        { options(strict), alias = foo, unused = concat(foo, _, &'static str) },
+        { fn alias() -> u32 { 1 } },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    (
+        strict,
+        { options(strict), alias = foo, unused = bar },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    // Private aliases are usable by other aliases, but aren't substituted into the block.
+    (
+        private_alias,
+        { let base = snake_case(MyType), getter = concat(get_, base) },
+        { fn getter(base: u32) -> u32 { base } },
+        { fn get_my_type(base: u32) -> u32 { base } },
+        None,
+    ),
+    // Builtin aliases could be shadowed by user-defined ones.
+    (
+        builtin_alias_shadowing,
+        { __seed = foo, __invocation_id = bar },
+        { fn __seed() -> u32 { __invocation_id } },
+        { fn foo() -> u32 { bar } },
+        None,
+    ),
+    // Private aliases aren't substituted into string placeholders of the block either.
+    (
+        private_alias_placeholder,
+        { let base = foo, name = concat(base, _bar) },
+        { #[doc = "%base% and %name%"] fn name() {} },
+        { #[doc = "%base% and foo_bar"] fn foo_bar() {} },
+        None,
+    ),
+    // Private aliases could be re-used by loops and aliases alike.
+    (
+        private_alias_loop,
+        { for x in [a, b]
+
+          let prefix = concat(x, _),
+          name = concat(prefix, fn)
+        },
+        { fn name(prefix: u32) {} },
+        { fn a_fn(prefix: u32) {} fn b_fn(prefix: u32) {} },
+        None,
+    ),
+    // A tuple of aliases could be destructured from a tuple of values.
+    (
+        tuple_destructuring,
+        { (ok_ty, err_ty) = (String, io::Error) },
+        { type R = Result<ok_ty, err_ty>; },
+        { type R = Result<String, io::Error>; },
+        None,
+    ),
+    (
+        tuple_destructuring_nested,
+        { (a, (b, c)) = (foo, (bar, baz)), name = concat(a, _, c) },
+        { fn name() -> u32 { b } },
+        { fn foo_baz() -> u32 { bar } },
+        None,
+    ),
+    (
+        tuple_destructuring_reuse,
+        { prefix = foo, (getter, setter) = (concat(get_, prefix), concat(set_, prefix)) },
+        { fn getter() {} fn setter() {} },
+        { fn get_foo() {} fn set_foo() {} },
+        None,
+    ),
+    (
+        tuple_destructuring_mismatched_length,
+        { (a, b) = (foo, bar, baz) },
+        { fn a() {} },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        tuple_destructuring_mismatched_shape,
+        { (a, (b, c)) = (foo, bar) },
+        { fn a() {} },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        tuple_destructuring_duplicate_aliases,
+        { (a, a) = (foo, bar) },
+        { fn a() {} },
+        { },
+        Some(ErrorType::RedefinedNameError),
+    ),
+    (
+        tuple_destructuring_duplicate_plain_alias,
+        { a = foo, (a, b) = (bar, baz) },
+        { fn a() {} },
+        { },
+        Some(ErrorType::RedefinedNameError),
+    ),
+    (
+        tuple_destructuring_placeholders,
+        { (a, _, (_, b)) = (foo, bar, (baz, qux)), (_, _) = (x, y) },
+        { fn a() -> u32 { b } },
+        { fn foo() -> u32 { qux } },
+        None,
+    ),
+
+);
+
+/// Checks that builtin aliases have the same value within a single invocation - including all the
+/// loop iterations and string placeholders - and differ between invocations.
+#[test]
+fn builtin_aliases() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use std::rc::Rc;
+
+    let input = r#"
+        for x in [a, b]
+        {
+            const x: (u64, u64, &str) = (__invocation_id, __seed, "%__invocation_id%/%__seed%");
+        }
+    "#;
+    let execute = || {
+        let environment = Rc::new(Environment::new_initialized(Environment::make_seed(
+            None, input,
+        )));
+        Environment::maybe_set_global(environment.clone());
+        DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+        let interpreter = Interpreter::new(environment.clone(), DeprecationService::scoped());
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        let expected = format!(
+            "({id} , {seed} , \"{id}/{seed}\")",
+            id = environment.invocation_id(),
+            seed = environment.seed(),
+        );
+        (interpreter.execute(raw_ast).unwrap().to_string(), expected)
+    };
+
+    let (first, first_expected) = execute();
+    assert_eq!(
+        first,
+        format!(
+            "const a : (u64 , u64 , & str) = {expected} ; const b : (u64 , u64 , & str) = {expected} ;",
+            expected = first_expected,
+        ),
+    );
+    let (second, second_expected) = execute();
+    assert_ne!(first, second);
+    assert_ne!(first_expected, second_expected);
+}
@@ -0,0 +1,91 @@
+//! Tests for `apply_attrs(...)` directive stamping attributes onto the generated items.
+
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    apply_attrs,
+    (
+        multiple_items,
+        { apply_attrs(#[automatically_derived], #[doc(hidden)]), name = Foo },
+        {
+            struct name;
+            impl Clone for name {
+                fn clone(&self) -> Self {
+                    name
+                }
+            }
+        },
+        {
+            #[automatically_derived]
+            #[doc(hidden)]
+            struct Foo;
+            #[automatically_derived]
+            #[doc(hidden)]
+            impl Clone for Foo {
+                fn clone(&self) -> Self {
+                    Foo
+                }
+            }
+        },
+        None,
+    ),
+    (
+        dedup,
+        { apply_attrs(#[doc(hidden)], #[allow(dead_code)]), name = Foo },
+        {
+            #[doc(hidden)]
+            struct name;
+        },
+        {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            struct Foo;
+        },
+        None,
+    ),
+    (
+        placeholders,
+        { for x in [a, b] apply_attrs(#[doc = "Generated for % x %."]), name = concat(x, _fn) },
+        {
+            fn name() {}
+        },
+        {
+            #[doc = "Generated for a."]
+            fn a_fn() {}
+            #[doc = "Generated for b."]
+            fn b_fn() {}
+        },
+        None,
+    ),
+    // Only the top-level items are affected.
+    (
+        nested_items,
+        { apply_attrs(#[doc(hidden)]), name = foo },
+        {
+            mod name {
+                pub fn inner() {}
+            }
+        },
+        {
+            #[doc(hidden)]
+            mod foo {
+                pub fn inner() {}
+            }
+        },
+        None,
+    ),
+    (
+        with_options,
+        { options(scoped), apply_attrs(#[doc(hidden)]), name = foo },
+        {
+            fn name() {}
+        },
+        {
+            const _: () = {
+                #[doc(hidden)]
+                fn foo() {}
+            };
+        },
+        None,
+    ),
+);
@@ -0,0 +1,76 @@
+//! Tests for the outer attributes of the invocation - `#[...] alias = ..., {...}`.
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::error::{Error, ErrorType};
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use rstest::rstest;
+use std::rc::Rc;
+
+fn execute(input: &str) -> Result<String, Error> {
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    Ok(interpreter.execute(raw_ast)?.to_string())
+}
+
+fn expand(input: &str) -> String {
+    syn::parse_str::<proc_macro2::TokenStream>(input)
+        .unwrap()
+        .to_string()
+}
+
+/// The attributes are attached to every generated item in front of its own attributes.
+#[rstest]
+#[case::allow(
+    "#[allow(non_snake_case)] name = concat(Get, Value), { fn name() {} }",
+    "#[allow(non_snake_case)] fn GetValue() {}"
+)]
+#[case::cfg(
+    "#[cfg(test)] name = foo, { fn name() {} struct Helper; }",
+    "#[cfg(test)] fn foo() {} #[cfg(test)] struct Helper;"
+)]
+#[case::custom_attribute(
+    "#[my_crate::marker(kind = \"getter\")] name = foo, { #[inline] fn name() {} const X: u8 = 1; }",
+    "#[my_crate::marker(kind = \"getter\")] #[inline] fn foo() {} \
+     #[my_crate::marker(kind = \"getter\")] const X: u8 = 1;"
+)]
+#[case::multiple_attributes(
+    "#[cfg(test)] #[allow(dead_code)] name = foo, { fn name() {} }",
+    "#[cfg(test)] #[allow(dead_code)] fn foo() {}"
+)]
+#[case::loops(
+    "#[cfg(test)] for name in [foo, bar] { fn name() {} }",
+    "#[cfg(test)] fn foo() {} #[cfg(test)] fn bar() {}"
+)]
+#[case::multiple_blocks(
+    "#[cfg(test)] name = foo, { fn name() {} }, { struct name; }",
+    "#[cfg(test)] fn foo() {} #[cfg(test)] struct foo;"
+)]
+#[case::block_first(
+    "#[cfg(test)] { fn name() {} }, name = foo",
+    "#[cfg(test)] fn foo() {}"
+)]
+#[case::statements(
+    "#[allow(unused)] name = foo, { let name = 1; println!(\"{}\", 1); }",
+    "#[allow(unused)] let foo = 1; #[allow(unused)] println!(\"{}\", 1);"
+)]
+#[case::doc_comment_goes_to_alias(
+    "#[cfg(test)] /// Docs.\nname = foo, { fn name() {} }",
+    "#[cfg(test)] fn foo() {}"
+)]
+#[case::no_attributes("name = foo, { fn name() {} }", "fn foo() {}")]
+fn attached(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(execute(input).unwrap(), expand(expected));
+}
+
+/// Expressions can't carry attributes on stable Rust.
+#[rstest]
+#[case::expression("#[cfg(test)] name = foo, { name + 1 }")]
+#[case::expression_after_item("#[cfg(test)] name = foo, { fn name() {} name() }")]
+fn expression_error(#[case] input: &str) {
+    let err = execute(input).unwrap_err();
+    assert_eq!(err.type_(), ErrorType::EvalError);
+}
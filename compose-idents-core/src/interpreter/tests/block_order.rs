@@ -0,0 +1,42 @@
+//! Tests for the block-first order - `{...}, for ... in [...] alias = ...`.
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use rstest::rstest;
+use std::rc::Rc;
+
+fn execute(input: &str) -> String {
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    interpreter.execute(raw_ast).unwrap().to_string()
+}
+
+/// Both orders expand the same way.
+#[rstest]
+#[case::aliases(
+    "{ fn getter() -> u32 { 1 } }, getter = concat(get_, foo)",
+    "getter = concat(get_, foo), { fn getter() -> u32 { 1 } }"
+)]
+#[case::loops(
+    "{ fn name() -> ty { 1 } }, for (name, ty) in [(foo, u8), (bar, u16)]",
+    "for (name, ty) in [(foo, u8), (bar, u16)] { fn name() -> ty { 1 } }"
+)]
+#[case::loops_and_aliases(
+    "{ fn getter() -> u32 { 1 } }, for x in [foo, bar], for y in [a, b] getter = concat(x, _, y),",
+    "for x in [foo, bar] for y in [a, b] getter = concat(x, _, y), { fn getter() -> u32 { 1 } }"
+)]
+#[case::substitution_table(
+    "{ const name: u32 = value; }, substitutions [ name value; [A] [1]; [B] [2] ]",
+    "substitutions [ name value; [A] [1]; [B] [2] ] { const name: u32 = value; }"
+)]
+#[case::options(
+    "{ fn name() {} }, options(strict), name = foo",
+    "options(strict), name = foo, { fn name() {} }"
+)]
+fn same_expansion(#[case] block_first: &str, #[case] block_last: &str) {
+    assert_eq!(execute(block_first), execute(block_last));
+}
@@ -0,0 +1,78 @@
+//! Tests for multiple blocks in a single invocation - `spec..., { block1 }, { block2 }`.
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use rstest::rstest;
+use std::rc::Rc;
+
+fn execute(input: &str) -> String {
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    interpreter.execute(raw_ast).unwrap().to_string()
+}
+
+#[rstest]
+#[case::aliases(
+    "name = concat(get_, foo), { fn name() {} }, { mod tests { fn name() {} } }",
+    "fn get_foo () { } mod tests { fn get_foo () { } }"
+)]
+#[case::loops(
+    "for x in [a, b] name = concat(x, _fn), { fn name() {} } { const X: &str = stringify!(name); }",
+    "fn a_fn () { } const X : & str = stringify ! (a_fn) ; \
+     fn b_fn () { } const X : & str = stringify ! (b_fn) ;"
+)]
+#[case::trailing_comma(
+    "name = foo, { fn name() {} }, { struct name; },",
+    "fn foo () { } struct foo ;"
+)]
+#[case::block_first(
+    "{ fn name() {} }, { struct name; }, for name in [a, b]",
+    "fn a () { } struct a ; fn b () { } struct b ;"
+)]
+#[case::aliases_used_by_one_block(
+    "a = foo, b = bar, { fn a() {} }, { fn b() {} }",
+    "fn foo () { } fn bar () { }"
+)]
+fn multiple_blocks(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(execute(input), expected);
+}
+
+/// Substitutions are made once per combination, so values of `global_counter()` agree between
+/// the blocks.
+#[test]
+fn shared_counter() {
+    let actual = execute(
+        "for name in [A, B] \
+         options(allow_global_state), id = global_counter(blocks), \
+         { const name: u32 = id; } \
+         { mod tests { const name: u32 = id; } }",
+    );
+    assert_eq!(
+        actual,
+        "const A : u32 = 0 ; mod tests { const A : u32 = 0 ; } \
+         const B : u32 = 1 ; mod tests { const B : u32 = 1 ; }",
+    );
+}
+
+/// The hashes of the same combination agree between the blocks, but differ between the
+/// combinations.
+#[test]
+fn shared_hash() {
+    let actual = execute(
+        "for x in [a, b] name = concat(x, _, hash(x)), \
+         { fn name() {} } \
+         { const NAME: &str = stringify!(name); }",
+    );
+    let names = actual
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|word| word.starts_with("a_") || word.starts_with("b_"))
+        .collect::<Vec<_>>();
+    assert_eq!(names.len(), 4);
+    assert_eq!(names[0], names[1]);
+    assert_eq!(names[2], names[3]);
+    assert_ne!(names[0], names[2]);
+}
@@ -0,0 +1,182 @@
+//! Tests for `<expr> as <type>` casts.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    casts,
+    (
+        to_ident,
+        { alias = "foo" as ident },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    (
+        to_path,
+        { alias = std::vec as path },
+        { fn f(_: alias::Vec<u32>) {} },
+        { fn f(_: std::vec::Vec<u32>) {} },
+        None,
+    ),
+    (
+        to_type,
+        { alias = Foo<Bar> as type },
+        { fn f(_: alias) {} },
+        { fn f(_: Foo<Bar>) {} },
+        None,
+    ),
+    (
+        to_expr,
+        { alias = concat(1, +, 2) as expr },
+        { const X: u32 = alias; },
+        { const X: u32 = 1 + 2; },
+        None,
+    ),
+    (
+        str_to_expr,
+        { alias = "1 + 2" as expr },
+        { const X: u32 = alias; },
+        { const X: u32 = 1 + 2; },
+        None,
+    ),
+    (
+        to_str,
+        { alias = some_ident as str },
+        { fn f() -> &'static str { alias } },
+        { fn f() -> &'static str { "some_ident" } },
+        None,
+    ),
+    (
+        to_byte_str,
+        { alias = "foo" as byte_str },
+        { static NAME: &[u8] = alias; },
+        { static NAME: &[u8] = b"foo"; },
+        None,
+    ),
+    (
+        to_int,
+        { alias = concat(4, 2) as int },
+        { const X: u32 = alias; },
+        { const X: u32 = 42; },
+        None,
+    ),
+    (
+        to_float,
+        { alias = to_tokens(2.5f64) as float },
+        { const X: f64 = alias; },
+        { const X: f64 = 2.5f64; },
+        None,
+    ),
+    (
+        int_to_float,
+        { alias = to_float(3) },
+        { const X: f64 = alias; },
+        { const X: f64 = 3.0; },
+        None,
+    ),
+    (
+        int_as_float,
+        { alias = 42 as float },
+        { const X: f64 = alias; },
+        { const X: f64 = 42.0; },
+        None,
+    ),
+    (
+        to_float_failure,
+        { alias = foo as float },
+        { const X: f64 = alias; },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        to_char,
+        { alias = to_tokens('c') as char },
+        { const X: char = alias; },
+        { const X: char = 'c'; },
+        None,
+    ),
+    (
+        to_char_failure,
+        { alias = foo as char },
+        { const X: char = alias; },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        to_lifetime,
+        { alias = to_tokens('a) as lifetime },
+        { struct S<'a> { s: std::borrow::Cow<alias, str> } },
+        { struct S<'a> { s: std::borrow::Cow<'a, str> } },
+        None,
+    ),
+    (
+        to_lifetime_from_ident,
+        { alias = concat(a, b) as lifetime },
+        { struct S<'alias> { s: &'alias str } },
+        { struct S<'ab> { s: &'ab str } },
+        None,
+    ),
+    (
+        to_lifetime_failure,
+        { alias = "a" as lifetime },
+        { struct S<'a> { s: std::borrow::Cow<alias, str> } },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        to_bool,
+        { alias = raw(true) as bool },
+        { const X: bool = alias; },
+        { const X: bool = true; },
+        None,
+    ),
+    (
+        to_bool_failure,
+        { alias = foo as bool },
+        { const X: bool = alias; },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        to_tokens,
+        { alias = foo as tokens },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    (
+        to_attr,
+        { alias = raw(#[inline]) as attr },
+        {
+            #[alias]
+            fn f() {}
+        },
+        {
+            #[inline]
+            fn f() {}
+        },
+        None,
+    ),
+    (
+        function_call_argument,
+        { alias = concat(upper(foo) as str, "_bar") as ident },
+        { fn alias() -> u32 { 1 } },
+        { fn FOO_bar() -> u32 { 1 } },
+        None,
+    ),
+    (
+        rust_cast_expression,
+        { alias = x as u32 },
+        { const Y: u32 = alias; },
+        { const Y: u32 = x as u32; },
+        None,
+    ),
+    (
+        invalid,
+        { alias = "foo bar" as ident },
+        { fn alias() -> u32 { 1 } },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+);
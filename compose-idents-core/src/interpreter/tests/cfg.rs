@@ -0,0 +1,91 @@
+//! Tests for `cfg(<predicate>, <then>, <else>)` expressions.
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    cfg,
+
+    // Disabled feature selects the else-branch.
+    (
+        feature_disabled,
+        { backend = cfg(feature = "compose-idents-missing", GpuBackend, CpuBackend) },
+        { struct backend; },
+        { struct CpuBackend; },
+        None,
+    ),
+    // Predicates could be combined.
+    (
+        combined_predicate,
+        { backend = cfg(not(any(test, feature = "compose-idents-missing")), GpuBackend, CpuBackend) },
+        { struct backend; },
+        { struct GpuBackend; },
+        None,
+    ),
+    // Branches could be arbitrary expressions.
+    (
+        call_branches,
+        { name = cfg(all(), concat(foo, _, bar), baz) },
+        { fn name() {} },
+        { fn foo_bar() {} },
+        None,
+    ),
+    // Commas within generic arguments don't split the branches.
+    (
+        generic_branches,
+        { ty = cfg(any(), Result<Option<T>, E>, HashMap<K, Vec<u8>>) },
+        { fn f(_: ty) {} },
+        { fn f(_: HashMap<K, Vec<u8>>) {} },
+        None,
+    ),
+    // `cfg()` could be nested in function calls and could use other aliases.
+    (
+        nested,
+        { prefix = foo, name = concat(cfg(any(), gpu, cpu), _, prefix) },
+        { fn name() {} },
+        { fn cpu_foo() {} },
+        None,
+    ),
+    // The unselected branch is not evaluated.
+    (
+        unselected_branch_not_evaluated,
+        { name = cfg(all(), foo, concat(foo, _, &'static str)) },
+        { fn name() {} },
+        { fn foo() {} },
+        None,
+    ),
+    // The selected branch is evaluated as usual.
+    (
+        selected_branch_evaluated,
+        { name = cfg(any(), foo, concat(foo, _, &'static str)) },
+        { fn name() {} },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+);
+
+/// Checks that enabled features are read from `CARGO_FEATURE_*` environment variables.
+#[test]
+fn feature_enabled() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use std::rc::Rc;
+
+    std::env::set_var("CARGO_FEATURE_COMPOSE_IDENTS_TEST_GPU", "1");
+
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    let raw_ast = syn::parse_str::<RawAST>(
+        "backend = cfg(feature = \"compose-idents-test-gpu\", GpuBackend, CpuBackend), \
+        { struct backend; }",
+    )
+    .unwrap();
+
+    assert_eq!(
+        interpreter.execute(raw_ast).unwrap().to_string(),
+        "struct GpuBackend ;",
+    );
+}
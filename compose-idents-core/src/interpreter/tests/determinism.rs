@@ -0,0 +1,45 @@
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use std::rc::Rc;
+
+/// Checks that the same input always produces byte-identical output - including the order of the
+/// generated items and the attached deprecation attributes.
+#[test]
+fn deterministic_output() {
+    let input = r#"
+        for (x, ty) in [(a, u8), (b, u16), (c, u32)]
+        for n in [1, 2, 3]
+        name = concat(x, _, n);
+        getter = concat(get_, name);
+        let base = upper(x);
+        constant = concat(base, _, n);
+        unique = concat(x, _, hash(n));
+        {
+            #[doc = "Docs of % name % (% unique %)."]
+            pub struct name(pub ty);
+
+            impl name {
+                pub fn getter(&self) -> ty { self.0 }
+            }
+
+            pub const constant: ty = n;
+            pub static unique: &str = "% getter %/% constant %";
+        }
+    "#;
+    let execute = || {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+        let environment = Rc::new(Environment::new_initialized(1));
+        let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        interpreter.execute(raw_ast).unwrap().to_string()
+    };
+
+    let expected = execute();
+    assert!(expected.contains("deprecated"));
+    for _ in 0..50 {
+        assert_eq!(execute(), expected);
+    }
+}
@@ -0,0 +1,58 @@
+//! Tests for bin() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    bin,
+    (
+        str,
+        { alias = bin(5) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "101"
+            }
+        },
+        None,
+    ),
+    (
+        concat,
+        { alias = concat(REG_, bin(5)) },
+        {
+            const alias: u32 = 1;
+        },
+        {
+            const REG_101: u32 = 1;
+        },
+        None,
+    ),
+    (
+        placeholder,
+        { addr = bin(0b1_0) },
+        {
+            #[doc = "Register % addr %"]
+            struct Reg;
+        },
+        {
+            #[doc = "Register 10"]
+            struct Reg;
+        },
+        None,
+    ),
+    (
+        str_failure,
+        { alias = bin("5") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
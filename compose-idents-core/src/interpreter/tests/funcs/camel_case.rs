@@ -28,13 +28,15 @@ make_interpreter_test!(
         None,
     ),
     (
-        path_failure,
-        { alias = camel_case(foo::bar) },
+        path,
+        { alias = camel_case(crate::models::user_profile<T>) },
         {
-            fn my_fn() -> &str { alias }
+            fn my_fn() -> alias { todo!() }
         },
-        {},
-        Some(ErrorType::SignatureError),
+        {
+            fn my_fn() -> crate::models::userProfile<T> { todo!() }
+        },
+        None,
     ),
     (
         expr_failure,
@@ -0,0 +1,61 @@
+//! Tests for capitalize() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    capitalize,
+    (
+        ident,
+        { alias = capitalize(someHTTPThing) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn SomeHTTPThing() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = capitalize("fooBar") },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "FooBar" }
+        },
+        None,
+    ),
+    (
+        empty_str,
+        { alias = capitalize("") },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "" }
+        },
+        None,
+    ),
+    (
+        path,
+        { alias = capitalize(crate::models::user<T>) },
+        {
+            fn my_fn() -> alias { todo!() }
+        },
+        {
+            fn my_fn() -> crate::models::User<T> { todo!() }
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = capitalize(42) },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
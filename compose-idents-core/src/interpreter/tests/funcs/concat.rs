@@ -0,0 +1,212 @@
+//! Tests for concat() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    concat,
+    (
+        idents,
+        { alias = concat(foo, Bar, Baz) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn fooBarBaz() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        idents_and_tokens,
+        { alias = concat(foo, _, 1, _, bar) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn foo_1_bar() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        idents_and_tokens_failure,
+        { alias = concat(foo, _, 1, _, &'static str) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    (
+        ident_str_and_int,
+        { alias = concat(foo, "_", 1) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn foo_1() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        ident_and_str,
+        { alias = concat(foo, "_bar") },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn foo_bar() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        ident_and_int,
+        { alias = concat(foo, 1) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn foo1() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        str_and_int,
+        { alias = concat("foo_", 1) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo_1"
+            }
+        },
+        None,
+    ),
+    (
+        strs,
+        { alias = concat("foo", "bar", "baz") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foobarbaz"
+            }
+        },
+        None,
+    ),
+    (
+        ints,
+        { alias = concat(1, 2, 3) },
+        {
+            fn my_fn() -> u32 {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> u32 {
+                123
+            }
+        },
+        None,
+    ),
+    (
+        negative_ints,
+        { alias = concat(-1, 2) },
+        {
+            fn my_fn() -> i32 {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> i32 {
+                -12
+            }
+        },
+        None,
+    ),
+    (
+        negative_ints_failure,
+        { alias = concat(1, -2) },
+        {
+            fn my_fn() -> i32 {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        tokens,
+        // Notice - raw() is used to fence comma-containing argument.
+        { alias = concat(Result<, raw(u32,), String, >) },
+        {
+            fn my_fn() -> alias {
+                Ok(42)
+            }
+        },
+        {
+            fn my_fn() -> Result<u32, String> {
+                Ok(42)
+            }
+        },
+        None,
+    ),
+    (
+        keyword,
+        { alias = concat(ma, tch) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn r#match() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        keyword_ident_tokens,
+        { alias = concat(ty, "pe") },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn r#type() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        non_raw_keyword_failure,
+        { alias = concat(se, lf) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+);
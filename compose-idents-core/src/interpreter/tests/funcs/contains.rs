@@ -0,0 +1,83 @@
+//! Tests for contains() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    contains,
+    (
+        ident_true,
+        { alias = contains(read_async_impl, "async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        ident_false,
+        { alias = contains(read_impl, "async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = contains("read_async_impl", "_async_") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        empty_needle,
+        { alias = contains(read, "") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        longer_needle,
+        { alias = contains(read, "read_async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        with_select,
+        { name = read_async_impl, alias = select(contains(name, "async"), yes, no) },
+        {
+            fn alias() {}
+        },
+        {
+            fn yes() {}
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = contains(42, "async") },
+        {
+            const A: bool = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
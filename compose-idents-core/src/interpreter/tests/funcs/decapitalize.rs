@@ -0,0 +1,61 @@
+//! Tests for decapitalize() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    decapitalize,
+    (
+        ident,
+        { alias = decapitalize(SomeHTTPThing) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn someHTTPThing() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = decapitalize("FooBar") },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "fooBar" }
+        },
+        None,
+    ),
+    (
+        empty_str,
+        { alias = decapitalize("") },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "" }
+        },
+        None,
+    ),
+    (
+        path,
+        { alias = decapitalize(crate::models::User<T>) },
+        {
+            fn my_fn() -> alias { todo!() }
+        },
+        {
+            fn my_fn() -> crate::models::user<T> { todo!() }
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = decapitalize(42) },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
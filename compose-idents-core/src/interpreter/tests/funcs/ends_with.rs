@@ -0,0 +1,83 @@
+//! Tests for ends_with() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    ends_with,
+    (
+        ident_true,
+        { alias = ends_with(read_async, "_async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        ident_false,
+        { alias = ends_with(async_read, "_async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = ends_with("read_async", "async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        empty_needle,
+        { alias = ends_with(read, "") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        longer_needle,
+        { alias = ends_with(read, "async_read") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        with_select,
+        { name = read_async, alias = select(ends_with(name, "_async"), yes, no) },
+        {
+            fn alias() {}
+        },
+        {
+            fn yes() {}
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = ends_with(42, "_async") },
+        {
+            const A: bool = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,96 @@
+//! Tests for env() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    env,
+    (
+        set,
+        { alias = env("CARGO_PKG_NAME") },
+        {
+            static NAME: &str = alias;
+        },
+        {
+            static NAME: &str = "compose-idents-core";
+        },
+        None,
+    ),
+    (
+        in_concat,
+        { alias = concat(crate_, normalize2(env("CARGO_PKG_NAME"))) },
+        {
+            fn alias() {}
+        },
+        {
+            fn crate_compose_idents_core() {}
+        },
+        None,
+    ),
+    (
+        default,
+        { alias = env("COMPOSE_IDENTS_TEST_UNSET", "release") },
+        {
+            static PROFILE: &str = alias;
+        },
+        {
+            static PROFILE: &str = "release";
+        },
+        None,
+    ),
+    (
+        default_ignored,
+        { alias = env("CARGO_PKG_NAME", "unknown") },
+        {
+            static NAME: &str = alias;
+        },
+        {
+            static NAME: &str = "compose-idents-core";
+        },
+        None,
+    ),
+    (
+        unset_failure,
+        { alias = env("COMPOSE_IDENTS_TEST_UNSET") },
+        {
+            static PROFILE: &str = alias;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        ident_failure,
+        { alias = env(PROFILE) },
+        {
+            static PROFILE: &str = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
+
+/// Checks that a variable set in-process is visible to the function.
+#[test]
+fn set_in_process() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use std::rc::Rc;
+
+    std::env::set_var("COMPOSE_IDENTS_TEST_PROFILE", "debug");
+
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    let raw_ast = syn::parse_str::<RawAST>(
+        "build = concat(build_, env(\"COMPOSE_IDENTS_TEST_PROFILE\")), { fn build() {} }",
+    )
+    .unwrap();
+
+    assert_eq!(
+        interpreter.execute(raw_ast).unwrap().to_string(),
+        "fn build_debug () { }",
+    );
+}
@@ -0,0 +1,72 @@
+//! Tests for eq() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    eq,
+    (
+        ident_true,
+        { alias = eq(u8, u8) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        ident_false,
+        { alias = eq(u8, u16) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        type_,
+        { alias = eq(Vec<u8>, Vec < u8 >) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        str_and_ident,
+        { alias = eq("foo", foo) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        select,
+        { alias = select(eq(foo, foo), yes, no) },
+        {
+            fn alias() {}
+        },
+        {
+            fn yes() {}
+        },
+        None,
+    ),
+    (
+        missing_argument,
+        { alias = eq(foo) },
+        {
+            const A: bool = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,84 @@
+//! Tests for global_counter() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    global_counter,
+    (
+        loop_,
+        { for name in [FOO, BAR, BAZ]
+
+          options(allow_global_state),
+          alias = global_counter(messages),
+        },
+        {
+            const name: u32 = alias;
+        },
+        {
+            const FOO: u32 = 0;
+            const BAR: u32 = 1;
+            const BAZ: u32 = 2;
+        },
+        None,
+    ),
+    (
+        separate_keys,
+        {
+          options(allow_global_state),
+          a = global_counter(foo),
+          b = global_counter("bar"),
+        },
+        {
+            const A: u32 = a;
+            const B: u32 = b;
+        },
+        {
+            const A: u32 = 0;
+            const B: u32 = 0;
+        },
+        None,
+    ),
+    (
+        not_allowed_failure,
+        { alias = global_counter(messages) },
+        {
+            const X: u32 = alias;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+);
+
+/// Runs two interpreters sharing the same thread and checks that the counter continues its
+/// sequence.
+#[test]
+fn sequence_continues_across_invocations() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use std::rc::Rc;
+
+    let execute = |input: &str| {
+        let environment = Rc::new(Environment::new_initialized(1));
+        Environment::maybe_set_global(environment.clone());
+        DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+        let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        interpreter.execute(raw_ast).unwrap().to_string()
+    };
+
+    assert_eq!(
+        execute(
+            "options(allow_global_state), id = global_counter(messages), { const A: u32 = id; }"
+        ),
+        "const A : u32 = 0 ;",
+    );
+    assert_eq!(
+        execute(
+            "options(allow_global_state), id = global_counter(messages), { const B: u32 = id; }"
+        ),
+        "const B : u32 = 1 ;",
+    );
+}
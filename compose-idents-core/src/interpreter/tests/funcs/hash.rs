@@ -0,0 +1,253 @@
+//! Tests for hash() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    hash,
+    (
+        ident,
+        { alias = hash(FooBar) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            // We prefix the hash result for an ident-arg prefixed with "__".
+            fn __6067540531923224280() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = hash("foo") },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "856179189752569506" }
+        },
+        None,
+    ),
+    (
+        tokens,
+        { alias = hash(let x = 1;) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn __13252863254122498203() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        ident_prefix,
+        { alias = hash(FooBar, "gen_") },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn gen_6067540531923224280() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        tokens_prefix,
+        { alias = hash(let x = 1;, "gen_") },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn gen_13252863254122498203() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        options_prefix,
+        { options(hash_prefix = "gen_"), alias = hash(FooBar), other = hash(FooBar, "_") },
+        {
+            fn alias() -> u32 { 1 }
+            fn other() -> u32 { 1 }
+        },
+        {
+            fn gen_6067540531923224280() -> u32 { 1 }
+            fn _6067540531923224280() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        ident_seed,
+        { alias = hash(FooBar, 42) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn __2376161548409360357() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        str_seed,
+        { alias = hash("foo", 42) },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "11900177801265368001" }
+        },
+        None,
+    ),
+    (
+        prefix_int_seed,
+        { alias = hash(FooBar, "gen_", 42) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn gen_2376161548409360357() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        prefix_str_seed,
+        { alias = hash(FooBar, "gen_", "my_seed") },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn gen_12063277543109900799() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        options_prefix_seed,
+        { options(hash_prefix = "gen_"), alias = hash(FooBar, 42) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn gen_2376161548409360357() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        str_arg_is_prefix,
+        { alias = hash(FooBar, "seed") },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            // The second string argument is always the prefix - string seeds go third.
+            fn seed6067540531923224280() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        seed_overflow,
+        { alias = hash(FooBar, 18446744073709551616) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        invalid_prefix,
+        { alias = hash(FooBar, "gen-") },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+);
+
+/// Runs two interpreters with the same pinned seed and checks that hashes are identical.
+#[test]
+fn pinned_seed() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use std::rc::Rc;
+
+    let input = "a = hash(foo), b = hash(\"foo\"), { fn a() -> &str { b } }";
+    let execute = |input: &str| {
+        let seed = Environment::make_seed(Some(12345), input);
+        let environment = Rc::new(Environment::new_initialized(seed));
+        Environment::maybe_set_global(environment.clone());
+        DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+        let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        interpreter.execute(raw_ast).unwrap().to_string()
+    };
+
+    assert_eq!(execute(input), execute(input));
+    assert_ne!(
+        execute(input),
+        execute("a = hash(foo), b = hash(\"foo\"), { fn a() -> &str { b }; }"),
+    );
+}
+
+/// Runs interpreters with different seeds and checks that only hashes with an explicit seed match.
+#[test]
+fn explicit_seed() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use std::rc::Rc;
+
+    let execute = |seed: u64, input: &str| {
+        let environment = Rc::new(Environment::new_initialized(seed));
+        Environment::maybe_set_global(environment.clone());
+        DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+        let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        interpreter.execute(raw_ast).unwrap().to_string()
+    };
+
+    for input in [
+        "a = hash(foo, 42), b = hash(\"foo\", 42), { fn a() -> &str { b } }",
+        "a = hash(foo, \"gen_\", 42), { fn a() {} }",
+        "a = hash(foo, \"gen_\", \"my_seed\"), { fn a() {} }",
+    ] {
+        assert_eq!(execute(1, input), execute(2, input), "Input: {}", input);
+    }
+
+    let input = "a = hash(foo), { fn a() {} }";
+    assert_ne!(execute(1, input), execute(2, input));
+}
+
+/// Hashes with an explicit seed don't depend on the spacing of the tokens - they could come from
+/// different invocations, e.g. through fragments of `macro_rules!`.
+#[test]
+fn explicit_seed_tokens_spacing() {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::interpreter::Interpreter;
+    use crate::util::deprecation::DeprecationService;
+    use proc_macro2::{Delimiter, Group};
+    use quote::quote;
+    use std::rc::Rc;
+
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    // An invisible group is what a `$t:ty` fragment of `macro_rules!` is passed as.
+    let fragment = Group::new(Delimiter::None, quote!(u8));
+    let raw_ast = syn::parse2::<RawAST>(quote!(
+        a = hash(Vec<#fragment>, 42),
+        b = hash(Vec < u8 >, 42),
+        c = hash(std::vec::Vec<u8>, 42),
+        d = hash(std: :vec: :Vec<u8>, 42),
+        { a; b; c; d; }
+    ))
+    .unwrap();
+    let output = interpreter.execute(raw_ast).unwrap().to_string();
+    let idents = output.split(';').map(str::trim).collect::<Vec<_>>();
+
+    assert_eq!(idents[0], idents[1]);
+    assert_eq!(idents[2], idents[3]);
+    assert_ne!(idents[0], idents[2]);
+}
@@ -0,0 +1,69 @@
+//! Tests for hex() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    hex,
+    (
+        str,
+        { alias = hex(255) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "ff"
+            }
+        },
+        None,
+    ),
+    (
+        concat,
+        { alias = concat(REG_, hex(255)) },
+        {
+            const alias: u32 = 1;
+        },
+        {
+            const REG_ff: u32 = 1;
+        },
+        None,
+    ),
+    (
+        to_ident,
+        { alias = to_ident(hex(255)) },
+        {
+            fn alias() {}
+        },
+        {
+            fn ff() {}
+        },
+        None,
+    ),
+    (
+        placeholder,
+        { addr = hex(0xFF_00) },
+        {
+            #[doc = "Register % addr %"]
+            struct Reg;
+        },
+        {
+            #[doc = "Register ff00"]
+            struct Reg;
+        },
+        None,
+    ),
+    (
+        str_failure,
+        { alias = hex("255") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,78 @@
+//! Tests for index() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    index,
+    (
+        ident,
+        { alias = index([foo, bar], 1) },
+        { fn alias() -> u32 { 1 } },
+        { fn bar() -> u32 { 1 } },
+        None,
+    ),
+    (
+        str,
+        { alias = index(["foo", "bar"], 0) },
+        { fn my_fn() -> &str { alias } },
+        { fn my_fn() -> &str { "foo" } },
+        None,
+    ),
+    (
+        list_alias,
+        { items = [foo, bar], alias = index(items, 0) },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    (
+        single_item_list,
+        { alias = index([foo], 0) },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    (
+        ragged_nested_list,
+        { alias = index(index([[a1, b1], [c1]], 1), 0) },
+        { fn alias() -> u32 { 1 } },
+        { fn c1() -> u32 { 1 } },
+        None,
+    ),
+    (
+        nested_call,
+        { alias = concat(index([foo, bar], 0), _, baz) },
+        { fn alias() -> u32 { 1 } },
+        { fn foo_baz() -> u32 { 1 } },
+        None,
+    ),
+    (
+        nested_list,
+        { alias = index([[1, 2], [3, 4]], 1) },
+        { const X: [u32; 2] = alias; },
+        { const X: [u32; 2] = [3, 4]; },
+        None,
+    ),
+    (
+        nested_list_item,
+        { alias = index(index([[a, b], [c, d]], 1), 0) },
+        { fn alias() -> u32 { 1 } },
+        { fn c() -> u32 { 1 } },
+        None,
+    ),
+    (
+        out_of_bounds,
+        { alias = index([foo, bar], 2) },
+        { fn alias() -> u32 { 1 } },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    (
+        not_a_list,
+        { alias = index(foo, 0) },
+        { fn alias() -> u32 { 1 } },
+        { },
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,150 @@
+//! Tests for join() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    join,
+    (
+        idents,
+        { alias = join("_", a, b, c) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn a_b_c() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        single_ident,
+        { alias = join("_", foo) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {
+            fn foo() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        strs,
+        { alias = join("::", "foo", "bar", "baz") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo::bar::baz"
+            }
+        },
+        None,
+    ),
+    (
+        tokens,
+        { alias = join("-", foo, 1, "bar") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo-1-bar"
+            }
+        },
+        None,
+    ),
+    (
+        path,
+        { alias = to_path(join("::", "foo", "bar", "baz")) },
+        {
+            fn my_fn() -> alias::Type {
+                todo!()
+            }
+        },
+        {
+            fn my_fn() -> foo::bar::baz::Type {
+                todo!()
+            }
+        },
+        None,
+    ),
+    (
+        idents_path,
+        { alias = join("::", foo, bar) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo::bar"
+            }
+        },
+        None,
+    ),
+    (
+        idents_path_to_str,
+        { alias = to_str(join("::", foo, bar)) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo::bar"
+            }
+        },
+        None,
+    ),
+    (
+        idents_to_path,
+        { alias = to_path(join("::", foo, bar)) },
+        {
+            fn my_fn() -> alias::Type {
+                todo!()
+            }
+        },
+        {
+            fn my_fn() -> foo::bar::Type {
+                todo!()
+            }
+        },
+        None,
+    ),
+    (
+        invalid_ident_failure,
+        { sep = "-", alias = join(sep, foo, bar) },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        no_args_failure,
+        { alias = join("_") },
+        {
+            fn alias() -> u32 {
+                42
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
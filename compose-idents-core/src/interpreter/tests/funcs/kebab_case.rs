@@ -0,0 +1,88 @@
+//! Tests for kebab_case() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    kebab_case,
+    (
+        ident,
+        { alias = kebab_case(MyLongName) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "my-long-name"
+            }
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = kebab_case("foo_bar") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo-bar"
+            }
+        },
+        None,
+    ),
+    (
+        placeholder,
+        { name = kebab_case(MyField) },
+        {
+            #[serde(rename = "% name %")]
+            struct Foo;
+        },
+        {
+            #[serde(rename = "my-field")]
+            struct Foo;
+        },
+        None,
+    ),
+    (
+        raw_ident,
+        { alias = kebab_case(r#type) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "type"
+            }
+        },
+        None,
+    ),
+    (
+        path_failure,
+        { alias = kebab_case(foo::Bar) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+    (
+        int_failure,
+        { alias = kebab_case(42) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,74 @@
+//! Tests for last_segment() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    last_segment,
+    (
+        path,
+        { alias = last_segment(std::net::TcpStream) },
+        {
+            struct alias;
+        },
+        {
+            struct TcpStream;
+        },
+        None,
+    ),
+    (
+        ident,
+        { alias = last_segment(File) },
+        {
+            struct alias;
+        },
+        {
+            struct File;
+        },
+        None,
+    ),
+    (
+        generic_args,
+        { alias = last_segment(std::vec::Vec<u8>) },
+        {
+            struct alias;
+        },
+        {
+            struct Vec;
+        },
+        None,
+    ),
+    (
+        loop_over_types,
+        {
+            for ty in [std::net::TcpStream, std::fs::File]
+            fn_name = concat(open_, snake_case(last_segment(ty)))
+        },
+        {
+            fn fn_name() {}
+        },
+        {
+            fn open_tcp_stream() {}
+            fn open_file() {}
+        },
+        None,
+    ),
+    (
+        reference_failure,
+        { alias = last_segment(&'static str) },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        qself_failure,
+        { alias = last_segment(<Vec<u8> as IntoIterator>::Item) },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+);
@@ -0,0 +1,99 @@
+//! Tests for len() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    len,
+    (
+        idents,
+        { alias = len([foo, bar, baz]) },
+        { const N: usize = alias; },
+        { const N: usize = 3; },
+        None,
+    ),
+    (
+        list_alias,
+        { items = ["foo", "bar"], alias = len(items) },
+        { const N: usize = alias; },
+        { const N: usize = 2; },
+        None,
+    ),
+    (
+        nested_list,
+        { alias = len([[a, b], [c, d], [e, f]]) },
+        { const N: usize = alias; },
+        { const N: usize = 3; },
+        None,
+    ),
+    (
+        nested_list_item,
+        { alias = len(index([[a, b, c], [d,]], 0)) },
+        { const N: usize = alias; },
+        { const N: usize = 3; },
+        None,
+    ),
+    (
+        single_item_list,
+        { alias = len([foo]) },
+        { const N: usize = alias; },
+        { const N: usize = 1; },
+        None,
+    ),
+    (
+        ragged_nested_list,
+        { items = [[a1, b1], [c1]], alias = len(index(items, 1)) },
+        { const N: usize = alias; },
+        { const N: usize = 1; },
+        None,
+    ),
+    (
+        str,
+        { alias = len("hello") },
+        { const N: usize = alias; },
+        { const N: usize = 5; },
+        None,
+    ),
+    (
+        str_non_ascii,
+        { alias = len("héllo") },
+        { const N: usize = alias; },
+        { const N: usize = 5; },
+        None,
+    ),
+    (
+        ident,
+        { alias = len(foo) },
+        { const N: usize = alias; },
+        { const N: usize = 3; },
+        None,
+    ),
+    (
+        ident_alias,
+        { name = my_name, alias = len(name) },
+        { const N: usize = alias; },
+        { const N: usize = 7; },
+        None,
+    ),
+    (
+        tokens,
+        { alias = len(raw(Vec<u8>)) },
+        { const N: usize = alias; },
+        { const N: usize = 10; },
+        None,
+    ),
+    (
+        in_concat,
+        { name = abc, alias = concat(buf_, len(name)) },
+        { struct alias; },
+        { struct buf_3; },
+        None,
+    ),
+    (
+        too_many_args,
+        { alias = len("a", "b") },
+        { const N: usize = alias; },
+        { },
+        Some(ErrorType::SignatureError),
+    ),
+);
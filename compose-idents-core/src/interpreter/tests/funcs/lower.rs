@@ -28,13 +28,15 @@ make_interpreter_test!(
         None,
     ),
     (
-        path_failure,
-        { alias = lower(foo::bar) },
+        path,
+        { alias = lower(crate::models::UserProfile<T>) },
         {
-            fn my_fn() -> &str { alias }
+            fn my_fn() -> alias { todo!() }
         },
-        {},
-        Some(ErrorType::SignatureError),
+        {
+            fn my_fn() -> crate::models::userprofile<T> { todo!() }
+        },
+        None,
     ),
     (
         expr_failure,
@@ -0,0 +1,49 @@
+//! Tests for calls of built-in functions.
+mod bin;
+mod camel_case;
+mod capitalize;
+mod concat;
+mod contains;
+mod decapitalize;
+mod ends_with;
+mod env;
+mod eq;
+mod global_counter;
+mod hash;
+mod hex;
+mod index;
+mod join;
+mod kebab_case;
+mod last_segment;
+mod len;
+mod lower;
+mod ne;
+mod normalize;
+mod normalize2;
+mod oct;
+mod pascal_case;
+mod raw;
+mod repeat;
+mod replace;
+mod select;
+mod short_hash;
+mod shouty_snake_case;
+mod snake_case;
+mod starts_with;
+mod strip_generics;
+mod strip_prefix;
+mod strip_ref;
+mod strip_suffix;
+mod title_case;
+mod to_attr;
+mod to_byte_str;
+mod to_expr;
+mod to_ident;
+mod to_int;
+mod to_list;
+mod to_path;
+mod to_str;
+mod to_tokens;
+mod to_type;
+mod unraw;
+mod upper;
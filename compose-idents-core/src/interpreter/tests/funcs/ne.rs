@@ -0,0 +1,50 @@
+//! Tests for ne() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    ne,
+    (
+        ident_true,
+        { alias = ne(u8, u16) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        ident_false,
+        { alias = ne(u8, u8) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        int,
+        { alias = ne(1, 2) },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        missing_argument,
+        { alias = ne(foo) },
+        {
+            const A: bool = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -1,5 +1,6 @@
 //! Tests for normalize() function.
 
+use crate::error::ErrorType;
 use crate::interpreter::test::make_interpreter_test;
 
 make_interpreter_test!(
@@ -70,4 +71,24 @@ make_interpreter_test!(
         },
         None,
     ),
+    (
+        keyword,
+        { alias = normalize(match) },
+        {
+            fn my_fn() { let alias = 7; }
+        },
+        {
+            fn my_fn() { let r#match = 7; }
+        },
+        None,
+    ),
+    (
+        non_raw_keyword_failure,
+        { alias = normalize(self) },
+        {
+            fn my_fn() { let alias = 7; }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
 );
@@ -0,0 +1,58 @@
+//! Tests for oct() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    oct,
+    (
+        str,
+        { alias = oct(8) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "10"
+            }
+        },
+        None,
+    ),
+    (
+        concat,
+        { alias = concat(REG_, oct(8)) },
+        {
+            const alias: u32 = 1;
+        },
+        {
+            const REG_10: u32 = 1;
+        },
+        None,
+    ),
+    (
+        placeholder,
+        { addr = oct(0o7_7) },
+        {
+            #[doc = "Register % addr %"]
+            struct Reg;
+        },
+        {
+            #[doc = "Register 77"]
+            struct Reg;
+        },
+        None,
+    ),
+    (
+        str_failure,
+        { alias = oct("8") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
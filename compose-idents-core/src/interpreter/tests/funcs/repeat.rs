@@ -0,0 +1,80 @@
+//! Tests for repeat() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    repeat,
+    (
+        str,
+        { alias = repeat("X", 4) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "XXXX"
+            }
+        },
+        None,
+    ),
+    (
+        ident,
+        { alias = repeat(ab, 3) },
+        {
+            struct alias;
+        },
+        {
+            struct ababab;
+        },
+        None,
+    ),
+    (
+        tokens,
+        { alias = repeat(raw(u8,), 3) },
+        {
+            tuple! { alias }
+        },
+        {
+            tuple! { u8, u8, u8, }
+        },
+        None,
+    ),
+    (
+        str_zero,
+        { alias = repeat("X", 0) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                ""
+            }
+        },
+        None,
+    ),
+    (
+        ident_zero_failure,
+        { alias = repeat(foo, 0) },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        too_many_failure,
+        { alias = repeat("X", 100000) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+);
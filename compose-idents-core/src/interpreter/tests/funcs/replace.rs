@@ -0,0 +1,105 @@
+//! Tests for replace() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    replace,
+    (
+        ident,
+        { alias = replace(AsyncReader, "Async", "Blocking") },
+        {
+            struct alias;
+        },
+        {
+            struct BlockingReader;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = replace("foo_bar", "_", "-") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo-bar"
+            }
+        },
+        None,
+    ),
+    (
+        no_match,
+        { alias = replace(foo_bar, "baz", "qux") },
+        {
+            fn alias() {}
+        },
+        {
+            fn foo_bar() {}
+        },
+        None,
+    ),
+    (
+        multiple_matches,
+        { alias = replace(foo_foo_bar, "foo", "baz") },
+        {
+            fn alias() {}
+        },
+        {
+            fn baz_baz_bar() {}
+        },
+        None,
+    ),
+    (
+        empty_to,
+        { alias = replace(get_foo_value, "_value", "") },
+        {
+            fn alias() {}
+        },
+        {
+            fn get_foo() {}
+        },
+        None,
+    ),
+    (
+        nested,
+        { alias = replace(lower(Foo), "f", "b") },
+        {
+            fn alias() {}
+        },
+        {
+            fn boo() {}
+        },
+        None,
+    ),
+    (
+        invalid_ident_failure,
+        { alias = replace(foo_bar, "_", "-") },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        empty_ident_failure,
+        { alias = replace(foo, "foo", "") },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        int_failure,
+        { alias = replace(42, "4", "5") },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,119 @@
+//! Tests for select() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    select,
+    (
+        true_ident,
+        { alias = select(true, foo, bar) },
+        {
+            fn alias() {}
+        },
+        {
+            fn foo() {}
+        },
+        None,
+    ),
+    (
+        false_ident,
+        { alias = select(false, foo, bar) },
+        {
+            fn alias() {}
+        },
+        {
+            fn bar() {}
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = select(true, "foo", "bar") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "foo"
+            }
+        },
+        None,
+    ),
+    (
+        raw_tokens,
+        { qual = select(true, raw(mut), raw()) },
+        {
+            binding! { qual x }
+        },
+        {
+            binding! { mut x }
+        },
+        None,
+    ),
+    (
+        empty_raw_tokens,
+        { qual = select(false, raw(mut), raw()) },
+        {
+            binding! { qual x }
+        },
+        {
+            binding! { x }
+        },
+        None,
+    ),
+    (
+        loop_tuples,
+        { for (name, is_mut) in [(foo, true), (bar, false)] qual = select(is_mut, raw(mut), raw()) },
+        {
+            binding! { name, qual x }
+        },
+        {
+            binding! { foo, mut x }
+            binding! { bar, x }
+        },
+        None,
+    ),
+    (
+        coercion_downstream,
+        { alias = concat(get_, select(false, foo, bar)) },
+        {
+            fn alias() {}
+        },
+        {
+            fn get_bar() {}
+        },
+        None,
+    ),
+    (
+        list,
+        { items = select(true, [a, b], [c, d]), alias = index(items, 1) },
+        {
+            fn alias() {}
+        },
+        {
+            fn b() {}
+        },
+        None,
+    ),
+    (
+        non_bool_failure,
+        { alias = select(yes, foo, bar) },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        str_cond_failure,
+        { alias = select("true", foo, bar) },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+);
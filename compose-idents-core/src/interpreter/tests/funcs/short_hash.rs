@@ -0,0 +1,70 @@
+//! Tests for short_hash() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    short_hash,
+    (
+        ident,
+        { alias = short_hash(FooBar, 6) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn __54343b() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = short_hash("foo", 8) },
+        {
+            fn my_fn() -> &str { alias }
+        },
+        {
+            fn my_fn() -> &str { "0be1c26f" }
+        },
+        None,
+    ),
+    (
+        tokens,
+        { alias = short_hash(let x = 1;, 4) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn __b7eb() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        options_prefix,
+        { options(hash_prefix = "gen_"), alias = short_hash(FooBar, 6) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {
+            fn gen_54343b() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        zero_len,
+        { alias = short_hash(FooBar, 0) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        too_long,
+        { alias = short_hash(FooBar, 17) },
+        {
+            fn alias() -> u32 { 1 }
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+);
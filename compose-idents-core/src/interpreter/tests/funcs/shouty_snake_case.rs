@@ -0,0 +1,71 @@
+//! Tests for shouty_snake_case() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    shouty_snake_case,
+    (
+        ident,
+        { alias = shouty_snake_case(fooBarBaz) },
+        {
+            const alias: u32 = 1;
+        },
+        {
+            const FOO_BAR_BAZ: u32 = 1;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = shouty_snake_case("FooBar") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "FOO_BAR"
+            }
+        },
+        None,
+    ),
+    (
+        path,
+        { alias = shouty_snake_case(crate::consts::maxRetries) },
+        {
+            fn my_fn() -> u32 {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> u32 {
+                crate::consts::MAX_RETRIES
+            }
+        },
+        None,
+    ),
+    (
+        nested,
+        { alias = shouty_snake_case(concat(max, Retries)) },
+        {
+            const alias: u32 = 1;
+        },
+        {
+            const MAX_RETRIES: u32 = 1;
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = shouty_snake_case(42) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
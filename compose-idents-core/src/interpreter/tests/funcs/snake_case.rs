@@ -28,13 +28,15 @@ make_interpreter_test!(
         None,
     ),
     (
-        path_failure,
-        { alias = snake_case(foo::bar) },
+        path,
+        { alias = snake_case(crate::models::UserProfile<T>) },
         {
-            fn my_fn() -> &str { alias }
+            fn my_fn() -> alias { todo!() }
         },
-        {},
-        Some(ErrorType::SignatureError),
+        {
+            fn my_fn() -> crate::models::user_profile<T> { todo!() }
+        },
+        None,
     ),
     (
         expr_failure,
@@ -0,0 +1,83 @@
+//! Tests for starts_with() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    starts_with,
+    (
+        ident_true,
+        { alias = starts_with(async_read, "async_") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        ident_false,
+        { alias = starts_with(read_async, "async_") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = starts_with("async_read", "async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        empty_needle,
+        { alias = starts_with(read, "") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = true;
+        },
+        None,
+    ),
+    (
+        longer_needle,
+        { alias = starts_with(read, "read_async") },
+        {
+            const A: bool = alias;
+        },
+        {
+            const A: bool = false;
+        },
+        None,
+    ),
+    (
+        with_select,
+        { name = async_read, alias = select(starts_with(name, "async_"), yes, no) },
+        {
+            fn alias() {}
+        },
+        {
+            fn yes() {}
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = starts_with(42, "async_") },
+        {
+            const A: bool = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
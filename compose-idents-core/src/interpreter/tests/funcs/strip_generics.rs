@@ -0,0 +1,83 @@
+//! Tests for strip_generics() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    strip_generics,
+    (
+        nested,
+        { alias = strip_generics(HashMap<K, Vec<V>>) },
+        {
+            type T = alias;
+        },
+        {
+            type T = HashMap;
+        },
+        None,
+    ),
+    (
+        path,
+        { alias = strip_generics(std::collections::HashMap<K, V>) },
+        {
+            type T = alias;
+        },
+        {
+            type T = std::collections::HashMap;
+        },
+        None,
+    ),
+    (
+        turbofish,
+        { alias = strip_generics(Vec::<u8>::new) },
+        {
+            const F: fn() -> Vec<u8> = alias;
+        },
+        {
+            const F: fn() -> Vec<u8> = Vec::new;
+        },
+        None,
+    ),
+    (
+        lifetimes_only,
+        { alias = strip_generics(Cow<'a>) },
+        {
+            type T = alias;
+        },
+        {
+            type T = Cow;
+        },
+        None,
+    ),
+    (
+        reference,
+        { alias = strip_generics(&'a Option<u8>) },
+        {
+            type T = alias;
+        },
+        {
+            type T = &'a Option;
+        },
+        None,
+    ),
+    (
+        to_ident,
+        { wrapper = concat(My, to_ident(strip_generics(Result<T, E>))) },
+        {
+            struct wrapper;
+        },
+        {
+            struct MyResult;
+        },
+        None,
+    ),
+    (
+        str_failure,
+        { alias = strip_generics("Vec<u8>") },
+        {
+            type T = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,87 @@
+//! Tests for strip_prefix() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    strip_prefix,
+    (
+        ident,
+        { alias = strip_prefix(tcp_listener, "tcp_") },
+        {
+            struct alias;
+        },
+        {
+            struct listener;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = strip_prefix("tcp_stream", "tcp_") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "stream"
+            }
+        },
+        None,
+    ),
+    (
+        not_present,
+        { alias = strip_prefix(udp_socket, "tcp_") },
+        {
+            struct alias;
+        },
+        {
+            struct udp_socket;
+        },
+        None,
+    ),
+    (
+        empty_str,
+        { alias = strip_prefix("abc", "abc") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                ""
+            }
+        },
+        None,
+    ),
+    (
+        empty_ident_failure,
+        { alias = strip_prefix(tcp_, "tcp_") },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        invalid_ident_failure,
+        { alias = strip_prefix(tcp_1, "tcp_") },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        int_failure,
+        { alias = strip_prefix(42, "4") },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,84 @@
+//! Tests for strip_ref() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    strip_ref,
+    (
+        static_str,
+        { alias = strip_ref(&'static str) },
+        {
+            type T = alias;
+        },
+        {
+            type T = str;
+        },
+        None,
+    ),
+    (
+        mut_with_lifetime,
+        { alias = strip_ref(&'a mut Foo<T>) },
+        {
+            type T = alias;
+        },
+        {
+            type T = Foo<T>;
+        },
+        None,
+    ),
+    (
+        nested,
+        { alias = strip_ref(&&u8) },
+        {
+            type T = alias;
+        },
+        {
+            type T = u8;
+        },
+        None,
+    ),
+    (
+        dyn_trait,
+        { alias = strip_ref(&dyn Trait) },
+        {
+            type T = Box<alias>;
+        },
+        {
+            type T = Box<dyn Trait>;
+        },
+        None,
+    ),
+    (
+        plain,
+        { alias = strip_ref(Foo) },
+        {
+            type T = alias;
+        },
+        {
+            type T = Foo;
+        },
+        None,
+    ),
+    (
+        loop_over_types,
+        { for ty in [&'static str, &mut Foo] owned = strip_ref(ty) },
+        {
+            impl From<ty> for Wrapper<owned> {}
+        },
+        {
+            impl From<&'static str> for Wrapper<str> {}
+            impl From<&mut Foo> for Wrapper<Foo> {}
+        },
+        None,
+    ),
+    (
+        str_failure,
+        { alias = strip_ref("&u8") },
+        {
+            type T = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,89 @@
+//! Tests for strip_suffix() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    strip_suffix,
+    (
+        ident,
+        { alias = strip_suffix(listener_tcp, "_tcp") },
+        {
+            struct alias;
+        },
+        {
+            struct listener;
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = strip_suffix("stream_tcp", "_tcp") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "stream"
+            }
+        },
+        None,
+    ),
+    (
+        not_present,
+        { alias = strip_suffix(socket_udp, "_tcp") },
+        {
+            struct alias;
+        },
+        {
+            struct socket_udp;
+        },
+        None,
+    ),
+    (
+        empty_str,
+        { alias = strip_suffix("abc", "abc") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                ""
+            }
+        },
+        None,
+    ),
+    (
+        empty_ident_failure,
+        { alias = strip_suffix(_tcp, "_tcp") },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+    (
+        raw_ident,
+        { alias = strip_suffix(r#fn_tcp, "_tcp") },
+        {
+            struct alias;
+        },
+        {
+            struct r#fn;
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = strip_suffix(42, "4") },
+        {
+            struct alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,92 @@
+//! Tests for title_case() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    title_case,
+    (
+        ident,
+        { alias = title_case(my_fn_name) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "My Fn Name"
+            }
+        },
+        None,
+    ),
+    (
+        str,
+        { alias = title_case("FooBar") },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "Foo Bar"
+            }
+        },
+        None,
+    ),
+    (
+        digits,
+        { alias = title_case(foo_v2) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "Foo V2"
+            }
+        },
+        None,
+    ),
+    (
+        consecutive_underscores,
+        { alias = title_case(foo__bar) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {
+            fn my_fn() -> &str {
+                "Foo Bar"
+            }
+        },
+        None,
+    ),
+    (
+        placeholder,
+        { pretty_name = title_case(my_fn_name) },
+        {
+            #[doc = "Implements % pretty_name % support"]
+            fn my_fn() {}
+        },
+        {
+            #[doc = "Implements My Fn Name support"]
+            fn my_fn() {}
+        },
+        None,
+    ),
+    (
+        int_failure,
+        { alias = title_case(42) },
+        {
+            fn my_fn() -> &str {
+                alias
+            }
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -0,0 +1,44 @@
+//! Tests for to_attr() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    to_attr,
+    (
+        tokens,
+        { alias = to_attr(raw(#[derive(Debug)])) },
+        {
+            #[alias]
+            struct Foo;
+        },
+        {
+            #[derive(Debug)]
+            struct Foo;
+        },
+        None,
+    ),
+    (
+        attr,
+        { alias = to_attr(#[inline]) },
+        {
+            #[alias]
+            fn foo() {}
+        },
+        {
+            #[inline]
+            fn foo() {}
+        },
+        None,
+    ),
+    (
+        invalid,
+        { alias = to_attr(foo) },
+        {
+            #[alias]
+            fn foo() {}
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+);
@@ -0,0 +1,81 @@
+//! Tests for to_byte_str() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    to_byte_str,
+    (
+        str,
+        { alias = to_byte_str("foo") },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {
+            static NAME: &[u8] = b"foo";
+        },
+        None,
+    ),
+    (
+        str_nul,
+        { alias = to_byte_str("foo", true) },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {
+            static NAME: &[u8] = b"foo\0";
+        },
+        None,
+    ),
+    (
+        str_no_nul,
+        { alias = to_byte_str("foo", false) },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {
+            static NAME: &[u8] = b"foo";
+        },
+        None,
+    ),
+    (
+        str_with_predicate,
+        { alias = to_byte_str("foo", ends_with(foo_c, "_c")) },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {
+            static NAME: &[u8] = b"foo\0";
+        },
+        None,
+    ),
+    (
+        byte_str,
+        { alias = to_byte_str(b"foo") },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {
+            static NAME: &[u8] = b"foo";
+        },
+        None,
+    ),
+    (
+        ident_failure,
+        { alias = to_byte_str(foo) },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {},
+        Some(ErrorType::TypeError),
+    ),
+    (
+        nul_failure,
+        { alias = to_byte_str("foo", yes) },
+        {
+            static NAME: &[u8] = alias;
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
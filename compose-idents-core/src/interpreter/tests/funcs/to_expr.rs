@@ -49,6 +49,7 @@ make_interpreter_test!(
         },
         None,
     ),
+    // A string literal is parsed by its content, like with to_type() and to_path().
     (
         str,
         { alias = to_expr("foo") },
@@ -56,7 +57,18 @@ make_interpreter_test!(
             fn f() { let _ = alias; }
         },
         {
-            fn f() { let _ = "foo"; }
+            fn f() { let _ = foo; }
+        },
+        None,
+    ),
+    (
+        str_expr,
+        { alias = to_expr("1 + 2") },
+        {
+            fn f() { let _ = alias; }
+        },
+        {
+            fn f() { let _ = 1 + 2; }
         },
         None,
     ),
@@ -46,14 +46,51 @@ make_interpreter_test!(
         Some(ErrorType::TypeError),
     ),
     (
-        int_failure,
+        int,
         { alias = to_ident(42) },
         {
-            fn my_fn() -> &str { alias }
+            fn alias() {}
+        },
+        {
+            fn _42() {}
+        },
+        None,
+    ),
+    (
+        negative_int_failure,
+        { alias = to_ident(-1) },
+        {
+            fn alias() {}
         },
         {},
         Some(ErrorType::TypeError),
     ),
+    (
+        str_leading_digit,
+        { alias = to_ident("123abc") },
+        {
+            fn alias() {}
+        },
+        {
+            fn _123abc() {}
+        },
+        None,
+    ),
+    (
+        int_loop,
+        { for i in [0, 1]
+
+          field = concat(field, to_ident(i))
+        },
+        {
+            struct Foo { field: u32 }
+        },
+        {
+            struct Foo { field_0: u32 }
+            struct Foo { field_1: u32 }
+        },
+        None,
+    ),
     (
         str_failure,
         { alias = to_ident("&foo") },
@@ -83,4 +120,35 @@ make_interpreter_test!(
         },
         None,
     ),
+    (
+        keyword,
+        { alias = to_ident(match) },
+        {
+            fn alias() {}
+        },
+        {
+            fn r#match() {}
+        },
+        None,
+    ),
+    (
+        str_keyword,
+        { alias = to_ident("type") },
+        {
+            fn alias() {}
+        },
+        {
+            fn r#type() {}
+        },
+        None,
+    ),
+    (
+        non_raw_keyword_failure,
+        { alias = to_ident(crate) },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
 );
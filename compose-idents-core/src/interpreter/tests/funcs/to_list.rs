@@ -0,0 +1,101 @@
+//! Tests for to_list() function and `as list` casts.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    to_list,
+    (
+        closed_range,
+        { alias = to_list(1..=3) },
+        { const X: [u8; 3] = alias; },
+        { const X: [u8; 3] = [1, 2, 3]; },
+        None,
+    ),
+    (
+        half_open_range,
+        { alias = to_list(0..3) },
+        { const X: [u8; 3] = alias; },
+        { const X: [u8; 3] = [0, 1, 2]; },
+        None,
+    ),
+    (
+        negative_bounds,
+        { alias = to_list(-2..=0) },
+        { const X: [i8; 3] = alias; },
+        { const X: [i8; 3] = [-2, -1, 0]; },
+        None,
+    ),
+    (
+        empty_range,
+        { alias = len(to_list(3..3)) },
+        { const N: usize = alias; },
+        { const N: usize = 0; },
+        None,
+    ),
+    (
+        cast,
+        { widths = 1..=8 as list },
+        { const X: [u8; 8] = widths; },
+        { const X: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8]; },
+        None,
+    ),
+    (
+        len,
+        { widths = 1..=8 as list, n = len(widths) },
+        { const N: usize = n; },
+        { const N: usize = 8; },
+        None,
+    ),
+    (
+        index,
+        { widths = 1..=8 as list, w = index(widths, 2) },
+        { const W: u8 = w; },
+        { const W: u8 = 3; },
+        None,
+    ),
+    // Without the cast a range stays an expression.
+    (
+        no_cast,
+        { range = 1..=8 },
+        { const R: core::ops::RangeInclusive<u8> = range; },
+        { const R: core::ops::RangeInclusive<u8> = 1..=8; },
+        None,
+    ),
+    // The characters of an uncast range aren't counted.
+    (
+        len_without_cast,
+        { widths = 1..=3, n = len(widths) },
+        { const N: usize = n; },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        index_without_cast,
+        { widths = 1..=3, w = index(widths, 0) },
+        { const W: u8 = w; },
+        { },
+        Some(ErrorType::SignatureError),
+    ),
+    (
+        open_range,
+        { alias = to_list(1..) },
+        { const X: u8 = alias; },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    (
+        non_literal_bounds,
+        { alias = to_list(a..b) },
+        { const X: u8 = alias; },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+    (
+        too_long_range,
+        { alias = to_list(0..1000000) },
+        { const X: u8 = alias; },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+);
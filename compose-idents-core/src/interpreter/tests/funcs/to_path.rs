@@ -45,9 +45,20 @@ make_interpreter_test!(
         {},
         Some(ErrorType::TypeError),
     ),
+    (
+        str,
+        { alias = to_path("std::vec::Vec") },
+        {
+            fn f() -> alias<u32> { alias::new() }
+        },
+        {
+            fn f() -> std::vec::Vec<u32> { std::vec::Vec::new() }
+        },
+        None,
+    ),
     (
         str_failure,
-        { alias = to_path("foo") },
+        { alias = to_path("foo bar") },
         {
             fn my_fn() -> &str { alias }
         },
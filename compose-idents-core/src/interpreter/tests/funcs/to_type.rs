@@ -27,6 +27,28 @@ make_interpreter_test!(
         },
         None,
     ),
+    (
+        generic_comma,
+        { alias = to_type(Result<Option<T>, E>) },
+        {
+            fn f(_: alias) {}
+        },
+        {
+            fn f(_: Result<Option<T>, E>) {}
+        },
+        None,
+    ),
+    (
+        generic_shr,
+        { alias = to_type(Vec<Vec<u8>>) },
+        {
+            fn f(_: alias) {}
+        },
+        {
+            fn f(_: Vec<Vec<u8>>) {}
+        },
+        None,
+    ),
     (
         expr_failure,
         { alias = to_type(|| 42) },
@@ -45,9 +67,20 @@ make_interpreter_test!(
         {},
         Some(ErrorType::TypeError),
     ),
+    (
+        str,
+        { alias = to_type("Result<u32, String>") },
+        {
+            fn f(x: alias) -> alias { x }
+        },
+        {
+            fn f(x: Result<u32, String>) -> Result<u32, String> { x }
+        },
+        None,
+    ),
     (
         str_failure,
-        { alias = to_type("foo") },
+        { alias = to_type("Result<u32,") },
         {
             fn my_fn() -> &str { alias }
         },
@@ -0,0 +1,50 @@
+//! Tests for unraw() function.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    unraw,
+    (
+        raw,
+        { alias = concat(unraw(r#type), _id) },
+        {
+            fn alias() {}
+        },
+        {
+            fn type_id() {}
+        },
+        None,
+    ),
+    (
+        plain,
+        { alias = unraw(foo) },
+        {
+            fn alias() {}
+        },
+        {
+            fn foo() {}
+        },
+        None,
+    ),
+    (
+        formatting,
+        { alias = unraw(r#fn) },
+        {
+            static S: &str = "% alias %";
+        },
+        {
+            static S: &str = "fn";
+        },
+        None,
+    ),
+    (
+        str_failure,
+        { alias = unraw("foo") },
+        {
+            fn alias() {}
+        },
+        {},
+        Some(ErrorType::SignatureError),
+    ),
+);
@@ -28,13 +28,25 @@ make_interpreter_test!(
         None,
     ),
     (
-        path_failure,
-        { alias = upper(foo::bar) },
+        path,
+        { alias = upper(foo::bar::Baz<T>) },
         {
-            fn my_fn() -> &str { alias }
+            fn my_fn() -> alias { todo!() }
+        },
+        {
+            fn my_fn() -> foo::bar::BAZ<T> { todo!() }
+        },
+        None,
+    ),
+    // Case conversion of a raw identifier can't produce an identifier.
+    (
+        raw_ident_failure,
+        { alias = upper(r#type) },
+        {
+            fn alias() -> u32 { 1 }
         },
         {},
-        Some(ErrorType::SignatureError),
+        Some(ErrorType::EvalError),
     ),
     (
         expr_failure,
@@ -0,0 +1,982 @@
+//! Tests for loop functionality.
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    loops,
+
+    // Simple loop.
+    (
+        simple_loop,
+        { for suffix in [foo, bar]
+
+          my_static = suffix,
+          my_fn = concat(my, _, suffix)
+        },
+        {
+            static my_static: u32 = 42;
+
+            fn my_fn() -> u32 {
+                42
+            }
+        },
+        {
+            static foo: u32 = 42;
+            fn my_foo() -> u32 {
+                42
+            }
+
+            static bar: u32 = 42;
+            fn my_bar() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+
+    // Nested loops - cartesian product semantics.
+    (
+        nested_cartesian,
+        { for a in [x, y]
+          for b in [1, 2]
+
+          fn_name = concat(a, _, b)
+        },
+        {
+            fn fn_name() -> u32 { 0 }
+        },
+        {
+            fn x_1() -> u32 { 0 }
+            fn x_2() -> u32 { 0 }
+            fn y_1() -> u32 { 0 }
+            fn y_2() -> u32 { 0 }
+        },
+        None,
+    ),
+
+    // Triple-nested loops (2 x 2 x 2).
+    (
+        triple_nested_cartesian,
+        { for a in [x, y]
+          for b in [1, 2]
+          for c in [A, B]
+          fn_name = concat(a, _, b, _, c)
+        },
+        {
+            fn fn_name() -> u32 { 0 }
+        },
+        {
+            fn x_1_A() -> u32 { 0 }
+            fn x_1_B() -> u32 { 0 }
+            fn x_2_A() -> u32 { 0 }
+            fn x_2_B() -> u32 { 0 }
+            fn y_1_A() -> u32 { 0 }
+            fn y_1_B() -> u32 { 0 }
+            fn y_2_A() -> u32 { 0 }
+            fn y_2_B() -> u32 { 0 }
+        },
+        None,
+    ),
+
+    // Tuple destructuring across iterations.
+    (
+        tuple_destructuring,
+        { for (name, type_, value) in [(foo, &'static str, "foo"), (bar, Option<u32>, None)]
+          fn_name = concat(make_, name)
+        },
+        {
+            fn fn_name() -> type_ { value }
+        },
+        {
+            fn make_foo() -> &'static str { "foo" }
+            fn make_bar() -> Option<u32> { None }
+        },
+        None,
+    ),
+
+    // Nested tuple destructuring.
+    (
+        nested_tuple_destructuring,
+        { for (name, (return_type, param_type)) in [
+              (fn1, (Result<&'static str, String>, Vec::<i32>)),
+              (fn2, (Option<(i32, i32)>, std::collections::HashMap<u8, u16>))
+          ]
+        },
+        {
+            fn name(arg: param_type) -> return_type { panic!() }
+        },
+        {
+            fn fn1(arg: Vec::<i32>) -> Result<&'static str, String> { panic!() }
+            fn fn2(arg: std::collections::HashMap<u8, u16>) -> Option<(i32, i32)> { panic!() }
+        },
+        None,
+    ),
+
+    // Alias reuse in user spec (expected to be re-evaluated per-iteration).
+    (
+        alias_reuse_in_user_spec,
+        {
+          for suffix in [FOO, BAR]
+
+          lower_suffix = lower(suffix),
+          fn_name = concat(make_, lower_suffix)
+        },
+        {
+            fn fn_name() -> u32 { 0 }
+        },
+        {
+            fn make_foo() -> u32 { 0 }
+            fn make_bar() -> u32 { 0 }
+        },
+        None,
+    ),
+
+    // Empty values list.
+    (
+        empty_source_list,
+        {
+          for suffix in []
+
+          fn_name = concat(my_, suffix)
+        },
+        {
+            fn fn_name() -> u32 { 0 }
+        },
+        { },
+        None,
+    ),
+
+    // String formatting.
+    (
+        string_formatting,
+        { for name in [foo, bar]
+          fn_name = concat(test_, name)
+        },
+        {
+            #[doc = "Docstring for % fn_name %"]
+            fn fn_name() -> u32 { 0 }
+        },
+        {
+            #[doc = "Docstring for test_foo"]
+            fn test_foo() -> u32 { 0 }
+
+            #[doc = "Docstring for test_bar"]
+            fn test_bar() -> u32 { 0 }
+        },
+        None,
+    ),
+
+    // Only loops with no user alias spec – block uses loop alias directly
+    (
+        only_loops_no_user_spec,
+        { for name in [alpha, beta] },
+        {
+            fn name() -> u32 { 0 }
+        },
+        {
+            fn alpha() -> u32 { 0 }
+            fn beta() -> u32 { 0 }
+        },
+        None,
+    ),
+
+    // The number of combinations within the limit.
+    (
+        max_combinations,
+        { for a in [x, y]
+          for b in [1, 2]
+
+          options(max_combinations = 4),
+          fn_name = concat(a, _, b)
+        },
+        {
+            fn fn_name() -> u32 { 0 }
+        },
+        {
+            fn x_1() -> u32 { 0 }
+            fn x_2() -> u32 { 0 }
+            fn y_1() -> u32 { 0 }
+            fn y_2() -> u32 { 0 }
+        },
+        None,
+    ),
+
+    // The number of combinations exceeds the limit.
+    (
+        max_combinations_exceeded,
+        { for a in [x, y]
+          for b in [1, 2]
+
+          options(max_combinations = 3),
+          fn_name = concat(a, _, b)
+        },
+        {
+            fn fn_name() -> u32 { 0 }
+        },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+
+    // The number of combinations exceeds the default limit.
+    (
+        default_max_combinations_exceeded,
+        { for a in nums
+          for b in nums
+
+          nums = 0..101 as list,
+          name = concat(item_, a, _, b)
+        },
+        {
+            const name: u32 = 0;
+        },
+        { },
+        Some(ErrorType::EvalError),
+    ),
+
+    // The default limit is raised with the option.
+    (
+        default_max_combinations_raised,
+        { for a in nums if eq(a, 100)
+          for b in nums if eq(b, 0)
+
+          options(max_combinations = 20000),
+          nums = 0..101 as list,
+          name = concat(item_, a, _, b)
+        },
+        {
+            const name: u32 = 0;
+        },
+        {
+            const item_100_0: u32 = 0;
+        },
+        None,
+    ),
+
+    // Substitution table with multiple columns.
+    (
+        substitution_table,
+        { substitutions [
+              int_type  max_value;
+              [u8]      [255];
+              [u16]     [65_535];
+          ]
+        },
+        {
+            impl IsMax for int_type {
+                fn is_max(&self) -> bool { *self == max_value }
+            }
+        },
+        {
+            impl IsMax for u8 {
+                fn is_max(&self) -> bool { *self == 255 }
+            }
+            impl IsMax for u16 {
+                fn is_max(&self) -> bool { *self == 65_535 }
+            }
+        },
+        None,
+    ),
+
+    // Substitution table combined with a loop and aliases.
+    (
+        substitution_table_with_loop,
+        { for prefix in [get, set]
+          substitutions [ name; foo; bar ]
+
+          fn_name = concat(prefix, _, name)
+        },
+        {
+            fn fn_name() {}
+        },
+        {
+            fn get_foo() {}
+            fn get_bar() {}
+            fn set_foo() {}
+            fn set_bar() {}
+        },
+        None,
+    ),
+
+    // Braces and parentheses are interchangeable with square brackets.
+    (
+        alternative_list_delimiters,
+        { for prefix in {get, set}
+          for (name, ty) in ((foo, u8), (bar, u16))
+
+          fn_name = concat(prefix, _, name)
+        },
+        {
+            fn fn_name() -> ty { 1 }
+        },
+        {
+            fn get_foo() -> u8 { 1 }
+            fn get_bar() -> u16 { 1 }
+            fn set_foo() -> u8 { 1 }
+            fn set_bar() -> u16 { 1 }
+        },
+        None,
+    ),
+
+    // Aliases bound to lists as loop sources.
+    (
+        range_alias_source,
+        { for w in widths
+
+          widths = 1..=3 as list,
+          fn_name = concat(width_, w)
+        },
+        {
+            const fn_name: u8 = w;
+        },
+        {
+            const width_1: u8 = 1;
+            const width_2: u8 = 2;
+            const width_3: u8 = 3;
+        },
+        None,
+    ),
+    (
+        list_alias_source,
+        { for name in names
+
+          names = [foo, bar,],
+          fn_name = concat(get_, name)
+        },
+        {
+            fn fn_name() {}
+        },
+        {
+            fn get_foo() {}
+            fn get_bar() {}
+        },
+        None,
+    ),
+    (
+        range_alias_source_with_len,
+        { for w in widths
+
+          widths = 0..2 as list,
+          n = len(widths),
+          name = concat(item_, w)
+        },
+        {
+            const name: usize = n;
+        },
+        {
+            const item_0: usize = 2;
+            const item_1: usize = 2;
+        },
+        None,
+    ),
+    (
+        unknown_alias_source,
+        { for w in widths
+
+          name = concat(item_, w)
+        },
+        {
+            const name: u8 = w;
+        },
+        { },
+        Some(ErrorType::UndefinedNameError),
+    ),
+    (
+        non_list_alias_source,
+        { for w in widths
+
+          widths = 1..=8,
+          name = concat(item_, w)
+        },
+        {
+            const name: u8 = w;
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    // Loop index aliases.
+    (
+        index_alias,
+        { for x in [a, b, c]
+
+          name = concat(x, _, __index__)
+        },
+        {
+            const name: (usize, usize) = (__index__, __index1__);
+        },
+        {
+            const a_0: (usize, usize) = (0, 1);
+            const b_1: (usize, usize) = (1, 2);
+            const c_2: (usize, usize) = (2, 3);
+        },
+        None,
+    ),
+    (
+        index_alias_nested,
+        { for a in [x, y]
+          for b in [1, 2]
+
+          fn_name = concat(a, _, b, _, __index1__)
+        },
+        {
+            fn fn_name() -> usize { __index__ }
+        },
+        {
+            fn x_1_1() -> usize { 0 }
+            fn x_2_2() -> usize { 1 }
+            fn y_1_3() -> usize { 2 }
+            fn y_2_4() -> usize { 3 }
+        },
+        None,
+    ),
+    (
+        index_alias_placeholder,
+        { for x in [A, B] },
+        {
+            #[doc = "Variant %__index__%."]
+            struct x;
+        },
+        {
+            #[doc = "Variant 0."]
+            struct A;
+            #[doc = "Variant 1."]
+            struct B;
+        },
+        None,
+    ),
+    (
+        index_alias_shadowing,
+        { for x in [a, b]
+
+          __index__ = concat(x, _idx)
+        },
+        {
+            const x: (&str, usize) = (stringify!(__index__), __index1__);
+        },
+        {
+            const a: (&str, usize) = (stringify!(a_idx), 1);
+            const b: (&str, usize) = (stringify!(b_idx), 2);
+        },
+        None,
+    ),
+    (
+        index_alias_loop_shadowing,
+        { for __index1__ in [a, b] },
+        {
+            fn __index1__() -> usize { __index__ }
+        },
+        {
+            fn a() -> usize { 0 }
+            fn b() -> usize { 1 }
+        },
+        None,
+    ),
+    // Zipped lists.
+    (
+        zip,
+        { for (name, n) in zip([x, y, z], [1, 2, 3]) },
+        {
+            const name: u8 = n;
+        },
+        {
+            const x: u8 = 1;
+            const y: u8 = 2;
+            const z: u8 = 3;
+        },
+        None,
+    ),
+    (
+        zip_tuples,
+        { for ((name, ty), n) in zip([(x, u8), (y, u16)], [1, 2])
+
+          getter = concat(get_, name)
+        },
+        {
+            fn getter() -> ty { n }
+        },
+        {
+            fn get_x() -> u8 { 1 }
+            fn get_y() -> u16 { 2 }
+        },
+        None,
+    ),
+    (
+        zip_three_lists_nested,
+        { for (a, b, c) in zip([x, y], [1, 2], [u8, u16])
+          for d in [p, q]
+
+          name = concat(a, _, d)
+        },
+        {
+            const name: c = b;
+        },
+        {
+            const x_p: u8 = 1;
+            const x_q: u8 = 1;
+            const y_p: u16 = 2;
+            const y_q: u16 = 2;
+        },
+        None,
+    ),
+    (
+        zip_alias,
+        { for (name, w) in zip([a, b], widths)
+
+          widths = [8, 16]
+        },
+        {
+            const name: u8 = w;
+        },
+        {
+            const a: u8 = 8;
+            const b: u8 = 16;
+        },
+        None,
+    ),
+    (
+        zip_length_mismatch,
+        { for (name, n) in zip([x, y, z], [1, 2]) },
+        {
+            const name: u8 = n;
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        zip_shape_mismatch,
+        { for (name, (n, m)) in zip([x, y], [1, 2]) },
+        {
+            const name: u8 = n;
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    // Loop filters.
+    (
+        filter,
+        { for a in [u8, u16, u32]
+          for b in [u8, u16, u32] if ne(a, b)
+
+          name = concat(a, _to_, b)
+        },
+        {
+            fn name() {}
+        },
+        {
+            fn u8_to_u16() {}
+            fn u8_to_u32() {}
+            fn u16_to_u8() {}
+            fn u16_to_u32() {}
+            fn u32_to_u8() {}
+            fn u32_to_u16() {}
+        },
+        None,
+    ),
+    (
+        filter_per_loop,
+        { for a in [foo, bar, baz] if ne(a, bar)
+          for b in [read, write] if starts_with(b, "w")
+
+          name = concat(a, _, b)
+        },
+        {
+            fn name() {}
+        },
+        {
+            fn foo_write() {}
+            fn baz_write() {}
+        },
+        None,
+    ),
+    (
+        filter_index,
+        { for x in [a, b, c] if ne(x, b)
+
+          name = concat(x, _, __index__)
+        },
+        {
+            fn name() {}
+        },
+        {
+            fn a_0() {}
+            fn c_1() {}
+        },
+        None,
+    ),
+    (
+        filter_tuple,
+        { for (name, enabled) in [(foo, true), (bar, false), (baz, true)] if enabled },
+        {
+            fn name() {}
+        },
+        {
+            fn foo() {}
+            fn baz() {}
+        },
+        None,
+    ),
+    (
+        filter_always_false,
+        { for x in [a, b] if false },
+        {
+            fn x() {}
+        },
+        { },
+        None,
+    ),
+    (
+        filter_not_bool,
+        { for x in [a, b] if concat(x, _fn) },
+        {
+            fn x() {}
+        },
+        { },
+        Some(ErrorType::SignatureError),
+    ),
+    // Aliases aren't visible to the filters - `a` is a plain identifier here.
+    (
+        filter_user_alias,
+        { for x in [a, b] if ne(x, a)
+
+          a = b
+        },
+        {
+            fn x() {}
+        },
+        {
+            fn b() {}
+        },
+        None,
+    ),
+    // Placeholders in loop aliases.
+    (
+        placeholder,
+        { for (name, _, default) in [(foo, u8, 1), (bar, u16, 2)] },
+        {
+            const name: u32 = default;
+        },
+        {
+            const foo: u32 = 1;
+            const bar: u32 = 2;
+        },
+        None,
+    ),
+    (
+        placeholder_nested_tuple,
+        { for (name, _) in [(foo, (u8, 1)), (bar, (u16, 2))] },
+        {
+            fn name() {}
+        },
+        {
+            fn foo() {}
+            fn bar() {}
+        },
+        None,
+    ),
+    (
+        placeholder_in_nested_tuple,
+        { for (name, (_, n)) in [(foo, (u8, 1)), (bar, (u16, 2))] },
+        {
+            const name: u32 = n;
+        },
+        {
+            const foo: u32 = 1;
+            const bar: u32 = 2;
+        },
+        None,
+    ),
+    (
+        placeholder_simple,
+        { for _ in [a, (b, c)] },
+        {
+            fn f() {}
+        },
+        {
+            fn f() {}
+            fn f() {}
+        },
+        None,
+    ),
+    (
+        placeholder_not_substituted,
+        { for (_, x) in [(a, b)] },
+        {
+            const _: () = x;
+        },
+        {
+            const _: () = b;
+        },
+        None,
+    ),
+    (
+        placeholder_mismatched_length,
+        { for (name, _) in [(foo, u8, 1)] },
+        {
+            fn name() {}
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        placeholder_mismatched_shape,
+        { for (name, (_, n)) in [(foo, u8)] },
+        {
+            fn name() {}
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    // Split strings.
+    (
+        split,
+        { for part in split("red,green,blue", ",")
+
+          name = upper(to_ident(part))
+        },
+        {
+            const name: &str = part;
+        },
+        {
+            const RED: &str = "red";
+            const GREEN: &str = "green";
+            const BLUE: &str = "blue";
+        },
+        None,
+    ),
+    (
+        split_empty_fragments,
+        { for part in split(",a,,b", ",") },
+        {
+            const _: &str = part;
+        },
+        {
+            const _: &str = "";
+            const _: &str = "a";
+            const _: &str = "";
+            const _: &str = "b";
+        },
+        None,
+    ),
+    (
+        split_chars,
+        { for c in split("xyz", "")
+
+          name = concat(get_, to_ident(c))
+        },
+        {
+            fn name() {}
+        },
+        {
+            fn get_x() {}
+            fn get_y() {}
+            fn get_z() {}
+        },
+        None,
+    ),
+    (
+        split_multichar_separator,
+        { for part in split("a::b::c", "::") },
+        {
+            const _: &str = part;
+        },
+        {
+            const _: &str = "a";
+            const _: &str = "b";
+            const _: &str = "c";
+        },
+        None,
+    ),
+    (
+        split_zip,
+        { for (name, ty) in zip(split("small large", " "), [u8, u64])
+
+          alias = to_ident(name)
+        },
+        {
+            type alias = ty;
+        },
+        {
+            type small = u8;
+            type large = u64;
+        },
+        None,
+    ),
+    (
+        alias_source_reused,
+        { for a in nums for b in nums if ne(a, b)
+
+          nums = [u8, u16],
+          name = concat(a, _to_, b)
+        },
+        {
+            type name = (a, b);
+        },
+        {
+            type u8_to_u16 = (u8, u16);
+            type u16_to_u8 = (u16, u8);
+        },
+        None,
+    ),
+    (
+        enumerate,
+        { for (i, name) in enumerate([foo, bar, baz]) },
+        {
+            const name: u32 = i;
+        },
+        {
+            const foo: u32 = 0;
+            const bar: u32 = 1;
+            const baz: u32 = 2;
+        },
+        None,
+    ),
+    (
+        enumerate_tuples,
+        { for (i, (name, ty)) in enumerate([(small, u8), (large, u64)])
+
+          alias = concat(name, _, i)
+        },
+        {
+            type alias = ty;
+        },
+        {
+            type small_0 = u8;
+            type large_1 = u64;
+        },
+        None,
+    ),
+    (
+        enumerate_alias_zip,
+        { for (i, (name, ty)) in enumerate(zip(names, [u8, u16]))
+
+          names = [foo, bar]
+        },
+        {
+            const name: ty = i;
+        },
+        {
+            const foo: u8 = 0;
+            const bar: u16 = 1;
+        },
+        None,
+    ),
+    (
+        enumerate_non_tuple_alias,
+        { for item in enumerate([foo, bar]) },
+        {
+            const item: u32 = 0;
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        chained_lists,
+        { for t in [u8, u16] + [f32, f64] + widths
+
+          widths = [i32, i64]
+        },
+        {
+            impl Foo for t {}
+        },
+        {
+            impl Foo for u8 {}
+            impl Foo for u16 {}
+            impl Foo for f32 {}
+            impl Foo for f64 {}
+            impl Foo for i32 {}
+            impl Foo for i64 {}
+        },
+        None,
+    ),
+    (
+        chained_tuples,
+        { for (name, ty) in [(small, u8)] + zip([large], [u64]) if ne(ty, u8) },
+        {
+            type name = ty;
+        },
+        {
+            type large = u64;
+        },
+        None,
+    ),
+    (
+        chained_shape_mismatch,
+        { for (name, ty) in [(small, u8)] + [large] },
+        {
+            type name = ty;
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+    (
+        unique,
+        { for t in unique([u8, u16, u8, u32, u16]) },
+        {
+            impl Foo for t {}
+        },
+        {
+            #[deprecated(
+                note = "compose_idents!: Duplicate loop values were removed by unique(...): u8, u16",
+            )]
+            impl Foo for u8 {}
+            #[deprecated(
+                note = "compose_idents!: Duplicate loop values were removed by unique(...): u8, u16",
+            )]
+            impl Foo for u16 {}
+            #[deprecated(
+                note = "compose_idents!: Duplicate loop values were removed by unique(...): u8, u16",
+            )]
+            impl Foo for u32 {}
+        },
+        None,
+    ),
+    (
+        unique_token_spacing,
+        { for ty in unique([Vec<u8>, Vec <u8>, Vec< Vec<u8> >, Vec<Vec<u8>>])
+
+          name = concat(alias_, __index__)
+        },
+        {
+            type name = ty;
+        },
+        {
+            #[deprecated(
+                note = "compose_idents!: Duplicate loop values were removed by unique(...): Vec < u8 >, Vec < Vec < u8 > >",
+            )]
+            type alias_0 = Vec<u8>;
+            #[deprecated(
+                note = "compose_idents!: Duplicate loop values were removed by unique(...): Vec < u8 >, Vec < Vec < u8 > >",
+            )]
+            type alias_1 = Vec<Vec<u8>>;
+        },
+        None,
+    ),
+    (
+        unique_without_duplicates,
+        { for (i, t) in enumerate(unique([u8, u16])) },
+        {
+            const t: usize = i;
+        },
+        {
+            const u8: usize = 0;
+            const u16: usize = 1;
+        },
+        None,
+    ),
+    (
+        unique_deny_deprecated,
+        { for t in unique([u8, u8])
+
+          options(deny_deprecated)
+        },
+        {
+            impl Foo for t {}
+        },
+        {
+            #[deprecated(
+                note = "compose_idents!: Duplicate loop values were removed by unique(...): u8",
+            )]
+            impl Foo for u8 {}
+        },
+        None,
+    ),
+);
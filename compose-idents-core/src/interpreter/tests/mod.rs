@@ -0,0 +1,23 @@
+mod aliases;
+mod apply_attrs;
+mod attrs;
+mod block_order;
+mod blocks;
+mod calls;
+mod casts;
+mod cfg;
+mod determinism;
+mod funcs;
+mod loops;
+mod non_terminals;
+#[cfg(feature = "pretty")]
+mod pretty;
+mod scoped;
+mod spec_order;
+mod string_formatting;
+#[cfg(feature = "trace")]
+mod trace;
+mod types;
+mod unicode;
+mod use_trees;
+mod when;
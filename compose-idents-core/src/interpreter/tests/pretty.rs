@@ -0,0 +1,115 @@
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::error::ErrorType;
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use proc_macro2::TokenStream;
+use rstest::rstest;
+use std::rc::Rc;
+
+fn make_interpreter() -> Interpreter {
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    Interpreter::new(environment, DeprecationService::scoped())
+}
+
+#[rstest]
+#[case::loop_(
+    r#"
+    for (x, ty) in [(a, u8), (b, u16)]
+    name = concat(x, _, ty),
+    {
+        struct name(ty);
+        impl name { fn get(&self) -> ty { self.0 } }
+    }
+    "#,
+    r#"// --- combination 1 (x=a, ty=u8) ---
+struct a_u8(u8);
+impl a_u8 {
+    fn get(&self) -> u8 {
+        self.0
+    }
+}
+// --- combination 2 (x=b, ty=u16) ---
+struct b_u16(u16);
+impl b_u16 {
+    fn get(&self) -> u16 {
+        self.0
+    }
+}
+"#
+)]
+#[case::no_loop(
+    r#"
+    name = upper(foo),
+    {
+        #[doc = "Docs of % name %."]
+        const name: u32 = 1;
+    }
+    "#,
+    r#"///Docs of FOO.
+const FOO: u32 = 1;
+"#
+)]
+fn snapshot(#[case] input: &str, #[case] expected: &str) {
+    let interpreter = make_interpreter();
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    let actual = interpreter.execute_pretty(raw_ast).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn non_items() {
+    let input = "x = foo, { let x = 1; }";
+    let interpreter = make_interpreter();
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    let err = interpreter.execute_pretty(raw_ast).unwrap_err();
+    assert_eq!(err.type_(), ErrorType::EvalError);
+}
+
+/// Formatted output consists of the same tokens as the regular one.
+#[test]
+fn same_tokens() {
+    let input = r#"
+        for x in [a, b]
+        {
+            fn x() -> u32 { 1 + 2 }
+        }
+    "#;
+    let raw_ast = || syn::parse_str::<RawAST>(input).unwrap();
+    let formatted = make_interpreter().execute_pretty(raw_ast()).unwrap();
+    let expected = make_interpreter().execute(raw_ast()).unwrap();
+    assert_eq!(
+        formatted.parse::<TokenStream>().unwrap().to_string(),
+        expected.to_string()
+    );
+}
+
+#[test]
+fn option_non_items() {
+    let input = "options(pretty), x = foo, { let x = 1; }";
+    let interpreter = make_interpreter();
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    let err = interpreter.execute(raw_ast).unwrap_err();
+    assert_eq!(err.type_(), ErrorType::EvalError);
+}
+
+/// With `options(pretty)` the output is produced from the formatted code - the tokens stay the
+/// same.
+#[test]
+fn option_same_tokens() {
+    let input = r#"
+        for x in [a, b]
+        options(pretty = PRETTY),
+        {
+            fn x() -> u32 { 1 + 2 }
+        }
+    "#;
+    let execute = |pretty: bool| {
+        let interpreter = make_interpreter();
+        let raw_ast = syn::parse_str::<RawAST>(&input.replace("PRETTY", &pretty.to_string()));
+        interpreter.execute(raw_ast.unwrap()).unwrap().to_string()
+    };
+    assert_eq!(execute(true), execute(false));
+}
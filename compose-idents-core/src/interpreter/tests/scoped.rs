@@ -0,0 +1,163 @@
+//! Tests for `options(scoped)` wrapping the generated items into an anonymous const scope.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    scoped,
+    (
+        items,
+        { options(scoped), name = concat(foo, _helper) },
+        {
+            struct name;
+            fn name() {}
+        },
+        {
+            const _: () = {
+                struct foo_helper;
+                fn foo_helper() {}
+            };
+        },
+        None,
+    ),
+    (
+        export,
+        { options(scoped), name = concat(foo, _helper), getter = concat(get_, foo) },
+        {
+            struct name;
+            #[compose(export)]
+            #[inline]
+            pub fn getter() -> u32 { 1 }
+        },
+        {
+            const _: () = {
+                struct foo_helper;
+            };
+            #[inline]
+            pub fn get_foo() -> u32 { 1 }
+        },
+        None,
+    ),
+    (
+        impl_outside,
+        { options(scoped), name = Foo },
+        {
+            trait Helper {}
+            impl Clone for name {
+                fn clone(&self) -> Self { Self }
+            }
+        },
+        {
+            const _: () = {
+                trait Helper {}
+            };
+            impl Clone for Foo {
+                fn clone(&self) -> Self { Self }
+            }
+        },
+        None,
+    ),
+    // Impls of the scoped types and traits stay next to them.
+    (
+        impl_of_scoped_type,
+        { options(scoped), name = Foo },
+        {
+            struct name;
+            impl name { fn f() {} }
+            impl<T> Clone for name<T> {}
+        },
+        {
+            const _: () = {
+                struct Foo;
+                impl Foo { fn f() {} }
+                impl<T> Clone for Foo<T> {}
+            };
+        },
+        None,
+    ),
+    (
+        impl_of_scoped_trait,
+        { options(scoped), name = Foo },
+        {
+            trait Helper {}
+            impl Helper for name {}
+            impl Helper for ::std::string::String {}
+        },
+        {
+            const _: () = {
+                trait Helper {}
+                impl Helper for Foo {}
+                impl Helper for ::std::string::String {}
+            };
+        },
+        None,
+    ),
+    (
+        impl_of_exported_type,
+        { options(scoped), name = Foo },
+        {
+            #[compose(export)]
+            struct name;
+            struct Helper;
+            impl name { fn f() {} }
+        },
+        {
+            const _: () = {
+                struct Helper;
+            };
+            struct Foo;
+            impl Foo { fn f() {} }
+        },
+        None,
+    ),
+    (
+        exported_only,
+        { options(scoped), name = foo },
+        {
+            #[compose(export)]
+            fn name() {}
+        },
+        {
+            fn foo() {}
+        },
+        None,
+    ),
+    (
+        loops,
+        { for x in [a, b] options(scoped), name = concat(x, _helper) },
+        {
+            fn name() {}
+        },
+        {
+            const _: () = {
+                fn a_helper() {}
+            };
+            const _: () = {
+                fn b_helper() {}
+            };
+        },
+        None,
+    ),
+    // The marker is stripped even if the items aren't scoped.
+    (
+        export_unscoped,
+        { name = foo },
+        {
+            #[compose(export)]
+            fn name() {}
+        },
+        {
+            fn foo() {}
+        },
+        None,
+    ),
+    (
+        statement_failure,
+        { options(scoped), name = foo },
+        {
+            let name = 1;
+        },
+        {},
+        Some(ErrorType::EvalError),
+    ),
+);
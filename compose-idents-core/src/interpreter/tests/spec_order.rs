@@ -0,0 +1,46 @@
+//! Tests for loops interleaved with the alias definitions - `alias = ..., for ... in [...]`.
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use rstest::rstest;
+use std::rc::Rc;
+
+fn execute(input: &str) -> String {
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment, DeprecationService::scoped());
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    interpreter.execute(raw_ast).unwrap().to_string()
+}
+
+/// Interleaved loops expand the same way as the loops going first.
+#[rstest]
+#[case::loops_after_aliases(
+    "getter = concat(get_, x), for x in [foo, bar] { fn getter() {} }",
+    "for x in [foo, bar] getter = concat(get_, x), { fn getter() {} }"
+)]
+#[case::loops_between_aliases(
+    "prefix = get, for x in [foo, bar], getter = concat(prefix, _, x), { fn getter() {} }",
+    "for x in [foo, bar] prefix = get, getter = concat(prefix, _, x), { fn getter() {} }"
+)]
+#[case::multiple_loops(
+    "for x in [foo, bar] name = concat(x, _, y), for y in [a, b] { fn name() {} }",
+    "for x in [foo, bar] for y in [a, b] name = concat(x, _, y), { fn name() {} }"
+)]
+#[case::substitution_table(
+    "name = concat(key, _fn), substitutions [ key value; [a] [1]; [b] [2] ] { fn name() -> u32 { value } }",
+    "substitutions [ key value; [a] [1]; [b] [2] ] name = concat(key, _fn), { fn name() -> u32 { value } }"
+)]
+#[case::options(
+    "options(strict), name = concat(x, _fn), for x in [foo, bar] { fn name() {} }",
+    "for x in [foo, bar] options(strict), name = concat(x, _fn), { fn name() {} }"
+)]
+#[case::block_first(
+    "{ fn getter() {} }, getter = concat(get_, x), for x in [foo, bar]",
+    "for x in [foo, bar] getter = concat(get_, x), { fn getter() {} }"
+)]
+fn same_expansion(#[case] interleaved: &str, #[case] loops_first: &str) {
+    assert_eq!(execute(interleaved), execute(loops_first));
+}
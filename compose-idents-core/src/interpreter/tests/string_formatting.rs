@@ -62,6 +62,20 @@ make_interpreter_test!(
         },
         None,
     ),
+    // Placeholders of raw identifier aliases are named either way.
+    (
+        raw_ident_placeholder,
+        { r#type = u8 },
+        {
+            static RAW: &str = "Type: % r#type %";
+            static PLAIN: &str = "Type: % type %";
+        },
+        {
+            static RAW: &str = "Type: u8";
+            static PLAIN: &str = "Type: u8";
+        },
+        None,
+    ),
     // Unterminated placeholder should remain unchanged.
     (
         unterminated_placeholder,
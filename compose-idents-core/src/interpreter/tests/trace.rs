@@ -0,0 +1,144 @@
+use crate::ast::RawAST;
+use crate::core::Environment;
+use crate::interpreter::Interpreter;
+use crate::util::deprecation::DeprecationService;
+use crate::util::trace::TRACE_FILE_NAME;
+use rstest::rstest;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Runs the interpreter on `input` writing the trace into `dir` and returns the invocation id.
+fn execute(input: &str, dir: &Path) -> u64 {
+    let environment = Rc::new(Environment::new_initialized(1));
+    Environment::maybe_set_global(environment.clone());
+    DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    let interpreter = Interpreter::new(environment.clone(), DeprecationService::scoped())
+        .with_trace_dir(dir.to_path_buf());
+    let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+    interpreter.execute(raw_ast).unwrap();
+    environment.invocation_id()
+}
+
+/// Reads the trace file back as a list of JSON records.
+fn read_trace(dir: &Path) -> Vec<serde_json::Value> {
+    std::fs::read_to_string(dir.join(TRACE_FILE_NAME))
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[rstest]
+#[case::loop_(
+    r#"for x in [a, b] y = upper(x), { fn y() {} }"#,
+    vec![vec![("x", "a"), ("y", "A")], vec![("x", "b"), ("y", "B")]],
+)]
+#[case::no_loop(r#"y = concat(foo, _, 1), { fn y() {} }"#, vec![vec![("y", "foo_1")]])]
+#[case::string(r#"y = "foo", { const C: &str = y; }"#, vec![vec![("y", "\"foo\"")]])]
+fn trace(#[case] input: &str, #[case] expected: Vec<Vec<(&str, &str)>>) {
+    let dir = tempfile::tempdir().unwrap();
+    let invocation_id = execute(input, dir.path());
+
+    let records = read_trace(dir.path());
+    assert_eq!(records.len(), expected.len());
+    for (i, (record, expected_aliases)) in records.iter().zip(&expected).enumerate() {
+        assert_eq!(record["invocation_id"], invocation_id);
+        assert_eq!(record["combination"], i);
+        assert_eq!(record["combinations"], expected.len());
+        for (alias, value) in expected_aliases.iter() {
+            assert_eq!(record["aliases"][alias], *value);
+        }
+    }
+}
+
+#[test]
+fn trace_appends() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = execute("for x in [a, b] { fn x() {} }", dir.path());
+    let second = execute("x = foo, { fn x() {} }", dir.path());
+
+    let ids = read_trace(dir.path())
+        .iter()
+        .map(|record| record["invocation_id"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    assert_ne!(first, second);
+    assert_eq!(ids, [first, first, second]);
+}
+
+#[test]
+fn trace_docs() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = r#"
+        for x in [a, b]
+
+        /// Name of the getter.
+        /// Prefixed with "get".
+        getter = concat(get_, x),
+        undocumented = upper(x),
+        /// Private aliases are traced too.
+        let private = foo,
+        {
+            fn getter() -> u32 { undocumented }
+        }
+    "#;
+    execute(input, dir.path());
+
+    for record in read_trace(dir.path()) {
+        assert_eq!(
+            record["docs"],
+            serde_json::json!({
+                "getter": "Name of the getter.\nPrefixed with \"get\".",
+                "private": "Private aliases are traced too.",
+            }),
+        );
+    }
+}
+
+#[test]
+fn trace_unused_and_private() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = r#"
+        for x in [a, b]
+
+        let prefix = get_,
+        getter = concat(prefix, x),
+        unused = upper(x),
+        let unused_private = concat(unused, _),
+        {
+            fn getter() {}
+        }
+    "#;
+    execute(input, dir.path());
+
+    let records = read_trace(dir.path());
+    assert_eq!(records.len(), 2);
+    for (record, x) in records.iter().zip(["a", "b"]) {
+        let aliases = &record["aliases"];
+        assert_eq!(aliases["prefix"], "get_");
+        assert_eq!(aliases["getter"], format!("get_{}", x));
+        assert_eq!(aliases["unused"], x.to_uppercase());
+        assert_eq!(aliases["unused_private"], format!("{}_", x.to_uppercase()));
+        assert_eq!(
+            record["private"],
+            serde_json::json!(["prefix", "unused_private"]),
+        );
+    }
+}
+
+#[test]
+fn trace_unused_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = r#"
+        name = foo,
+        unused = concat(name, "-"),
+        {
+            fn name() {}
+        }
+    "#;
+    execute(input, dir.path());
+
+    let records = read_trace(dir.path());
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["aliases"]["name"], "foo");
+    assert!(records[0]["aliases"].get("unused").is_none());
+}
@@ -0,0 +1,594 @@
+//! Tests for substitution with values of different types.
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    types,
+
+    // Ident cases.
+    (
+        ident,
+        { alias = foo },
+        { fn alias() -> u32 { 1 } },
+        { fn foo() -> u32 { 1 } },
+        None,
+    ),
+    (
+        raw_keyword,
+        { alias = r#type },
+        { fn alias() -> u32 { 1 } },
+        { fn r#type() -> u32 { 1 } },
+        None,
+    ),
+    (
+        ident_with_digits,
+        { alias = foo123 },
+        { fn alias() {} },
+        { fn foo123() {} },
+        None,
+    ),
+    (
+        underscore,
+        { alias = _ },
+        { fn f() { let alias = 1; } },
+        { fn f() { let _ = 1; } },
+        None,
+    ),
+
+    // Path cases.
+    (
+        path_simple,
+        { alias = foo::bar },
+        { fn f(_: alias) {} },
+        { fn f(_: foo::bar) {} },
+        None,
+    ),
+    (
+        path_with_generic_args,
+        { alias = Result<i32, String> },
+        { fn f(_: alias) {} },
+        { fn f(_: Result<i32, String>) {} },
+        None,
+    ),
+    (
+        path_with_lifetime_generic_param,
+        { alias = std::slice::Iter<'a, T> },
+        { fn f<'a, T>(_: alias) {} },
+        { fn f<'a, T>(_: std::slice::Iter<'a, T>) {} },
+        None,
+    ),
+
+    // Type.
+    (
+        type_array,
+        { alias = [u8; 32] },
+        { fn f(_: alias) {} },
+        { fn f(_: [u8; 32]) {} },
+        None,
+    ),
+    (
+        type_fn,
+        { alias = fn(i32) -> i32 },
+        { fn f(_: alias) {} },
+        { fn f(_: fn(i32) -> i32) {} },
+        None,
+    ),
+    (
+        type_dyn_bounds,
+        { alias = dyn Iterator<Item = u8> + Send + 'static },
+        { fn f(_: alias) {} },
+        { fn f(_: dyn Iterator<Item = u8> + Send + 'static) {} },
+        None,
+    ),
+
+    // Expr.
+    (
+        expr_binary,
+        { alias = 2 + 2 },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = 2 + 2; } },
+        None,
+    ),
+    (
+        expr_block,
+        { alias = { let x = 1; x + 1 } },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = { let x = 1; x + 1 }; } },
+        None,
+    ),
+    (
+        expr_call,
+        { alias = foo(1, 2) },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = foo(1, 2); } },
+        None,
+    ),
+
+    // LitInt.
+    (
+        litint_dec,
+        { alias = 123 },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = 123; } },
+        None,
+    ),
+    (
+        litint_underscore,
+        { alias = 1_000_000 },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = 1_000_000; } },
+        None,
+    ),
+    (
+        litint_negative,
+        { alias = -1 },
+        { const X: i32 = alias; },
+        { const X: i32 = -1; },
+        None,
+    ),
+    (
+        litint_negative_in_loop,
+        { for (name, value) in [(min, -128), (max, 127)]
+
+          alias = upper(name)
+        },
+        { const alias: i8 = value; },
+        {
+            const MIN: i8 = -128;
+            const MAX: i8 = 127;
+        },
+        None,
+    ),
+
+    // LitFloat.
+    (
+        litfloat,
+        { alias = 0.5 },
+        { const LIMIT: f64 = alias; },
+        { const LIMIT: f64 = 0.5; },
+        None,
+    ),
+    (
+        litfloat_in_loop,
+        { for (name, limit) in [(fast, 0.5), (slow, 2.75)]
+
+          alias = upper(name)
+        },
+        { const alias: f64 = limit; },
+        {
+            const FAST: f64 = 0.5;
+            const SLOW: f64 = 2.75;
+        },
+        None,
+    ),
+    (
+        litfloat_negative,
+        { alias = -1.25 },
+        { const LIMIT: f64 = alias; },
+        { const LIMIT: f64 = -1.25; },
+        None,
+    ),
+    (
+        litfloat_annotated,
+        { alias: float = 1e3 },
+        { const LIMIT: f64 = alias; },
+        { const LIMIT: f64 = 1e3; },
+        None,
+    ),
+    (
+        litfloat_formatting,
+        { alias = 1.5f32 },
+        { static S: &str = "Limit is % alias %"; },
+        { static S: &str = "Limit is 1.5f32"; },
+        None,
+    ),
+
+    // LitByteStr.
+    (
+        litbytestr,
+        { alias = b"foo" },
+        { static NAME: &[u8] = alias; },
+        { static NAME: &[u8] = b"foo"; },
+        None,
+    ),
+    (
+        litbytestr_in_loop,
+        { for (name, bytes) in [(foo, b"foo\0"), (bar, b"bar\0")]
+
+          alias = upper(name)
+        },
+        { static alias: &[u8] = bytes; },
+        {
+            static FOO: &[u8] = b"foo\0";
+            static BAR: &[u8] = b"bar\0";
+        },
+        None,
+    ),
+    (
+        litbytestr_formatting,
+        { alias = b"foo" },
+        { static S: &str = "Name is % alias %"; },
+        { static S: &str = "Name is foo"; },
+        None,
+    ),
+
+    // LitChar.
+    (
+        litchar_in_match,
+        { alias = 'a' },
+        { fn f(c: char) -> bool { match c { alias => true, _ => false } } },
+        { fn f(c: char) -> bool { match c { 'a' => true, _ => false } } },
+        None,
+    ),
+    (
+        litchar_in_loop,
+        { for (c, name) in [('a', alpha), ('b', beta)]
+
+          alias = upper(name)
+        },
+        { const alias: char = c; },
+        {
+            const ALPHA: char = 'a';
+            const BETA: char = 'b';
+        },
+        None,
+    ),
+    (
+        litchar_to_str,
+        { alias = to_str('x') },
+        { static S: &str = alias; },
+        { static S: &str = "x"; },
+        None,
+    ),
+    (
+        litchar_as_str_arg,
+        { alias = upper('x') },
+        { static S: &str = alias; },
+        { static S: &str = "X"; },
+        None,
+    ),
+    (
+        litchar_formatting,
+        { alias = 'z' },
+        { static S: &str = "Char is % alias %"; },
+        { static S: &str = "Char is z"; },
+        None,
+    ),
+
+    // LitBool.
+    (
+        litbool,
+        { alias = true },
+        { const FLAG: bool = alias; },
+        { const FLAG: bool = true; },
+        None,
+    ),
+    (
+        litbool_in_loop,
+        { for (name, flag) in [(foo, true), (bar, false)]
+
+          alias = upper(name)
+        },
+        { const alias: bool = flag; },
+        {
+            const FOO: bool = true;
+            const BAR: bool = false;
+        },
+        None,
+    ),
+    (
+        litbool_as_ident,
+        { alias = concat(is_, false) },
+        { fn alias() {} },
+        { fn is_false() {} },
+        None,
+    ),
+    (
+        litbool_formatting,
+        { alias = true },
+        { static S: &str = "Flag is % alias %"; },
+        { static S: &str = "Flag is true"; },
+        None,
+    ),
+
+    // Lifetime.
+    (
+        lifetime,
+        { alias = 'a },
+        { struct S<'a> { s: std::borrow::Cow<alias, str> } },
+        { struct S<'a> { s: std::borrow::Cow<'a, str> } },
+        None,
+    ),
+    (
+        lifetime_quoted_alias,
+        { alias = 'a },
+        { struct S<'alias> { s: &'alias str } },
+        { struct S<'a> { s: &'a str } },
+        None,
+    ),
+    (
+        lifetime_in_loop,
+        { for (lt, name) in [('a, borrowed), ('static, owned)]
+
+          alias = pascal_case(name)
+        },
+        { struct alias<'a> { s: &'lt str, p: std::borrow::Cow<lt, str> } },
+        {
+            struct Borrowed<'a> { s: &'a str, p: std::borrow::Cow<'a, str> }
+            struct Owned<'a> { s: &'static str, p: std::borrow::Cow<'static, str> }
+        },
+        None,
+    ),
+    (
+        lifetime_formatting,
+        { alias = 'a },
+        { static S: &str = "Lifetime is % alias %"; },
+        { static S: &str = "Lifetime is 'a"; },
+        None,
+    ),
+
+    // LitStr.
+    (
+        litstr_simple,
+        { alias = "hello" },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = "hello"; } },
+        None,
+    ),
+    (
+        litstr_raw_hashes,
+        { alias = r#"he"llo"# },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = r#"he"llo"#; } },
+        None,
+    ),
+
+    // Tokens.
+    (
+        tokens_item_injection,
+        { alias = #[derive(Default)] struct X; },
+        { alias },
+        { #[derive(Default)] struct X; },
+        None,
+    ),
+    (
+        tokens_let_stmt,
+        { alias = let x = 1; },
+        { fn f() { alias } },
+        { fn f() { let x = 1; } },
+        None,
+    ),
+
+    // List.
+    (
+        list,
+        { alias = [1, 2, 3] },
+        { const X: [u32; 3] = alias; },
+        { const X: [u32; 3] = [1, 2, 3]; },
+        None,
+    ),
+    (
+        list_nested,
+        { alias = [[1, 2], [3, 4]] },
+        { const X: [[u32; 2]; 2] = alias; },
+        { const X: [[u32; 2]; 2] = [[1, 2], [3, 4]]; },
+        None,
+    ),
+    (
+        list_of_mixed_items,
+        { alias = [foo, 1, "bar"] },
+        { m!(alias); },
+        { m!([foo, 1, "bar"]); },
+        None,
+    ),
+    (
+        list_annotated_as_expr,
+        { alias: expr = [1, 2, 3] },
+        { const X: [u32; 3] = alias; },
+        { const X: [u32; 3] = [1, 2, 3]; },
+        None,
+    ),
+    (
+        list_reused,
+        { items = [foo, bar], alias = items },
+        { m!(alias); },
+        { m!([foo, bar]); },
+        None,
+    ),
+
+    // Attr.
+    (
+        attr_above_fn,
+        { attrs = #[inline(always)] },
+        {
+            #[attrs]
+            fn f() {}
+        },
+        {
+            #[inline(always)]
+            fn f() {}
+        },
+        None,
+    ),
+    (
+        attr_above_field,
+        { attrs = #[serde(rename = "bar")] },
+        {
+            struct S {
+                #[attrs]
+                foo: u32,
+            }
+        },
+        {
+            struct S {
+                #[serde(rename = "bar")]
+                foo: u32,
+            }
+        },
+        None,
+    ),
+    (
+        attr_above_enum_variant,
+        { attrs = #[default] },
+        {
+            #[derive(Default)]
+            enum E {
+                #[attrs]
+                A,
+                B,
+            }
+        },
+        {
+            #[derive(Default)]
+            enum E {
+                #[default]
+                A,
+                B,
+            }
+        },
+        None,
+    ),
+    (
+        attr_derives,
+        { derives = #[derive(Debug, Clone)] },
+        {
+            #[derives]
+            #[repr(C)]
+            struct S;
+        },
+        {
+            #[derive(Debug, Clone)]
+            #[repr(C)]
+            struct S;
+        },
+        None,
+    ),
+    (
+        attr_sequence,
+        { attrs = #[derive(Debug)] #[repr(C)] },
+        {
+            #[attrs]
+            struct S;
+        },
+        {
+            #[derive(Debug)]
+            #[repr(C)]
+            struct S;
+        },
+        None,
+    ),
+    (
+        attr_list,
+        { attrs = [#[derive(Debug)], #[repr(C)]] },
+        {
+            #[attrs]
+            struct S;
+        },
+        {
+            #[derive(Debug)]
+            #[repr(C)]
+            struct S;
+        },
+        None,
+    ),
+    (
+        attr_list_item,
+        { attrs = [#[derive(Debug)], #[repr(C)]], attr = index(attrs, 1) },
+        {
+            #[attr]
+            struct S;
+        },
+        {
+            #[repr(C)]
+            struct S;
+        },
+        None,
+    ),
+    (
+        attr_in_loop,
+        { for (name, derives) in [(Foo, #[derive(Debug)]), (Bar, #[derive(Clone)])] },
+        {
+            #[derives]
+            struct name;
+        },
+        {
+            #[derive(Debug)]
+            struct Foo;
+            #[derive(Clone)]
+            struct Bar;
+        },
+        None,
+    ),
+
+    // Type annotations.
+    (
+        annotation_type,
+        { alias: type = [u8; 4] },
+        { fn f(_: alias) {} },
+        { fn f(_: [u8; 4]) {} },
+        None,
+    ),
+    (
+        annotation_expr,
+        { alias: expr = [u8; 4] },
+        { fn f() { let _ = alias; } },
+        { fn f() { let _ = [u8; 4]; } },
+        None,
+    ),
+    (
+        annotation_ident_coerced_to_path,
+        { alias: path = foo },
+        { fn f(_: alias) {} },
+        { fn f(_: foo) {} },
+        None,
+    ),
+    (
+        annotation_selects_concat_overload,
+        { alias: tokens = concat(foo, bar) },
+        { fn f() { m!(alias); } },
+        { fn f() { m!(foo bar); } },
+        None,
+    ),
+    (
+        annotation_tokens_grouping,
+        { alias: tokens = (u8, u16) },
+        { fn f() { m!(alias); } },
+        { fn f() { m!(u8, u16); } },
+        None,
+    ),
+    (
+        unannotated_tuple_type,
+        { alias = (u8, u16) },
+        { fn f() -> alias { unimplemented!() } },
+        { fn f() -> (u8, u16) { unimplemented!() } },
+        None,
+    ),
+    (
+        unannotated_concat_overload,
+        { alias = concat(foo, bar) },
+        { fn f() { m!(alias); } },
+        { fn f() { m!(foobar); } },
+        None,
+    ),
+    (
+        annotation_str_content_as_type,
+        { alias: type = "Result<T, E>" },
+        { fn f<T, E>(x: alias) -> alias { x } },
+        { fn f<T, E>(x: Result<T, E>) -> Result<T, E> { x } },
+        None,
+    ),
+    (
+        annotation_str_content_as_expr,
+        { alias: expr = "1 + 2" },
+        { const X: u32 = alias; },
+        { const X: u32 = 1 + 2; },
+        None,
+    ),
+    (
+        annotation_incompatible_with_alias,
+        { a = foo, alias: str = a },
+        { fn f() {} },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+);
@@ -0,0 +1,92 @@
+//! Tests for non-ASCII identifiers flowing through the alias definitions, the substitution and
+//! the string formatting.
+
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    unicode,
+    (
+        german,
+        { größe = concat(get_, snake_case(MaximaleGröße)) },
+        {
+            #[doc = "Returns % größe %."]
+            fn größe() -> u32 {
+                42
+            }
+        },
+        {
+            #[doc = "Returns get_maximale_größe."]
+            fn get_maximale_größe() -> u32 {
+                42
+            }
+        },
+        None,
+    ),
+    (
+        greek,
+        { for λέξη in [Όνομα, Μέγεθος] μέθοδος = concat(λάβε_, lower(λέξη)) },
+        {
+            static ΜΕΘΟΔΟΣ: &str = "% μέθοδος %";
+            fn μέθοδος() {}
+        },
+        {
+            static ΜΕΘΟΔΟΣ: &str = "λάβε_όνομα";
+            fn λάβε_όνομα() {}
+            static ΜΕΘΟΔΟΣ: &str = "λάβε_μέγεθος";
+            fn λάβε_μέγεθος() {}
+        },
+        None,
+    ),
+    (
+        cjk,
+        { 名前 = concat(取得_, 値), 型 = pascal_case(名前) },
+        {
+            struct 型;
+            fn 名前() -> &'static str {
+                "%名前%"
+            }
+        },
+        {
+            struct 取得値;
+            fn 取得_値() -> &'static str {
+                "取得_値"
+            }
+        },
+        None,
+    ),
+    (
+        normalize,
+        { name = normalize("größe wert"), other = normalize(名前 値) },
+        {
+            fn name() {}
+            fn other() {}
+        },
+        {
+            fn größe_wert() {}
+            fn 名前_値() {}
+        },
+        None,
+    ),
+    (
+        str_to_ident,
+        { name = to_ident("१foo") },
+        {
+            fn name() {}
+        },
+        {
+            fn _१foo() {}
+        },
+        None,
+    ),
+    (
+        combining_marks,
+        { name = snake_case(नमस्ते) },
+        {
+            fn name() {}
+        },
+        {
+            fn नमस्ते() {}
+        },
+        None,
+    ),
+);
@@ -0,0 +1,99 @@
+//! Tests for substitutions within use-declarations - including aliases expanding to
+//! multi-segment paths.
+
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    use_trees,
+    // Simple use-tree.
+    (
+        simple_ident,
+        { alias = foo },
+        { use backends::alias::Handler; },
+        { use backends::foo::Handler; },
+        None,
+    ),
+    (
+        simple_path,
+        { alias = foo::bar },
+        { use backends::alias::Handler; },
+        { use backends::foo::bar::Handler; },
+        None,
+    ),
+    // Glob use-tree.
+    (
+        glob_ident,
+        { alias = foo },
+        { pub use alias::*; },
+        { pub use foo::*; },
+        None,
+    ),
+    (
+        glob_path,
+        { alias = crate::foo::bar },
+        { pub use alias::*; },
+        { pub use crate::foo::bar::*; },
+        None,
+    ),
+    // Grouped use-tree.
+    (
+        grouped_ident,
+        { alias = foo },
+        { use backends::alias::{Handler, Config}; },
+        { use backends::foo::{Handler, Config}; },
+        None,
+    ),
+    (
+        grouped_path,
+        { alias = foo::bar },
+        { use backends::alias::{Handler, Config}; },
+        { use backends::foo::bar::{Handler, Config}; },
+        None,
+    ),
+    // Renamed use-tree.
+    (
+        renamed_ident,
+        { alias = foo, name = concat(alias, _handler) },
+        { use backends::alias::Handler as name; },
+        { use backends::foo::Handler as foo_handler; },
+        None,
+    ),
+    (
+        renamed_path,
+        { alias = foo::bar },
+        { use alias as bar_mod; },
+        { use foo::bar as bar_mod; },
+        None,
+    ),
+    // Nested use-tree.
+    (
+        nested_ident,
+        { alias = foo },
+        { use backends::{alias::{Handler, Config}, common::alias::*}; },
+        { use backends::{foo::{Handler, Config}, common::foo::*}; },
+        None,
+    ),
+    (
+        nested_path,
+        { alias = foo::bar },
+        { use backends::{alias::{Handler, Config}, common::alias::*}; },
+        { use backends::{foo::bar::{Handler, Config}, common::foo::bar::*}; },
+        None,
+    ),
+    // Use-tree within a module and a function.
+    (
+        nested_items_path,
+        { alias = foo::bar },
+        { mod m { use super::alias::Handler; } fn f() { use alias::Config; } },
+        { mod m { use super::foo::bar::Handler; } fn f() { use foo::bar::Config; } },
+        None,
+    ),
+    // Path coming from a loop variable.
+    (
+        loop_path,
+        { for alias in [foo::bar, baz] },
+        { pub use backends::alias::*; },
+        { pub use backends::foo::bar::*; pub use backends::baz::*; },
+        None,
+    ),
+);
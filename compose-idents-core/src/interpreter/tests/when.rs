@@ -0,0 +1,83 @@
+//! Tests for `when(...)` directive generating the code conditionally.
+
+use crate::error::ErrorType;
+use crate::interpreter::test::make_interpreter_test;
+
+make_interpreter_test!(
+    when,
+    (
+        loop_values,
+        { for (ty, bits) in [(u8, 8), (Unit, 0), (u16, 16)] when(ne(bits, 0)) },
+        {
+            impl Bits for ty {
+                const BITS: u32 = bits;
+            }
+        },
+        {
+            impl Bits for u8 {
+                const BITS: u32 = 8;
+            }
+            impl Bits for u16 {
+                const BITS: u32 = 16;
+            }
+        },
+        None,
+    ),
+    (
+        aliases,
+        { for x in [foo, bar, baz]
+
+          when(contains(name, "A")),
+          let upper_x = upper(x),
+          name = concat(upper_x, _fn)
+        },
+        {
+            fn name() {}
+        },
+        {
+            fn BAR_fn() {}
+            fn BAZ_fn() {}
+        },
+        None,
+    ),
+    (
+        unused_alias,
+        { for x in [a, b] when(eq(flag, on)), flag = select(eq(x, a), on, off) },
+        {
+            fn x() {}
+        },
+        {
+            fn a() {}
+        },
+        None,
+    ),
+    (
+        without_loops_enabled,
+        { when(eq(env("COMPOSE_IDENTS_TEST_UNSET", "release"), "release")), name = foo },
+        {
+            fn name() {}
+        },
+        {
+            fn foo() {}
+        },
+        None,
+    ),
+    (
+        without_loops_disabled,
+        { when(false), name = foo },
+        {
+            fn name() {}
+        },
+        { },
+        None,
+    ),
+    (
+        non_bool,
+        { when(foo), name = foo },
+        {
+            fn name() {}
+        },
+        { },
+        Some(ErrorType::TypeError),
+    ),
+);
@@ -0,0 +1,166 @@
+//! Provides analysis of which aliases are actually used by a block-rewrite.
+
+use crate::ast::{AliasSpec, Expr, ExprKind};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use std::collections::HashSet;
+use syn::ext::IdentExt;
+use syn::LitStr;
+
+/// Collects names that could potentially reference aliases within the token-stream.
+///
+/// # Notes
+///
+/// - Identifiers are collected from the whole stream including the insides of token groups (which
+///   are traversed iteratively).
+/// - For string literals every identifier-like word is collected, which over-approximates the set
+///   of `% alias %`-style placeholders.
+fn collect_names(stream: TokenStream, names: &mut HashSet<String>) {
+    let mut streams = vec![stream];
+    while let Some(stream) = streams.pop() {
+        for tt in stream {
+            match tt {
+                TokenTree::Ident(ident) => {
+                    names.insert(ident.unraw().to_string());
+                }
+                TokenTree::Group(group) => streams.push(group.stream()),
+                TokenTree::Literal(literal) => {
+                    let Ok(lit_str) = syn::parse2::<LitStr>(literal.into_token_stream()) else {
+                        continue;
+                    };
+                    let value = lit_str.value();
+                    names.extend(
+                        value
+                            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                            .filter(|word| !word.is_empty())
+                            .map(String::from),
+                    );
+                }
+                TokenTree::Punct(_) => {}
+            }
+        }
+    }
+}
+
+/// Collects names that could potentially reference aliases within an expression.
+fn collect_expr_names(expr: &Expr, names: &mut HashSet<String>) {
+    match expr.kind() {
+        ExprKind::ValueExpr(value) => collect_names(value.to_token_stream(), names),
+        ExprKind::FuncCallExpr(call) => {
+            for arg in call.raw_args() {
+                collect_expr_names(arg, names);
+            }
+            if let Some(tokens) = call.raw_tokens() {
+                collect_expr_names(&tokens, names);
+            }
+        }
+    }
+}
+
+/// Determines the names of aliases from the spec that are used by the block either directly or
+/// transitively through other used aliases.
+///
+/// Private aliases are never substituted into the block, so they could only be used through other
+/// aliases.
+pub fn used_aliases(spec: &AliasSpec, block: &syn::Block) -> HashSet<String> {
+    let mut block_names = HashSet::new();
+    collect_names(block.to_token_stream(), &mut block_names);
+    let mut names = HashSet::new();
+
+    // The expand phase orders aliases so that they only reference the ones defined before them,
+    // so a single reverse pass is enough to account for transitive usages.
+    let mut used = HashSet::new();
+    for item in spec.items().rev() {
+        let name = item.alias().name();
+        if names.contains(&name) || (!item.is_private() && block_names.contains(&name)) {
+            collect_expr_names(&item.value().expr(), &mut names);
+            used.insert(name);
+        }
+    }
+    used
+}
+
+/// Determines the names of aliases from the spec that are used by the expression (e.g. the
+/// condition of `when(...)` directive) either directly or transitively.
+///
+/// Unlike the block, the expression could use private aliases directly.
+pub fn expr_used_aliases(spec: &AliasSpec, expr: &Expr) -> HashSet<String> {
+    let mut names = HashSet::new();
+    collect_expr_names(expr, &mut names);
+
+    let mut used = HashSet::new();
+    for item in spec.items().rev() {
+        let name = item.alias().name();
+        if names.contains(&name) {
+            collect_expr_names(&item.value().expr(), &mut names);
+            used.insert(name);
+        }
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expr_used_aliases, used_aliases};
+    use crate::ast::{AliasSpec, Expr};
+    use crate::core::Environment;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case::direct("a = foo, b = bar", "{ fn a() {} }", &["a"])]
+    #[case::transitive(
+        "a = foo, b = concat(a, _, bar), c = upper(b), d = baz",
+        "{ fn c() {} }",
+        &["a", "b", "c"],
+    )]
+    #[case::nested_group("a = foo, b = bar", "{ fn f() { let x = [(b)]; } }", &["b"])]
+    #[case::string_placeholder(
+        "a = foo, b = bar",
+        "{ #[doc = \"Docs for %a%.\"] fn f() {} }",
+        &["a"],
+    )]
+    #[case::unused("a = foo", "{ fn f() {} }", &[])]
+    #[case::private_used_by_alias(
+        "let a = foo, b = concat(a, _, bar)",
+        "{ fn a() {} fn b() {} }",
+        &["a", "b"],
+    )]
+    #[case::private_used_by_block_only("let a = foo, b = bar", "{ fn a() {} }", &[])]
+    fn used_aliases_detection(#[case] spec: &str, #[case] block: &str, #[case] expected: &[&str]) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(spec).unwrap();
+        let block = syn::parse_str::<syn::Block>(block).unwrap();
+
+        let actual = used_aliases(&spec, &block);
+        let expected = expected
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case::direct("a = foo, b = bar", "eq(a, foo)", &["a"])]
+    #[case::private_transitive("let a = foo, b = concat(a, _), c = baz", "ne(b, 0)", &["a", "b"])]
+    #[case::unused("a = foo", "eq(x, y)", &[])]
+    fn expr_used_aliases_detection(
+        #[case] spec: &str,
+        #[case] expr: &str,
+        #[case] expected: &[&str],
+    ) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(spec).unwrap();
+        let expr = syn::parse_str::<Expr>(expr).unwrap();
+
+        let actual = expr_used_aliases(&spec, &expr);
+        let expected = expected
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,61 @@
+//! Library part of [compose-idents](https://crates.io/crates/compose-idents).
+//!
+//! `compose-idents` is a procedural macro crate, which can't export anything but macros - this
+//! crate provides its expansion pipeline and string manipulation functions for build scripts and
+//! other procedural macros.
+//!
+//! Expanding a `compose_item!`-style specification from another procedural macro:
+//!
+//! ```rust
+//! use compose_idents_core::{expand, Session};
+//! use quote::quote;
+//!
+//! let session = Session::new("my_macro!: ", 0);
+//! let expanded = expand(
+//!     &session,
+//!     quote!(for ty in [u8, u32] name = concat(get_, ty)),
+//!     quote!(fn name() -> ty { 1 }),
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     expanded.to_string(),
+//!     quote!(fn get_u8() -> u8 { 1 } fn get_u32() -> u32 { 1 }).to_string(),
+//! );
+//! ```
+//!
+//! String helpers:
+//!
+//! ```rust
+//! use compose_idents_core::{is_valid_ident, normalize, to_snake_case};
+//!
+//! let ident = to_snake_case(&normalize("My::Enum"));
+//! assert_eq!(ident, "my_enum");
+//! assert!(is_valid_ident(&ident));
+//! ```
+
+mod ast;
+mod core;
+mod error;
+mod eval;
+mod expand;
+mod funcs;
+mod interpreter;
+mod parse;
+mod resolve;
+mod session;
+mod strings;
+mod substitution;
+mod util;
+
+pub use error::{Error, ErrorType};
+#[cfg(feature = "pretty")]
+pub use session::expand_compose_pretty;
+pub use session::{
+    expand, expand_compose, expand_compose_const, expand_compose_expr, expand_compose_static,
+    expand_compose_test, Session,
+};
+pub use strings::{
+    capitalize, concat, decapitalize, escape_keyword, is_valid_ident, join, lower, normalize,
+    to_camel_case, to_kebab_case, to_pascal_case, to_shouty_snake_case, to_snake_case,
+    to_title_case, upper,
+};
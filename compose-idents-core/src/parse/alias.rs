@@ -0,0 +1,18 @@
+use crate::ast::{Alias, PLACEHOLDER_ALIAS};
+use crate::util::unique_id::next_unique_id;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, Token};
+
+/// Parses an alias - a placeholder (`_`) is accepted as well, it is only meaningful in
+/// destructuring positions.
+impl Parse for Alias {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![_]) {
+            let underscore = input.parse::<Token![_]>()?;
+            let ident = Ident::new(PLACEHOLDER_ALIAS, underscore.span);
+            return Ok(Alias::new(next_unique_id(), ident));
+        }
+        let ident: Ident = input.parse()?;
+        Ok(Alias::new(next_unique_id(), ident))
+    }
+}
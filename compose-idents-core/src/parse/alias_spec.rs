@@ -0,0 +1,328 @@
+use crate::ast::{AliasSpec, AliasSpecEntry, Expr};
+use crate::core::Options;
+use crate::error::combine_errors;
+use crate::parse::{peek_alias_spec_item, peek_loop_spec, MIXING_SEP_ERROR};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Delimiter, Spacing};
+use std::rc::Rc;
+use syn::buffer::Cursor;
+use syn::parse::{discouraged::Speculative, Parse, ParseStream};
+use syn::token::Paren;
+use syn::{parenthesized, Attribute, Ident, Token};
+
+/// Name of the directive stamping attributes onto the generated items - `apply_attrs(#[...])`.
+pub const APPLY_ATTRS_KEYWORD: &str = "apply_attrs";
+/// Name of the directive generating the code conditionally - `when(<expr>)`.
+pub const WHEN_KEYWORD: &str = "when";
+
+impl Parse for AliasSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut entries = Vec::new();
+        let mut is_comma_used = None;
+        let mut semicolon_span = None;
+
+        // The directives go before the alias definitions - each of them at most once.
+        let mut options = None;
+        let mut apply_attrs = None;
+        let mut when = None;
+        loop {
+            if options.is_none() && peek_directive(input, "options") {
+                options = Some(input.parse::<Options>()?);
+            } else if apply_attrs.is_none() && peek_directive(input, APPLY_ATTRS_KEYWORD) {
+                apply_attrs = Some(parse_apply_attrs(input)?);
+            } else if when.is_none() && peek_directive(input, WHEN_KEYWORD) {
+                when = Some(parse_when(input)?);
+            } else {
+                break;
+            }
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+                is_comma_used = Some(true);
+            } else if input.peek(Token![;]) {
+                let semicolon = input.parse::<Token![;]>()?;
+                semicolon_span = Some(semicolon.span);
+                is_comma_used = Some(false);
+            }
+        }
+        let has_directives = options.is_some() || apply_attrs.is_some() || when.is_some();
+        let options = options.unwrap_or_default();
+        let apply_attrs = apply_attrs.unwrap_or_default();
+
+        // The spec could consist of the directives alone.
+        if has_directives && !peek_alias_spec_item(input) {
+            return Ok(
+                AliasSpec::new(next_unique_id(), vec![], is_comma_used, options)
+                    .with_semicolon_span(semicolon_span)
+                    .with_apply_attrs(apply_attrs)
+                    .with_when(when),
+            );
+        }
+
+        // A failed item doesn't abort parsing - the error is recorded and parsing continues from
+        // the next item, so that all the problems of the spec are reported at once.
+        let span = input.span();
+        let mut errors = Vec::new();
+        loop {
+            let fork = input.fork();
+            match fork.parse::<AliasSpecEntry>() {
+                Ok(entry) => {
+                    input.advance_to(&fork);
+                    entries.push(entry);
+                }
+                Err(err) => {
+                    errors.push(err);
+                    skip_to_item_boundary(input)?;
+                }
+            }
+
+            if input.is_empty() {
+                break;
+            }
+
+            let is_comma_current_sep = if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+                true
+            } else if input.peek(Token![;]) {
+                let semicolon = input.parse::<Token![;]>()?;
+                semicolon_span.get_or_insert(semicolon.span);
+                false
+            } else {
+                errors.push(input.error(r#"Expected "," or ";""#));
+                break;
+            };
+
+            if let Some(is_comma_used) = is_comma_used {
+                if is_comma_used != is_comma_current_sep {
+                    errors.push(input.error(MIXING_SEP_ERROR));
+                    break;
+                }
+            } else {
+                is_comma_used = Some(is_comma_current_sep);
+            }
+
+            // The loops could follow the alias definitions - they end the spec.
+            if !peek_alias_spec_item(input) || peek_loop_spec(input) {
+                break;
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(combine_errors(
+                "Failed to parse the alias specification (see errors below)",
+                span,
+                errors,
+            ));
+        }
+
+        Ok(
+            AliasSpec::from_entries(next_unique_id(), entries, is_comma_used, options)
+                .with_semicolon_span(semicolon_span)
+                .with_apply_attrs(apply_attrs)
+                .with_when(when),
+        )
+    }
+}
+
+/// Checks whether the input starts with the directive of the given name - `name(...)`.
+pub fn peek_directive(input: ParseStream, name: &str) -> bool {
+    let is_keyword = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == name);
+    is_keyword && input.peek2(Paren)
+}
+
+/// Parses `when(<expr>)` directive into the condition expression.
+fn parse_when(input: ParseStream) -> syn::Result<Rc<Expr>> {
+    let keyword = input.parse::<Ident>()?;
+    let content;
+    parenthesized!(content in input);
+    if content.is_empty() {
+        return Err(syn::Error::new(
+            keyword.span(),
+            "when(...) expects a condition",
+        ));
+    }
+    Ok(Rc::new(content.parse::<Expr>()?))
+}
+
+/// Parses `apply_attrs(#[...], ...)` directive into the list of the attributes.
+fn parse_apply_attrs(input: ParseStream) -> syn::Result<Vec<Attribute>> {
+    let keyword = input.parse::<Ident>()?;
+    let content;
+    parenthesized!(content in input);
+    let mut attrs = Vec::new();
+    while !content.is_empty() {
+        if !content.peek(Token![#]) || content.peek2(Token![!]) {
+            return Err(content.error("Expected an outer attribute - #[...]"));
+        }
+        attrs.extend(content.call(Attribute::parse_outer)?);
+        if content.is_empty() {
+            break;
+        }
+        content.parse::<Token![,]>()?;
+    }
+    if attrs.is_empty() {
+        return Err(syn::Error::new(
+            keyword.span(),
+            "apply_attrs(...) expects at least one attribute",
+        ));
+    }
+    Ok(attrs)
+}
+
+/// Skips the tokens of a malformed spec item up to the separator that ends it.
+///
+/// Commas could occur within the value of an item (e.g. `Result<T, E>`), so only a separator
+/// followed by something that starts a new item, the code block, or the end of the input is
+/// considered the boundary.
+fn skip_to_item_boundary(input: ParseStream) -> syn::Result<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((_, next)) = rest.token_tree() {
+            if is_item_boundary(rest) {
+                break;
+            }
+            rest = next;
+        }
+        Ok(((), rest))
+    })
+}
+
+fn is_item_boundary(cursor: Cursor) -> bool {
+    let Some((punct, rest)) = cursor.punct() else {
+        return false;
+    };
+    if punct.as_char() != ',' && punct.as_char() != ';' {
+        return false;
+    }
+    if rest.eof() || rest.group(Delimiter::Brace).is_some() {
+        return true;
+    }
+    if rest
+        .punct()
+        .is_some_and(|(punct, _)| punct.as_char() == '#')
+    {
+        return true;
+    }
+    if let Some((_, _, rest)) = rest.group(Delimiter::Parenthesis) {
+        return rest
+            .punct()
+            .is_some_and(|(punct, _)| punct.as_char() == '=' && punct.spacing() == Spacing::Alone);
+    }
+    let Some((ident, rest)) = rest.ident() else {
+        return false;
+    };
+    if ident == "let" {
+        return true;
+    }
+    rest.punct().is_some_and(|(punct, _)| {
+        matches!(punct.as_char(), '=' | ':') && punct.spacing() == Spacing::Alone
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::AliasSpec;
+    use crate::core::Environment;
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case::single("a: idnet = foo, b = bar", &["Unknown type: idnet"])]
+    #[case::multiple(
+        "a: idnet = foo, b = bar, c: strr = baz",
+        &["Unknown type: idnet", "Unknown type: strr"],
+    )]
+    #[case::commas_within_value(
+        "a: idnet = Result<u8, u16>, b = bar, c: strr = baz",
+        &["Unknown type: idnet", "Unknown type: strr"],
+    )]
+    #[case::private_item(
+        "a: idnet = foo, let b: strr = bar",
+        &["Unknown type: idnet", "Unknown type: strr"],
+    )]
+    #[case::documented_item_after_failed_item(
+        "a: idnet = foo, /// Docs.\nb: strr = bar",
+        &["Unknown type: idnet", "Unknown type: strr"],
+    )]
+    #[case::separator_after_failed_item(
+        "a: idnet = foo; b: strr = bar",
+        &["Unknown type: idnet", "Unknown type: strr"],
+    )]
+    fn error_recovery(#[case] input: &str, #[case] expected: &[&str]) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = syn::parse_str::<AliasSpec>(input).err().unwrap();
+        let messages = err
+            .into_iter()
+            .map(|err| err.to_string())
+            .filter(|message| !message.starts_with("Failed to parse the alias specification"))
+            .map(|message| message.split(',').next().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, expected);
+    }
+
+    #[rstest]
+    #[case::flat("(a, b) = (foo, bar)", 1)]
+    #[case::nested("(a, (b, c)) = (foo, (bar, baz)), d = qux", 2)]
+    #[case::mixed("a = foo, (b, c) = (bar, concat(a, _, baz)), d = qux", 3)]
+    #[case::documented("/// Docs.\na = foo, /// More docs.\nb = bar, c = baz", 3)]
+    fn tuple_entries(#[case] input: &str, #[case] expected_len: usize) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(input).unwrap();
+        assert_eq!(spec.entries().len(), expected_len);
+    }
+
+    #[rstest]
+    #[case::alone("apply_attrs(#[doc(hidden)])", 1, 0)]
+    #[case::multiple("apply_attrs(#[doc(hidden)], #[inline]), a = foo", 2, 1)]
+    #[case::without_commas("apply_attrs(#[doc(hidden)] #[inline]), a = foo", 2, 1)]
+    #[case::after_options("options(strict), apply_attrs(#[inline]), a = foo", 1, 1)]
+    #[case::before_options("apply_attrs(#[inline]), options(strict), a = foo", 1, 1)]
+    fn apply_attrs(
+        #[case] input: &str,
+        #[case] expected_attrs: usize,
+        #[case] expected_len: usize,
+    ) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(input).unwrap();
+        assert_eq!(spec.apply_attrs().len(), expected_attrs);
+        assert_eq!(spec.entries().len(), expected_len);
+    }
+
+    #[rstest]
+    #[case::alone("when(eq(a, b))", 0)]
+    #[case::with_aliases("when(ne(bits, 0)), bits = 8, a = foo", 2)]
+    #[case::after_options("options(strict), when(true), a = foo", 1)]
+    fn when(#[case] input: &str, #[case] expected_len: usize) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(input).unwrap();
+        assert!(spec.when().is_some());
+        assert_eq!(spec.entries().len(), expected_len);
+    }
+
+    #[rstest]
+    #[case::empty("when(), a = foo", "when(...) expects a condition")]
+    #[case::repeated("when(true), when(false), a = foo", "expected `=`")]
+    fn when_failure(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = syn::parse_str::<AliasSpec>(input).err().unwrap();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::empty(
+        "apply_attrs(), a = foo",
+        "apply_attrs(...) expects at least one attribute"
+    )]
+    #[case::inner(
+        "apply_attrs(#![allow(unused)])",
+        "Expected an outer attribute - #[...]"
+    )]
+    #[case::not_attribute("apply_attrs(inline)", "Expected an outer attribute - #[...]")]
+    fn apply_attrs_failure(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = syn::parse_str::<AliasSpec>(input).err().unwrap();
+        assert_eq!(err.to_string(), expected);
+    }
+}
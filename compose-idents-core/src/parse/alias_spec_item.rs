@@ -0,0 +1,331 @@
+use crate::ast::{
+    Alias, AliasSpecEntry, AliasSpecItem, AliasValue, Ast, Call, Expr, ExprKind, LoopSourceValue,
+    Tuple, TupleAliasSpecItem, Value, ValueKind,
+};
+use crate::core::Type;
+use crate::strings::is_valid_ident;
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Delimiter, Ident, TokenStream, TokenTree};
+use quote::{quote_spanned, ToTokens};
+use std::rc::Rc;
+use syn::parse::{Parse, ParseStream};
+use syn::token::Paren;
+use syn::{Attribute, ExprLit, Lit, Meta, MetaNameValue, Token};
+
+/// Parses the doc-comments (`/// ...` or `#[doc = "..."]`) preceding an alias definition and
+/// returns their text - the lines are trimmed and joined with newlines.
+fn parse_docs(input: ParseStream) -> syn::Result<Option<String>> {
+    let attrs = input.call(Attribute::parse_outer)?;
+    let mut lines = Vec::new();
+    for attr in attrs.iter() {
+        let doc = match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                path,
+                value:
+                    syn::Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit_str),
+                        ..
+                    }),
+                ..
+            }) if path.is_ident("doc") => lit_str.value(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Only doc-comments are allowed before alias definitions",
+                ))
+            }
+        };
+        lines.extend(doc.lines().map(|line| line.trim().to_string()));
+    }
+    let docs = lines.join("\n").trim().to_string();
+    Ok((!docs.is_empty()).then_some(docs))
+}
+
+impl Parse for AliasSpecItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let docs = parse_docs(input)?;
+        let is_private = input.parse::<Option<Token![let]>>()?.is_some();
+        let alias = input.parse::<Alias>()?.with_docs(docs);
+
+        let type_annotation = if input.peek(Token![:]) && !input.peek(Token![::]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse::<Type>()?)
+        } else {
+            None
+        };
+
+        input.parse::<Token![=]>()?;
+
+        let mut value: AliasValue = input.parse()?;
+        if type_annotation != Some(Type::Tokens) {
+            value = desugar_juxtaposition(value)?;
+        }
+        if let Some(type_) = &type_annotation {
+            value = cast_value(value, type_)?;
+        }
+
+        Ok(AliasSpecItem::new(
+            next_unique_id(),
+            Rc::new(alias),
+            type_annotation,
+            Rc::new(value),
+        )
+        .with_private(is_private))
+    }
+}
+
+impl Parse for TupleAliasSpecItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let aliases = input.parse::<Tuple<Alias>>()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<LoopSourceValue>()?;
+
+        Ok(TupleAliasSpecItem::new(next_unique_id(), aliases, value))
+    }
+}
+
+impl Parse for AliasSpecEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        let attrs = fork.call(Attribute::parse_outer)?;
+        if fork.peek(Paren) {
+            if let Some(attr) = attrs.first() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Doc-comments are not supported on tuple destructurings",
+                ));
+            }
+            Ok(AliasSpecEntry::Tuple(Rc::new(input.parse()?)))
+        } else {
+            Ok(AliasSpecEntry::Item(Rc::new(input.parse()?)))
+        }
+    }
+}
+
+/// Re-interprets a plain (non-call) alias value as the annotated type.
+///
+/// Identifiers are left as is since they could reference other aliases - their coercion is
+/// handled during the resolve phase. A value annotated as `tokens` and enclosed in parentheses
+/// is unwrapped - the parentheses only group comma-containing tokens.
+fn cast_value(value: AliasValue, type_: &Type) -> syn::Result<AliasValue> {
+    let expr = value.expr();
+    let ExprKind::ValueExpr(inner) = expr.kind() else {
+        return Ok(value);
+    };
+    if type_ == &Type::Tokens {
+        if let Some(tokens) = paren_group_content(inner.to_token_stream()) {
+            return Ok(AliasValue::new(
+                next_unique_id(),
+                Rc::new(Expr::from_value(Value::from_tokens(tokens))),
+                value.span(),
+            ));
+        }
+    }
+    if matches!(inner.kind(), ValueKind::Ident(_)) || &inner.type_() == type_ {
+        return Ok(value);
+    }
+    let casted = inner.try_cast(type_)?;
+
+    Ok(AliasValue::new(
+        next_unique_id(),
+        Rc::new(Expr::from_value(casted)),
+        value.span(),
+    ))
+}
+
+/// Desugars a juxtaposition of atoms - identifiers, underscores and integers - into the
+/// equivalent `concat(...)` call: `prefix _ name _suffix` is `concat(prefix, _, name, _suffix)`.
+///
+/// Only a value that doesn't parse as any single typed value and consists of two or more atoms is
+/// desugared. Keywords aren't atoms, so token sequences like `pub async` are kept as is.
+fn desugar_juxtaposition(value: AliasValue) -> syn::Result<AliasValue> {
+    let expr = value.expr();
+    let ExprKind::ValueExpr(inner) = expr.kind() else {
+        return Ok(value);
+    };
+    let ValueKind::Tokens(tokens) = inner.kind() else {
+        return Ok(value);
+    };
+    let atoms = tokens.clone().into_iter().collect::<Vec<_>>();
+    if atoms.len() < 2 || !atoms.iter().all(is_atom) {
+        return Ok(value);
+    }
+    let name = Ident::new("concat", atoms[0].span());
+    let call = syn::parse2::<Call>(quote_spanned!(atoms[0].span()=> #name(#(#atoms),*)))?;
+
+    Ok(AliasValue::new(
+        next_unique_id(),
+        Rc::new(Expr::from_call(call)),
+        value.span(),
+    ))
+}
+
+/// Whether a token could be a part of a juxtaposition desugared into `concat(...)`.
+fn is_atom(token: &TokenTree) -> bool {
+    match token {
+        TokenTree::Ident(ident) => ident == "_" || is_valid_ident(&ident.to_string()),
+        TokenTree::Literal(literal) => {
+            syn::parse2::<syn::LitInt>(literal.to_token_stream()).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Returns the content of a token-stream consisting of a single parenthesized group.
+fn paren_group_content(tokens: TokenStream) -> Option<TokenStream> {
+    let mut trees = tokens.into_iter();
+    match (trees.next(), trees.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::Parenthesis => {
+            Some(group.stream())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{AliasSpecEntry, AliasSpecItem, ExprKind};
+    use crate::core::{Environment, Type};
+    use quote::ToTokens;
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case::none("alias = [u8; 4]", None, Some(Type::Type))]
+    #[case::type_("alias: type = [u8; 4]", Some(Type::Type), Some(Type::Type))]
+    #[case::expr("alias: expr = [u8; 4]", Some(Type::Expr), Some(Type::Expr))]
+    #[case::tokens("alias: tokens = [u8; 4]", Some(Type::Tokens), Some(Type::Tokens))]
+    #[case::str_("alias: str = \"foo\"", Some(Type::LitStr), Some(Type::LitStr))]
+    #[case::int("alias: int = 42", Some(Type::LitInt), Some(Type::LitInt))]
+    #[case::ident_is_kept("alias: path = foo", Some(Type::Path), Some(Type::Ident))]
+    #[case::call("alias: tokens = concat(foo, bar)", Some(Type::Tokens), None)]
+    fn annotation_parsing(
+        #[case] input: &str,
+        #[case] expected_annotation: Option<Type>,
+        #[case] expected_value_type: Option<Type>,
+    ) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<AliasSpecItem>(input).unwrap();
+
+        assert_eq!(item.type_annotation(), expected_annotation.as_ref());
+        let value_type = match item.value().expr().kind() {
+            ExprKind::ValueExpr(value) => Some(value.type_()),
+            ExprKind::FuncCallExpr(_) => None,
+        };
+        assert_eq!(value_type, expected_value_type);
+    }
+
+    #[rstest]
+    #[case::tuple_type("alias = (A, B)", "(A , B)", Type::Type)]
+    #[case::tuple_type_annotated("alias: type = (A, B)", "(A , B)", Type::Type)]
+    #[case::expr_annotated("alias: expr = (a + b)", "(a + b)", Type::Expr)]
+    #[case::grouped_tokens("alias: tokens = (A, B)", "A , B", Type::Tokens)]
+    #[case::grouped_arbitrary_tokens("alias: tokens = (a, b c)", "a , b c", Type::Tokens)]
+    #[case::grouped_once("alias: tokens = ((A, B))", "(A , B)", Type::Tokens)]
+    #[case::not_a_single_group("alias: tokens = (A, B) + C", "(A , B) + C", Type::Tokens)]
+    fn grouping_parsing(
+        #[case] input: &str,
+        #[case] expected_tokens: &str,
+        #[case] expected_type: Type,
+    ) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<AliasSpecItem>(input).unwrap();
+
+        let expr = item.value().expr();
+        let ExprKind::ValueExpr(value) = expr.kind() else {
+            panic!("expected a value");
+        };
+        assert_eq!(value.to_token_stream().to_string(), expected_tokens);
+        assert_eq!(value.type_(), expected_type);
+    }
+
+    #[rstest]
+    #[case::idents(
+        "alias = prefix _ name _suffix",
+        Some("concat(prefix, _, name, _suffix)")
+    )]
+    #[case::trailing_int("alias = foo _ 1", Some("concat(foo, _, 1)"))]
+    #[case::leading_underscore("alias = _ name", Some("concat(_, name)"))]
+    #[case::raw_ident("alias = get _ r#type", Some("concat(get, _, r#type)"))]
+    #[case::single_ident("alias = foo", None)]
+    #[case::single_underscore("alias = _", None)]
+    #[case::path("alias = foo::bar", None)]
+    #[case::type_("alias = dyn Foo", None)]
+    #[case::impl_type("alias = impl Foo", None)]
+    #[case::keywords("alias = pub async", None)]
+    #[case::ident_and_keyword("alias = foo fn", None)]
+    #[case::non_atoms("alias = foo _ (bar)", None)]
+    #[case::tokens_annotated("alias: tokens = foo _ bar", None)]
+    fn juxtaposition_parsing(#[case] input: &str, #[case] expected_call: Option<&str>) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<AliasSpecItem>(input).unwrap();
+
+        let expr = item.value().expr();
+        let call = match expr.kind() {
+            ExprKind::FuncCallExpr(call) => Some(call.to_string()),
+            ExprKind::ValueExpr(_) => None,
+        };
+        assert_eq!(call.as_deref(), expected_call);
+    }
+
+    #[rstest]
+    #[case::public("alias = foo", false)]
+    #[case::private("let alias = foo", true)]
+    #[case::private_annotated("let alias: str = \"foo\"", true)]
+    fn private_parsing(#[case] input: &str, #[case] expected: bool) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<AliasSpecItem>(input).unwrap();
+
+        assert_eq!(item.alias().ident().to_string(), "alias");
+        assert_eq!(item.is_private(), expected);
+    }
+
+    #[rstest]
+    #[case::none("alias = foo", None)]
+    #[case::doc_comment("/// Wire-format name.\nalias = foo", Some("Wire-format name."))]
+    #[case::multiple_lines(
+        "/// First line.\n/// Second line.\nalias = foo",
+        Some("First line.\nSecond line.")
+    )]
+    #[case::block_comment("/** Block\n  comment. */\nalias = foo", Some("Block\ncomment."))]
+    #[case::doc_attr("#[doc = \"Docs.\"] alias = foo", Some("Docs."))]
+    #[case::empty("///\nalias = foo", None)]
+    #[case::private("/// Docs.\nlet alias = foo", Some("Docs."))]
+    #[case::annotated("/// Docs.\nalias: str = \"foo\"", Some("Docs."))]
+    fn docs_parsing(#[case] input: &str, #[case] expected: Option<&str>) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<AliasSpecItem>(input).unwrap();
+
+        assert_eq!(item.alias().ident().to_string(), "alias");
+        assert_eq!(item.alias().docs(), expected);
+    }
+
+    #[rstest]
+    #[case::other_attr(
+        "#[inline] alias = foo",
+        "Only doc-comments are allowed before alias definitions"
+    )]
+    #[case::doc_list(
+        "#[doc(hidden)] alias = foo",
+        "Only doc-comments are allowed before alias definitions"
+    )]
+    #[case::tuple(
+        "/// Docs.\n(a, b) = (foo, bar)",
+        "Doc-comments are not supported on tuple destructurings"
+    )]
+    fn docs_parsing_errors(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = syn::parse_str::<AliasSpecEntry>(input).err().unwrap();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::unknown_type("alias: byte = foo")]
+    #[case::raw_type("alias: raw = foo")]
+    #[case::incompatible_value("alias: int = \"foo\"")]
+    #[case::missing_type("alias: = foo")]
+    fn annotation_parsing_errors(#[case] input: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        assert!(syn::parse_str::<AliasSpecItem>(input).is_err());
+    }
+}
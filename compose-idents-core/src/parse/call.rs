@@ -1,7 +1,6 @@
-use crate::ast::{Call, Expr, NodeId, Value};
+use crate::ast::{Call, Expr, NodeId, TerminatedExpr, Value};
 use crate::core::Environment;
 use crate::error::Error;
-use crate::util::terminated::Terminated;
 use crate::util::unique_id::next_unique_id;
 use proc_macro2::TokenStream;
 use std::rc::Rc;
@@ -25,11 +24,11 @@ impl Parse for Call {
         parenthesized!(raw_args in input);
         let punctuated = raw_args
             .fork()
-            .parse_terminated(Terminated::<Expr, Token![,]>::parse, Token![,]);
+            .parse_terminated(TerminatedExpr::<Token![,]>::parse, Token![,]);
         let args = match punctuated {
             Ok(punctuated) => punctuated
                 .into_iter()
-                .map(|arg| Rc::new(arg.into_value()))
+                .map(|arg| Rc::new(arg.into_expr()))
                 .collect::<Vec<_>>(),
             Err(_) => vec![],
         };
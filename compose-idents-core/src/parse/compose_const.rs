@@ -0,0 +1,81 @@
+use crate::ast::{AliasSpec, RawAST};
+use crate::parse::{add_deprecation_warnings, parse_loops_and_aliases};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::quote_spanned;
+use std::rc::Rc;
+use syn::parse::ParseStream;
+use syn::{Attribute, Block, Expr, Token, Type, Visibility};
+
+/// Name of the alias the items generated by `compose_const!` and `compose_static!` are named by.
+pub const ITEM_NAME_ALIAS: &str = "__item_name";
+
+/// Parses the tokens of the name expression - everything up to the `:` preceding the type.
+fn parse_name_expr(input: ParseStream) -> syn::Result<TokenStream> {
+    let mut tokens = TokenStream::new();
+    // A single `:` ends the name, while `::` could be a part of it.
+    let is_type_sep = |input: ParseStream| input.peek(Token![:]) && !input.peek(Token![::]);
+    while !input.is_empty() && !is_type_sep(input) {
+        tokens.extend([input.parse::<TokenTree>()?]);
+    }
+    if tokens.is_empty() {
+        return Err(input.error("expected a name expression before the type"));
+    }
+    Ok(tokens)
+}
+
+/// Parses `NAME_EXPR : Type = value_expr; aliases...` into a block holding a single item declared
+/// by the `keyword` (`const` or `static`).
+///
+/// The name expression is evaluated as an alias definition after the aliases of the spec, the
+/// item itself is located at the invocation.
+fn parse_item_decl(input: ParseStream, keyword: &str) -> syn::Result<RawAST> {
+    let span = Span::call_site();
+    let attrs = input.call(Attribute::parse_outer)?;
+    let vis = input.parse::<Visibility>()?;
+    let name_expr = parse_name_expr(input)?;
+    input.parse::<Token![:]>()?;
+    let ty = input.parse::<Type>()?;
+    input.parse::<Token![=]>()?;
+    let value = input.parse::<Expr>()?;
+
+    let (loops, user_spec) = if input.is_empty() {
+        (None, None)
+    } else {
+        input.parse::<Token![;]>()?;
+        parse_loops_and_aliases(input)?
+    };
+    add_deprecation_warnings(loops.as_ref(), user_spec.as_ref())?;
+
+    let name = Ident::new(ITEM_NAME_ALIAS, span);
+    let mut spec = syn::parse2::<AliasSpec>(quote_spanned!(span=> #name = #name_expr))?;
+    if let Some(mut user_spec) = user_spec {
+        user_spec.append(spec);
+        spec = user_spec;
+    }
+
+    let keyword = Ident::new(keyword, span);
+    let block = syn::parse2::<Block>(quote_spanned! {span=>
+        {
+            #(#attrs)*
+            #vis #keyword #name: #ty = #value;
+        }
+    })?;
+
+    Ok(RawAST::new(
+        next_unique_id(),
+        loops.map(Rc::new),
+        Some(Rc::new(spec)),
+        vec![block],
+    ))
+}
+
+/// Parses the input of `compose_const!` - `NAME_EXPR : Type = value_expr; aliases...`.
+pub fn parse_compose_const(input: ParseStream) -> syn::Result<RawAST> {
+    parse_item_decl(input, "const")
+}
+
+/// Parses the input of `compose_static!` - `NAME_EXPR : Type = value_expr; aliases...`.
+pub fn parse_compose_static(input: ParseStream) -> syn::Result<RawAST> {
+    parse_item_decl(input, "static")
+}
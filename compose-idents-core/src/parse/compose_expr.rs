@@ -0,0 +1,35 @@
+use crate::ast::{Ast, RawAST};
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::Stmt;
+
+/// Parses the input of `compose_expr!` - it is the same as the one of `compose!`, but without loops
+/// and with a single block consisting of a single expression, so that the output is an expression.
+pub fn parse_compose_expr(input: ParseStream) -> syn::Result<RawAST> {
+    let span = input.span();
+    let raw_ast = input.parse::<RawAST>()?;
+    if let Some(loops) = raw_ast.loops() {
+        return Err(syn::Error::new(
+            loops.span(),
+            "compose_expr! doesn't support loops since an expression can't expand to multiple values",
+        ));
+    }
+    let block = match raw_ast.blocks() {
+        [block] => block,
+        [_, block, ..] => {
+            return Err(syn::Error::new(
+                block.span(),
+                "compose_expr! expects a single block",
+            ))
+        }
+        [] => return Err(syn::Error::new(span, "compose_expr! expects a block")),
+    };
+    match block.stmts.as_slice() {
+        [Stmt::Expr(_, None)] => Ok(raw_ast),
+        [Stmt::Macro(mac)] if mac.semi_token.is_none() => Ok(raw_ast),
+        _ => Err(syn::Error::new(
+            block.span(),
+            "compose_expr! expects a block consisting of a single expression - { ... }",
+        )),
+    }
+}
@@ -0,0 +1,26 @@
+use crate::ast::ComposeItemSpec;
+use crate::parse::{add_deprecation_warnings, parse_loops_and_aliases};
+use crate::util::unique_id::next_unique_id;
+use syn::parse::{Parse, ParseStream};
+use syn::Token;
+
+impl Parse for ComposeItemSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (loops, spec) = parse_loops_and_aliases(input)?;
+        add_deprecation_warnings(loops.as_ref(), spec.as_ref())?;
+
+        if spec.is_some() {
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            } else if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+            }
+        }
+
+        Ok(ComposeItemSpec::new(
+            next_unique_id(),
+            loops.map(std::rc::Rc::new),
+            spec.map(std::rc::Rc::new),
+        ))
+    }
+}
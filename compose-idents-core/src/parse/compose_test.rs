@@ -0,0 +1,84 @@
+use crate::ast::{Alias, AliasSpec, Ast, LoopAlias, RawAST, Tuple, TupleValueKind};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use std::rc::Rc;
+use syn::parse::ParseStream;
+use syn::Block;
+
+/// Name of the alias the test functions generated by `compose_test!` are named by.
+pub const TEST_FN_ALIAS: &str = "__test_fn";
+
+/// Reads the alias naming the test cases of a loop - the first element of the loop variable
+/// (`name` in `for (name, input, expected) in [...]`) unless it is a placeholder.
+fn test_name_alias(alias: &LoopAlias) -> Option<Rc<Alias>> {
+    fn first_alias(tuple: &Tuple<Alias>) -> Option<Rc<Alias>> {
+        match tuple.values().first()?.kind() {
+            TupleValueKind::Value(alias) => Some(alias.clone()),
+            TupleValueKind::Tuple(tuple) => first_alias(tuple),
+        }
+    }
+    let alias = match alias {
+        LoopAlias::Simple(alias) => alias.clone(),
+        LoopAlias::Tuple(tuple) => first_alias(tuple)?,
+    };
+    (!alias.is_placeholder()).then_some(alias)
+}
+
+/// Parses the input of `compose_test!` - it is the same as the one of `compose!`, but each block
+/// is wrapped into a `#[test]` function.
+///
+/// The function is named `test_<name>`, where `<name>` is the normalized first element of the loop
+/// variable (or the first elements of the variables of several loops joined by `_`) - so each
+/// combination of loop values produces a separate test.
+pub fn parse_compose_test(input: ParseStream) -> syn::Result<RawAST> {
+    let span = input.span();
+    let raw_ast = input.parse::<RawAST>()?;
+    let Some(loops) = raw_ast.loops() else {
+        return Err(syn::Error::new(
+            span,
+            "compose_test! expects a loop naming the test cases - for (name, ...) in [...]",
+        ));
+    };
+    let names = loops
+        .loops()
+        .iter()
+        .filter_map(|item| test_name_alias(&item.alias()))
+        .map(|alias| {
+            let ident = alias.ident();
+            quote!(normalize2(#ident))
+        })
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return Err(syn::Error::new(
+            loops.span(),
+            "compose_test! expects the first element of a loop variable to name the test cases",
+        ));
+    }
+
+    let test_fn = Ident::new(TEST_FN_ALIAS, Span::call_site());
+    let mut spec = syn::parse2::<AliasSpec>(quote!(#test_fn = join("_", test, #(#names),*)))?;
+    if let Some(user_spec) = raw_ast.spec() {
+        let mut user_spec = user_spec.as_ref().clone();
+        user_spec.append(spec);
+        spec = user_spec;
+    }
+
+    let blocks = raw_ast
+        .blocks()
+        .iter()
+        .map(|block| {
+            syn::parse2::<Block>(quote! {
+                {
+                    #[test]
+                    fn #test_fn() #block
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(
+        RawAST::new(next_unique_id(), Some(loops), Some(Rc::new(spec)), blocks)
+            .with_attrs(raw_ast.attrs().to_vec()),
+    )
+}
@@ -0,0 +1,133 @@
+//! Shared parsing helpers and constants.
+
+use crate::ast::{Alias, AliasSpec, AliasSpecEntry, Ast, LoopAlias, LoopSpec};
+use crate::interpreter::{INDEX1_ALIAS, INDEX_ALIAS};
+use crate::parse::SUBSTITUTIONS_KEYWORD;
+use crate::util::deprecation::DeprecationService;
+use crate::util::unique_id::next_unique_id;
+use std::rc::Rc;
+use syn::parse::ParseStream;
+use syn::token::{Bracket, Paren};
+use syn::Token;
+
+/// Error message used when the user mixes separators of different kinds ("," and ";").
+pub const MIXING_SEP_ERROR: &str = r#"Mixing "," and ";" as separators is not allowed"#;
+
+/// Checks whether the input starts with an alias definition - a plain (`alias = ...`), a private
+/// one (`let alias = ...`), a tuple destructuring (`(a, b) = ...`) or a documented one
+/// (`/// Docs.` followed by any of the above).
+pub fn peek_alias_spec_item(input: ParseStream) -> bool {
+    input.peek(syn::Ident) || input.peek(Token![let]) || input.peek(Paren) || input.peek(Token![#])
+}
+
+/// Checks whether the input starts with a substitution table - `substitutions [...]`.
+pub fn peek_substitution_table(input: ParseStream) -> bool {
+    let is_keyword = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == SUBSTITUTIONS_KEYWORD);
+    is_keyword && input.peek2(Bracket)
+}
+
+/// Checks whether the input starts with loops - `for ... in [...]` or a substitution table.
+pub fn peek_loop_spec(input: ParseStream) -> bool {
+    input.peek(Token![for]) || peek_substitution_table(input)
+}
+
+/// Parses the loops and the alias spec - both are optional.
+///
+/// The loops could be interleaved with the alias definitions (e.g. `alias = ..., for x in [...]`),
+/// so they are gathered regardless of the order - the loops into a single [`LoopSpec`] and the
+/// aliases into a single [`AliasSpec`].
+pub fn parse_loops_and_aliases(
+    input: ParseStream,
+) -> syn::Result<(Option<LoopSpec>, Option<AliasSpec>)> {
+    let mut loops = Vec::new();
+    let mut spec: Option<AliasSpec> = None;
+    loop {
+        if peek_loop_spec(input) {
+            loops.extend(input.parse::<LoopSpec>()?.loops().iter().cloned());
+        } else if peek_alias_spec_item(input) {
+            let chunk = input.parse::<AliasSpec>()?;
+            let Some(spec) = spec.as_mut() else {
+                spec = Some(chunk);
+                continue;
+            };
+            if let Some(span) = chunk.options().span() {
+                return Err(syn::Error::new(
+                    span,
+                    "The options must go before the alias definitions",
+                ));
+            }
+            if let Some(attr) = chunk.apply_attrs().first() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "The apply_attrs(...) directive must go before the alias definitions",
+                ));
+            }
+            if let Some(when) = chunk.when() {
+                return Err(syn::Error::new(
+                    when.span(),
+                    "The when(...) directive must go before the alias definitions",
+                ));
+            }
+            if let (Some(is_comma_used), Some(is_chunk_comma_used)) =
+                (spec.is_comma_used(), chunk.is_comma_used())
+            {
+                if is_comma_used != is_chunk_comma_used {
+                    return Err(syn::Error::new(chunk.span(), MIXING_SEP_ERROR));
+                }
+            }
+            spec.append(chunk);
+        } else {
+            break;
+        }
+    }
+
+    let loops = (!loops.is_empty()).then(|| LoopSpec::new(next_unique_id(), loops));
+    Ok((loops, spec))
+}
+
+/// Reads the aliases defined by the loops and by the alias spec - including the destructured ones.
+fn defined_aliases(loops: Option<&LoopSpec>, spec: Option<&AliasSpec>) -> Vec<Rc<Alias>> {
+    let loop_aliases = loops
+        .into_iter()
+        .flat_map(|loops| loops.loops().iter().map(|item| item.alias()))
+        .flat_map(|alias| match alias.as_ref() {
+            LoopAlias::Simple(alias) => vec![alias.clone()],
+            LoopAlias::Tuple(tuple) => tuple.iter_recursive().collect(),
+        });
+    let spec_aliases = spec
+        .into_iter()
+        .flat_map(|spec| spec.entries().iter())
+        .flat_map(|entry| match entry {
+            AliasSpecEntry::Item(item) => vec![item.alias()],
+            AliasSpecEntry::Tuple(item) => item.aliases().iter_recursive().collect(),
+        });
+    loop_aliases.chain(spec_aliases).collect()
+}
+
+/// Registers the deprecation warnings of the loops and the alias spec - about semicolon separators
+/// and about user-defined aliases shadowing the loop index aliases.
+pub fn add_deprecation_warnings(
+    loops: Option<&LoopSpec>,
+    spec: Option<&AliasSpec>,
+) -> syn::Result<()> {
+    let deprecation_service = DeprecationService::scoped();
+    let deny = spec.is_some_and(|spec| spec.options().deny_deprecated());
+    if let Some(spec) = spec {
+        deprecation_service.allow(spec.options().allow_deprecated());
+        if let Some(span) = spec.semicolon_span() {
+            deprecation_service.add_semicolon_separator_warning(span, deny)?;
+        }
+    }
+    if loops.is_some() {
+        for alias in defined_aliases(loops, spec) {
+            let name = alias.name();
+            if name == INDEX_ALIAS || name == INDEX1_ALIAS {
+                deprecation_service.add_index_alias_shadowing_warning(&name);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,378 @@
+//! Implements loop sources defined by inline data documents:
+//!
+//! ```plain,ignore
+//! for (name, ty) in toml(r#"
+//!     [[rows]]
+//!     name = "foo"
+//!     ty = "u8"
+//! "#)
+//! ```
+//!
+//! Each row of the document turns into a loop value - fields are mapped to the loop aliases by
+//! name and string fields are parsed just like any other values. Requires `serde` feature.
+use crate::ast::{Alias, Ast, Expr, LoopAlias, LoopSourceValue, LoopSourceValueList, Tuple};
+use crate::ast::{TupleValue, TupleValueKind};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use std::collections::HashMap;
+use std::rc::Rc;
+use syn::parse::ParseStream;
+use syn::token::Paren;
+use syn::{parenthesized, LitStr, Token};
+
+/// Names of the supported document formats.
+pub const DATA_SOURCE_FORMATS: &[&str] = &["toml", "json"];
+
+/// Error message used when the loop aliases of a document loop source contain placeholders.
+const PLACEHOLDER_ERROR: &str = "Placeholders are not supported by document loop sources";
+
+/// A row of a document - source code of the field values by their keys.
+type Row = HashMap<String, String>;
+
+/// Checks whether the input starts with a data loop source - `toml(...)` or `json(...)`.
+pub fn peek_data_source(input: ParseStream) -> bool {
+    let is_format = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| DATA_SOURCE_FORMATS.contains(&ident.to_string().as_str()));
+    is_format && input.peek2(Paren)
+}
+
+/// Sets the span of all the tokens (including the nested ones) to `span`.
+fn respan(tokens: TokenStream, span: Span) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut new_group = Group::new(group.delimiter(), respan(group.stream(), span));
+                new_group.set_span(span);
+                token = TokenTree::Group(new_group);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+/// Reads the names of the loop aliases the document fields are mapped to.
+fn alias_names(alias: &LoopAlias) -> syn::Result<Vec<Rc<Alias>>> {
+    match alias {
+        LoopAlias::Simple(alias) if alias.is_placeholder() => {
+            Err(syn::Error::new(alias.span(), PLACEHOLDER_ERROR))
+        }
+        LoopAlias::Simple(alias) => Ok(vec![alias.clone()]),
+        LoopAlias::Tuple(tuple) => tuple
+            .values()
+            .iter()
+            .map(|value| match value.kind() {
+                TupleValueKind::Value(alias) if alias.is_placeholder() => {
+                    Err(syn::Error::new(value.span(), PLACEHOLDER_ERROR))
+                }
+                TupleValueKind::Value(alias) => Ok(alias.clone()),
+                TupleValueKind::Tuple(_) => Err(syn::Error::new(
+                    value.span(),
+                    "Nested tuples are not supported by document loop sources",
+                )),
+            })
+            .collect(),
+    }
+}
+
+/// Parses a field value into an expression pointing at the document literal.
+fn parse_field(source: &str, lit: &LitStr) -> syn::Result<Expr> {
+    let tokens = source.parse::<TokenStream>().map_err(|err| {
+        syn::Error::new(
+            lit.span(),
+            format!("Failed to tokenize {:?}: {}", source, err),
+        )
+    })?;
+    syn::parse2::<Expr>(respan(tokens, lit.span())).map_err(|err| {
+        syn::Error::new(lit.span(), format!("Failed to parse {:?}: {}", source, err))
+    })
+}
+
+/// Extracts rows from a TOML document - it must contain a single array of tables.
+#[cfg(feature = "serde")]
+fn parse_toml_rows(text: &str) -> Result<Vec<Row>, String> {
+    use toml::Value;
+
+    let table = text.parse::<toml::Table>().map_err(|err| err.to_string())?;
+    let mut values = table.values();
+    let (Some(Value::Array(rows)), None) = (values.next(), values.next()) else {
+        return Err("Expected a document with a single array of tables".to_string());
+    };
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let Value::Table(row) = row else {
+                return Err(format!("Row {} is not a table", i + 1));
+            };
+            row.iter()
+                .map(|(key, value)| {
+                    let source = match value {
+                        Value::String(string) => string.clone(),
+                        Value::Integer(int) => int.to_string(),
+                        Value::Boolean(bool_) => bool_.to_string(),
+                        _ => {
+                            return Err(format!(
+                                "Unsupported value of key \"{}\" in row {}: expected a string, \
+                                an integer or a boolean",
+                                key,
+                                i + 1
+                            ))
+                        }
+                    };
+                    Ok((key.clone(), source))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Extracts rows from a JSON document - it must be an array of objects.
+#[cfg(feature = "serde")]
+fn parse_json_rows(text: &str) -> Result<Vec<Row>, String> {
+    use serde_json::Value;
+
+    let document = serde_json::from_str::<Value>(text).map_err(|err| err.to_string())?;
+    let Value::Array(rows) = document else {
+        return Err("Expected an array of objects".to_string());
+    };
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let Value::Object(row) = row else {
+                return Err(format!("Row {} is not an object", i + 1));
+            };
+            row.iter()
+                .map(|(key, value)| {
+                    let source = match value {
+                        Value::String(string) => string.clone(),
+                        Value::Number(number) if number.is_i64() || number.is_u64() => {
+                            number.to_string()
+                        }
+                        Value::Bool(bool_) => bool_.to_string(),
+                        _ => {
+                            return Err(format!(
+                                "Unsupported value of key \"{}\" in row {}: expected a string, \
+                                an integer or a boolean",
+                                key,
+                                i + 1
+                            ))
+                        }
+                    };
+                    Ok((key.clone(), source))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Extracts rows from a document of the given format.
+#[cfg(feature = "serde")]
+fn parse_rows(format: &syn::Ident, lit: &LitStr) -> syn::Result<Vec<Row>> {
+    let text = lit.value();
+    let rows = if format == "toml" {
+        parse_toml_rows(&text)
+    } else {
+        parse_json_rows(&text)
+    };
+    rows.map_err(|err| {
+        syn::Error::new(
+            lit.span(),
+            format!("Failed to read {} document: {}", format, err),
+        )
+    })
+}
+
+#[cfg(not(feature = "serde"))]
+fn parse_rows(format: &syn::Ident, _lit: &LitStr) -> syn::Result<Vec<Row>> {
+    Err(syn::Error::new(
+        format.span(),
+        format!("{}(...) loop source requires \"serde\" feature", format),
+    ))
+}
+
+/// Parses a `toml("...")` or `json("...")` loop source mapping the document rows onto `alias`.
+pub fn parse_data_source(
+    input: ParseStream,
+    alias: &LoopAlias,
+) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    let format = input.parse::<syn::Ident>()?;
+    let content;
+    parenthesized!(content in input);
+    let lit = content.parse::<LitStr>()?;
+    content.parse::<Option<Token![,]>>()?;
+
+    let aliases = alias_names(alias)?;
+    let rows = parse_rows(&format, &lit)?;
+
+    let values = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let exprs = aliases
+                .iter()
+                .map(|alias| {
+                    let key = alias.name();
+                    let Some(source) = row.get(&key) else {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!("Row {} of the document is missing key \"{}\"", i + 1, key),
+                        ));
+                    };
+                    parse_field(source, &lit)
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            Ok(match alias {
+                LoopAlias::Simple(_) => {
+                    LoopSourceValue::from_value(exprs.into_iter().next().unwrap())
+                }
+                LoopAlias::Tuple(_) => {
+                    let values = exprs
+                        .into_iter()
+                        .map(|expr| TupleValue::from_value(next_unique_id(), Rc::new(expr), span))
+                        .collect();
+                    LoopSourceValue::from_tuple(Tuple::new(next_unique_id(), values, span))
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(LoopSourceValueList::new(next_unique_id(), values, span))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{LoopSourceValue, LoopSpecItem, TupleValueKind};
+    use crate::core::Environment;
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    fn parse_rows(input: &str) -> syn::Result<Vec<String>> {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<LoopSpecItem>(input)?;
+        let rows = item
+            .list()
+            .values()
+            .iter()
+            .map(|value| match value {
+                LoopSourceValue::Value(expr) => expr.to_string(),
+                LoopSourceValue::Tuple(tuple) => format!(
+                    "({})",
+                    tuple
+                        .values()
+                        .iter()
+                        .map(|value| match value.kind() {
+                            TupleValueKind::Value(expr) => expr.to_string(),
+                            TupleValueKind::Tuple(_) => unreachable!(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            })
+            .collect();
+        Ok(rows)
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::toml(
+        r##"for (name, ty) in toml(r#"
+            [[rows]]
+            ty = "u8"
+            name = "foo"
+
+            [[rows]]
+            name = "bar"
+            ty = "Vec<u16>"
+        "#)"##,
+        &["(foo, u8)", "(bar, Vec < u16 >)"],
+    )]
+    #[case::toml_simple(
+        r##"for name in toml(r#"
+            [[items]]
+            name = "foo"
+            [[items]]
+            name = "concat(bar, _, baz)"
+        "#)"##,
+        &["foo", "concat(bar, _, baz)"],
+    )]
+    #[case::toml_scalars(
+        r##"for (int, flag, extra) in toml(r#"
+            [[rows]]
+            int = 42
+            flag = true
+            extra = "x"
+        "#)"##,
+        &["(42, true, x)"],
+    )]
+    #[case::json(
+        r##"for (name, ty) in json(r#"[
+            {"ty": "u8", "name": "foo"},
+            {"name": "bar", "ty": "u16"}
+        ]"#)"##,
+        &["(foo, u8)", "(bar, u16)"],
+    )]
+    #[case::json_empty(r#"for name in json("[]")"#, &[])]
+    #[case::trailing_comma(r#"for name in json("[{\"name\": \"foo\"}]",)"#, &["foo"])]
+    fn parsing(#[case] input: &str, #[case] expected: &[&str]) {
+        assert_eq!(parse_rows(input).unwrap(), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[rstest]
+    #[case::toml_missing_key(
+        r##"for (name, ty) in toml(r#"
+            [[rows]]
+            name = "foo"
+            ty = "u8"
+            [[rows]]
+            name = "bar"
+        "#)"##,
+        "Row 2 of the document is missing key \"ty\""
+    )]
+    #[case::json_missing_key(
+        r#"for (name, ty) in json("[{\"name\": \"foo\"}]")"#,
+        "Row 1 of the document is missing key \"ty\""
+    )]
+    #[case::toml_syntax(r#"for name in toml("[[rows]")"#, "Failed to read toml document")]
+    #[case::json_syntax(r#"for name in json("[")"#, "Failed to read json document")]
+    #[case::toml_no_rows(
+        r#"for name in toml("name = \"foo\"")"#,
+        "Failed to read toml document: Expected a document with a single array of tables"
+    )]
+    #[case::json_unsupported_value(
+        r#"for name in json("[{\"name\": [1]}]")"#,
+        "Failed to read json document: Unsupported value of key \"name\" in row 1"
+    )]
+    #[case::nested_alias(
+        r#"for (name, (a, b)) in json("[]")"#,
+        "Nested tuples are not supported by document loop sources"
+    )]
+    #[case::placeholder_alias(
+        r#"for (name, _) in json("[]")"#,
+        "Placeholders are not supported by document loop sources"
+    )]
+    fn parsing_errors(#[case] input: &str, #[case] expected: &str) {
+        let message = parse_rows(input).unwrap_err().to_string();
+        assert!(message.starts_with(expected), "{}", message);
+    }
+
+    #[cfg(not(feature = "serde"))]
+    #[rstest]
+    #[case::toml(
+        r#"for name in toml("")"#,
+        "toml(...) loop source requires \"serde\" feature"
+    )]
+    #[case::json(
+        r#"for name in json("")"#,
+        "json(...) loop source requires \"serde\" feature"
+    )]
+    fn feature_disabled(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(parse_rows(input).unwrap_err().to_string(), expected);
+    }
+}
@@ -0,0 +1,324 @@
+use crate::ast::{Call, Expr, TerminatedExpr, TerminatedValue, Value, ValueKind};
+use crate::core::Type;
+use crate::error::combine_errors;
+use crate::parse::TYPE_NAMES;
+use crate::util::cfg::{env_lookup, eval_cfg_predicate};
+use crate::util::terminated::Terminated;
+use crate::util::token_distance::top_level_token_distance;
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+use syn::parse::discouraged::Speculative;
+use syn::parse::{Parse, ParseStream};
+use syn::token::Paren;
+use syn::{parenthesized, Meta, Token};
+
+/// Name of the function a cast to the given type desugars into.
+fn cast_func_name(type_: &Type) -> &'static str {
+    match type_ {
+        Type::Ident => "to_ident",
+        Type::Path => "to_path",
+        Type::Type => "to_type",
+        Type::Expr => "to_expr",
+        Type::LitStr => "to_str",
+        Type::LitByteStr => "to_byte_str",
+        Type::LitInt => "to_int",
+        Type::LitFloat => "to_float",
+        Type::LitChar => "to_char",
+        Type::LitBool => "to_bool",
+        Type::Lifetime => "to_lifetime",
+        Type::Attr => "to_attr",
+        _ => "to_tokens",
+    }
+}
+
+/// Finds top-level `as <ident>` suffixes that are followed by a terminator (`,` or `;`) or by the
+/// end of the input.
+///
+/// Returns the number of tokens preceding each of the suffixes along with the cast target.
+fn find_cast_suffixes(input: ParseStream) -> Vec<(usize, Ident)> {
+    let mut suffixes = Vec::new();
+    let mut cursor = input.cursor();
+    let mut num_tokens = 0;
+    while let Some((token, next)) = cursor.token_tree() {
+        if matches!(&token, TokenTree::Ident(ident) if ident == "as") {
+            if let Some((TokenTree::Ident(target), after)) = next.token_tree() {
+                let is_terminated = match after.token_tree() {
+                    None => true,
+                    Some((TokenTree::Punct(punct), _)) => matches!(punct.as_char(), ',' | ';'),
+                    Some(_) => false,
+                };
+                if is_terminated {
+                    suffixes.push((num_tokens, target));
+                }
+            }
+        }
+        cursor = next;
+        num_tokens += 1;
+    }
+    suffixes
+}
+
+impl Expr {
+    /// Parses an expression with an `as <type>` suffix desugaring it into a call of the
+    /// corresponding cast function: `foo as str` is equivalent to `to_str(foo)`.
+    ///
+    /// The cast has the lowest precedence - it applies to the whole preceding expression.
+    fn try_parse_cast(input: ParseStream) -> Option<Self> {
+        for (num_tokens, target) in find_cast_suffixes(input) {
+            // `as list` isn't a type - it converts a range to the list of its items.
+            let func_name = if target == "list" {
+                "to_list"
+            } else if let Ok(type_) = syn::parse2::<Type>(target.into_token_stream()) {
+                cast_func_name(&type_)
+            } else {
+                continue;
+            };
+            let fork = input.fork();
+            let mut tokens = TokenStream::new();
+            for _ in 0..num_tokens {
+                tokens.extend([fork.parse::<TokenTree>().ok()?]);
+            }
+            let Ok(expr) = syn::parse2::<Expr>(tokens.clone()) else {
+                continue;
+            };
+            let span = fork.parse::<Token![as]>().ok()?.span;
+            let target = fork.call(Ident::parse_any).ok()?;
+            input.advance_to(&fork);
+
+            let call = Call::new(
+                next_unique_id(),
+                Ident::new(func_name, target.span()),
+                vec![Rc::new(expr)],
+                Some(Rc::new(Expr::from_value(Value::from_raw(tokens)))),
+                span,
+            );
+            return Some(Expr::from_call(call));
+        }
+        None
+    }
+
+    /// Parses a `cfg(<predicate>, <then>, <else>)` expression desugaring it into the selected
+    /// branch.
+    ///
+    /// The predicate is evaluated right away against the environment variables of the proc-macro
+    /// host. The unselected branch is kept as raw tokens - it is neither parsed nor evaluated.
+    fn parse_cfg(input: ParseStream) -> syn::Result<Self> {
+        let keyword = input.call(Ident::parse_any)?;
+        let content;
+        parenthesized!(content in input);
+        let predicate = content.parse::<Meta>()?;
+        content.parse::<Token![,]>()?;
+        let branches = content
+            .parse_terminated(Self::parse_cfg_branch, Token![,])?
+            .into_iter()
+            .collect::<Vec<_>>();
+        let [then_branch, else_branch] = branches.as_slice() else {
+            return Err(syn::Error::new(
+                keyword.span(),
+                "cfg(...) expects a predicate and two branches: cfg(<predicate>, <then>, <else>)",
+            ));
+        };
+
+        let branch = if eval_cfg_predicate(&predicate, &env_lookup)? {
+            then_branch
+        } else {
+            else_branch
+        };
+        syn::parse2::<Expr>(branch.clone())
+    }
+
+    /// Parses a branch of a `cfg(...)` expression as raw tokens.
+    ///
+    /// The extent of the branch is found by parsing it as an expression, so that commas within
+    /// generic arguments (`Result<T, E>`) don't split it. Branches that fail to parse (they are
+    /// only reported if selected) span up to the next comma.
+    fn parse_cfg_branch(input: ParseStream) -> syn::Result<TokenStream> {
+        let fork = input.fork();
+        if fork.parse::<TerminatedExpr<Token![,]>>().is_err() {
+            return Ok(input
+                .parse::<Terminated<TokenStream, Token![,]>>()?
+                .into_value());
+        }
+        let mut tokens = TokenStream::new();
+        for _ in 0..top_level_token_distance(&input.cursor(), &fork.cursor()) {
+            tokens.extend([input.parse::<TokenTree>()?]);
+        }
+        Ok(tokens)
+    }
+
+    /// Reports an unknown cast target if the value turned out to be an unparseable token
+    /// sequence ending with `as <ident>`.
+    fn check_cast_target(value: &Value) -> syn::Result<()> {
+        let ValueKind::Tokens(tokens) = value.kind() else {
+            return Ok(());
+        };
+        let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+        let [.., TokenTree::Ident(as_), TokenTree::Ident(target)] = tokens.as_slice() else {
+            return Ok(());
+        };
+        if as_ != "as" {
+            return Ok(());
+        }
+        Err(syn::Error::new(
+            target.span(),
+            format!(
+                "Unknown cast target type: {}, expected one of: {}",
+                target,
+                TYPE_NAMES.join(", ")
+            ),
+        ))
+    }
+}
+
+/// Just like impl of [`Parse`] for [`Value`] - parses the input either until the end or a comma.
+///
+/// Supports `<expr> as <type>` casts.
+impl Parse for Expr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if let Some(expr) = Self::try_parse_cast(input) {
+            return Ok(expr);
+        }
+        let is_cfg = input
+            .cursor()
+            .ident()
+            .is_some_and(|(ident, _)| ident == "cfg");
+        if is_cfg && input.peek2(Paren) {
+            return Self::parse_cfg(input);
+        }
+
+        let mut errors: Vec<syn::Error> = Vec::new();
+        let fork = input.fork();
+
+        match fork.parse::<Call>() {
+            Ok(func) => {
+                let expr = Expr::from_call(func);
+                input.advance_to(&fork);
+                return Ok(expr);
+            }
+            Err(err) => errors.push(err),
+        }
+
+        match input.parse::<TerminatedValue<Token![,]>>() {
+            Ok(terminated_value) => {
+                let value = terminated_value.into_value();
+                Self::check_cast_target(&value)?;
+                return Ok(Expr::from_value(value));
+            }
+            Err(err) => errors.push(err),
+        }
+
+        Err(combine_errors(
+            "Expected argument or function call (see the errors below)",
+            Span::call_site(),
+            errors,
+        ))
+    }
+}
+
+/// Parses an `Expr` followed by an optional terminator.
+///
+/// This differs from `Terminated<Expr, ...>` in that we let `syn` parse the `Expr`
+/// first (so internal commas are handled properly), and only then we ensure that
+/// either the input ended or the next token is the terminator `Term`. The terminator
+/// is not consumed by this parser.
+impl<Term: Parse> Parse for TerminatedExpr<Term> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        let expr = fork.parse::<Expr>()?;
+
+        let term_fork = fork.fork();
+        if !term_fork.is_empty() {
+            let _ = term_fork.parse::<Term>()?;
+        }
+
+        input.advance_to(&fork);
+        Ok(TerminatedExpr::new(expr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::{Expr, ExprKind};
+    use crate::core::Environment;
+    use proc_macro2::TokenStream;
+    use rstest::rstest;
+    use std::rc::Rc;
+    use syn::parse::{Parse, ParseStream, Parser};
+
+    fn parse_expr(input: &str) -> syn::Result<(Expr, TokenStream)> {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let parser = |input: ParseStream| -> syn::Result<(Expr, TokenStream)> {
+            Ok((Expr::parse(input)?, input.parse::<TokenStream>()?))
+        };
+        parser.parse_str(input)
+    }
+
+    #[rstest]
+    #[case::ident("\"foo\" as ident", "to_ident(\"foo\")", "")]
+    #[case::path("foo as path", "to_path(foo)", "")]
+    #[case::type_("Foo<Bar> as type", "to_type(Foo < Bar >)", "")]
+    #[case::type_with_commas("Result<u32, String> as type", "to_type(Result < u32 , String >)", "")]
+    #[case::expr("1 + 2 as expr", "to_expr(1 + 2)", "")]
+    #[case::str_("some_ident as str", "to_str(some_ident)", "")]
+    #[case::int("\"42\" as int", "to_int(\"42\")", "")]
+    #[case::tokens("foo as tokens", "to_tokens(foo)", "")]
+    #[case::attr("foo as attr", "to_attr(foo)", "")]
+    #[case::list("1..=8 as list", "to_list(1 ..= 8)", "")]
+    #[case::call("upper(foo) as str", "to_str(upper (foo))", "")]
+    #[case::chained("foo as str as ident", "to_ident(foo as str)", "")]
+    #[case::terminated("foo as str, bar", "to_str(foo)", ", bar")]
+    #[case::rust_cast("x as u32", "x as u32", "")]
+    #[case::rust_cast_terminated("x as u32, bar", "x as u32", ", bar")]
+    fn cast_parsing(#[case] input: &str, #[case] expected_expr: &str, #[case] expected_rest: &str) {
+        let (expr, rest) = parse_expr(input).unwrap();
+        assert_eq!(expr.to_string(), expected_expr);
+        assert_eq!(rest.to_string(), expected_rest);
+    }
+
+    #[rstest]
+    fn cast_desugars_into_call() {
+        let (expr, _) = parse_expr("foo as str").unwrap();
+        let ExprKind::FuncCallExpr(call) = expr.kind() else {
+            panic!("Expected a function call, got: {}", expr);
+        };
+        assert_eq!(call.name().to_string(), "to_str");
+        assert_eq!(call.raw_args().len(), 1);
+    }
+
+    #[rstest]
+    fn unknown_cast_target() {
+        let err = parse_expr("Foo<Bar> as typ").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("typ"), "{}", message);
+        assert!(
+            message.contains("expected one of: ident, path"),
+            "{}",
+            message
+        );
+    }
+
+    #[rstest]
+    #[case::then_branch("cfg(all(), foo, bar)", "foo", "")]
+    #[case::else_branch("cfg(any(), foo, bar)", "bar", "")]
+    #[case::call_branch("cfg(all(), upper(foo), bar), baz", "upper(foo)", ", baz")]
+    #[case::unselected_garbage("cfg(all(), foo, undefined_func(,))", "foo", "")]
+    #[case::trailing_comma("cfg(all(), foo, bar,)", "foo", "")]
+    #[case::plain_ident("cfg", "cfg", "")]
+    fn cfg_parsing(#[case] input: &str, #[case] expected_expr: &str, #[case] expected_rest: &str) {
+        let (expr, rest) = parse_expr(input).unwrap();
+        assert_eq!(expr.to_string(), expected_expr);
+        assert_eq!(rest.to_string(), expected_rest);
+    }
+
+    #[rstest]
+    #[case::missing_else("cfg(all(), foo)")]
+    #[case::extra_branch("cfg(all(), foo, bar, baz)")]
+    #[case::unsupported_predicate("cfg(unix, foo, bar)")]
+    #[case::missing_predicate("cfg()")]
+    fn cfg_parsing_errors(#[case] input: &str) {
+        assert!(parse_expr(input).is_err());
+    }
+}
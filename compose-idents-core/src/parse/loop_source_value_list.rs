@@ -0,0 +1,382 @@
+use crate::ast::{Expr, LoopSourceValue, LoopSourceValueList, Tuple};
+use crate::error::combine_errors;
+use crate::util::unique_id::next_unique_id;
+use syn::parse::{discouraged::Speculative, Parse, ParseStream};
+use syn::token::{Brace, Bracket, Paren};
+use syn::{braced, bracketed, parenthesized, LitStr, Token};
+
+/// Keyword of the loop source pairing the elements of multiple lists - `zip([...], [...])`.
+pub const ZIP_KEYWORD: &str = "zip";
+
+/// Checks whether the input starts with a zipped loop source - `zip(...)`.
+pub fn peek_zip(input: ParseStream) -> bool {
+    let is_keyword = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == ZIP_KEYWORD);
+    is_keyword && input.peek2(Paren)
+}
+
+/// Keyword of the loop source iterating over the fragments of a string - `split("a,b", ",")`.
+pub const SPLIT_KEYWORD: &str = "split";
+
+/// Checks whether the input starts with a split loop source - `split(...)`.
+pub fn peek_split(input: ParseStream) -> bool {
+    let is_keyword = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == SPLIT_KEYWORD);
+    is_keyword && input.peek2(Paren)
+}
+
+/// Keyword of the loop source pairing the elements of a list with their indices -
+/// `enumerate([...])`.
+pub const ENUMERATE_KEYWORD: &str = "enumerate";
+
+/// Checks whether the input starts with an enumerated loop source - `enumerate(...)`.
+pub fn peek_enumerate(input: ParseStream) -> bool {
+    let is_keyword = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == ENUMERATE_KEYWORD);
+    is_keyword && input.peek2(Paren)
+}
+
+/// Parses a split loop source - `split("red,green,blue", ",")`.
+///
+/// The string is split into fragments during the expand phase.
+pub fn parse_split(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    input.parse::<syn::Ident>()?;
+    let content;
+    parenthesized!(content in input);
+    let string = content.parse::<LitStr>()?;
+    content.parse::<Token![,]>()?;
+    let separator = content.parse::<LitStr>()?;
+    content.parse::<Option<Token![,]>>()?;
+    if !content.is_empty() {
+        return Err(content.error("split(...) expects a string and a separator"));
+    }
+    Ok(LoopSourceValueList::from_split(
+        next_unique_id(),
+        string,
+        separator,
+        span,
+    ))
+}
+
+/// Parses a list zipped by `zip(...)` - a list literal, an alias bound to a list or a split
+/// string.
+fn parse_zipped_list(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    if peek_split(input) {
+        return parse_split(input);
+    }
+    if input.peek(syn::Ident) {
+        return Ok(LoopSourceValueList::from_alias(
+            next_unique_id(),
+            input.parse::<syn::Ident>()?,
+        ));
+    }
+    input.parse::<LoopSourceValueList>()
+}
+
+/// Parses a zipped loop source - `zip([x, y], [1, 2])`.
+///
+/// The elements of the lists are paired positionally during the expand phase.
+pub fn parse_zip(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    input.parse::<syn::Ident>()?;
+    let content;
+    parenthesized!(content in input);
+    let lists = content
+        .parse_terminated(parse_zipped_list, Token![,])?
+        .into_iter()
+        .collect::<Vec<_>>();
+    if lists.len() < 2 {
+        return Err(syn::Error::new(span, "zip(...) expects at least two lists"));
+    }
+    Ok(LoopSourceValueList::from_zip(next_unique_id(), lists, span))
+}
+
+/// Keyword of the loop source removing duplicate values of a list - `unique([...])`.
+pub const UNIQUE_KEYWORD: &str = "unique";
+
+/// Checks whether the input starts with a deduplicated loop source - `unique(...)`.
+pub fn peek_unique(input: ParseStream) -> bool {
+    let is_keyword = input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident == UNIQUE_KEYWORD);
+    is_keyword && input.peek2(Paren)
+}
+
+/// Parses the single list wrapped by `enumerate(...)` or `unique(...)` - a list literal, an
+/// alias, `split(...)`, `zip(...)` or another wrapper.
+fn parse_wrapped_list(input: ParseStream, keyword: &str) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    input.parse::<syn::Ident>()?;
+    let content;
+    parenthesized!(content in input);
+    let error = format!("{}(...) expects a single list", keyword);
+    if content.is_empty() {
+        return Err(syn::Error::new(span, error));
+    }
+    let list = if peek_zip(&content) {
+        parse_zip(&content)?
+    } else if peek_enumerate(&content) {
+        parse_enumerate(&content)?
+    } else if peek_unique(&content) {
+        parse_unique(&content)?
+    } else {
+        parse_zipped_list(&content)?
+    };
+    content.parse::<Option<Token![,]>>()?;
+    if !content.is_empty() {
+        return Err(content.error(error));
+    }
+    Ok(list)
+}
+
+/// Parses an enumerated loop source - `enumerate([foo, bar])`.
+///
+/// The elements of the list are paired with their indices during the expand phase.
+pub fn parse_enumerate(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    let list = parse_wrapped_list(input, ENUMERATE_KEYWORD)?;
+    Ok(LoopSourceValueList::from_enumerate(
+        next_unique_id(),
+        list,
+        span,
+    ))
+}
+
+/// Parses a deduplicated loop source - `unique([foo, bar, foo])`.
+///
+/// Duplicates are removed during the expand phase.
+pub fn parse_unique(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    let list = parse_wrapped_list(input, UNIQUE_KEYWORD)?;
+    Ok(LoopSourceValueList::from_unique(
+        next_unique_id(),
+        list,
+        span,
+    ))
+}
+
+impl Parse for LoopSourceValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut errors: Vec<syn::Error> = Vec::new();
+
+        let fork = input.fork();
+        if fork.peek(Paren) {
+            match fork.parse::<Tuple<Expr>>() {
+                Ok(tuple) => {
+                    input.advance_to(&fork);
+
+                    return Ok(LoopSourceValue::from_tuple(tuple));
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        let fork = input.fork();
+        match fork.parse::<Expr>() {
+            Ok(expr) => {
+                input.advance_to(&fork);
+
+                return Ok(LoopSourceValue::from_value(expr));
+            }
+            Err(err) => errors.push(err),
+        }
+
+        Err(combine_errors(
+            "Failed to parse loop source value (see errors below)",
+            input.span(),
+            errors,
+        ))
+    }
+}
+
+impl Parse for LoopSourceValueList {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        // `[...]` is the canonical form, `{...}` and `(...)` are accepted interchangeably.
+        let content;
+        if input.peek(Bracket) {
+            bracketed!(content in input);
+        } else if input.peek(Brace) {
+            braced!(content in input);
+        } else if input.peek(Paren) {
+            parenthesized!(content in input);
+        } else {
+            return Err(input.error("Expected a list of values in [...], {...} or (...)"));
+        }
+
+        let punctuated = content.parse_terminated(LoopSourceValue::parse, Token![,])?;
+        let source_values: Vec<LoopSourceValue> = punctuated.into_iter().collect();
+
+        Ok(LoopSourceValueList::new(
+            next_unique_id(),
+            source_values,
+            span,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::Expr;
+    use crate::ast::{LoopSourceValue, LoopSourceValueList, Tuple, TupleValueKind};
+    use crate::core::Environment;
+    use crate::parse::{parse_enumerate, parse_split, parse_unique, parse_zip};
+    use rstest::rstest;
+    use std::rc::Rc;
+    use syn::parse::Parser;
+
+    fn format_tuple(tuple: &Tuple<Expr>) -> String {
+        let values = tuple
+            .values()
+            .iter()
+            .map(|value| match value.kind() {
+                TupleValueKind::Value(expr) => expr.to_string(),
+                TupleValueKind::Tuple(tuple) => format_tuple(tuple),
+            })
+            .collect::<Vec<_>>();
+        format!("({})", values.join(", "))
+    }
+
+    #[rstest]
+    #[case::brackets("[foo, bar]", &["foo", "bar"])]
+    #[case::braces("{foo, bar}", &["foo", "bar"])]
+    #[case::parens("(foo, bar)", &["foo", "bar"])]
+    #[case::parens_tuples("((foo, u8), (bar, u16))", &["(foo, u8)", "(bar, u16)"])]
+    #[case::mixed_nesting(
+        "{(foo, [a, b]), (bar, (c, d)), baz}",
+        &["(foo, [a, b])", "(bar, (c, d))", "baz"],
+    )]
+    #[case::empty("{}", &[])]
+    #[case::tuple_generic_shr(
+        "[(foo, Vec<Vec<u8>>), (bar, u8)]",
+        &["(foo, Vec < Vec < u8 > >)", "(bar, u8)"],
+    )]
+    #[case::tuple_generic_comma(
+        "[(foo, Result<Option<T>, E>), (bar, u8)]",
+        &["(foo, Result < Option < T > , E >)", "(bar, u8)"],
+    )]
+    #[case::tuple_generic_shr_assign(
+        "[(foo, Foo<Bar<Baz>>=x), (bar, u8)]",
+        &["(foo, Foo < Bar < Baz >>= x)", "(bar, u8)"],
+    )]
+    #[case::generic_shr("[Vec<Vec<u8>>, Result<Option<T>, E>]", &["Vec < Vec < u8 > >", "Result < Option < T > , E >"])]
+    fn delimiters(#[case] input: &str, #[case] expected: &[&str]) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let list = syn::parse_str::<LoopSourceValueList>(input).unwrap();
+        let values = list
+            .values()
+            .iter()
+            .map(|value| match value {
+                LoopSourceValue::Value(expr) => expr.to_string(),
+                LoopSourceValue::Tuple(tuple) => format_tuple(tuple),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected);
+    }
+
+    #[rstest]
+    #[case::lists("zip([a, b], [1, 2])", 2)]
+    #[case::aliases("zip(names, [1, 2], widths,)", 3)]
+    #[case::split(r#"zip(split("a,b", ","), [1, 2])"#, 2)]
+    #[case::tuples("zip([(a, u8), (b, u16)], {1, 2})", 2)]
+    fn zip(#[case] input: &str, #[case] expected: usize) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let list = parse_zip.parse_str(input).unwrap();
+        assert_eq!(list.zipped().len(), expected);
+        assert!(list.values().is_empty());
+    }
+
+    #[rstest]
+    #[case::single_list("zip([a, b])", "zip(...) expects at least two lists")]
+    #[case::no_lists("zip()", "zip(...) expects at least two lists")]
+    #[case::not_a_list("zip([a, b], 1)", "Expected a list of values in [...], {...} or (...)")]
+    fn zip_errors(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = parse_zip.parse_str(input).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::simple(r#"split("red,green,blue", ",")"#, "red,green,blue", ",")]
+    #[case::trailing_comma(r#"split("a b", " ",)"#, "a b", " ")]
+    fn split(#[case] input: &str, #[case] expected_string: &str, #[case] expected_sep: &str) {
+        let list = parse_split.parse_str(input).unwrap();
+        let (string, separator) = list.split().unwrap();
+        assert_eq!(string.value(), expected_string);
+        assert_eq!(separator.value(), expected_sep);
+        assert!(list.values().is_empty());
+    }
+
+    #[rstest]
+    #[case::missing_separator(r#"split("a,b")"#, "expected `,`")]
+    #[case::ident_argument(r#"split(foo, ",")"#, "expected string literal")]
+    #[case::extra_argument(
+        r#"split("a,b", ",", 1)"#,
+        "split(...) expects a string and a separator"
+    )]
+    fn split_errors(#[case] input: &str, #[case] expected: &str) {
+        let err = parse_split.parse_str(input).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::list("enumerate([a, b])", false)]
+    #[case::alias("enumerate(names,)", false)]
+    #[case::split(r#"enumerate(split("a,b", ","))"#, false)]
+    #[case::zip("enumerate(zip([a, b], [1, 2]))", true)]
+    fn enumerate(#[case] input: &str, #[case] is_zip: bool) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let list = parse_enumerate.parse_str(input).unwrap();
+        let enumerated = list.enumerated().unwrap();
+        assert_eq!(!enumerated.zipped().is_empty(), is_zip);
+        assert!(list.values().is_empty());
+    }
+
+    #[rstest]
+    #[case::no_lists("enumerate()", "enumerate(...) expects a single list")]
+    #[case::two_lists("enumerate([a], [b])", "enumerate(...) expects a single list")]
+    #[case::not_a_list("enumerate(1)", "Expected a list of values in [...], {...} or (...)")]
+    fn enumerate_errors(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = parse_enumerate.parse_str(input).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::list("unique([a, b, a])", false)]
+    #[case::enumerate("unique(enumerate(names))", true)]
+    fn unique(#[case] input: &str, #[case] is_enumerate: bool) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let list = parse_unique.parse_str(input).unwrap();
+        let deduplicated = list.deduplicated().unwrap();
+        assert_eq!(deduplicated.enumerated().is_some(), is_enumerate);
+        assert!(list.values().is_empty());
+    }
+
+    #[rstest]
+    #[case::no_lists("unique()", "unique(...) expects a single list")]
+    #[case::two_lists("unique([a], [b])", "unique(...) expects a single list")]
+    fn unique_errors(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = parse_unique.parse_str(input).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn missing_delimiters() {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let err = syn::parse_str::<LoopSourceValueList>("foo, bar").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expected a list of values in [...], {...} or (...)"
+        );
+    }
+}
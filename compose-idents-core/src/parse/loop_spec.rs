@@ -0,0 +1,30 @@
+use crate::ast::{LoopSpec, LoopSpecItem};
+use crate::parse::{peek_substitution_table, SubstitutionTable};
+use crate::util::unique_id::next_unique_id;
+use syn::parse::{Parse, ParseStream};
+use syn::Token;
+
+impl Parse for LoopSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut loops: Vec<LoopSpecItem> = Vec::new();
+
+        loop {
+            if input.peek(Token![for]) {
+                loops.push(input.parse::<LoopSpecItem>()?);
+            } else if peek_substitution_table(input) {
+                let table = input.parse::<SubstitutionTable>()?;
+                loops.push(table.into_loop_spec_item());
+            } else {
+                break;
+            }
+            // Loops could be optionally separated from each other and from the rest by a comma.
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        if loops.is_empty() {
+            return Err(input.error("Failed to parse any loops"));
+        }
+
+        Ok(LoopSpec::new(next_unique_id(), loops))
+    }
+}
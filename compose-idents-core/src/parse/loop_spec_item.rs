@@ -0,0 +1,110 @@
+use crate::ast::{Expr, LoopAlias, LoopSourceValueList, LoopSpecItem};
+use crate::parse::{
+    parse_data_source, parse_enumerate, parse_split, parse_unique, parse_zip, peek_data_source,
+    peek_directive, peek_enumerate, peek_split, peek_substitution_table, peek_unique, peek_zip,
+    APPLY_ATTRS_KEYWORD, WHEN_KEYWORD,
+};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{TokenStream, TokenTree};
+use std::rc::Rc;
+use syn::parse::{Parse, ParseStream};
+use syn::token::Brace;
+use syn::Token;
+
+/// Checks whether the input continues with something following the loop filter - a separator,
+/// the next loop, a directive, an alias definition or the code block.
+fn peek_filter_end(input: ParseStream) -> bool {
+    input.is_empty()
+        || input.peek(Token![,])
+        || input.peek(Token![;])
+        || input.peek(Token![for])
+        || input.peek(Token![let])
+        || input.peek(Token![#])
+        || input.peek(Brace)
+        || peek_substitution_table(input)
+        || peek_directive(input, "options")
+        || peek_directive(input, APPLY_ATTRS_KEYWORD)
+        || peek_directive(input, WHEN_KEYWORD)
+        || (input.peek(syn::Ident) && input.peek2(Token![=]))
+}
+
+/// Parses the filter condition of a loop - `if <expr>`.
+///
+/// The condition spans up to the next separator, loop, alias definition or the code block.
+fn parse_filter(input: ParseStream) -> syn::Result<Option<Rc<Expr>>> {
+    if !input.peek(Token![if]) {
+        return Ok(None);
+    }
+    let span = input.parse::<Token![if]>()?.span;
+    let mut tokens = TokenStream::new();
+    while !peek_filter_end(input) {
+        tokens.extend([input.parse::<TokenTree>()?]);
+    }
+    if tokens.is_empty() {
+        return Err(syn::Error::new(span, "Expected a condition after `if`"));
+    }
+    Ok(Some(Rc::new(syn::parse2::<Expr>(tokens)?)))
+}
+
+/// Parses a single list of a loop source - a list literal, an alias bound to a list, `zip(...)`,
+/// `split(...)`, `enumerate(...)` or `unique(...)`.
+fn parse_list(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    if peek_zip(input) {
+        parse_zip(input)
+    } else if peek_split(input) {
+        parse_split(input)
+    } else if peek_enumerate(input) {
+        parse_enumerate(input)
+    } else if peek_unique(input) {
+        parse_unique(input)
+    } else if input.peek(syn::Ident) {
+        Ok(LoopSourceValueList::from_alias(
+            next_unique_id(),
+            input.parse::<syn::Ident>()?,
+        ))
+    } else {
+        input.parse::<LoopSourceValueList>()
+    }
+}
+
+/// Parses a loop source consisting of one or more lists concatenated with `+`.
+fn parse_chained_lists(input: ParseStream) -> syn::Result<LoopSourceValueList> {
+    let span = input.span();
+    let list = parse_list(input)?;
+    if !input.peek(Token![+]) {
+        return Ok(list);
+    }
+    let mut lists = vec![list];
+    while input.peek(Token![+]) {
+        input.parse::<Token![+]>()?;
+        lists.push(parse_list(input)?);
+    }
+    Ok(LoopSourceValueList::from_chain(
+        next_unique_id(),
+        lists,
+        span,
+    ))
+}
+
+impl Parse for LoopSpecItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        input.parse::<Token![for]>()?;
+
+        let alias = input.parse::<LoopAlias>()?;
+
+        input.parse::<Token![in]>()?;
+
+        let list = if peek_data_source(input) {
+            parse_data_source(input, &alias)?
+        } else {
+            parse_chained_lists(input)?
+        };
+        let filter = parse_filter(input)?;
+
+        Ok(
+            LoopSpecItem::new(next_unique_id(), Rc::new(alias), Rc::new(list), span)
+                .with_filter(filter),
+        )
+    }
+}
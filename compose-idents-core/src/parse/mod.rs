@@ -50,5 +50,23 @@ mod loop_spec;
 
 pub use loop_spec::*;
 
+mod data_source;
+pub use data_source::*;
+
+mod substitution_table;
+pub use substitution_table::*;
+
 mod compose_item_spec;
 pub use compose_item_spec::*;
+
+mod compose_test;
+pub use compose_test::*;
+
+mod compose_const;
+pub use compose_const::*;
+
+mod compose_expr;
+pub use compose_expr::*;
+
+mod options;
+pub use options::*;
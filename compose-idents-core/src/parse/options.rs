@@ -0,0 +1,484 @@
+use crate::core::{ExpansionOrder, Options, OPTION_NAMES};
+use crate::strings::is_valid_ident_start;
+use crate::util::deprecation::DEPRECATION_WARNING_IDS;
+use std::collections::HashMap;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Paren;
+use syn::{parenthesized, Ident, LitBool, Token};
+
+/// A single `key`, `key = value` or `key(item, ...)` entry of the `options(...)` directive.
+///
+/// The value is either a literal or a word - an identifier (`key = word`).
+struct OptionEntry {
+    key: Ident,
+    value: Option<syn::Lit>,
+    word: Option<Ident>,
+    items: Option<Vec<Ident>>,
+}
+
+impl Parse for OptionEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<Ident>()?;
+        let mut value = None;
+        let mut word = None;
+        let mut items = None;
+        if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(Ident) {
+                word = Some(input.parse::<Ident>()?);
+            } else {
+                value = Some(input.parse::<syn::Lit>()?);
+            }
+        } else if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            let punctuated = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+            items = Some(punctuated.into_iter().collect());
+        }
+        Ok(Self {
+            key,
+            value,
+            word,
+            items,
+        })
+    }
+}
+
+impl OptionEntry {
+    /// Rejects the `key(item, ...)` form for options that don't expect it.
+    fn no_items(&self) -> syn::Result<()> {
+        match &self.items {
+            Some(_) => Err(syn::Error::new(
+                self.key.span(),
+                format!("Option {} doesn't expect a list of items", self.key),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Rejects the `key = word` form for options that expect a literal.
+    fn no_word(&self) -> syn::Result<()> {
+        match &self.word {
+            Some(word) => Err(syn::Error::new(
+                word.span(),
+                format!("Option {} expects a literal value", self.key),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads the value of a flag - either a bare key or a boolean literal.
+    fn flag(&self) -> syn::Result<bool> {
+        self.no_items()?;
+        self.no_word()?;
+        match &self.value {
+            None => Ok(true),
+            Some(syn::Lit::Bool(LitBool { value, .. })) => Ok(*value),
+            Some(lit) => Err(syn::Error::new(
+                lit.span(),
+                format!("Option {} expects a boolean value", self.key),
+            )),
+        }
+    }
+
+    /// Reads an integer value.
+    fn int(&self) -> syn::Result<usize> {
+        self.no_items()?;
+        self.no_word()?;
+        match &self.value {
+            Some(syn::Lit::Int(lit_int)) => lit_int.base10_parse(),
+            Some(lit) => Err(syn::Error::new(
+                lit.span(),
+                format!("Option {} expects an integer value", self.key),
+            )),
+            None => Err(syn::Error::new(
+                self.key.span(),
+                format!("Option {} expects a value: {} = <int>", self.key, self.key),
+            )),
+        }
+    }
+
+    /// Reads a string value.
+    fn str(&self) -> syn::Result<syn::LitStr> {
+        self.no_items()?;
+        self.no_word()?;
+        match &self.value {
+            Some(syn::Lit::Str(lit_str)) => Ok(lit_str.clone()),
+            Some(lit) => Err(syn::Error::new(
+                lit.span(),
+                format!("Option {} expects a string value", self.key),
+            )),
+            None => Err(syn::Error::new(
+                self.key.span(),
+                format!(
+                    "Option {} expects a value: {} = \"...\"",
+                    self.key, self.key
+                ),
+            )),
+        }
+    }
+
+    /// Reads a word that has to be one of `allowed`.
+    fn word(&self, allowed: &[&str]) -> syn::Result<String> {
+        self.no_items()?;
+        let Some(word) = &self.word else {
+            return Err(syn::Error::new(
+                self.key.span(),
+                format!("Option {} expects one of: {}", self.key, allowed.join(", ")),
+            ));
+        };
+        let name = word.to_string();
+        if !allowed.contains(&name.as_str()) {
+            return Err(syn::Error::new(
+                word.span(),
+                format!(
+                    "Unknown value of option {}: {}, expected one of: {}",
+                    self.key,
+                    name,
+                    allowed.join(", ")
+                ),
+            ));
+        }
+        Ok(name)
+    }
+
+    /// Reads a list of items each of which has to be one of `allowed`.
+    fn items(&self, allowed: &[&str]) -> syn::Result<Vec<String>> {
+        let Some(items) = &self.items else {
+            return Err(syn::Error::new(
+                self.key.span(),
+                format!(
+                    "Option {} expects a list of items: {}(...)",
+                    self.key, self.key
+                ),
+            ));
+        };
+        items
+            .iter()
+            .map(|item| {
+                let name = item.to_string();
+                if allowed.contains(&name.as_str()) {
+                    Ok(name)
+                } else {
+                    Err(syn::Error::new(
+                        item.span(),
+                        format!(
+                            "Unknown item of option {}: {}, expected one of: {}",
+                            self.key,
+                            name,
+                            allowed.join(", ")
+                        ),
+                    ))
+                }
+            })
+            .collect()
+    }
+}
+
+impl Parse for Options {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keyword = input.parse::<Ident>()?;
+        if keyword != "options" {
+            return Err(syn::Error::new(keyword.span(), "Expected options(...)"));
+        }
+        let content;
+        parenthesized!(content in input);
+        let entries = Punctuated::<OptionEntry, Token![,]>::parse_terminated(&content)?;
+
+        let mut seen = HashMap::<String, Ident>::new();
+        let mut allow_global_state = false;
+        let mut strict = false;
+        let mut max_combinations = None;
+        let mut deny_deprecated = false;
+        let mut allow_deprecated = Vec::new();
+        let mut trace = false;
+        let mut pretty = false;
+        let mut expand = ExpansionOrder::default();
+        let mut hash_prefix = None;
+        let mut scoped = false;
+        for entry in entries {
+            let name = entry.key.to_string();
+            if let Some(first) = seen.get(&name) {
+                let mut err = syn::Error::new(
+                    entry.key.span(),
+                    format!("Option {} is specified more than once", name),
+                );
+                err.combine(syn::Error::new(
+                    first.span(),
+                    format!("Option {} is first specified here", name),
+                ));
+                return Err(err);
+            }
+            match name.as_str() {
+                "allow_global_state" => allow_global_state = entry.flag()?,
+                "strict" => strict = entry.flag()?,
+                "max_combinations" => max_combinations = Some(entry.int()?),
+                "deny_deprecated" => deny_deprecated = entry.flag()?,
+                "allow_deprecated" => allow_deprecated = entry.items(DEPRECATION_WARNING_IDS)?,
+                "trace" => {
+                    trace = entry.flag()?;
+                    if trace && cfg!(not(feature = "trace")) {
+                        return Err(syn::Error::new(
+                            entry.key.span(),
+                            "Option trace requires \"trace\" feature",
+                        ));
+                    }
+                }
+                "pretty" => {
+                    pretty = entry.flag()?;
+                    if pretty && cfg!(not(feature = "pretty")) {
+                        return Err(syn::Error::new(
+                            entry.key.span(),
+                            "Option pretty requires \"pretty\" feature",
+                        ));
+                    }
+                }
+                "expand" => {
+                    expand = match entry.word(&["before", "after"])?.as_str() {
+                        "after" => ExpansionOrder::After,
+                        _ => ExpansionOrder::Before,
+                    }
+                }
+                "hash_prefix" => {
+                    let lit_str = entry.str()?;
+                    if !is_valid_ident_start(&lit_str.value()) {
+                        return Err(syn::Error::new(
+                            lit_str.span(),
+                            format!(
+                                "Option hash_prefix must be a valid start of an identifier, got: {:?}",
+                                lit_str.value()
+                            ),
+                        ));
+                    }
+                    hash_prefix = Some(lit_str.value());
+                }
+                "scoped" => scoped = entry.flag()?,
+                _ => {
+                    return Err(syn::Error::new(
+                        entry.key.span(),
+                        format!(
+                            "Unknown option: {}, expected one of: {}",
+                            name,
+                            OPTION_NAMES.join(", ")
+                        ),
+                    ))
+                }
+            }
+            seen.insert(name, entry.key);
+        }
+
+        Ok(Options::new(
+            allow_global_state,
+            strict,
+            max_combinations,
+            deny_deprecated,
+            allow_deprecated,
+            trace,
+            pretty,
+            expand,
+            hash_prefix,
+            scoped,
+            Some(keyword.span()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::AliasSpec;
+    use crate::core::{Environment, ExpansionOrder, Options};
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case::empty("options()", false, false, None)]
+    #[case::allow_global_state("options(allow_global_state)", true, false, None)]
+    #[case::trailing_comma("options(allow_global_state,)", true, false, None)]
+    #[case::strict("options(strict)", false, true, None)]
+    #[case::strict_bool("options(strict = false)", false, false, None)]
+    #[case::max_combinations("options(max_combinations = 100)", false, false, Some(100))]
+    #[case::all(
+        "options(strict, max_combinations = 5, allow_global_state = true)",
+        true,
+        true,
+        Some(5)
+    )]
+    fn options_parsing(
+        #[case] input: &str,
+        #[case] allow_global_state: bool,
+        #[case] strict: bool,
+        #[case] max_combinations: Option<usize>,
+    ) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.allow_global_state(), allow_global_state);
+        assert_eq!(options.strict(), strict);
+        assert_eq!(options.max_combinations(), max_combinations);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", false)]
+    #[case::flag("options(deny_deprecated)", true)]
+    #[case::explicit("options(deny_deprecated = false)", false)]
+    fn deny_deprecated_parsing(#[case] input: &str, #[case] expected: bool) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.deny_deprecated(), expected);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", false)]
+    #[case::flag("options(scoped)", true)]
+    #[case::explicit("options(scoped = false)", false)]
+    fn scoped_parsing(#[case] input: &str, #[case] expected: bool) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.scoped(), expected);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", &[])]
+    #[case::empty("options(allow_deprecated())", &[])]
+    #[case::semicolon_separator(
+        "options(allow_deprecated(semicolon_separator,))",
+        &["semicolon_separator"]
+    )]
+    fn allow_deprecated_parsing(#[case] input: &str, #[case] expected: &[&str]) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.allow_deprecated(), expected);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", false)]
+    #[case::disabled("options(trace = false)", false)]
+    #[cfg_attr(feature = "trace", case::flag("options(trace)", true))]
+    fn trace_parsing(#[case] input: &str, #[case] expected: bool) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.trace(), expected);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", false)]
+    #[case::disabled("options(pretty = false)", false)]
+    #[cfg_attr(feature = "pretty", case::flag("options(pretty)", true))]
+    fn pretty_parsing(#[case] input: &str, #[case] expected: bool) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.pretty(), expected);
+    }
+
+    #[rstest]
+    #[case::unknown_option(
+        "options(foo)",
+        &["Unknown option: foo, expected one of: allow_global_state, strict, max_combinations, deny_deprecated, allow_deprecated, trace, pretty, expand, hash_prefix, scoped"],
+    )]
+    #[case::unknown_option_with_value(
+        "options(foo = 1)",
+        &["Unknown option: foo, expected one of: allow_global_state, strict, max_combinations, deny_deprecated, allow_deprecated, trace, pretty, expand, hash_prefix, scoped"],
+    )]
+    #[case::duplicate_option(
+        "options(strict, max_combinations = 1, strict = false)",
+        &[
+            "Option strict is specified more than once",
+            "Option strict is first specified here",
+        ],
+    )]
+    #[case::flag_value("options(strict = 1)", &["Option strict expects a boolean value"])]
+    #[case::int_value(
+        "options(max_combinations = \"1\")",
+        &["Option max_combinations expects an integer value"],
+    )]
+    #[case::missing_value(
+        "options(max_combinations)",
+        &["Option max_combinations expects a value: max_combinations = <int>"],
+    )]
+    #[case::unknown_deprecation(
+        "options(allow_deprecated(semicolon_separator, bracket_syntax))",
+        &["Unknown item of option allow_deprecated: bracket_syntax, expected one of: semicolon_separator, \
+        index_alias_shadowing, duplicate_loop_values"],
+    )]
+    #[case::missing_items(
+        "options(allow_deprecated)",
+        &["Option allow_deprecated expects a list of items: allow_deprecated(...)"],
+    )]
+    #[case::unexpected_items(
+        "options(strict(semicolon_separator))",
+        &["Option strict doesn't expect a list of items"],
+    )]
+    #[cfg_attr(
+        not(feature = "trace"),
+        case::trace_disabled(
+            "options(trace)",
+            &["Option trace requires \"trace\" feature"],
+        )
+    )]
+    #[cfg_attr(
+        not(feature = "pretty"),
+        case::pretty_disabled(
+            "options(pretty)",
+            &["Option pretty requires \"pretty\" feature"],
+        )
+    )]
+    #[case::missing_expand_value(
+        "options(expand)",
+        &["Option expand expects one of: before, after"],
+    )]
+    #[case::unknown_expand_value(
+        "options(expand = later)",
+        &["Unknown value of option expand: later, expected one of: before, after"],
+    )]
+    #[case::hash_prefix_value(
+        "options(hash_prefix = 1)",
+        &["Option hash_prefix expects a string value"],
+    )]
+    #[case::missing_hash_prefix_value(
+        "options(hash_prefix)",
+        &["Option hash_prefix expects a value: hash_prefix = \"...\""],
+    )]
+    #[case::invalid_hash_prefix(
+        "options(hash_prefix = \"gen-\")",
+        &["Option hash_prefix must be a valid start of an identifier, got: \"gen-\""],
+    )]
+    #[case::empty_hash_prefix(
+        "options(hash_prefix = \"\")",
+        &["Option hash_prefix must be a valid start of an identifier, got: \"\""],
+    )]
+    #[case::word_value("options(strict = yes)", &["Option strict expects a literal value"])]
+    #[case::not_options("foo(allow_global_state)", &["Expected options(...)"])]
+    fn options_parsing_errors(#[case] input: &str, #[case] expected: &[&str]) {
+        let err = syn::parse_str::<Options>(input).unwrap_err();
+        let messages = err.into_iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(messages, expected);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", ExpansionOrder::Before)]
+    #[case::before("options(expand = before)", ExpansionOrder::Before)]
+    #[case::after("options(expand = after)", ExpansionOrder::After)]
+    fn expand_parsing(#[case] input: &str, #[case] expected: ExpansionOrder) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.expand(), expected);
+    }
+
+    #[rstest]
+    #[case::absent("options(strict)", "__")]
+    #[case::specified("options(hash_prefix = \"gen_\")", "gen_")]
+    #[case::underscore("options(hash_prefix = \"_\")", "_")]
+    fn hash_prefix_parsing(#[case] input: &str, #[case] expected: &str) {
+        let options = syn::parse_str::<Options>(input).unwrap();
+        assert_eq!(options.hash_prefix(), expected);
+    }
+
+    #[rstest]
+    #[case::options_only("options(allow_global_state)", 0, None)]
+    #[case::options_and_aliases("options(allow_global_state), a = foo, b = bar", 2, Some(true))]
+    #[case::semicolons("options(allow_global_state); a = foo", 1, Some(false))]
+    fn alias_spec_options(
+        #[case] input: &str,
+        #[case] num_items: usize,
+        #[case] is_comma_used: Option<bool>,
+    ) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let spec = syn::parse_str::<AliasSpec>(input).unwrap();
+        assert!(spec.options().allow_global_state());
+        assert_eq!(spec.entries().len(), num_items);
+        assert_eq!(spec.is_comma_used(), is_comma_used);
+    }
+}
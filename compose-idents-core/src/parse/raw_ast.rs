@@ -0,0 +1,298 @@
+use crate::ast::{AliasSpec, LoopSpec, RawAST};
+use crate::parse::{add_deprecation_warnings, parse_loops_and_aliases, MIXING_SEP_ERROR};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Span, TokenTree};
+use std::rc::Rc;
+use syn::parse::{Parse, ParseStream};
+use syn::token::Brace;
+use syn::{Attribute, Block, Token};
+
+/// A separator around the blocks - whether it is a comma and its span.
+type Separator = (bool, Span);
+
+const MISSING_BLOCK_ERROR: &str = r#"expected a block "{...}" after the spec or before it"#;
+
+impl RawAST {
+    /// Parses the leading outer attributes of the invocation.
+    ///
+    /// Doc-comments are left in place since they document the first alias definition - so the
+    /// attributes end at the first doc-comment.
+    fn parse_attrs(input: ParseStream) -> syn::Result<Vec<Attribute>> {
+        let attrs = input
+            .fork()
+            .call(Attribute::parse_outer)?
+            .into_iter()
+            .take_while(|attr| !attr.path().is_ident("doc"))
+            .collect::<Vec<_>>();
+        // An outer attribute consists of exactly two token trees - `#` and `[...]`.
+        for _ in 0..attrs.len() * 2 {
+            input.parse::<TokenTree>()?;
+        }
+        Ok(attrs)
+    }
+
+    /// Parses the loops and the alias spec - both are optional and could be interleaved.
+    fn parse_spec(input: ParseStream) -> syn::Result<(Option<LoopSpec>, Option<AliasSpec>)> {
+        let (loops, spec) = parse_loops_and_aliases(input)?;
+        add_deprecation_warnings(loops.as_ref(), spec.as_ref())?;
+
+        Ok((loops, spec))
+    }
+
+    /// Parses an optional separator between the blocks and the spec or between the blocks.
+    ///
+    /// Returns whether the separator is a comma along with its span.
+    fn parse_separator(input: ParseStream) -> syn::Result<Option<Separator>> {
+        if input.peek(Token![,]) {
+            Ok(Some((true, input.parse::<Token![,]>()?.span)))
+        } else if input.peek(Token![;]) {
+            Ok(Some((false, input.parse::<Token![;]>()?.span)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parses one or more blocks along with the optional separators between them and after them.
+    fn parse_blocks(input: ParseStream) -> syn::Result<(Vec<Block>, Vec<Separator>)> {
+        if !input.peek(Brace) {
+            return Err(input.error(MISSING_BLOCK_ERROR));
+        }
+        let mut blocks = Vec::new();
+        let mut separators = Vec::new();
+        loop {
+            blocks.push(input.parse::<Block>()?);
+            if let Some(separator) = Self::parse_separator(input)? {
+                separators.push(separator);
+            }
+            if !input.peek(Brace) {
+                break;
+            }
+        }
+        Ok((blocks, separators))
+    }
+
+    /// Checks that the separators around the blocks are the same as the one used within the spec.
+    fn check_separators(separators: &[Separator], spec: Option<&AliasSpec>) -> syn::Result<()> {
+        let Some(is_comma_used) = spec.and_then(|spec| spec.is_comma_used()) else {
+            return Ok(());
+        };
+        for (is_comma_sep, span) in separators {
+            if *is_comma_sep != is_comma_used {
+                return Err(syn::Error::new(*span, MIXING_SEP_ERROR));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the block-first order - `{...}, for ... in [...] alias = ...`.
+    fn parse_block_first(input: ParseStream) -> syn::Result<Self> {
+        let (blocks, separators) = Self::parse_blocks(input)?;
+        let (loops, spec) = Self::parse_spec(input)?;
+        Self::check_separators(&separators, spec.as_ref())?;
+
+        if input.peek(Brace) {
+            return Err(input.error("The blocks are already given before the spec"));
+        }
+
+        Ok(RawAST::new(
+            next_unique_id(),
+            loops.map(Rc::new),
+            spec.map(Rc::new),
+            blocks,
+        ))
+    }
+
+    /// Parses everything following the attributes - the spec and the blocks in either order.
+    fn parse_body(input: ParseStream) -> syn::Result<Self> {
+        // The blocks go after the spec canonically, but could also precede it.
+        if input.peek(Brace) {
+            return Self::parse_block_first(input);
+        }
+
+        let (loops, spec) = Self::parse_spec(input)?;
+        let (blocks, separators) = Self::parse_blocks(input)?;
+        Self::check_separators(&separators, spec.as_ref())?;
+
+        Ok(RawAST::new(
+            next_unique_id(),
+            loops.map(Rc::new),
+            spec.map(Rc::new),
+            blocks,
+        ))
+    }
+}
+
+impl Parse for RawAST {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = Self::parse_attrs(input)?;
+        Ok(Self::parse_body(input)?.with_attrs(attrs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::RawAST;
+    use crate::core::Environment;
+    use crate::util::deprecation::DeprecationService;
+    use quote::ToTokens;
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    fn set_globals() {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        DeprecationService::maybe_set_global(DeprecationService::new_rc("compose!: "));
+    }
+
+    #[rstest]
+    #[case::block_last("x = foo, { fn x() {} }", false, true)]
+    #[case::block_first("{ fn x() {} }, x = foo", false, true)]
+    #[case::block_first_trailing_comma("{ fn x() {} }, x = foo,", false, true)]
+    #[case::block_first_semicolons("{ fn x() {} }; x = foo; y = bar", false, true)]
+    #[case::block_first_loops("{ fn x() {} }, for x in [a, b]", true, false)]
+    #[case::block_first_loops_and_aliases(
+        "{ fn y() {} }, for x in [a, b] y = concat(x, _fn)",
+        true,
+        true
+    )]
+    #[case::block_first_no_separator("{ fn x() {} } for x in [a, b]", true, false)]
+    #[case::block_first_options("{ fn x() {} }, options(strict), x = foo", false, true)]
+    #[case::block_only("{ fn x() {} }", false, false)]
+    #[case::documented_alias("/// Docs.\nx = foo, { fn x() {} }", false, true)]
+    #[case::block_first_documented_alias("{ fn x() {} }, /// Docs.\nx = foo", false, true)]
+    #[case::multiple_blocks("x = foo, { fn x() {} }, { fn x() {} }", false, true)]
+    #[case::multiple_blocks_no_separators("for x in [a] { fn x() {} } { fn x() {} }", true, false)]
+    #[case::multiple_blocks_semicolons(
+        "x = concat(foo); { fn x() {} }; { fn x() {} };",
+        false,
+        true
+    )]
+    #[case::multiple_blocks_first("{ fn x() {} }, { fn x() {} }, x = foo", false, true)]
+    #[case::loops_after_aliases("x = foo, for y in [a, b] { fn x() {} }", true, true)]
+    #[case::loops_between_aliases("x = foo, for y in [a, b], z = bar, { fn x() {} }", true, true)]
+    #[case::block_first_loops_after_aliases("{ fn x() {} }, x = foo, for y in [a, b]", true, true)]
+    #[case::loop_filter("for x in [a, b] if ne(x, a) { fn x() {} }", true, false)]
+    #[case::loop_filter_aliases(
+        "for x in [a, b] if ne(x, a) y = concat(x, _fn), { fn y() {} }",
+        true,
+        true
+    )]
+    #[case::loop_filter_nested(
+        "for x in [a, b] if ne(x, a), for y in [c, d] if true { fn x() {} }",
+        true,
+        false
+    )]
+    fn blocks(#[case] input: &str, #[case] has_loops: bool, #[case] has_spec: bool) {
+        set_globals();
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        assert_eq!(raw_ast.loops().is_some(), has_loops);
+        assert_eq!(raw_ast.spec().is_some(), has_spec);
+        let blocks = raw_ast
+            .blocks()
+            .iter()
+            .map(|block| block.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(blocks.len(), input.matches("{ fn ").count());
+        assert!(blocks.iter().all(|block| block.starts_with("{ fn ")));
+    }
+
+    #[rstest]
+    #[case::none("x = foo, { fn x() {} }", 0, false)]
+    #[case::single("#[cfg(test)] x = foo, { fn x() {} }", 1, false)]
+    #[case::multiple("#[cfg(test)] #[allow(dead_code)] x = foo, { fn x() {} }", 2, false)]
+    #[case::block_first("#[cfg(test)] { fn x() {} }, x = foo", 1, false)]
+    #[case::doc_comment("/// Docs.\nx = foo, { fn x() {} }", 0, true)]
+    #[case::doc_comment_after_attrs("#[cfg(test)] /// Docs.\nx = foo, { fn x() {} }", 1, true)]
+    fn attrs(#[case] input: &str, #[case] count: usize, #[case] is_documented: bool) {
+        set_globals();
+        let raw_ast = syn::parse_str::<RawAST>(input).unwrap();
+        assert_eq!(raw_ast.attrs().len(), count);
+        let spec = raw_ast.spec().unwrap();
+        let item = spec.items().next().unwrap();
+        assert_eq!(item.alias().docs().is_some(), is_documented);
+    }
+
+    #[rstest]
+    #[case::missing_block(
+        "x = foo,",
+        r#"unexpected end of input, expected a block "{...}" after the spec or before it"#
+    )]
+    #[case::missing_block_loops(
+        "for x in [a, b]",
+        r#"unexpected end of input, expected a block "{...}" after the spec or before it"#
+    )]
+    #[case::blocks_around_spec(
+        "{ fn x() {} }, x = foo, { fn x() {} }",
+        "The blocks are already given before the spec"
+    )]
+    #[case::mixed_separators_between_blocks(
+        "x = foo, y = bar, { fn x() {} }; { fn y() {} }",
+        r#"Mixing "," and ";" as separators is not allowed"#
+    )]
+    #[case::mixed_separators(
+        "{ fn x() {} }; x = foo, y = bar",
+        r#"Mixing "," and ";" as separators is not allowed"#
+    )]
+    #[case::mixed_separators_around_loops(
+        "x = concat(foo); for y in [a] z = bar, w = baz, { fn x() {} }",
+        r#"Mixing "," and ";" as separators is not allowed"#
+    )]
+    #[case::missing_loop_filter(
+        "for x in [a, b] if { fn x() {} }",
+        "Expected a condition after `if`"
+    )]
+    #[case::options_after_loops(
+        "x = foo, for y in [a] options(strict), z = bar, { fn x() {} }",
+        "The options must go before the alias definitions"
+    )]
+    #[case::when_after_loops(
+        "x = foo, for y in [a] when(true), z = bar, { fn x() {} }",
+        "The when(...) directive must go before the alias definitions"
+    )]
+    fn blocks_errors(#[case] input: &str, #[case] expected: &str) {
+        set_globals();
+        let err = syn::parse_str::<RawAST>(input).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    /// Shadowing of the loop index aliases is reported with a warning - even when deprecations are
+    /// denied, since it isn't a deprecation.
+    #[rstest]
+    #[case::alias(
+        "for x in [a] options(deny_deprecated), __index__ = foo, { fn x() {} }",
+        Some("__index__")
+    )]
+    #[case::loop_alias(
+        "for __index1__ in [a] options(deny_deprecated), x = foo, { fn x() {} }",
+        Some("__index1__")
+    )]
+    #[case::tuple_alias(
+        "for x in [a] options(deny_deprecated), (y, __index__) = (foo, bar), { fn x() {} }",
+        Some("__index__")
+    )]
+    #[case::allowed(
+        "for x in [a] options(deny_deprecated, allow_deprecated(index_alias_shadowing)), \
+        __index__ = foo, { fn x() {} }",
+        None
+    )]
+    #[case::no_loops("options(deny_deprecated), __index__ = foo, { fn x() {} }", None)]
+    fn index_alias_shadowing(#[case] input: &str, #[case] expected_alias: Option<&str>) {
+        set_globals();
+        let scope = DeprecationService::scoped();
+        syn::parse_str::<RawAST>(input).unwrap();
+        let mut block = syn::parse_str::<syn::Block>("{ fn foo() {} }").unwrap();
+        scope.emit(&mut block);
+
+        let tokens = block.to_token_stream().to_string();
+        match expected_alias {
+            Some(alias) => {
+                let note = format!(
+                    "compose!: Alias {} shadows the builtin loop index alias, consider renaming it",
+                    alias
+                );
+                assert!(tokens.contains(&note), "{}", tokens);
+                assert!(!tokens.contains("since"), "{}", tokens);
+            }
+            None => assert!(!tokens.contains("deprecated"), "{}", tokens),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Implements parsing of substitution tables - an alternative loop header compatible with the
+//! tabular syntax of the `duplicate` crate:
+//!
+//! ```plain,ignore
+//! substitutions [
+//!     name    int_type;
+//!     [foo]   [u8];
+//!     [bar]   [u16];
+//! ]
+//! ```
+//!
+//! The table is desugared into `for (name, int_type) in [(foo, u8), (bar, u16)]`.
+use crate::ast::{
+    Alias, Ast, Expr, LoopAlias, LoopSourceValue, LoopSourceValueList, LoopSpecItem, Tuple,
+    TupleValue,
+};
+use crate::util::unique_id::next_unique_id;
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use std::rc::Rc;
+use syn::parse::{Parse, ParseStream};
+use syn::{bracketed, Token};
+
+/// Keyword introducing a substitution table.
+pub const SUBSTITUTIONS_KEYWORD: &str = "substitutions";
+
+/// A substitution table - a header row of aliases followed by rows of their values.
+pub struct SubstitutionTable {
+    header: Vec<Alias>,
+    rows: Vec<(Vec<Expr>, Span)>,
+    span: Span,
+}
+
+/// Parses a single cell of the table - either a token sequence enclosed in `[...]` or `{...}`
+/// or a single token.
+fn parse_cell(input: ParseStream) -> syn::Result<Expr> {
+    let tokens = match input.parse::<TokenTree>()? {
+        TokenTree::Group(group)
+            if matches!(group.delimiter(), Delimiter::Bracket | Delimiter::Brace) =>
+        {
+            group.stream()
+        }
+        token => TokenStream::from(token),
+    };
+    syn::parse2::<Expr>(tokens)
+}
+
+impl Parse for SubstitutionTable {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let keyword = input.parse::<syn::Ident>()?;
+        if keyword != SUBSTITUTIONS_KEYWORD {
+            return Err(syn::Error::new(
+                keyword.span(),
+                "Expected substitutions [...]",
+            ));
+        }
+        let content;
+        bracketed!(content in input);
+
+        let mut header = Vec::new();
+        while !content.is_empty() && !content.peek(Token![;]) {
+            header.push(content.parse::<Alias>()?);
+        }
+        if header.is_empty() {
+            return Err(content.error("Expected at least one substitution identifier"));
+        }
+        if !content.is_empty() {
+            content.parse::<Token![;]>()?;
+        }
+
+        let mut rows = Vec::new();
+        while !content.is_empty() {
+            let row_span = content.span();
+            let mut cells = Vec::new();
+            while !content.is_empty() && !content.peek(Token![;]) {
+                cells.push(parse_cell(&content)?);
+            }
+            if cells.len() != header.len() {
+                return Err(syn::Error::new(
+                    row_span,
+                    format!(
+                        "Row has {} values, but {} substitution identifiers are declared",
+                        cells.len(),
+                        header.len(),
+                    ),
+                ));
+            }
+            rows.push((cells, row_span));
+            if !content.is_empty() {
+                content.parse::<Token![;]>()?;
+            }
+        }
+
+        Ok(Self { header, rows, span })
+    }
+}
+
+impl SubstitutionTable {
+    /// Desugars the table into a loop over its rows.
+    pub fn into_loop_spec_item(self) -> LoopSpecItem {
+        let SubstitutionTable { header, rows, span } = self;
+        let is_simple = header.len() == 1;
+
+        let alias = if is_simple {
+            LoopAlias::from_simple(header.into_iter().next().unwrap())
+        } else {
+            let values = header
+                .into_iter()
+                .map(|alias| {
+                    let span = alias.span();
+                    TupleValue::from_value(next_unique_id(), Rc::new(alias), span)
+                })
+                .collect();
+            LoopAlias::from_tuple(Tuple::new(next_unique_id(), values, span))
+        };
+
+        let values = rows
+            .into_iter()
+            .map(|(cells, row_span)| {
+                if is_simple {
+                    return LoopSourceValue::from_value(cells.into_iter().next().unwrap());
+                }
+                let values = cells
+                    .into_iter()
+                    .map(|expr| {
+                        let span = expr.span();
+                        TupleValue::from_value(next_unique_id(), Rc::new(expr), span)
+                    })
+                    .collect();
+                LoopSourceValue::from_tuple(Tuple::new(next_unique_id(), values, row_span))
+            })
+            .collect();
+        let list = LoopSourceValueList::new(next_unique_id(), values, span);
+
+        LoopSpecItem::new(next_unique_id(), Rc::new(alias), Rc::new(list), span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubstitutionTable;
+    use crate::core::Environment;
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case::single_column("substitutions [ name; foo; bar; ]", "name", &["foo", "bar"])]
+    #[case::multiple_columns(
+        "substitutions [ name int_type; [foo] [u8]; [bar] [u16]; ]",
+        "(name, int_type)",
+        &["(foo, u8)", "(bar, u16)"],
+    )]
+    #[case::bare_cells(
+        "substitutions [ name int_type; foo u8; bar u16 ]",
+        "(name, int_type)",
+        &["(foo, u8)", "(bar, u16)"],
+    )]
+    #[case::complex_cells(
+        "substitutions [ name ty; [foo] [Vec<u8>]; {bar} {concat(a, b)}; ]",
+        "(name, ty)",
+        &["(foo, Vec < u8 >)", "(bar, concat(a, b))"],
+    )]
+    #[case::header_only("substitutions [ name ]", "name", &[])]
+    fn parsing(#[case] input: &str, #[case] expected_alias: &str, #[case] expected_rows: &[&str]) {
+        use crate::ast::{LoopAlias, LoopSourceValue, TupleValueKind};
+
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let item = syn::parse_str::<SubstitutionTable>(input)
+            .unwrap()
+            .into_loop_spec_item();
+
+        let alias = match item.alias().as_ref() {
+            LoopAlias::Simple(alias) => alias.name(),
+            LoopAlias::Tuple(tuple) => format!(
+                "({})",
+                tuple
+                    .iter_recursive()
+                    .map(|alias| alias.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        assert_eq!(alias, expected_alias);
+
+        let rows = item
+            .list()
+            .values()
+            .iter()
+            .map(|value| match value {
+                LoopSourceValue::Value(expr) => expr.to_string(),
+                LoopSourceValue::Tuple(tuple) => format!(
+                    "({})",
+                    tuple
+                        .values()
+                        .iter()
+                        .map(|value| match value.kind() {
+                            TupleValueKind::Value(expr) => expr.to_string(),
+                            TupleValueKind::Tuple(_) => unreachable!(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(rows, expected_rows);
+    }
+
+    #[rstest]
+    #[case::missing_value(
+        "substitutions [ name int_type; [foo] [u8]; [bar]; ]",
+        "Row has 1 values, but 2 substitution identifiers are declared"
+    )]
+    #[case::extra_value(
+        "substitutions [ name; foo bar; ]",
+        "Row has 2 values, but 1 substitution identifiers are declared"
+    )]
+    #[case::empty_header(
+        "substitutions [ ; foo; ]",
+        "Expected at least one substitution identifier"
+    )]
+    fn parsing_errors(#[case] input: &str, #[case] expected: &str) {
+        Environment::maybe_set_global(Rc::new(Environment::new_initialized(1)));
+        let Err(err) = syn::parse_str::<SubstitutionTable>(input) else {
+            panic!("Expected an error");
+        };
+        assert_eq!(err.to_string(), expected);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::core::Type;
+use proc_macro2::Ident;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+
+/// Names of the types that could be referenced by the user (in type annotations and casts).
+pub const TYPE_NAMES: &[&str] = &[
+    "ident", "path", "type", "expr", "str", "byte_str", "int", "float", "char", "bool", "lifetime",
+    "tokens", "attr",
+];
+
+impl Parse for Type {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = Ident::parse_any(input)?;
+        match ident.to_string().as_str() {
+            "ident" => Ok(Type::Ident),
+            "type" => Ok(Type::Type),
+            "path" => Ok(Type::Path),
+            "expr" => Ok(Type::Expr),
+            "str" => Ok(Type::LitStr),
+            "byte_str" => Ok(Type::LitByteStr),
+            "int" => Ok(Type::LitInt),
+            "float" => Ok(Type::LitFloat),
+            "char" => Ok(Type::LitChar),
+            "bool" => Ok(Type::LitBool),
+            "lifetime" => Ok(Type::Lifetime),
+            "tokens" => Ok(Type::Tokens),
+            "attr" => Ok(Type::Attr),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                format!(
+                    "Unknown type: {}, expected one of: {}",
+                    ident,
+                    TYPE_NAMES.join(", ")
+                ),
+            )),
+        }
+    }
+}
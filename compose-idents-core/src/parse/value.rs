@@ -0,0 +1,870 @@
+use crate::ast::{TerminatedValue, Value};
+use crate::util::terminated::Terminated;
+use crate::util::token_distance::top_level_token_distance;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::rc::Rc;
+use syn::parse::discouraged::Speculative;
+use syn::parse::{Parse, ParseBuffer, ParseStream};
+use syn::token::Bracket;
+use syn::{bracketed, Token};
+
+/// A sequence of one or more outer attributes.
+struct OuterAttrs(Vec<syn::Attribute>);
+
+impl Parse for OuterAttrs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        if attrs.is_empty() {
+            return Err(input.error("expected an outer attribute"));
+        }
+        Ok(Self(attrs))
+    }
+}
+
+/// An integer literal with an optional leading minus sign folded into the literal.
+struct SignedLitInt(syn::LitInt);
+
+impl Parse for SignedLitInt {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Token![-]) {
+            return Ok(Self(input.parse()?));
+        }
+        input.parse::<Token![-]>()?;
+        let lit_int = input.parse::<syn::LitInt>()?;
+        Ok(Self(syn::LitInt::new(
+            format!("-{}", lit_int).as_str(),
+            lit_int.span(),
+        )))
+    }
+}
+
+/// A float literal with an optional leading minus sign folded into the literal.
+struct SignedLitFloat(syn::LitFloat);
+
+impl Parse for SignedLitFloat {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Token![-]) {
+            return Ok(Self(input.parse()?));
+        }
+        input.parse::<Token![-]>()?;
+        let lit_float = input.parse::<syn::LitFloat>()?;
+        Ok(Self(syn::LitFloat::new(
+            format!("-{}", lit_float).as_str(),
+            lit_float.span(),
+        )))
+    }
+}
+
+impl<Term: Parse> TerminatedValue<Term> {
+    fn try_parse<'a, T: Parse>(input: &'a ParseStream) -> syn::Result<(usize, ParseBuffer<'a>, T)> {
+        let fork = input.fork();
+        let value = fork.parse::<T>()?;
+        let term_fork = fork.fork();
+        if !term_fork.is_empty() {
+            let _ = term_fork.parse::<Term>()?;
+        }
+        // Values always end at the top level of the input, so counting only top-level tokens is enough
+        // to compare the candidates.
+        let num_tokens = top_level_token_distance(&input.cursor(), &fork.cursor());
+        Ok((num_tokens, fork, value))
+    }
+
+    /// Parses a single-token value (an integer, a float, a string, byte string, char or boolean
+    /// literal, `_`, an identifier or a lifetime) or a negative number that is immediately followed by the terminator or by the
+    /// end of the input.
+    ///
+    /// Such values are the most common and the full speculative parsing is wasteful for them since
+    /// no other candidate could consume more tokens - as long as the terminator can't continue an
+    /// expression (which holds for `,` - the only terminator in use).
+    fn try_parse_simple(input: ParseStream) -> Option<Self> {
+        let fork = input.fork();
+        let value = if fork.peek(syn::LitInt) || fork.peek(Token![-]) && fork.peek2(syn::LitInt) {
+            Value::from_lit_int(fork.parse::<SignedLitInt>().ok()?.0)
+        } else if fork.peek(syn::LitFloat) || fork.peek(Token![-]) && fork.peek2(syn::LitFloat) {
+            Value::from_lit_float(fork.parse::<SignedLitFloat>().ok()?.0)
+        } else if fork.peek(syn::LitStr) {
+            Value::from_lit_str(fork.parse::<syn::LitStr>().ok()?)
+        } else if fork.peek(syn::LitByteStr) {
+            Value::from_lit_byte_str(fork.parse::<syn::LitByteStr>().ok()?)
+        } else if fork.peek(syn::LitChar) {
+            Value::from_lit_char(fork.parse::<syn::LitChar>().ok()?)
+        } else if fork.peek(syn::LitBool) {
+            Value::from_lit_bool(fork.parse::<syn::LitBool>().ok()?)
+        } else if fork.peek(Token![_]) {
+            Value::from_ident(fork.parse::<Token![_]>().ok()?.into())
+        } else if fork.peek(syn::Ident) {
+            Value::from_ident(fork.parse::<syn::Ident>().ok()?)
+        } else if fork.peek(syn::Lifetime) {
+            Value::from_lifetime(fork.parse::<syn::Lifetime>().ok()?)
+        } else {
+            return None;
+        };
+        if !fork.is_empty() && fork.fork().parse::<Term>().is_err() {
+            return None;
+        }
+        input.advance_to(&fork);
+        Some(Self::new(value))
+    }
+
+    /// Parses the contents of an invisible group (produced by `macro_rules!` for `$ty`, `$expr`
+    /// and other fragments) that is followed by the terminator or by the end of the input.
+    ///
+    /// The group is unwrapped, so the fragments are recognized the same way as literal input
+    /// would be. Returns `Ok(None)` if the input doesn't start with such a group.
+    fn try_parse_none_group(input: ParseStream) -> syn::Result<Option<Self>> {
+        let fork = input.fork();
+        let Ok(stream) = fork.step(|cursor| match cursor.token_tree() {
+            Some((TokenTree::Group(group), next)) if group.delimiter() == Delimiter::None => {
+                Ok((group.stream(), next))
+            }
+            _ => Err(cursor.error("expected an invisible group")),
+        }) else {
+            return Ok(None);
+        };
+        if !fork.is_empty() && fork.fork().parse::<Term>().is_err() {
+            return Ok(None);
+        }
+        let Ok(value) = syn::parse2::<Self>(stream) else {
+            return Ok(None);
+        };
+        input.advance_to(&fork);
+        Ok(Some(value))
+    }
+
+    /// Parses a list literal - comma-separated values enclosed in square brackets - that is
+    /// followed by the terminator or by the end of the input.
+    ///
+    /// At least one top-level comma is required, so that bracketed types (`[T]`, `[u8; 4]`) are
+    /// not recognized as lists - such values are re-interpreted as lists only where a list is
+    /// expected (see [`Value::bracketed_list`]). Returns `Ok(None)` if the input doesn't look like a
+    /// list literal.
+    fn try_parse_list(input: ParseStream) -> syn::Result<Option<Self>> {
+        if !input.peek(Bracket) {
+            return Ok(None);
+        }
+        let fork = input.fork();
+        let Ok((items, true)) = Self::parse_list_items(&fork) else {
+            return Ok(None);
+        };
+        if !fork.is_empty() && fork.fork().parse::<Term>().is_err() {
+            return Ok(None);
+        }
+        let value = Value::from_list(items)?;
+        input.advance_to(&fork);
+        Ok(Some(Self::new(value)))
+    }
+
+    /// Parses a bracketed list item of a list literal - unlike a top-level list literal it doesn't
+    /// require a comma, so that `[[a, b], [c]]` is a list of lists. Bracketed types with a length
+    /// (`[u8; 4]`) and `[]` are not recognized as lists.
+    fn try_parse_nested_list(input: ParseStream) -> syn::Result<Option<Value>> {
+        if !input.peek(Bracket) {
+            return Ok(None);
+        }
+        let fork = input.fork();
+        let Ok((items, _)) = Self::parse_list_items(&fork) else {
+            return Ok(None);
+        };
+        if items.is_empty() || !fork.is_empty() && !fork.peek(Token![,]) {
+            return Ok(None);
+        }
+        let value = Value::from_list(items)?;
+        input.advance_to(&fork);
+        Ok(Some(value))
+    }
+
+    /// Parses bracketed comma-separated list items, also tells whether the items were separated
+    /// by at least one top-level comma.
+    pub(crate) fn parse_list_items(input: ParseStream) -> syn::Result<(Vec<Rc<Value>>, bool)> {
+        let content;
+        bracketed!(content in input);
+        // `[u8; 4]` and `[0; 4]` are an array type and an array expression, not a list.
+        let mut rest = content.cursor();
+        while let Some((tree, next)) = rest.token_tree() {
+            if matches!(&tree, TokenTree::Punct(punct) if punct.as_char() == ';') {
+                return Err(content.error("expected comma-separated list items"));
+            }
+            rest = next;
+        }
+        let mut items = Vec::new();
+        let mut has_comma = false;
+        while !content.is_empty() {
+            let item = match TerminatedValue::<Token![,]>::try_parse_nested_list(&content)? {
+                Some(list) => list,
+                None => content.parse::<TerminatedValue<Token![,]>>()?.into_value(),
+            };
+            items.push(Rc::new(item));
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+            has_comma = true;
+        }
+        Ok((items, has_comma))
+    }
+
+    /// Parses the value by trying all supported types and choosing the longest match.
+    fn parse_speculative(input: ParseStream) -> syn::Result<Self> {
+        let mut errors = Vec::new();
+        let mut max_num_tokens = Option::<usize>::None;
+        let mut value = Option::<(ParseBuffer, Value)>::None;
+
+        match Self::try_parse::<SignedLitInt>(&input) {
+            Ok((num_tokens, fork, SignedLitInt(int))) => {
+                max_num_tokens = max_num_tokens
+                    .map(|n| n.max(num_tokens))
+                    .or(Some(num_tokens));
+                value = Some((fork, Value::from_lit_int(int)));
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<SignedLitFloat>(&input) {
+            Ok((num_tokens, fork, SignedLitFloat(float))) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_lit_float(float)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::LitStr>(&input) {
+            Ok((num_tokens, fork, lit_str)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_lit_str(lit_str)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::LitByteStr>(&input) {
+            Ok((num_tokens, fork, lit_byte_str)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_lit_byte_str(lit_byte_str)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::LitChar>(&input) {
+            Ok((num_tokens, fork, lit_char)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_lit_char(lit_char)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::LitBool>(&input) {
+            Ok((num_tokens, fork, lit_bool)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_lit_bool(lit_bool)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<Token![_]>(&input) {
+            Ok((num_tokens, fork, underscore)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_ident(underscore.into())));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::Ident>(&input) {
+            Ok((num_tokens, fork, ident)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_ident(ident)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::Lifetime>(&input) {
+            Ok((num_tokens, fork, lifetime)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_lifetime(lifetime)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::Path>(&input) {
+            Ok((num_tokens, fork, path)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_path(path)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::Type>(&input) {
+            Ok((num_tokens, fork, type_)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_type(type_)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        match Self::try_parse::<syn::Expr>(&input) {
+            Ok((num_tokens, fork, expr)) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    value = Some((fork, Value::from_expr(expr)));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+        // Multiple consecutive attributes form a list.
+        #[allow(unused_assignments)]
+        match Self::try_parse::<OuterAttrs>(&input) {
+            Ok((num_tokens, fork, OuterAttrs(mut attrs))) => {
+                if max_num_tokens.is_none() || num_tokens > max_num_tokens.unwrap() {
+                    max_num_tokens = Some(num_tokens);
+                    let attr_value = match attrs.len() {
+                        1 => Value::from_attr(attrs.remove(0)),
+                        _ => Value::from_list(
+                            attrs
+                                .into_iter()
+                                .map(|attr| Rc::new(Value::from_attr(attr)))
+                                .collect(),
+                        )?,
+                    };
+                    value = Some((fork, attr_value));
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+
+        if value.is_none() {
+            // Falling back to tokens if no other types have been matched.
+            let fork = input.fork();
+            match fork.parse::<Terminated<TokenStream, Term>>() {
+                Ok(terminated) => {
+                    let tokens = terminated.into_value();
+                    value = Some((fork, Value::from_tokens(tokens)));
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        let Some((fork, arg)) = value else {
+            unreachable!()
+        };
+
+        input.advance_to(&fork);
+        Ok(Self::new(arg))
+    }
+}
+
+/// Parses the argument intelligently determining its type.
+///
+/// Tries to parse different types of expressions and chooses the one that consumes the most tokens.
+/// In case of a failure falls back to raw tokens. Simple single-token values and list literals
+/// are recognized upfront without speculative parsing, invisible groups are unwrapped.
+impl<Term: Parse> Parse for TerminatedValue<Term> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if let Some(value) = Self::try_parse_none_group(input)? {
+            return Ok(value);
+        }
+        if let Some(value) = Self::try_parse_list(input)? {
+            return Ok(value);
+        }
+        if let Some(value) = Self::try_parse_simple(input) {
+            return Ok(value);
+        }
+        Self::parse_speculative(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Type;
+    use crate::util::token_stream::TokenStreamExt;
+    use proc_macro2::Group;
+    use quote::ToTokens;
+    use rstest::rstest;
+    use syn::parse::Parser;
+    use syn::Token;
+
+    fn list(item_type: Type) -> Type {
+        Type::List(Box::new(item_type))
+    }
+
+    #[rstest]
+    // Ident.
+    #[case::ident_simple("foo, 42", "foo", Type::Ident, ", 42")]
+    #[case::ident_type("u32, 42", "u32", Type::Ident, ", 42")]
+    #[case::ident_underscore("_, 42", "_", Type::Ident, ", 42")]
+    #[case::ident_camel_case("FooBar, 42", "FooBar", Type::Ident, ", 42")]
+    #[case::ident_with_digits("foo123, 42", "foo123", Type::Ident, ", 42")]
+    #[case::ident_leading_underscore("_foo, 42", "_foo", Type::Ident, ", 42")]
+    #[case::ident_double_underscore("__, 42", "__", Type::Ident, ", 42")]
+    #[case::ident_snake_mixed("foo_bar_123, 42", "foo_bar_123", Type::Ident, ", 42")]
+    #[case::ident_raw_keyword("r#type, 42", "r#type", Type::Ident, ", 42")]
+    #[case::ident_option_plain("Option, 42", "Option", Type::Ident, ", 42")]
+    // Path cases.
+    #[case::path_simple("foo::bar, 42", "foo :: bar", Type::Path, ", 42")]
+    #[case::path_type_generic(
+        "Result<i32, String>, 42",
+        "Result < i32 , String >",
+        Type::Path,
+        ", 42"
+    )]
+    #[case::path_type_generic_turbofish("Vec::<i32>, 42", "Vec :: < i32 >", Type::Path, ", 42")]
+    #[case::path_type_generic_lifetime(
+        "std::slice::Iter<'a, T>, 42",
+        "std :: slice :: Iter < 'a , T >",
+        Type::Path,
+        ", 42"
+    )]
+    #[case::path_generic_nested(
+        "a::b::C<D<E<F>>, G<H>>, 42",
+        "a :: b :: C < D < E < F > > , G < H > >",
+        Type::Path,
+        ", 42"
+    )]
+    #[case::path_generic_shr("Vec<Vec<u8>>, 42", "Vec < Vec < u8 > >", Type::Path, ", 42")]
+    #[case::path_generic_shr_comma(
+        "Result<Option<T>, E>, 42",
+        "Result < Option < T > , E >",
+        Type::Path,
+        ", 42"
+    )]
+    #[case::path_root_crate("crate::foo, 42", "crate :: foo", Type::Path, ", 42")]
+    #[case::path_expr(
+        "Option::<i32>::None, 42",
+        "Option :: < i32 > :: None",
+        Type::Path,
+        ", 42"
+    )]
+    #[case::path_raw_segments(
+        "r#type::r#match::r#dyn, 42",
+        "r#type :: r#match :: r#dyn",
+        Type::Path,
+        ", 42"
+    )]
+    // Type cases.
+    #[case::type_array("[u8; 32], 42", "[u8 ; 32]", Type::Type, ", 42")]
+    #[case::type_array_const_expr("[u8; 1 + 2], 42", "[u8 ; 1 + 2]", Type::Type, ", 42")]
+    #[case::type_never("!, 42", "!", Type::Type, ", 42")]
+    #[case::type_tuple("(u32, u32), 42", "(u32 , u32)", Type::Type, ", 42")]
+    #[case::type_unit("(), 42", "()", Type::Type, ", 42")]
+    #[case::type_paren_wrapped("(T), 42", "(T)", Type::Type, ", 42")]
+    #[case::type_macro_simple("ty!(u32), 42", "ty ! (u32)", Type::Type, ", 42")]
+    #[case::type_macro_nested_args(
+        "ty!(Result<u8, E>), 42",
+        "ty ! (Result < u8 , E >)",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_ref("&'a mut T, 42", "& 'a mut T", Type::Type, ", 42")]
+    #[case::type_fn("fn(i32) -> i32, 42", "fn (i32) -> i32", Type::Type, ", 42")]
+    #[case::type_fn_zero_args("fn(), 42", "fn ()", Type::Type, ", 42")]
+    #[case::type_fn_hrtb(
+        "for<'a> fn(&'a str) -> &'a str, 42",
+        "for < 'a > fn (& 'a str) -> & 'a str",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_fn_extern_c(
+        "extern \"C\" fn(i32) -> i32, 42",
+        "extern \"C\" fn (i32) -> i32",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_dyn_simple("dyn Display, 42", "dyn Display", Type::Type, ", 42")]
+    #[case::type_dyn_bounds(
+        "dyn Iterator<Item = u8> + Send + 'static, 42",
+        "dyn Iterator < Item = u8 > + Send + 'static",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_dyn_hrtb_fn(
+        "dyn for<'a> Fn(&'a T) + Send, 42",
+        "dyn for < 'a > Fn (& 'a T) + Send",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_impl_trait(
+        "impl Iterator<Item=u8>, 42",
+        "impl Iterator < Item = u8 >",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_impl_multiple_bounds(
+        "impl Read + Write + 'a, 42",
+        "impl Read + Write + 'a",
+        Type::Type,
+        ", 42"
+    )]
+    #[case::type_qual_path_assoc(
+        "<T as Iterator>::Item, 42",
+        "< T as Iterator > :: Item",
+        Type::Type,
+        ", 42"
+    )]
+    // Expr cases.
+    #[case::expr_unary_not("!flag, 42", "! flag", Type::Expr, ", 42")]
+    #[case::expr_binary("2 + 2, 42", "2 + 2", Type::Expr, ", 42")]
+    #[case::expr_if_else(
+        "if cond { 1 } else { 0 }, 42",
+        "if cond { 1 } else { 0 }",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_if_let(
+        "if let Some(x) = opt { x } else { 0 }, 42",
+        "if let Some (x) = opt { x } else { 0 }",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_match(
+        "match x { 0 => 1, _ => 2 }, 42",
+        "match x { 0 => 1 , _ => 2 }",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_block("{ let x = 1; x }, 42", "{ let x = 1 ; x }", Type::Expr, ", 42")]
+    #[case::expr_async_block("async { 1 + 2 }, 42", "async { 1 + 2 }", Type::Expr, ", 42")]
+    #[case::expr_unsafe_block(
+        "unsafe { do_something() }, 42",
+        "unsafe { do_something () }",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_call_with_args("foo(1, 2), 42", "foo (1 , 2)", Type::Expr, ", 42")]
+    #[case::expr_method_call("obj.method(1, 2), 42", "obj . method (1 , 2)", Type::Expr, ", 42")]
+    #[case::expr_method_call_turbofish(
+        "obj.method::<T>(x), 42",
+        "obj . method :: < T > (x)",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_await_call(
+        "make_future().await, 42",
+        "make_future () . await",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_array_method_call(
+        "[1, 2, 3].len(), 42",
+        "[1 , 2 , 3] . len ()",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_array_repeat("[0; N], 42", "[0 ; N]", Type::Expr, ", 42")]
+    #[case::expr_array_index("arr[0], 42", "arr [0]", Type::Expr, ", 42")]
+    #[case::expr_tuple_struct_literal("Point(1, 2), 42", "Point (1 , 2)", Type::Expr, ", 42")]
+    #[case::expr_field_access("s.field, 42", "s . field", Type::Expr, ", 42")]
+    #[case::expr_closure("|| 42, 42", "| | 42", Type::Expr, ", 42")]
+    #[case::expr_return("return 5, 42", "return 5", Type::Expr, ", 42")]
+    #[case::expr_pathlike_1("foo::<i32>(), 42", "foo :: < i32 > ()", Type::Expr, ", 42")]
+    #[case::expr_pathlike_2(
+        "Option::<i32>::Some(1), 42",
+        "Option :: < i32 > :: Some (1)",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_macro_chained_method(
+        "vec![1, 2].len(), 42",
+        "vec ! [1 , 2] . len ()",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_path_call_turbofish(
+        "Option::<i32>::Some(1), 42",
+        "Option :: < i32 > :: Some (1)",
+        Type::Expr,
+        ", 42"
+    )]
+    #[case::expr_trait_fn_call("Trait::method(x), 42", "Trait :: method (x)", Type::Expr, ", 42")]
+    // LitChar cases.
+    #[case::litchar("'a', 42", "'a'", Type::LitChar, ", 42")]
+    #[case::litchar_escape("'\\n', 42", "'\\n'", Type::LitChar, ", 42")]
+    #[case::litchar_at_end("'a'", "'a'", Type::LitChar, "")]
+    #[case::expr_litchar_range("'a'..='z', 42", "'a' ..= 'z'", Type::Expr, ", 42")]
+    // Lifetime cases.
+    #[case::lifetime("'a, 42", "'a", Type::Lifetime, ", 42")]
+    #[case::lifetime_static("'static, 42", "'static", Type::Lifetime, ", 42")]
+    #[case::lifetime_at_end("'a", "'a", Type::Lifetime, "")]
+    #[case::type_lifetime_bound("'a + Send, 42", "'a + Send", Type::Type, ", 42")]
+    // LitBool cases.
+    #[case::litbool_true("true, 42", "true", Type::LitBool, ", 42")]
+    #[case::litbool_false("false, 42", "false", Type::LitBool, ", 42")]
+    #[case::litbool_at_end("true", "true", Type::LitBool, "")]
+    #[case::expr_litbool_negation("!true, 42", "! true", Type::Expr, ", 42")]
+    // LitFloat cases.
+    #[case::litfloat("1.5, 42", "1.5", Type::LitFloat, ", 42")]
+    #[case::litfloat_suffix("1.5f32, 42", "1.5f32", Type::LitFloat, ", 42")]
+    #[case::litfloat_exponent("1e-3, 42", "1e-3", Type::LitFloat, ", 42")]
+    #[case::litfloat_negative("-2.5, 42", "- 2.5", Type::LitFloat, ", 42")]
+    #[case::litfloat_at_end("2.75", "2.75", Type::LitFloat, "")]
+    #[case::expr_litfloat_sum("0.5 + 1.0, 42", "0.5 + 1.0", Type::Expr, ", 42")]
+    // LitInt cases.
+    #[case::litint_dec("123, 42", "123", Type::LitInt, ", 42")]
+    #[case::litint_underscore("1_000_000, 42", "1_000_000", Type::LitInt, ", 42")]
+    #[case::litint_hex("0xFF, 42", "0xFF", Type::LitInt, ", 42")]
+    #[case::litint_bin_suffix("0b1010_1010u8, 42", "0b1010_1010u8", Type::LitInt, ", 42")]
+    #[case::litint_suffix_usize("0usize, 42", "0usize", Type::LitInt, ", 42")]
+    #[case::litint_negative("-1, 42", "- 1", Type::LitInt, ", 42")]
+    #[case::litint_negative_hex("-0x10, 42", "- 0x10", Type::LitInt, ", 42")]
+    #[case::litint_negative_expr("-1 + 2, 42", "- 1 + 2", Type::Expr, ", 42")]
+    // LitStr cases.
+    #[case::litstr_simple("\"hello\", 42", "\"hello\"", Type::LitStr, ", 42")]
+    #[case::litstr_escape("\"a \\\"quote\\\"\", 42", "\"a \\\"quote\\\"\"", Type::LitStr, ", 42")]
+    #[case::litstr_unicode("\"😀\", 42", "\"😀\"", Type::LitStr, ", 42")]
+    #[case::litstr_raw("r\"no escapes\", 42", "r\"no escapes\"", Type::LitStr, ", 42")]
+    #[case::litstr_raw_hashes("r#\"he\"llo\"#, 42", "r#\"he\"llo\"#", Type::LitStr, ", 42")]
+    // LitByteStr cases.
+    #[case::litbytestr("b\"abc\", 42", "b\"abc\"", Type::LitByteStr, ", 42")]
+    #[case::litbytestr_escape("b\"a\\0\", 42", "b\"a\\0\"", Type::LitByteStr, ", 42")]
+    #[case::litbytestr_raw("br\"abc\", 42", "br\"abc\"", Type::LitByteStr, ", 42")]
+    // Tokens cases.
+    #[case::tokens_simple(
+        "pub fn foo() -> u32 { 42 }, 42",
+        "pub fn foo () -> u32 { 42 }",
+        Type::Tokens,
+        ", 42"
+    )]
+    #[case::tokens_let_stmt("let x = 1;, 42", "let x = 1 ;", Type::Tokens, ", 42")]
+    #[case::tokens_use_stmt(
+        "use crate::path::Item;, 42",
+        "use crate :: path :: Item ;",
+        Type::Tokens,
+        ", 42"
+    )]
+    #[case::tokens_keyword("fn, 42", "fn", Type::Tokens, ", 42")]
+    #[case::tokens_attr_struct(
+        "#[derive(Debug)] struct S;, 42",
+        "# [derive (Debug)] struct S ;",
+        Type::Tokens,
+        ", 42"
+    )]
+    #[case::tokens_impl_block(
+        "impl Trait for T {}, 42",
+        "impl Trait for T { }",
+        Type::Tokens,
+        ", 42"
+    )]
+    #[case::list_ints("[1, 2, 3], 42", "[1 , 2 , 3]", list(Type::LitInt), ", 42")]
+    #[case::list_idents("[foo, bar], 42", "[foo , bar]", list(Type::Ident), ", 42")]
+    #[case::list_trailing_comma("[foo,], 42", "[foo]", list(Type::Ident), ", 42")]
+    #[case::list_common_type("[foo, bar::baz], 42", "[foo , bar :: baz]", list(Type::Path), ", 42")]
+    #[case::list_mixed_types("[foo, 1], 42", "[foo , 1]", list(Type::Tokens), ", 42")]
+    #[case::list_comma_containing_items(
+        "[Result<u32, ()>, Vec<u8>], 42",
+        "[Result < u32 , () > , Vec < u8 >]",
+        list(Type::Path),
+        ", 42"
+    )]
+    #[case::list_nested(
+        "[[a, b], [c, d]], 42",
+        "[[a , b] , [c , d]]",
+        list(list(Type::Ident)),
+        ", 42"
+    )]
+    #[case::list_nested_mixed_types(
+        "[[a, b], [1, 2]], 42",
+        "[[a , b] , [1 , 2]]",
+        list(list(Type::Tokens)),
+        ", 42"
+    )]
+    #[case::list_nested_ragged(
+        "[[a1, b1], [c1]], 42",
+        "[[a1 , b1] , [c1]]",
+        list(list(Type::Ident)),
+        ", 42"
+    )]
+    #[case::list_nested_single_items(
+        "[[a], [b]], 42",
+        "[[a] , [b]]",
+        list(list(Type::Ident)),
+        ", 42"
+    )]
+    #[case::list_nested_array_types(
+        "[[u8; 4], [u16; 2]], 42",
+        "[[u8 ; 4] , [u16 ; 2]]",
+        list(Type::Type),
+        ", 42"
+    )]
+    #[case::list_at_end("[foo, bar]", "[foo , bar]", list(Type::Ident), "")]
+    #[case::not_list_single_item("[u8], 42", "[u8]", Type::Type, ", 42")]
+    #[case::attr(
+        "#[derive(Debug, Clone)], 42",
+        "# [derive (Debug , Clone)]",
+        Type::Attr,
+        ", 42"
+    )]
+    #[case::attr_sequence(
+        "#[derive(Debug)] #[repr(C)], 42",
+        "[# [derive (Debug)] , # [repr (C)]]",
+        list(Type::Attr),
+        ", 42"
+    )]
+    // `>>=`/`>=` right after closing generics - a near-miss of a type, kept whole.
+    #[case::tokens_generic_shr_assign(
+        "Foo<Bar<Baz>>=x, 42",
+        "Foo < Bar < Baz >>= x",
+        Type::Tokens,
+        ", 42"
+    )]
+    #[case::tokens_generic_ge(
+        "Foo<Bar<Baz>> >= x, 42",
+        "Foo < Bar < Baz >> >= x",
+        Type::Tokens,
+        ", 42"
+    )]
+    #[case::tokens_where_clause("where T: Trait, 42", "where T : Trait", Type::Tokens, ", 42")]
+    #[case::tokens_angle_params_one("<'a>, 42", "<'a >", Type::Tokens, ", 42")]
+    fn terminated_value_parsing(
+        #[case] input: &str,
+        #[case] expected_value: &str,
+        #[case] expected_type: Type,
+        #[case] expected_rest: &str,
+    ) {
+        let parser =
+            |input: ParseStream| -> syn::Result<(TerminatedValue<Token![,]>, TokenStream)> {
+                Ok((
+                    TerminatedValue::<Token![,]>::parse(input)?,
+                    input.parse::<TokenStream>()?,
+                ))
+            };
+        let result = parser.parse_str(input);
+        assert!(result.is_ok(), "Failed to parse input: {:?}", result);
+        let (terminated_value, actual_rest) = result.unwrap();
+        let actual_value = terminated_value.into_value();
+
+        assert_eq!(actual_value.to_token_stream().to_string(), expected_value);
+        assert_eq!(actual_value.type_(), expected_type);
+        assert_eq!(actual_rest.to_string(), expected_rest);
+    }
+
+    /// Values wrapped into invisible groups by `macro_rules!` are recognized the same way as
+    /// literal input.
+    #[rstest]
+    #[case::ident("foo", ", 42", "foo", Type::Ident, ", 42")]
+    #[case::ident_at_end("foo", "", "foo", Type::Ident, "")]
+    #[case::path("Vec<u8>", ", 42", "Vec < u8 >", Type::Path, ", 42")]
+    #[case::type_tuple("(u8, String)", ", 42", "(u8 , String)", Type::Type, ", 42")]
+    #[case::type_ref("&'static str", ", 42", "& 'static str", Type::Type, ", 42")]
+    #[case::expr("1 + 2", ", 42", "1 + 2", Type::Expr, ", 42")]
+    #[case::lit_int("-1", ", 42", "- 1", Type::LitInt, ", 42")]
+    #[case::lit_str("\"foo\"", ", 42", "\"foo\"", Type::LitStr, ", 42")]
+    #[case::list("[a, b]", ", 42", "[a , b]", list(Type::Ident), ", 42")]
+    fn none_group_parsing(
+        #[case] content: &str,
+        #[case] rest: &str,
+        #[case] expected_value: &str,
+        #[case] expected_type: Type,
+        #[case] expected_rest: &str,
+    ) {
+        let group = Group::new(Delimiter::None, content.parse().unwrap());
+        let rest = rest.parse::<TokenStream>().unwrap();
+        let parser =
+            |input: ParseStream| -> syn::Result<(TerminatedValue<Token![,]>, TokenStream)> {
+                Ok((
+                    TerminatedValue::<Token![,]>::parse(input)?,
+                    input.parse::<TokenStream>()?,
+                ))
+            };
+        let (terminated_value, actual_rest) = parser.parse2(quote::quote!(#group #rest)).unwrap();
+        let actual_value = terminated_value.into_value();
+
+        assert!(!matches!(
+            actual_value.to_token_stream().into_iter().next(),
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::None,
+        ));
+        assert_eq!(actual_value.to_token_stream().to_string(), expected_value);
+        assert_eq!(actual_value.type_(), expected_type);
+        assert_eq!(actual_rest.to_string(), expected_rest);
+    }
+
+    /// A fragment followed by other tokens is a part of a larger value.
+    #[test]
+    fn none_group_within_value() {
+        let group = Group::new(Delimiter::None, quote::quote!(u8));
+        let value = syn::parse2::<TerminatedValue<Token![,]>>(quote::quote!(Vec<#group>))
+            .unwrap()
+            .into_value();
+        assert_eq!(value.type_(), Type::Path);
+        assert_eq!(value.to_token_stream().to_string(), "Vec < u8 >");
+    }
+
+    #[rstest]
+    #[case::ident("foo, 42")]
+    #[case::ident_at_end("foo")]
+    #[case::ident_raw("r#type, 42")]
+    #[case::underscore("_, 42")]
+    #[case::lit_int("0b1010_1010u8, 42")]
+    #[case::lit_str("r#\"he\"llo\"#, 42")]
+    #[case::not_simple_path("foo::bar, 42")]
+    #[case::not_simple_call("foo(1, 2), 42")]
+    #[case::not_simple_keyword("fn, 42")]
+    fn simple_value_parsing_matches_speculative(#[case] input: &str) {
+        let parse = |speculative: bool| {
+            let parser =
+                |input: ParseStream| -> syn::Result<(TerminatedValue<Token![,]>, TokenStream)> {
+                    let value = if speculative {
+                        TerminatedValue::<Token![,]>::parse_speculative(input)?
+                    } else {
+                        TerminatedValue::<Token![,]>::parse(input)?
+                    };
+                    Ok((value, input.parse::<TokenStream>()?))
+                };
+            let (value, rest) = parser.parse_str(input).unwrap();
+            let value = value.into_value();
+            (
+                value.to_token_stream().to_string(),
+                value.type_(),
+                rest.to_string(),
+            )
+        };
+
+        assert_eq!(parse(false), parse(true));
+    }
+
+    /// A spec of simple values is parsed without a single speculative parse - each of them takes
+    /// the fast path and yields the same value the speculative parsing would.
+    #[test]
+    fn simple_values_take_fast_path() {
+        let input = (0..500)
+            .map(|i| match i % 3 {
+                0 => format!("ident_{}", i),
+                1 => format!("{}", i),
+                _ => format!("\"str_{}\"", i),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let parser = |input: ParseStream| -> syn::Result<usize> {
+            let mut count = 0;
+            while !input.is_empty() {
+                let speculative = TerminatedValue::<Token![,]>::parse_speculative(&input.fork())?;
+                let Some(fast) = TerminatedValue::<Token![,]>::try_parse_simple(input) else {
+                    return Err(input.error("expected the fast path to be taken"));
+                };
+                let (fast, speculative) = (fast.into_value(), speculative.into_value());
+                assert_eq!(fast.type_(), speculative.type_());
+                assert_eq!(
+                    fast.to_token_stream().to_string(),
+                    speculative.to_token_stream().to_string(),
+                );
+                count += 1;
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+            }
+            Ok(count)
+        };
+
+        assert_eq!(parser.parse_str(&input).unwrap(), 500);
+    }
+
+    #[rstest]
+    fn list_mixing_lists_and_scalars_fails() {
+        let result = syn::parse_str::<TerminatedValue<Token![,]>>("[[a, b], c]");
+        assert!(result.is_err());
+    }
+}
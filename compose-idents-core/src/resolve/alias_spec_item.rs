@@ -0,0 +1,31 @@
+use crate::ast::{AliasSpecItem, ExprKind};
+use crate::core::{Environment, Type};
+use crate::error::Error;
+use crate::resolve::{resolve_annotated_call, Resolve, Scope};
+
+impl Resolve for AliasSpecItem {
+    /// Resolves an [`AliasSpecItem`] by adding its alias to the global scope and checking for
+    /// redefinition of aliases.
+    ///
+    /// An explicit type annotation of the alias takes precedence over the expected type and makes
+    /// the function overloads producing exactly the annotated type preferable.
+    fn resolve(
+        &self,
+        environment: &Environment,
+        scope: &mut Scope,
+        expected_type: Option<&Type>,
+    ) -> Result<(), Error> {
+        let name = self.alias().name();
+        let expr = self.value().expr();
+        match (self.type_annotation(), expr.kind()) {
+            (Some(type_), ExprKind::FuncCallExpr(call)) => {
+                resolve_annotated_call(call, environment, scope, type_)?
+            }
+            (type_annotation, _) => {
+                expr.resolve(environment, scope, type_annotation.or(expected_type))?
+            }
+        }
+        scope.try_add_name(name, self.value().expr())?;
+        Ok(())
+    }
+}
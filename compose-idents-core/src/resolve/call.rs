@@ -0,0 +1,365 @@
+use crate::ast::{Ast, Call, Expr, ExprKind, ValueKind};
+use crate::core::{Environment, Func, Type};
+use crate::error::{internal_error, Error};
+use crate::resolve::{Resolve, Scope};
+use crate::util::log::debug;
+use std::rc::Rc;
+
+/// Reason of a failure to resolve a call.
+///
+/// Deliberately lightweight - mismatches between calls and function candidates are routine during
+/// overload resolution and are discarded as long as some other candidate matches. A user-facing
+/// error is built only if no candidate matches at all.
+enum Mismatch {
+    /// The call is incompatible with the function candidate(s).
+    Incompatible,
+    /// An error that has to be propagated as is.
+    Fatal(Error),
+}
+
+impl From<Error> for Mismatch {
+    fn from(err: Error) -> Self {
+        match err {
+            err @ Error::InternalError(_) => Mismatch::Fatal(err),
+            _ => Mismatch::Incompatible,
+        }
+    }
+}
+
+fn resolve_arg(
+    arg: Rc<Expr>,
+    coercion_cost_multiplier: u32,
+    resolved_coercion_cost: &mut u32,
+    resolved_args: &mut Vec<Rc<Expr>>,
+    environment: &Environment,
+    scope: &mut Scope,
+    expected_type: Option<&Type>,
+) -> Result<(), Mismatch> {
+    debug!(
+        "Resolving argument \"{:?}\" with expected type \"{:?}\"...",
+        arg, expected_type
+    );
+
+    match arg.kind() {
+        ExprKind::ValueExpr(value) => value.resolve(environment, scope, expected_type)?,
+        ExprKind::FuncCallExpr(call) => {
+            resolve_call(call, environment, scope, expected_type, false)?
+        }
+    }
+    let metadata = scope.metadata_mut();
+
+    let arg_coercion_cost = match arg.kind() {
+        ExprKind::ValueExpr(value) => {
+            let Some(value_metadata) = metadata.get_value_metadata(value.id()) else {
+                return Err(Mismatch::Fatal(internal_error!(
+                    "Value metadata is expected be set after resolve call"
+                )));
+            };
+            value_metadata.coercion_cost
+        }
+        ExprKind::FuncCallExpr(call) => {
+            let Some(call_metadata) = metadata.get_call_metadata(call.id()) else {
+                return Err(Mismatch::Fatal(internal_error!(
+                    "Call metadata is expected be set after resolve call"
+                )));
+            };
+            call_metadata.coercion_cost
+        }
+    };
+    *resolved_coercion_cost += arg_coercion_cost * coercion_cost_multiplier;
+    resolved_args.push(arg.clone());
+
+    debug!("Successfully resolved argument.");
+    Ok(())
+}
+
+/// Attempts to resolve a call for a particular function type.
+///
+/// Call resolutions involves:
+///
+///   - Checking call argument and function argument types for compatibility.
+///   - Assigning coercion cost to the call.
+///
+/// Upon success returns a tuple containing the coercion cost and a vector of resolved arguments.
+fn resolve_call_for_func(
+    func: &Func,
+    call: &Call,
+    environment: &Environment,
+    scope: &mut Scope,
+    expected_type: Option<&Type>,
+) -> Result<(u32, Vec<Rc<Expr>>), Mismatch> {
+    debug!(
+        "Resolving call \"{:?}\" for function \"{:?}\" and expected type \"{:?}\"...",
+        call, func, expected_type
+    );
+
+    let mut coercion_cost = 0;
+
+    // Account for coercing the return value.
+    if let Some(expected_type) = expected_type {
+        match Type::coercion_cost(func.out_type(), expected_type) {
+            Some(cost) => {
+                coercion_cost += cost;
+            }
+            None => return Err(Mismatch::Incompatible),
+        }
+    }
+
+    let raw_args = match (
+        func.arg_types(),
+        func.is_variadic(),
+        call.raw_args(),
+        call.raw_tokens(),
+    ) {
+        // Case of a function that takes raw tokens as the input. A single argument is always
+        // expected.
+        ([arg_type], _, _, Some(raw_arg)) if *arg_type == Type::Raw => {
+            debug_assert!(matches!(
+                raw_arg.kind(),
+                ExprKind::ValueExpr(value) if matches!(value.kind(), ValueKind::Raw(_)),
+            ));
+
+            raw_arg.resolve(environment, scope, Some(&Type::Raw))?;
+            return Ok((coercion_cost, vec![raw_arg.clone()]));
+        }
+        // Case of a non-variadic function - compare by the number of arguments.
+        (arg_types, false, args, _) if arg_types.len() == args.len() => args,
+        // Case of a variadic function - compare by the minimum number of arguments, but allow
+        // for extra arguments.
+        (_, true, args, _) if func.min_num_args() <= args.len() => args,
+        _ => return Err(Mismatch::Incompatible),
+    };
+    if !func.accepts_args(raw_args) {
+        return Err(Mismatch::Incompatible);
+    }
+
+    let mut args: Vec<Rc<Expr>> = vec![];
+
+    for (expected_type, arg) in func.non_variadic_arg_types().iter().zip(raw_args.iter()) {
+        resolve_arg(
+            arg.clone(),
+            1,
+            &mut coercion_cost,
+            &mut args,
+            environment,
+            scope,
+            Some(expected_type),
+        )?;
+    }
+
+    if let Some(variadic_arg_type) = func.variadic_arg_type() {
+        for arg in raw_args.iter().skip(func.non_variadic_arg_types().len()) {
+            resolve_arg(
+                arg.clone(),
+                2,
+                &mut coercion_cost,
+                &mut args,
+                environment,
+                scope,
+                Some(&variadic_arg_type),
+            )?;
+        }
+    }
+
+    Ok((coercion_cost, args))
+}
+
+type FuncResolutionMetadata = (u32, Scope, Rc<Func>, Vec<Rc<Expr>>);
+
+/// Resolves a function call by resolving its arguments and binding the call to the most suitable
+/// built-in function.
+///
+/// If `exact` is set - function candidates producing exactly the expected type take precedence
+/// over the ones whose output has to be coerced.
+fn resolve_call(
+    call: &Call,
+    environment: &Environment,
+    scope: &mut Scope,
+    expected_type: Option<&Type>,
+    exact: bool,
+) -> Result<(), Mismatch> {
+    let name = call.name().to_string();
+    debug!("Resolving a call for function: {:?}...", call);
+
+    let func_candidates = match environment.get_func_variants(name.as_str()) {
+        Some(funcs) => funcs,
+        None => {
+            return Err(Mismatch::Fatal(Error::UndefinedFunctionError(
+                name,
+                call.span(),
+            )));
+        }
+    };
+
+    debug!("Found {} candidate func-types...", func_candidates.len());
+    let mut funcs: Vec<FuncResolutionMetadata> = vec![];
+
+    for func in func_candidates {
+        let mut scope_candidate = scope.deep_clone();
+        match resolve_call_for_func(func, call, environment, &mut scope_candidate, expected_type) {
+            Ok((coercion_cost, args)) => {
+                funcs.push((coercion_cost, scope_candidate, func.clone(), args))
+            }
+            Err(Mismatch::Fatal(err)) => return Err(Mismatch::Fatal(err)),
+            Err(Mismatch::Incompatible) => continue,
+        };
+    }
+    // Selecting the function with the lowest coercion cost using additional criteria for
+    // tie-breaking.
+    funcs.sort_by_key(|x| {
+        let inexact = exact && Some(x.2.out_type()) != expected_type;
+        (inexact, x.0, x.2.is_variadic(), x.2.num_args(), x.2.id())
+    });
+
+    let Some((coercion_cost, scope_candidate, func, args)) = funcs.into_iter().next() else {
+        return Err(Mismatch::Incompatible);
+    };
+    if func.uses_global_state() && !environment.options().allow_global_state() {
+        return Err(Mismatch::Fatal(Error::EvalError(
+            format!(
+                "{}() relies on state shared between macro invocations, \
+                acknowledge its caveats with options(allow_global_state)",
+                name,
+            ),
+            call.span(),
+        )));
+    }
+    scope_candidate.metadata_mut().set_call_metadata(
+        call.id(),
+        args,
+        func.clone(),
+        expected_type.unwrap_or(func.out_type()).clone(),
+        coercion_cost,
+    );
+    *scope = scope_candidate;
+
+    Ok(())
+}
+
+/// Resolves a call whose result type is explicitly annotated.
+///
+/// Unlike [`Resolve::resolve`] prefers the overloads producing exactly the annotated type over
+/// the ones whose output would have to be coerced to it.
+pub fn resolve_annotated_call(
+    call: &Call,
+    environment: &Environment,
+    scope: &mut Scope,
+    type_: &Type,
+) -> Result<(), Error> {
+    resolve_call(call, environment, scope, Some(type_), true)
+        .map_err(|mismatch| make_error(call, environment, mismatch))
+}
+
+/// Converts a [`Mismatch`] into a user-facing error.
+fn make_error(call: &Call, environment: &Environment, mismatch: Mismatch) -> Error {
+    match mismatch {
+        Mismatch::Fatal(err) => err,
+        Mismatch::Incompatible => {
+            let pretty_sig = environment
+                .make_pretty_func_sig(&call.name().to_string())
+                .unwrap_or_else(|| "unknown function".to_string());
+            Error::SignatureError(pretty_sig, call.to_string(), call.span())
+        }
+    }
+}
+
+impl Resolve for Call {
+    /// Resolves a function call by resolving its arguments and binding the call to a built-in
+    /// function.
+    fn resolve(
+        &self,
+        environment: &Environment,
+        scope: &mut Scope,
+        expected_type: Option<&Type>,
+    ) -> Result<(), Error> {
+        resolve_call(self, environment, scope, expected_type, false)
+            .map_err(|mismatch| make_error(self, environment, mismatch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_call_for_func, Mismatch};
+    use crate::ast::{Ast, Call};
+    use crate::core::{Environment, Type};
+    use crate::error::ErrorType;
+    use crate::resolve::{Resolve, Scope};
+    use rstest::rstest;
+    use std::rc::Rc;
+
+    fn resolve_signature(input: &str, expected_type: Option<&Type>) -> String {
+        let environment = Rc::new(Environment::new_initialized(1));
+        Environment::maybe_set_global(environment.clone());
+        let call = syn::parse_str::<Call>(input).unwrap();
+        let mut scope = Scope::default();
+        call.resolve(&environment, &mut scope, expected_type)
+            .unwrap();
+        let metadata = scope.metadata_mut();
+        metadata
+            .get_call_metadata(call.id())
+            .unwrap()
+            .func
+            .signature()
+    }
+
+    #[rstest]
+    #[case::idents("concat(foo, _, bar)", None, "concat(ident...+) -> ident")]
+    #[case::ident_and_tokens("concat(foo, _, 1)", None, "concat(ident, tokens...+) -> ident")]
+    #[case::strs("concat(\"foo\", \"bar\")", None, "concat(str...+) -> str")]
+    #[case::ints("concat(1, 2)", None, "concat(int...+) -> int")]
+    #[case::tokens("concat(1, foo)", None, "concat(tokens...+) -> tokens")]
+    #[case::str_and_tokens("concat(\"foo_\", 1)", None, "concat(str, tokens...+) -> str")]
+    #[case::nested("concat(upper(foo), lower(Bar))", None, "concat(ident...+) -> ident")]
+    #[case::path("snake_case(foo::BarBaz<T>)", None, "snake_case(path) -> path")]
+    #[case::ident_over_path("snake_case(FooBar)", Some(&Type::Path), "snake_case(ident) -> ident")]
+    #[case::expected_path("concat(foo, bar)", Some(&Type::Path), "concat(ident...+) -> ident")]
+    #[case::expected_str("concat(\"foo\", \"bar\")", Some(&Type::LitStr), "concat(str...+) -> str")]
+    fn overload_selection(
+        #[case] input: &str,
+        #[case] expected_type: Option<&Type>,
+        #[case] expected_signature: &str,
+    ) {
+        assert_eq!(resolve_signature(input, expected_type), expected_signature);
+    }
+
+    #[rstest]
+    #[case::concat("concat()")]
+    #[case::concat_nested("upper(concat())")]
+    fn min_arity(#[case] input: &str) {
+        let environment = Rc::new(Environment::new_initialized(1));
+        Environment::maybe_set_global(environment.clone());
+        let call = syn::parse_str::<Call>(input).unwrap();
+        let mut scope = Scope::default();
+        let err = call.resolve(&environment, &mut scope, None).unwrap_err();
+        assert_eq!(err.type_(), ErrorType::SignatureError);
+    }
+
+    /// Overloads of nested calls are selected the same way regardless of how many of them there
+    /// are.
+    #[test]
+    fn many_overloaded_calls() {
+        for i in 0..200 {
+            let input = format!(
+                "concat(upper(foo_{i}), concat(1, {i}), lower(concat(\"a\", \"b\")), {i})",
+                i = i
+            );
+            assert_eq!(
+                resolve_signature(&input, None),
+                "concat(ident, tokens...+) -> ident"
+            );
+        }
+    }
+
+    /// Candidates that don't match the call are discarded without building an error.
+    #[test]
+    fn candidate_mismatch_is_lightweight() {
+        let environment = Rc::new(Environment::new_initialized(1));
+        Environment::maybe_set_global(environment.clone());
+        let call = syn::parse_str::<Call>("upper(1, 2)").unwrap();
+        for func in environment.get_func_variants("upper").unwrap() {
+            let mut scope = Scope::default();
+            let result = resolve_call_for_func(func, &call, &environment, &mut scope, None);
+            assert!(matches!(result, Err(Mismatch::Incompatible)));
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::ast::{Ast, Value, ValueKind};
 use crate::core::{Environment, Type};
 use crate::error::Error;
 use crate::resolve::{Resolve, Scope};
+use syn::ext::IdentExt;
 
 impl Resolve for Value {
     /// Resolves a function call by resolving its arguments and binding the call to a built-in
@@ -14,7 +15,7 @@ impl Resolve for Value {
     ) -> Result<(), Error> {
         let mut metadata = scope.metadata_mut();
         let from_type = match self.kind() {
-            ValueKind::Ident(ident) => match scope.get_name(ident.to_string().as_str()) {
+            ValueKind::Ident(ident) => match scope.get_name(ident.unraw().to_string().as_str()) {
                 Some(value) => {
                     let id = value.id();
                     if let Some(metadata) = metadata.get_value_metadata(id) {
@@ -32,6 +33,14 @@ impl Resolve for Value {
             },
             _ => self.type_(),
         };
+        // A bracketed value like `[x]` is a slice type, but where a list is expected it is a list.
+        let from_type = match (&from_type, expected_type) {
+            (Type::List(_), _) => from_type,
+            (_, Some(Type::List(_))) => {
+                self.bracketed_list().map_or(from_type, |list| list.type_())
+            }
+            _ => from_type,
+        };
 
         let expected_type = match expected_type {
             Some(expected_type) => expected_type,
@@ -0,0 +1,730 @@
+//! Library entry points of the expansion pipeline.
+
+use crate::ast::{Ast, ComposeItemSpec, RawAST};
+use crate::core::{Environment, ExpansionOrder, InvocationGuard, DEFAULT_MAX_DEPTH};
+use crate::error::Error;
+use crate::interpreter::Interpreter;
+use crate::parse::{
+    parse_compose_const, parse_compose_expr, parse_compose_static, parse_compose_test,
+};
+use crate::util::attrs::item_attrs_mut;
+use crate::util::deprecation::DeprecationService;
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::cell::RefCell;
+use std::rc::Rc;
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+
+/// Marker the deferred attribute is re-emitted with (see [`Session::defer`]) - the attribute
+/// carrying it expands right away instead of being deferred again.
+const DEFERRED_MARKER: &str = "__compose_item_deferred";
+
+/// State of a single macro invocation.
+///
+/// Internal components access the environment and the deprecation service of the invocation being
+/// expanded through thread-local globals - a session owns them and installs them only for the
+/// duration of an [`expand`]/[`expand_compose`] call, restoring the previous ones afterward. That
+/// makes expansion re-entrant: a procedural macro could expand another specification while
+/// handling its own.
+///
+/// A session also enters the invocation for its lifetime (see [`Environment::enter_invocation`]),
+/// so the expansion fails once the sessions are nested within each other deeper than the limit -
+/// that usually means the expansion re-enters itself. Invocations emitted into the expanded code
+/// aren't nested sessions, they are expanded by the compiler separately.
+pub struct Session {
+    environment: Rc<Environment>,
+    deprecation_service: Rc<RefCell<DeprecationService>>,
+    attr_path: Option<syn::Path>,
+    prefix: String,
+    max_depth: u64,
+    invocation: InvocationGuard,
+}
+
+impl Session {
+    /// Makes a session with an explicit seed of `hash()`/`random()` functions.
+    ///
+    /// `prefix` is prepended to the deprecation warnings - usually the name of the calling macro
+    /// (e.g. `"my_macro!: "`).
+    pub fn new(prefix: impl Into<String>, seed: u64) -> Self {
+        let prefix = prefix.into();
+        Self {
+            environment: Rc::new(Environment::new_initialized(seed)),
+            deprecation_service: DeprecationService::new_rc(prefix.clone()),
+            attr_path: None,
+            prefix,
+            max_depth: DEFAULT_MAX_DEPTH,
+            invocation: Environment::enter_invocation(),
+        }
+    }
+
+    /// Sets the limit of the invocation depth.
+    pub fn with_max_depth(mut self, max_depth: u64) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the path the attribute macro could be referenced by from the expanded code (e.g.
+    /// `::compose_idents::compose_item`) - it is required by `options(expand = after)`.
+    pub fn with_attr_path(mut self, path: syn::Path) -> Self {
+        self.attr_path = Some(path);
+        self
+    }
+
+    /// Makes a session the same way the macros of `compose-idents` do - the seed is unique per
+    /// session unless it is pinned with the `COMPOSE_IDENTS_SEED` environment variable, in which
+    /// case it is derived from the pinned seed and `input`.
+    ///
+    /// The limit of the invocation depth could be overridden with the `COMPOSE_IDENTS_MAX_DEPTH`
+    /// environment variable.
+    pub fn from_input(prefix: impl Into<String>, input: &str) -> Result<Self, Error> {
+        let pinned_seed = Environment::pinned_seed_from_env()?;
+        let max_depth = Environment::max_depth_from_env()?;
+        Ok(Self::new(prefix, Environment::make_seed(pinned_seed, input)).with_max_depth(max_depth))
+    }
+
+    /// Installs the state of the session as the thread-local globals.
+    ///
+    /// Fails if the invocation is nested deeper than the limit.
+    fn enter(&self) -> Result<SessionGuard, Error> {
+        if self.invocation.depth() > self.max_depth {
+            return Err(Error::EvalError(
+                format!(
+                    "{} invocations nested more than {} levels deep - possible self-recursive \
+                    expansion",
+                    self.prefix.trim_end_matches([':', ' ']),
+                    self.max_depth,
+                ),
+                Span::call_site(),
+            ));
+        }
+        Ok(SessionGuard {
+            environment: Environment::replace_global(Some(self.environment.clone())),
+            deprecation_service: DeprecationService::replace_global(Some(
+                self.deprecation_service.clone(),
+            )),
+        })
+    }
+
+    /// Defers the expansion of an attribute-style invocation with `options(expand = after)` by
+    /// re-emitting the attribute beneath the other outer attributes of the item, so that they are
+    /// expanded first.
+    ///
+    /// Returns `None` if there is nothing to defer the expansion after.
+    fn defer(
+        &self,
+        spec: &ComposeItemSpec,
+        spec_tokens: TokenStream,
+        block: &TokenStream,
+    ) -> Result<Option<TokenStream>, Error> {
+        let Some(path) = &self.attr_path else {
+            return Err(Error::EvalError(
+                "Option expand = after isn't supported by this macro".to_string(),
+                spec.span(),
+            ));
+        };
+        let Ok(mut item) = syn::parse2::<syn::Item>(block.clone()) else {
+            return Ok(None);
+        };
+        let Some(attrs) = item_attrs_mut(&mut item) else {
+            return Ok(None);
+        };
+        if attrs.is_empty() {
+            return Ok(None);
+        }
+        // The code generated by a derive is emitted next to the item, so it would never be
+        // processed by the deferred attribute.
+        if let Some(derive) = attrs.iter().find(|attr| attr.path().is_ident("derive")) {
+            return Err(Error::EvalError(
+                "Option expand = after can't defer the expansion after #[derive(...)] - the code \
+                generated by the derive would never be processed, use expand = before instead"
+                    .to_string(),
+                derive.span(),
+            ));
+        }
+        let marker = Ident::new(DEFERRED_MARKER, Span::call_site());
+        attrs.push(syn::parse_quote!(#[#path(#marker, #spec_tokens)]));
+        Ok(Some(item.into_token_stream()))
+    }
+
+    fn execute(&self, parse: impl FnOnce() -> syn::Result<RawAST>) -> Result<TokenStream, Error> {
+        let _guard = self.enter()?;
+        let interpreter = Interpreter::new(self.environment.clone(), DeprecationService::scoped());
+        let args = parse()?;
+        interpreter.execute(args)
+    }
+}
+
+/// Restores the globals that were installed before [`Session::enter`].
+struct SessionGuard {
+    environment: Option<Rc<Environment>>,
+    deprecation_service: Option<Rc<RefCell<DeprecationService>>>,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        Environment::replace_global(self.environment.take());
+        DeprecationService::replace_global(self.deprecation_service.take());
+    }
+}
+
+/// Expands an attribute-style invocation - `spec` is the argument of `#[compose_item(...)]` and
+/// `block` is the annotated item(s).
+///
+/// With `options(expand = after)` the expansion is deferred until the other attributes of the
+/// item are expanded (see [`Session::with_attr_path`]).
+pub fn expand(
+    session: &Session,
+    spec: TokenStream,
+    block: TokenStream,
+) -> Result<TokenStream, Error> {
+    let (is_deferred, spec) = strip_deferred_marker(spec);
+    let _guard = session.enter()?;
+    let compose_item_spec: ComposeItemSpec = syn::parse2(spec.clone())?;
+    let expand_order = compose_item_spec
+        .spec()
+        .map_or(ExpansionOrder::Before, |spec| spec.options().expand());
+    if expand_order == ExpansionOrder::After && !is_deferred {
+        if let Some(item) = session.defer(&compose_item_spec, spec, &block)? {
+            return Ok(item);
+        }
+    }
+    session.execute(|| {
+        let block: syn::Block = syn::parse2(quote!({ #block }))?;
+        Ok(RawAST::from_compose_item_spec(&compose_item_spec, block))
+    })
+}
+
+/// Strips [`DEFERRED_MARKER`] from the spec of a deferred attribute.
+fn strip_deferred_marker(spec: TokenStream) -> (bool, TokenStream) {
+    let mut tokens = spec.clone().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(punct)))
+            if ident == DEFERRED_MARKER && punct.as_char() == ',' =>
+        {
+            (true, tokens.collect())
+        }
+        _ => (false, spec),
+    }
+}
+
+/// Expands a function-like invocation - `input` is the argument of `compose!(...)`.
+pub fn expand_compose(session: &Session, input: TokenStream) -> Result<TokenStream, Error> {
+    session.execute(|| {
+        let raw_ast: RawAST = syn::parse2(input)?;
+        if let Some(spec) = raw_ast.spec() {
+            if spec.options().expand() == ExpansionOrder::After {
+                return Err(syn::Error::new(
+                    spec.options().span().unwrap_or_else(Span::call_site),
+                    "Option expand is only supported by #[compose_item]",
+                ));
+            }
+        }
+        Ok(raw_ast)
+    })
+}
+
+/// Expands a function-like invocation like [`expand_compose`] and formats the output - the
+/// expansion of each loop combination is formatted with `prettyplease` and preceded by a comment
+/// with the values of the loop variables.
+///
+/// Meant for snapshot tests and other tooling - the code block must consist of items.
+#[cfg(feature = "pretty")]
+pub fn expand_compose_pretty(session: &Session, input: TokenStream) -> Result<String, Error> {
+    let _guard = session.enter()?;
+    let interpreter = Interpreter::new(session.environment.clone(), DeprecationService::scoped());
+    let args: RawAST = syn::parse2(input)?;
+    interpreter.execute_pretty(args)
+}
+
+/// Expands a `compose_test!` invocation - same as [`expand_compose`], but each block is wrapped
+/// into a `#[test]` function named after the loop values.
+pub fn expand_compose_test(session: &Session, input: TokenStream) -> Result<TokenStream, Error> {
+    session.execute(|| parse_compose_test.parse2(input))
+}
+
+/// Expands a `compose_const!` invocation - a single `const` item named by an alias expression.
+pub fn expand_compose_const(session: &Session, input: TokenStream) -> Result<TokenStream, Error> {
+    session.execute(|| parse_compose_const.parse2(input))
+}
+
+/// Expands a `compose_static!` invocation - a single `static` item named by an alias expression.
+pub fn expand_compose_static(session: &Session, input: TokenStream) -> Result<TokenStream, Error> {
+    session.execute(|| parse_compose_static.parse2(input))
+}
+
+/// Expands a `compose_expr!` invocation - a single expression with the aliases substituted.
+pub fn expand_compose_expr(session: &Session, input: TokenStream) -> Result<TokenStream, Error> {
+    session.execute(|| parse_compose_expr.parse2(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        expand, expand_compose, expand_compose_const, expand_compose_expr, expand_compose_static,
+        expand_compose_test, Session,
+    };
+    use crate::core::Environment;
+    use crate::error::ErrorType;
+    use crate::util::deprecation::DeprecationService;
+    use proc_macro2::TokenStream;
+    use quote::quote;
+    use rstest::rstest;
+
+    #[test]
+    fn attribute_form() {
+        let session = Session::new("test!: ", 0);
+        let expanded = expand(
+            &session,
+            quote!(for x in [foo, bar] name = concat(x, _fn)),
+            quote!(
+                fn name() -> u32 {
+                    1
+                }
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            expanded.to_string(),
+            quote!(
+                fn foo_fn() -> u32 {
+                    1
+                }
+                fn bar_fn() -> u32 {
+                    1
+                }
+            )
+            .to_string(),
+        );
+    }
+
+    #[test]
+    fn compose_form() {
+        let session = Session::new("test!: ", 0);
+        let expanded = expand_compose(
+            &session,
+            quote!(name = upper(foo), {
+                const name: u32 = 1;
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            expanded.to_string(),
+            quote!(
+                const FOO: u32 = 1;
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn compose_test_form() {
+        let session = Session::new("test!: ", 0);
+        let expanded = expand_compose_test(
+            &session,
+            quote!(
+                for (name, input, expected) in [(empty, "", 0), ("two words", "a b", 3)]
+                for ty in [u8]
+                {
+                    assert_eq!(input.len() as ty, expected);
+                }
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            expanded.to_string(),
+            quote!(
+                #[test]
+                fn test_empty_u8() {
+                    assert_eq!("".len() as u8, 0);
+                }
+                #[test]
+                fn test_two_words_u8() {
+                    assert_eq!("a b".len() as u8, 3);
+                }
+            )
+            .to_string()
+        );
+    }
+
+    #[rstest]
+    #[case::no_loops(quote!(name = foo, { fn name() {} }), "compose_test! expects a loop")]
+    #[case::placeholder(
+        quote!(for (_, input) in [(a, 1)] { assert!(input > 0); }),
+        "compose_test! expects the first element",
+    )]
+    fn compose_test_form_errors(#[case] input: TokenStream, #[case] expected: &str) {
+        let session = Session::new("test!: ", 0);
+        let err = expand_compose_test(&session, input).unwrap_err();
+        assert!(err.to_string().starts_with(expected), "{}", err);
+    }
+
+    #[test]
+    fn compose_const_form() {
+        let session = Session::new("test!: ", 0);
+        let expanded = expand_compose_const(
+            &session,
+            quote!(pub concat(upper(prefix), _LIMIT): u32 = 42; prefix = foo),
+        )
+        .unwrap();
+        assert_eq!(
+            expanded.to_string(),
+            quote!(
+                pub const FOO_LIMIT: u32 = 42;
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn compose_static_form() {
+        let session = Session::new("test!: ", 0);
+        let expanded = expand_compose_static(
+            &session,
+            quote!(
+                #[allow(dead_code)]
+                concat(upper(x), _NAME): &str = "% x %";
+                for x in [foo, bar]
+            ),
+        )
+        .unwrap();
+        assert_eq!(
+            expanded.to_string(),
+            quote!(
+                #[allow(dead_code)]
+                static FOO_NAME: &str = "foo";
+                #[allow(dead_code)]
+                static BAR_NAME: &str = "bar";
+            )
+            .to_string()
+        );
+    }
+
+    #[rstest]
+    #[case::no_name(quote!(: u32 = 1), "expected a name expression")]
+    #[case::no_type(quote!(FOO = 1), "expected `:`")]
+    #[case::no_value(quote!(FOO: u32), "expected `=`")]
+    fn compose_const_form_errors(#[case] input: TokenStream, #[case] expected: &str) {
+        let session = Session::new("test!: ", 0);
+        let err = expand_compose_const(&session, input).unwrap_err();
+        assert!(err.to_string().contains(expected), "{}", err);
+    }
+
+    #[test]
+    fn compose_expr_form() {
+        let session = Session::new("test!: ", 0);
+        let expanded = expand_compose_expr(
+            &session,
+            quote!(getter = concat(get_, name), name = foo, { getter() + 1 }),
+        )
+        .unwrap();
+        assert_eq!(expanded.to_string(), quote!(get_foo() + 1).to_string());
+    }
+
+    #[rstest]
+    #[case::loops(quote!(for x in [a, b] { x() }), "compose_expr! doesn't support loops")]
+    #[case::multiple_blocks(quote!(x = a, { x() }, { x() }), "compose_expr! expects a single")]
+    #[case::statements(
+        quote!(x = a, { let y = x(); y }),
+        "compose_expr! expects a block consisting of a single expression",
+    )]
+    #[case::trailing_semicolon(
+        quote!(x = a, { x(); }),
+        "compose_expr! expects a block consisting of a single expression",
+    )]
+    fn compose_expr_form_errors(#[case] input: TokenStream, #[case] expected: &str) {
+        let session = Session::new("test!: ", 0);
+        let err = expand_compose_expr(&session, input).unwrap_err();
+        assert!(err.to_string().starts_with(expected), "{}", err);
+    }
+
+    #[test]
+    fn globals_restored() {
+        let session = Session::new("test!: ", 0);
+        expand_compose(
+            &session,
+            quote!(name = foo, {
+                fn name() {}
+            }),
+        )
+        .unwrap();
+        assert!(Environment::get_global().is_none());
+        assert!(DeprecationService::get_global().is_none());
+    }
+
+    /// Seeds differ, so the hashes within the same invocation must come from its own session.
+    #[test]
+    fn nested() {
+        let outer = Session::new("outer!: ", 1);
+        let inner = Session::new("inner!: ", 2);
+        let expected_inner = expand_compose(
+            &Session::new("inner!: ", 2),
+            quote!(name = concat(h_, hash(foo)), {
+                fn name() {}
+            }),
+        )
+        .unwrap();
+
+        let _guard = outer.enter().unwrap();
+        let outer_before = expand_compose(
+            &outer,
+            quote!(name = concat(h_, hash(foo)), {
+                fn name() {}
+            }),
+        );
+        let actual_inner = expand_compose(
+            &inner,
+            quote!(name = concat(h_, hash(foo)), {
+                fn name() {}
+            }),
+        )
+        .unwrap();
+        let outer_after = expand_compose(
+            &outer,
+            quote!(name = concat(h_, hash(foo)), {
+                fn name() {}
+            }),
+        );
+
+        assert_eq!(actual_inner.to_string(), expected_inner.to_string());
+        assert_eq!(
+            outer_before.unwrap().to_string(),
+            outer_after.unwrap().to_string(),
+        );
+        assert!(std::rc::Rc::ptr_eq(
+            &Environment::get_global().unwrap(),
+            &outer.environment,
+        ));
+    }
+
+    /// Nested sessions past the limit fail instead of expanding.
+    #[test]
+    fn max_depth() {
+        fn expand_nested(levels: u64) -> Result<TokenStream, crate::error::Error> {
+            let session = Session::new("test!: ", 0).with_max_depth(3);
+            let expanded = expand_compose(
+                &session,
+                quote!(name = foo, {
+                    fn name() {}
+                }),
+            )?;
+            if levels > 1 {
+                return expand_nested(levels - 1);
+            }
+            Ok(expanded)
+        }
+
+        assert!(expand_nested(3).is_ok());
+        let err = expand_nested(4).unwrap_err();
+        assert_eq!(err.type_(), ErrorType::EvalError);
+        assert_eq!(
+            err.to_string(),
+            "EvalError: test! invocations nested more than 3 levels deep - possible self-recursive \
+            expansion",
+        );
+        // The depth is restored once the sessions are dropped.
+        assert!(expand_nested(3).is_ok());
+    }
+
+    /// Sequential expansions of the same input don't count as nested ones.
+    #[test]
+    fn max_depth_sequential() {
+        let input = "name = foo, { fn name() {} }";
+        for _ in 0..40 {
+            let session = Session::from_input("test!: ", input)
+                .unwrap()
+                .with_max_depth(3);
+            assert!(expand_compose(&session, input.parse().unwrap()).is_ok());
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let session = Session::new("test!: ", 0);
+        let err = expand(
+            &session,
+            quote!(= foo),
+            quote!(
+                fn name() {}
+            ),
+        )
+        .unwrap_err();
+        assert_eq!(err.type_(), ErrorType::ParseError);
+        assert_eq!(syn::Error::from(err).to_string(), "unexpected token",);
+    }
+
+    /// Trailing commas are accepted in every comma-separated part of the spec.
+    #[rstest]
+    #[case::loop_values(
+        "for x in [a, b,] name = concat(f_, x), { fn name() {} }",
+        "for x in [a, b] name = concat(f_, x), { fn name() {} }"
+    )]
+    #[case::loop_tuple_values(
+        "for (x, y) in [(a, b,), (c, d)] name = concat(x, y), { fn name() {} }",
+        "for (x, y) in [(a, b), (c, d)] name = concat(x, y), { fn name() {} }"
+    )]
+    #[case::loop_tuple_alias(
+        "for (x, y,) in [(a, b), (c, d)] name = concat(x, y), { fn name() {} }",
+        "for (x, y) in [(a, b), (c, d)] name = concat(x, y), { fn name() {} }"
+    )]
+    #[case::between_loops(
+        "for x in [a], for y in [b] name = concat(x, y), { fn name() {} }",
+        "for x in [a] for y in [b] name = concat(x, y), { fn name() {} }"
+    )]
+    #[case::loop_header(
+        "for x in [a, b], name = concat(f_, x), { fn name() {} }",
+        "for x in [a, b] name = concat(f_, x), { fn name() {} }"
+    )]
+    #[case::loop_header_before_block(
+        "for x in [a, b], { fn x() {} }",
+        "for x in [a, b] { fn x() {} }"
+    )]
+    #[case::substitution_table(
+        "substitutions [x; [a]; [b];], { fn x() {} }",
+        "substitutions [x; [a]; [b]] { fn x() {} }"
+    )]
+    #[case::options_entries(
+        "options(strict,), name = foo, { fn name() {} }",
+        "options(strict), name = foo, { fn name() {} }"
+    )]
+    #[case::options_alone("options(strict), { fn foo() {} }", "{ fn foo() {} }")]
+    #[case::call_args(
+        "name = concat(f_, a,), { fn name() {} }",
+        "name = concat(f_, a), { fn name() {} }"
+    )]
+    #[case::list_value(
+        "names = [a, b,], name = index(names, 1), { fn name() {} }",
+        "names = [a, b], name = index(names, 1), { fn name() {} }"
+    )]
+    #[case::block("name = foo, { fn name() {} },", "name = foo, { fn name() {} }")]
+    fn trailing_commas(#[case] input: &str, #[case] expected: &str) {
+        let expand_str = |input: &str| {
+            let session = Session::new("test!: ", 0);
+            expand_compose(&session, input.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(expand_str(input), expand_str(expected));
+    }
+
+    /// Trailing commas are accepted after every part of the attribute spec.
+    #[rstest]
+    #[case::loops("for x in [a, b],", "for x in [a, b]")]
+    #[case::loops_and_aliases(
+        "for x in [a, b], name = concat(f_, x),",
+        "for x in [a, b] name = concat(f_, x)"
+    )]
+    #[case::options("options(strict),", "options(strict)")]
+    fn trailing_commas_attribute(#[case] spec: &str, #[case] expected: &str) {
+        let expand_str = |spec: &str| {
+            let session = Session::new("test!: ", 0);
+            let block: TokenStream = "fn x() {} fn name() {}".parse().unwrap();
+            expand(&session, spec.parse().unwrap(), block)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(expand_str(spec), expand_str(expected));
+    }
+
+    fn expand_item(spec: &str, item: &str) -> Result<String, crate::error::Error> {
+        let session =
+            Session::new("test!: ", 0).with_attr_path(syn::parse_quote!(::my_crate::compose_item));
+        expand(&session, spec.parse().unwrap(), item.parse().unwrap())
+            .map(|expanded| expanded.to_string())
+    }
+
+    fn tokens(input: &str) -> String {
+        input.parse::<TokenStream>().unwrap().to_string()
+    }
+
+    /// With `expand = after` the attribute is re-emitted beneath the other attributes of the item.
+    #[rstest]
+    #[case::single_attribute(
+        "options(expand = after), name = foo",
+        "#[other] fn name() {}",
+        "#[other] #[::my_crate::compose_item(__compose_item_deferred, options(expand = after), name = foo)] fn name() {}"
+    )]
+    #[case::multiple_attributes(
+        "options(expand = after), name = foo",
+        "#[inline] #[other(name)] fn name() {}",
+        "#[inline] #[other(name)] #[::my_crate::compose_item(__compose_item_deferred, options(expand = after), name = foo)] fn name() {}"
+    )]
+    #[case::no_attributes("options(expand = after), name = foo", "fn name() {}", "fn foo() {}")]
+    #[case::deferred(
+        "__compose_item_deferred, options(expand = after), name = foo",
+        "#[other] fn name() {}",
+        "#[other] fn foo() {}"
+    )]
+    #[case::before(
+        "options(expand = before), name = foo",
+        "#[other(name)] fn name() {}",
+        "#[other(foo)] fn foo() {}"
+    )]
+    fn expand_order(#[case] spec: &str, #[case] item: &str, #[case] expected: &str) {
+        assert_eq!(expand_item(spec, item).unwrap(), tokens(expected));
+    }
+
+    #[rstest]
+    #[case::derive(
+        "options(expand = after), name = foo",
+        "#[derive(Debug)] struct name;",
+        "Option expand = after can't defer the expansion after #[derive(...)]"
+    )]
+    fn expand_order_errors(#[case] spec: &str, #[case] item: &str, #[case] expected: &str) {
+        let err = expand_item(spec, item).unwrap_err();
+        assert_eq!(err.type_(), ErrorType::EvalError);
+        assert!(err.to_string().contains(expected), "{}", err);
+    }
+
+    /// Deferring requires the path of the attribute to re-emit it.
+    #[test]
+    fn expand_after_without_attr_path() {
+        let session = Session::new("test!: ", 0);
+        let err = expand(
+            &session,
+            quote!(options(expand = after), name = foo),
+            quote!(
+                #[other]
+                fn name() {}
+            ),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Option expand = after isn't supported by this macro"));
+    }
+
+    #[test]
+    fn expand_after_compose() {
+        let session = Session::new("test!: ", 0);
+        let err = expand_compose(
+            &session,
+            quote!(options(expand = after), name = foo, {
+                fn name() {}
+            }),
+        )
+        .unwrap_err();
+        assert_eq!(
+            syn::Error::from(err).to_string(),
+            "Option expand is only supported by #[compose_item]"
+        );
+    }
+
+    #[cfg(feature = "pretty")]
+    #[test]
+    fn pretty_compose() {
+        let session = Session::new("test!: ", 0);
+        let formatted = super::expand_compose_pretty(
+            &session,
+            quote!(for x in [foo, bar] name = concat(x, _fn), {
+                fn name() -> u32 { 1 }
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            formatted,
+            "// --- combination 1 (x=foo) ---\nfn foo_fn() -> u32 {\n    1\n}\n\
+             // --- combination 2 (x=bar) ---\nfn bar_fn() -> u32 {\n    1\n}\n",
+        );
+    }
+}
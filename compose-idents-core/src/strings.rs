@@ -0,0 +1,369 @@
+//! Pure string and identifier functions - they don't depend on the rest of the library.
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase, ToSnakeCase, ToTitleCase,
+};
+
+/// Keywords that can't be used as identifiers (unless they are raw identifiers).
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "Self", "self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be used even as raw identifiers.
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "super", "Self"];
+
+/// Converts the input string to uppercase.
+pub fn upper(input: &str) -> String {
+    input.to_uppercase()
+}
+
+/// Converts the input string to lowercase.
+pub fn lower(input: &str) -> String {
+    input.to_lowercase()
+}
+
+/// Converts the first character of the input string to uppercase leaving the rest untouched.
+///
+/// Only the first character produced by [`char::to_uppercase`] is kept.
+pub fn capitalize(input: &str) -> String {
+    map_first_char(input, |char| char.to_uppercase().next().unwrap_or(char))
+}
+
+/// Converts the first character of the input string to lowercase leaving the rest untouched.
+///
+/// Only the first character produced by [`char::to_lowercase`] is kept.
+pub fn decapitalize(input: &str) -> String {
+    map_first_char(input, |char| char.to_lowercase().next().unwrap_or(char))
+}
+
+/// Applies the mapping to the first character of the input string.
+fn map_first_char(input: &str, map: impl Fn(char) -> char) -> String {
+    let mut chars = input.chars();
+    match chars.next() {
+        Some(first) => std::iter::once(map(first)).chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Whether `heck` would treat the character as a word boundary and drop it while it could be a
+/// part of an identifier - e.g. combining marks of Devanagari or connector punctuation (`‿`).
+fn is_dropped_by_heck(char: char) -> bool {
+    char != '_' && !char.is_alphanumeric() && unicode_ident::is_xid_continue(char)
+}
+
+/// Applies a `heck` case conversion.
+///
+/// `heck` splits words by the rules of cased alphabets, so the inputs containing the characters it
+/// would drop are returned as is.
+fn convert_case(input: &str, convert: impl Fn(&str) -> String) -> String {
+    if input.chars().any(is_dropped_by_heck) {
+        return input.to_string();
+    }
+    convert(input)
+}
+
+/// Converts the input string to snake_case.
+pub fn to_snake_case(input: &str) -> String {
+    convert_case(input, ToSnakeCase::to_snake_case)
+}
+
+/// Converts the input string to SHOUTY_SNAKE_CASE.
+pub fn to_shouty_snake_case(input: &str) -> String {
+    convert_case(input, ToShoutySnakeCase::to_shouty_snake_case)
+}
+
+/// Converts the input string to kebab-case.
+pub fn to_kebab_case(input: &str) -> String {
+    convert_case(input, ToKebabCase::to_kebab_case)
+}
+
+/// Converts the input string to Title Case.
+pub fn to_title_case(input: &str) -> String {
+    convert_case(input, ToTitleCase::to_title_case)
+}
+
+/// Converts the input string to camelCase.
+pub fn to_camel_case(input: &str) -> String {
+    convert_case(input, ToLowerCamelCase::to_lower_camel_case)
+}
+
+/// Converts the input string to PascalCase.
+pub fn to_pascal_case(input: &str) -> String {
+    convert_case(input, ToPascalCase::to_pascal_case)
+}
+
+/// Normalizes a string to be a valid Rust identifier.
+///
+/// - Replaces all characters not valid for identifier with underscores - the validity is
+///   determined by the Unicode rules Rust follows (`XID_Start`/`XID_Continue`), so `größe` and
+///   `名前` are preserved.
+/// - Prefixes the result with an underscore if it starts with a digit or another character that
+///   can't start an identifier.
+/// - Ensures there are no consecutive underscores when generated from invalid characters.
+/// - Any consecutive underscores already in the input are preserved.
+/// - Redundant leading and trailing underscores (generated or original) are stripped.
+pub fn normalize(input: &str) -> String {
+    let mut result = String::new();
+    let mut inserted_underscore = false;
+
+    let num_chars = input.chars().count();
+
+    for (i, char) in input.chars().enumerate() {
+        let is_first = result.is_empty();
+        let is_last = i == num_chars - 1;
+        let should_strip = is_first || is_last;
+
+        if unicode_ident::is_xid_continue(char) {
+            if result.is_empty()
+                && char != '_'
+                && !unicode_ident::is_xid_start(char)
+                && !inserted_underscore
+            {
+                result.push('_');
+            } else if char == '_' && should_strip {
+                continue;
+            }
+            result.push(char);
+            inserted_underscore = false;
+        } else if !inserted_underscore && !should_strip {
+            result.push('_');
+            inserted_underscore = true;
+        }
+    }
+    if inserted_underscore {
+        result.pop();
+    }
+    if result.is_empty() {
+        result.push('_');
+    }
+
+    result
+}
+
+/// Concatenates multiple string inputs.
+pub fn concat(inputs: &[&str]) -> String {
+    inputs.join("")
+}
+
+/// Concatenates multiple string inputs putting the separator between them.
+pub fn join(separator: &str, inputs: &[&str]) -> String {
+    inputs.join(separator)
+}
+
+/// Checks whether the input is a valid Rust identifier - the same rules are applied by
+/// `compose-idents` when an identifier is made out of a string.
+///
+/// Keywords and a single underscore are not identifiers, while raw identifiers (`r#type`) are.
+pub fn is_valid_ident(input: &str) -> bool {
+    let (name, is_raw) = match input.strip_prefix("r#") {
+        Some(name) => (name, true),
+        None => (input, false),
+    };
+    let mut chars = name.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let is_well_formed = (first == '_' || unicode_ident::is_xid_start(first))
+        && chars.all(unicode_ident::is_xid_continue);
+    if !is_well_formed || name == "_" {
+        return false;
+    }
+    if is_raw {
+        !NON_RAW_KEYWORDS.contains(&name)
+    } else {
+        !KEYWORDS.contains(&name)
+    }
+}
+
+/// Checks whether the input could start an identifier - any identifier characters (e.g. digits)
+/// could follow it.
+pub(crate) fn is_valid_ident_start(input: &str) -> bool {
+    is_valid_ident(&format!("{}0", input))
+}
+
+/// Checks whether the input could be put inside an identifier - it consists of identifier
+/// characters only.
+pub(crate) fn is_ident_part(input: &str) -> bool {
+    input.chars().all(unicode_ident::is_xid_continue)
+}
+
+/// Escapes a keyword as a raw identifier (`match` becomes `r#match`) leaving any other input
+/// untouched.
+///
+/// Returns `None` for the keywords that can't be raw identifiers (`crate`, `self`, `super` and
+/// `Self`).
+pub fn escape_keyword(input: &str) -> Option<String> {
+    if NON_RAW_KEYWORDS.contains(&input) {
+        return None;
+    }
+    if KEYWORDS.contains(&input) {
+        return Some(format!("r#{}", input));
+    }
+    Some(input.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("hello_world", "hello_world")]
+    #[case("$hello_world", "hello_world")]
+    #[case("_hello_world", "hello_world")]
+    #[case("hello_world$", "hello_world")]
+    #[case("hello world", "hello_world")]
+    #[case("hello__world", "hello__world")]
+    #[case("hello-world", "hello_world")]
+    #[case("hello.world", "hello_world")]
+    #[case("hello...world", "hello_world")]
+    #[case("hello-_-world", "hello___world")]
+    #[case("123hello", "_123hello")]
+    #[case("123", "_123")]
+    #[case("_123", "_123")]
+    #[case("#$%^&*", "_")]
+    #[case("", "_")]
+    #[case("a__b___c", "a__b___c")]
+    #[case("a b c", "a_b_c")]
+    #[case("a.b.c", "a_b_c")]
+    #[case("a!@#b$%^c", "a_b_c")]
+    #[case("a_!@#_b", "a___b")]
+    #[case("&'static str", "static_str")]
+    #[case("&'static str ", "static_str")]
+    #[case("Result<T, E>", "Result_T_E")]
+    #[case("Result< T, E >", "Result_T_E")]
+    #[case::german("größe wert", "größe_wert")]
+    #[case::greek("μέγεθος-τιμή", "μέγεθος_τιμή")]
+    #[case::cjk("名前 値", "名前_値")]
+    #[case::combining_marks("नमस्ते दुनिया", "नमस्ते_दुनिया")]
+    #[case::leading_combining_mark("\u{94d}foo", "_\u{94d}foo")]
+    #[case::leading_unicode_digit("١٢foo", "_١٢foo")]
+    #[case::superscript("x²", "x")]
+    fn test_normalize(#[case] input: &str, #[case] expected: &str) {
+        let actual = normalize(input);
+        assert_eq!(actual, expected, "Input: {}", input);
+    }
+
+    #[rstest]
+    #[case::german_snake("GrößeWert", to_snake_case, "größe_wert")]
+    #[case::german_camel("größe_wert", to_camel_case, "größeWert")]
+    #[case::german_pascal("größe_wert", to_pascal_case, "GrößeWert")]
+    #[case::german_upper("straße", upper, "STRASSE")]
+    #[case::greek_snake("ΜεγάλοΌνομα", to_snake_case, "μεγάλο_όνομα")]
+    #[case::greek_lower("ΌΝΟΜΑ", lower, "όνομα")]
+    #[case::german_shouty_snake("größeWert", to_shouty_snake_case, "GRÖSSE_WERT")]
+    #[case::cjk_snake("名前", to_snake_case, "名前")]
+    #[case::cjk_pascal("名前_foo", to_pascal_case, "名前Foo")]
+    #[case::combining_marks_snake("नमस्तेDuniya", to_snake_case, "नमस्तेDuniya")]
+    #[case::connector_punctuation_camel("foo‿bar", to_camel_case, "foo‿bar")]
+    fn test_unicode_case(
+        #[case] input: &str,
+        #[case] func: fn(&str) -> String,
+        #[case] expected: &str,
+    ) {
+        let actual = func(input);
+        assert_eq!(actual, expected, "Input: {}", input);
+        assert!(is_valid_ident(&actual), "Result: {}", actual);
+    }
+
+    #[rstest]
+    #[case::capitalize("someHTTPThing", capitalize, "SomeHTTPThing")]
+    #[case::capitalize_capitalized("Foo", capitalize, "Foo")]
+    #[case::capitalize_empty("", capitalize, "")]
+    #[case::capitalize_unicode("ärger", capitalize, "Ärger")]
+    #[case::capitalize_multi_char("ßfoo", capitalize, "Sfoo")]
+    #[case::decapitalize("SomeHTTPThing", decapitalize, "someHTTPThing")]
+    #[case::decapitalize_empty("", decapitalize, "")]
+    #[case::decapitalize_unicode("Ωmega", decapitalize, "ωmega")]
+    fn test_first_char_case(
+        #[case] input: &str,
+        #[case] func: fn(&str) -> String,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(func(input), expected, "Input: {}", input);
+    }
+
+    #[rstest]
+    #[case(&[], "")]
+    #[case(&["hello"], "hello")]
+    #[case(&["hello", "world"], "helloworld")]
+    #[case(&["foo", "_", "bar"], "foo_bar")]
+    #[case(&["a", "b", "c", "d"], "abcd")]
+    #[case(&["", "hello", "", "world", ""], "helloworld")]
+    fn test_concat(#[case] inputs: &[&str], #[case] expected: &str) {
+        let actual = concat(inputs);
+        assert_eq!(actual, expected, "Inputs: {:?}", inputs);
+    }
+
+    #[rstest]
+    #[case("_", &[], "")]
+    #[case("_", &["hello"], "hello")]
+    #[case("_", &["a", "b", "c"], "a_b_c")]
+    #[case("::", &["foo", "bar", "baz"], "foo::bar::baz")]
+    #[case("", &["foo", "bar"], "foobar")]
+    #[case("_", &["", "foo", ""], "_foo_")]
+    fn test_join(#[case] separator: &str, #[case] inputs: &[&str], #[case] expected: &str) {
+        let actual = join(separator, inputs);
+        assert_eq!(actual, expected, "Inputs: {:?}", inputs);
+    }
+
+    #[rstest]
+    #[case::simple("foo", true)]
+    #[case::underscore_prefix("_foo", true)]
+    #[case::digits("foo_123", true)]
+    #[case::unicode("привет", true)]
+    #[case::german("größe", true)]
+    #[case::greek("μέγεθος", true)]
+    #[case::cjk("名前", true)]
+    #[case::leading_combining_mark("\u{94d}foo", false)]
+    #[case::superscript("x²", false)]
+    #[case::contextual_keyword("union", true)]
+    #[case::raw_keyword("r#type", true)]
+    #[case::empty("", false)]
+    #[case::underscore("_", false)]
+    #[case::leading_digit("1foo", false)]
+    #[case::punctuation("foo-bar", false)]
+    #[case::whitespace("foo bar", false)]
+    #[case::keyword("type", false)]
+    #[case::self_type("Self", false)]
+    #[case::raw_self("r#self", false)]
+    #[case::raw_empty("r#", false)]
+    fn test_is_valid_ident(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_ident(input), expected, "Input: {}", input);
+    }
+
+    #[rstest]
+    #[case::double_underscore("__", true)]
+    #[case::underscore("_", true)]
+    #[case::word("gen_", true)]
+    #[case::unicode("größe_", true)]
+    #[case::empty("", false)]
+    #[case::leading_digit("1_", false)]
+    #[case::punctuation("gen-", false)]
+    #[case::raw("r#", false)]
+    fn test_is_valid_ident_start(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(is_valid_ident_start(input), expected, "Input: {}", input);
+    }
+
+    #[rstest]
+    #[case::plain("foo", Some("foo"))]
+    #[case::keyword("match", Some("r#match"))]
+    #[case::reserved_keyword("abstract", Some("r#abstract"))]
+    #[case::raw_keyword("r#type", Some("r#type"))]
+    #[case::contextual_keyword("union", Some("union"))]
+    #[case::self_value("self", None)]
+    #[case::self_type("Self", None)]
+    #[case::crate_("crate", None)]
+    #[case::super_("super", None)]
+    fn test_escape_keyword(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(
+            escape_keyword(input).as_deref(),
+            expected,
+            "Input: {}",
+            input
+        );
+    }
+}
@@ -3,18 +3,19 @@
 
 use crate::ast::Value;
 use crate::error::Error;
-use crate::substitution::substitute_idents;
+use crate::substitution::{substitute_idents, substitute_idents_single_pass};
 use crate::util::log::debug;
 use proc_macro2::TokenStream;
 use quote::ToTokens;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::rc::Rc;
 use syn::parse::Parse;
 use syn::visit_mut::VisitMut;
 use syn::{
     parse_quote, Block, Fields, ImplItem, ImplItemConst, ImplItemFn, ImplItemMacro, ImplItemType,
-    Item, ItemEnum, ItemFn, ItemForeignMod, ItemImpl, ItemMod, ItemStruct, ItemTrait, ItemUnion,
-    Stmt, TraitItem, TraitItemConst, TraitItemFn, TraitItemMacro, TraitItemType,
+    Item, ItemConst, ItemEnum, ItemFn, ItemForeignMod, ItemImpl, ItemMacro, ItemMod, ItemStatic,
+    ItemStruct, ItemTrait, ItemType, ItemUnion, Stmt, TraitItem, TraitItemConst, TraitItemFn,
+    TraitItemMacro, TraitItemType,
 };
 
 struct OuterAttributes(Vec<syn::Attribute>);
@@ -50,20 +51,40 @@ macro_rules! check_error {
 
 /// Visitor that replaces aliases in the provided code block with their definitions.
 ///
-/// Recursively and incrementally operates on AST-level.
+/// [`AliasSubstitutionVisitor::substitute`] is the main entry point. The [`VisitMut`]
+/// implementation recursively and incrementally operates on AST-level.
 pub struct AliasSubstitutionVisitor {
-    substitutions: HashMap<String, Rc<Value>>,
+    substitutions: BTreeMap<String, Rc<Value>>,
     error: Option<Error>,
 }
 
 impl AliasSubstitutionVisitor {
-    pub fn new(substitutions: HashMap<String, Rc<Value>>) -> Self {
+    pub fn new(substitutions: BTreeMap<String, Rc<Value>>) -> Self {
         Self {
             substitutions,
             error: None,
         }
     }
 
+    /// Substitutes aliases in the provided code block - the main entry point of the visitor.
+    ///
+    /// # Notes
+    ///
+    /// - The whole block is processed in a single pass and parsed back only once at the end.
+    /// - If the result turns out to be invalid - the block is processed again recursively and
+    ///   incrementally by [`VisitMut::visit_block_mut`] in order to attribute the error precisely.
+    pub fn substitute(&mut self, block: &mut Block) {
+        check_error!(self);
+        match substitute_idents_single_pass(&*block, &self.substitutions) {
+            Ok(Some(new_block)) => *block = new_block,
+            Ok(None) => {
+                debug!("Single-pass substitution failed, falling back to incremental one...");
+                self.visit_block_mut(block);
+            }
+            Err(err) => self.error = Some(err),
+        }
+    }
+
     /// An error occurred during the substitution process.
     pub fn error(&self) -> Option<&Error> {
         self.error.as_ref()
@@ -100,11 +121,12 @@ impl AliasSubstitutionVisitor {
     fn visit_attrs_mut(&mut self, attrs: &mut Vec<syn::Attribute>) {
         debug!("Visiting attributes: {:?}", attrs);
         check_error!(self);
-        *attrs = match substitute_idents(&OuterAttributes(attrs.clone()), &self.substitutions) {
-            Ok(attrs) => attrs.into(),
+        let outer_attrs = OuterAttributes(std::mem::take(attrs));
+        *attrs = match substitute_idents(&outer_attrs, &self.substitutions) {
+            Ok(new_attrs) => new_attrs.into(),
             Err(err) => {
                 self.error = Some(err);
-                return;
+                outer_attrs.into()
             }
         };
     }
@@ -137,39 +159,55 @@ impl VisitMut for AliasSubstitutionVisitor {
     fn visit_block_mut(&mut self, item: &mut Block) {
         debug!("Visiting block: {:?}", item);
         let mut new_stmts = Vec::new();
-        for stmt in item.stmts.iter_mut() {
-            let new_stmts_ = match stmt {
+        for mut stmt in std::mem::take(&mut item.stmts) {
+            let new_stmts_ = match &mut stmt {
                 Stmt::Item(Item::Fn(item_fn)) => {
                     self.visit_item_fn_mut(item_fn);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::Struct(item_struct)) => {
                     self.visit_item_struct_mut(item_struct);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::Enum(item_enum)) => {
                     self.visit_item_enum_mut(item_enum);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::Union(item_union)) => {
                     self.visit_item_union_mut(item_union);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::Trait(item_trait)) => {
                     self.visit_item_trait_mut(item_trait);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::Impl(item_impl)) => {
                     self.visit_item_impl_mut(item_impl);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::Mod(item_mod)) => {
                     self.visit_item_mod_mut(item_mod);
-                    vec![stmt.clone()]
+                    vec![stmt]
                 }
                 Stmt::Item(Item::ForeignMod(item_foreign_mod)) => {
                     self.visit_item_foreign_mod_mut(item_foreign_mod);
-                    vec![stmt.clone()]
+                    vec![stmt]
+                }
+                Stmt::Item(Item::Static(item_static)) => {
+                    self.visit_item_static_mut(item_static);
+                    vec![stmt]
+                }
+                Stmt::Item(Item::Const(item_const)) => {
+                    self.visit_item_const_mut(item_const);
+                    vec![stmt]
+                }
+                Stmt::Item(Item::Type(item_type)) => {
+                    self.visit_item_type_mut(item_type);
+                    vec![stmt]
+                }
+                Stmt::Item(Item::Macro(item_macro)) => {
+                    self.visit_item_macro_mut(item_macro);
+                    vec![stmt]
                 }
                 // A workaround for the problem of `Stmt` being context-dependent AST type and
                 // not supporting AST -> tokens -> AST conversion roundtrip.
@@ -233,6 +271,26 @@ impl VisitMut for AliasSubstitutionVisitor {
         check_error!(self);
         self.visit_mut(i);
     }
+    fn visit_item_const_mut(&mut self, i: &mut ItemConst) {
+        debug!("Visiting a const: {:?}", i);
+        check_error!(self);
+        self.visit_attrs_mut(&mut i.attrs);
+        check_error!(self);
+        debug!("Visiting a const's visibility...");
+        self.visit_mut(&mut i.vis);
+        check_error!(self);
+        debug!("Visiting a const's identifier...");
+        self.visit_mut(&mut i.ident);
+        check_error!(self);
+        debug!("Visiting a const's generic parameters...");
+        self.visit_mut(&mut i.generics);
+        check_error!(self);
+        debug!("Visiting a const's type...");
+        self.visit_boxed_mut(&mut i.ty);
+        check_error!(self);
+        debug!("Visiting a const's expression...");
+        self.visit_boxed_mut(&mut i.expr);
+    }
     fn visit_item_enum_mut(&mut self, i: &mut ItemEnum) {
         debug!("Visiting an enum: {:?}", i);
         check_error!(self);
@@ -325,6 +383,19 @@ impl VisitMut for AliasSubstitutionVisitor {
             check_error!(self);
         }
     }
+    fn visit_item_macro_mut(&mut self, i: &mut ItemMacro) {
+        debug!("Visiting a macro item: {:?}", i);
+        check_error!(self);
+        self.visit_attrs_mut(&mut i.attrs);
+        check_error!(self);
+        if let Some(ident) = &mut i.ident {
+            debug!("Visiting a macro item's identifier...");
+            self.visit_mut(ident);
+            check_error!(self);
+        }
+        debug!("Visiting a macro item's invocation...");
+        self.visit_mut(&mut i.mac);
+    }
     fn visit_item_mod_mut(&mut self, i: &mut ItemMod) {
         debug!("Visiting a module: {:?}", i);
         check_error!(self);
@@ -349,6 +420,10 @@ impl VisitMut for AliasSubstitutionVisitor {
                     Item::Impl(im) => self.visit_item_impl_mut(im),
                     Item::Mod(m) => self.visit_item_mod_mut(m),
                     Item::ForeignMod(fm) => self.visit_item_foreign_mod_mut(fm),
+                    Item::Static(st) => self.visit_item_static_mut(st),
+                    Item::Const(c) => self.visit_item_const_mut(c),
+                    Item::Type(ty) => self.visit_item_type_mut(ty),
+                    Item::Macro(mac) => self.visit_item_macro_mut(mac),
                     other => {
                         debug!("Visiting a generic module item: {:?}", other);
                         self.visit_mut(other)
@@ -358,6 +433,23 @@ impl VisitMut for AliasSubstitutionVisitor {
             }
         }
     }
+    fn visit_item_static_mut(&mut self, i: &mut ItemStatic) {
+        debug!("Visiting a static: {:?}", i);
+        check_error!(self);
+        self.visit_attrs_mut(&mut i.attrs);
+        check_error!(self);
+        debug!("Visiting a static's visibility...");
+        self.visit_mut(&mut i.vis);
+        check_error!(self);
+        debug!("Visiting a static's identifier...");
+        self.visit_mut(&mut i.ident);
+        check_error!(self);
+        debug!("Visiting a static's type...");
+        self.visit_boxed_mut(&mut i.ty);
+        check_error!(self);
+        debug!("Visiting a static's expression...");
+        self.visit_boxed_mut(&mut i.expr);
+    }
     fn visit_item_struct_mut(&mut self, i: &mut ItemStruct) {
         debug!("Visiting a struct: {:?}", i);
         check_error!(self);
@@ -407,6 +499,23 @@ impl VisitMut for AliasSubstitutionVisitor {
             check_error!(self);
         }
     }
+    fn visit_item_type_mut(&mut self, i: &mut ItemType) {
+        debug!("Visiting a type alias: {:?}", i);
+        check_error!(self);
+        self.visit_attrs_mut(&mut i.attrs);
+        check_error!(self);
+        debug!("Visiting a type alias' visibility...");
+        self.visit_mut(&mut i.vis);
+        check_error!(self);
+        debug!("Visiting a type alias' identifier...");
+        self.visit_mut(&mut i.ident);
+        check_error!(self);
+        debug!("Visiting a type alias' generic parameters...");
+        self.visit_mut(&mut i.generics);
+        check_error!(self);
+        debug!("Visiting a type alias' type...");
+        self.visit_boxed_mut(&mut i.ty);
+    }
     fn visit_item_union_mut(&mut self, i: &mut ItemUnion) {
         debug!("Visiting a union: {:?}", i);
         check_error!(self);
@@ -465,7 +574,7 @@ mod tests {
     use crate::ast::Value;
     use proc_macro2::{Ident, Span};
     use rstest::rstest;
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
     use std::rc::Rc;
     use syn::parse_quote;
 
@@ -520,8 +629,17 @@ mod tests {
     fn basic_substitution(
         #[case] mut input: Block,
         #[case] expected: Block,
-        #[case] substitutions: HashMap<String, Rc<Value>>,
+        #[case] substitutions: BTreeMap<String, Rc<Value>>,
     ) {
+        let mut single_pass_input = input.clone();
+        let mut visitor = AliasSubstitutionVisitor::new(substitutions.clone());
+        visitor.substitute(&mut single_pass_input);
+        assert!(
+            visitor.error().is_none(),
+            "Visitor error during single-pass substitution: {:?}",
+            visitor.error(),
+        );
+
         let mut visitor = AliasSubstitutionVisitor::new(substitutions);
         visitor.visit_block_mut(&mut input);
         assert!(
@@ -531,6 +649,7 @@ mod tests {
         );
 
         assert_eq!(input, expected);
+        assert_eq!(single_pass_input, expected);
     }
 
     /// Various cases that verify correctness of recursive AST traversal and substitution.
@@ -769,6 +888,136 @@ mod tests {
             "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
         ),
     )]
+    // Static, const and type alias cases.
+    #[case::static_substitution(
+        parse_quote!{{
+            #[doc = "Counter of % name %."]
+            static foo: T = T::new();
+        }},
+        parse_quote!{{
+            #[doc = "Counter of bar."]
+            static bar: AtomicU32 = AtomicU32::new();
+        }},
+        make_substitutions!(
+            "name" => Value::from_ident(Ident::new("bar", Span::call_site())),
+            "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
+            "T" => Value::from_ident(Ident::new("AtomicU32", Span::call_site())),
+        ),
+    )]
+    #[case::static_mut_substitution(
+        parse_quote!{{
+            #[doc = "% name %"]
+            static mut foo: u32 = 1;
+        }},
+        parse_quote!{{
+            #[doc = "bar"]
+            static mut bar: u32 = 1;
+        }},
+        make_substitutions!(
+            "name" => Value::from_ident(Ident::new("bar", Span::call_site())),
+            "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
+        ),
+    )]
+    #[case::const_substitution(
+        parse_quote!{{
+            #[doc = "Default % name %."]
+            pub const FOO: T = foo;
+        }},
+        parse_quote!{{
+            #[doc = "Default bar."]
+            pub const BAR: u32 = 1;
+        }},
+        make_substitutions!(
+            "name" => Value::from_ident(Ident::new("bar", Span::call_site())),
+            "FOO" => Value::from_ident(Ident::new("BAR", Span::call_site())),
+            "T" => Value::from_ident(Ident::new("u32", Span::call_site())),
+            "foo" => Value::from_expr(syn::parse_str::<syn::Expr>("1").unwrap()),
+        ),
+    )]
+    #[case::type_alias_substitution(
+        parse_quote!{{
+            type Foo<T> = Result<T, E>;
+        }},
+        parse_quote!{{
+            type Bar<T> = Result<T, std::io::Error>;
+        }},
+        make_substitutions!(
+            "Foo" => Value::from_ident(Ident::new("Bar", Span::call_site())),
+            "E" => Value::from_type(syn::parse_str::<syn::Type>("std::io::Error").unwrap()),
+        ),
+    )]
+    // Macro item cases.
+    #[case::macro_rules_substitution(
+        parse_quote!{{
+            #[doc = "Makes % name %."]
+            macro_rules! foo { () => { bar!() }; }
+        }},
+        parse_quote!{{
+            #[doc = "Makes qux."]
+            macro_rules! qux { () => { baz!() }; }
+        }},
+        make_substitutions!(
+            "name" => Value::from_ident(Ident::new("qux", Span::call_site())),
+            "foo" => Value::from_ident(Ident::new("qux", Span::call_site())),
+            "bar" => Value::from_ident(Ident::new("baz", Span::call_site())),
+        ),
+    )]
+    #[case::module_recursive_static_substitution(
+        parse_quote!{{
+            mod m { static foo: u32 = 1; type T = foo; }
+        }},
+        parse_quote!{{
+            mod m { static bar: u32 = 1; type T = bar; }
+        }},
+        make_substitutions!(
+            "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
+        ),
+    )]
+    // Use-declaration cases.
+    #[case::use_glob_path_substitution(
+        parse_quote!{{
+            pub use foo::*;
+        }},
+        parse_quote!{{
+            pub use bar::baz::*;
+        }},
+        make_substitutions!(
+            "foo" => Value::from_path(syn::parse_str::<syn::Path>("bar::baz").unwrap()),
+        ),
+    )]
+    #[case::use_grouped_path_substitution(
+        parse_quote!{{
+            use backends::foo::{Handler, Config};
+        }},
+        parse_quote!{{
+            use backends::bar::baz::{Handler, Config};
+        }},
+        make_substitutions!(
+            "foo" => Value::from_path(syn::parse_str::<syn::Path>("bar::baz").unwrap()),
+        ),
+    )]
+    #[case::use_renamed_path_substitution(
+        parse_quote!{{
+            use foo as qux;
+        }},
+        parse_quote!{{
+            use bar::baz as qux;
+        }},
+        make_substitutions!(
+            "foo" => Value::from_path(syn::parse_str::<syn::Path>("bar::baz").unwrap()),
+        ),
+    )]
+    #[case::use_nested_path_substitution(
+        parse_quote!{{
+            mod m { use a::{foo::X, b::{foo::*}}; }
+        }},
+        parse_quote!{{
+            mod m { use a::{bar::baz::X, b::{bar::baz::*}}; }
+        }},
+        make_substitutions!(
+            "foo" => Value::from_path(syn::parse_str::<syn::Path>("bar::baz").unwrap()),
+        ),
+    )]
     // Block cases.
     #[case::block_trailing_semicolon(
         parse_quote!{{
@@ -807,8 +1056,17 @@ mod tests {
     fn ast_recursive_substitution(
         #[case] mut input: Block,
         #[case] expected: Block,
-        #[case] substitutions: HashMap<String, Rc<Value>>,
+        #[case] substitutions: BTreeMap<String, Rc<Value>>,
     ) {
+        let mut single_pass_input = input.clone();
+        let mut visitor = AliasSubstitutionVisitor::new(substitutions.clone());
+        visitor.substitute(&mut single_pass_input);
+        assert!(
+            visitor.error().is_none(),
+            "Visitor error during single-pass substitution: {:?}",
+            visitor.error(),
+        );
+
         let mut visitor = AliasSubstitutionVisitor::new(substitutions);
         visitor.visit_block_mut(&mut input);
         assert!(
@@ -818,5 +1076,95 @@ mod tests {
         );
 
         assert_eq!(input, expected);
+        assert_eq!(single_pass_input, expected);
+    }
+
+    /// Substituted identifiers are located at the alias occurrences, so IDEs could map the
+    /// generated items back to the code block.
+    #[rstest]
+    #[case::fn_("fn foo() {}\nfn g() { foo() }")]
+    #[case::struct_("struct foo;\nimpl foo {}")]
+    #[case::enum_variant("enum E { foo }\nconst C: E = E::foo;")]
+    #[case::trait_method("trait T { fn foo(); }\nimpl T for () { fn foo() {} }")]
+    #[case::nested("mod m {\n    pub const foo: u8 = 1;\n}\nconst C: u8 = m::foo;")]
+    fn substituted_ident_spans(#[case] input: &str) {
+        fn ident_locations(block: &Block, name: &str) -> Vec<(usize, usize)> {
+            fn collect(stream: TokenStream, name: &str, locations: &mut Vec<(usize, usize)>) {
+                for token in stream {
+                    match token {
+                        proc_macro2::TokenTree::Ident(ident) if ident == name => {
+                            let start = ident.span().start();
+                            locations.push((start.line, start.column));
+                        }
+                        proc_macro2::TokenTree::Group(group) => {
+                            collect(group.stream(), name, locations)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            let mut locations = Vec::new();
+            collect(block.to_token_stream(), name, &mut locations);
+            locations
+        }
+
+        let mut block = syn::parse_str::<Block>(&format!("{{\n{}\n}}", input)).unwrap();
+        let expected = ident_locations(&block, "foo");
+        assert!(!expected.is_empty());
+
+        // The value is evaluated from the alias spec - it's located somewhere else.
+        let value = syn::parse_str::<Ident>("\n\n\n\n\n                bar").unwrap();
+        let mut visitor = AliasSubstitutionVisitor::new(make_substitutions!(
+            "foo" => Value::from_ident(value),
+        ));
+        visitor.substitute(&mut block);
+        assert!(visitor.error().is_none());
+
+        assert!(ident_locations(&block, "foo").is_empty());
+        assert_eq!(ident_locations(&block, "bar"), expected);
+    }
+
+    /// A large realistic block is substituted in a single pass - it is parsed back once without
+    /// falling back to the incremental substitution, and the result is the same.
+    #[test]
+    fn large_block_single_pass() {
+        let substitutions = (0..100)
+            .map(|i| {
+                (
+                    format!("alias_{}", i),
+                    Rc::new(Value::from_ident(Ident::new(
+                        &format!("value_{}", i),
+                        Span::call_site(),
+                    ))),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        let items = (0..100)
+            .map(|i| {
+                format!(
+                    "/// Docs for % alias_{i} %.
+                    struct alias_{i} {{ a: u32, b: alias_{i}, c: Vec<alias_{i}> }}
+                    impl alias_{i} {{
+                        #[inline]
+                        fn alias_{i}(&self) -> u32 {{ let x = 1; x + self.a }}
+                    }}",
+                    i = i
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let block = syn::parse_str::<Block>(&format!("{{ {} }}", items)).unwrap();
+
+        let single_pass = substitute_idents_single_pass(&block, &substitutions)
+            .unwrap()
+            .expect("expected the single pass to succeed without a fallback");
+
+        let mut incremental = block.clone();
+        let mut visitor = AliasSubstitutionVisitor::new(substitutions.clone());
+        visitor.visit_block_mut(&mut incremental);
+        assert!(visitor.error().is_none());
+        let rendered = single_pass.to_token_stream().to_string();
+        assert_eq!(rendered, incremental.to_token_stream().to_string());
+        assert!(!rendered.contains("alias_"), "{}", rendered);
     }
 }
@@ -0,0 +1,153 @@
+//! Provides [`format_string`] function that substitutes `%alias%`-style placeholders in strings.
+
+use crate::ast::{Value, ValueKind};
+use quote::ToTokens;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Formats a [`Value`] instance into a string representation.
+fn format_value(value: &Value) -> String {
+    match &value.kind() {
+        ValueKind::Ident(ident) => ident.to_string(),
+        ValueKind::Path(path) => path
+            .segments
+            .iter()
+            .map(|seg| seg.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        ValueKind::Type(type_) => type_.to_token_stream().to_string(),
+        ValueKind::Expr(expr) => expr.to_token_stream().to_string(),
+        ValueKind::LitStr(lit_str) => lit_str.value(),
+        ValueKind::LitByteStr(lit_byte_str) => {
+            String::from_utf8_lossy(&lit_byte_str.value()).into_owned()
+        }
+        ValueKind::LitInt(lit_int) => lit_int.to_string(),
+        ValueKind::LitFloat(lit_float) => lit_float.to_string(),
+        ValueKind::LitChar(lit_char) => lit_char.value().to_string(),
+        ValueKind::LitBool(lit_bool) => lit_bool.value().to_string(),
+        ValueKind::Lifetime(lifetime) => lifetime.to_string(),
+        ValueKind::Tokens(tokens) => tokens.to_string(),
+        ValueKind::Raw(tokens) => tokens.to_string(),
+        ValueKind::Attr(attr) => attr.to_token_stream().to_string(),
+        ValueKind::List(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(|item| format_value(item))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Substitutes `% alias %`-style placeholders in a string.
+///
+/// Along with the formatted string the byte offsets of the substituted placeholders (including
+/// the enclosing `%`) within the original string are returned - errors could point at them.
+///
+/// # Notes
+///
+/// - Whitespace around the alias name is optional: `%alias%` and `%  alias  %` are equivalent.
+/// - `%%` is an escaped percent sign.
+/// - Placeholders of undefined aliases and unterminated placeholders are kept as is.
+pub fn format_string(
+    value: &str,
+    substitutions: &BTreeMap<String, Rc<Value>>,
+) -> (String, Vec<Range<usize>>) {
+    if !value.contains('%') {
+        return (value.to_string(), vec![]);
+    }
+
+    let mut formatted = String::with_capacity(value.len());
+    let mut offsets = Vec::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find('%') {
+        let offset = value.len() - rest.len() + start;
+        formatted.push_str(&rest[..start]);
+        let after_start = &rest[start + 1..];
+
+        if let Some(after_escape) = after_start.strip_prefix('%') {
+            formatted.push('%');
+            rest = after_escape;
+            continue;
+        }
+
+        let Some(end) = after_start.find('%') else {
+            // Unterminated placeholder.
+            formatted.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let name = after_start[..end].trim();
+        match substitutions.get(name.strip_prefix("r#").unwrap_or(name)) {
+            Some(sub) => {
+                formatted.push_str(format_value(sub.as_ref()).as_str());
+                offsets.push(offset..offset + end + 2);
+            }
+            None => formatted.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after_start[end + 1..];
+    }
+    formatted.push_str(rest);
+
+    (formatted, offsets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test::make_substitutions;
+    use super::format_string;
+    use crate::ast::Value;
+    use proc_macro2::{Ident, Span};
+    use rstest::rstest;
+    use std::collections::BTreeMap;
+    use std::rc::Rc;
+
+    #[rstest]
+    #[case::no_placeholders("Hello, world!", "Hello, world!")]
+    #[case::empty("", "")]
+    #[case::whitespace("Hello, % name %!", "Hello, foo!")]
+    #[case::no_whitespace("Hello, %name%!", "Hello, foo!")]
+    #[case::extra_whitespace("Hello, %  name  %!", "Hello, foo!")]
+    #[case::asymmetric_whitespace("Hello, %name  %!", "Hello, foo!")]
+    #[case::tabs_and_newlines("Hello, %\tname\n%!", "Hello, foo!")]
+    #[case::multiple("%name%, %other%, %name%", "foo, bar, foo")]
+    #[case::adjacent("%name%%other%", "foobar")]
+    #[case::escape("100%% %name%", "100% foo")]
+    #[case::escapes_only("%%%%", "%%")]
+    #[case::undefined("Hello, % undefined %!", "Hello, % undefined %!")]
+    #[case::undefined_followed_by_defined("%undefined%name%", "%undefined%name%")]
+    #[case::whitespace_inside_name("Hello, % na me %!", "Hello, % na me %!")]
+    #[case::unterminated("Hello, % name", "Hello, % name")]
+    #[case::raw_name("Hello, % r#name %!", "Hello, foo!")]
+    #[case::unicode("Привет, %name%! 😀", "Привет, foo! 😀")]
+    fn formatting(#[case] input: &str, #[case] expected: &str) {
+        let substitutions = make_substitutions!(
+            "name" => Value::from_ident(Ident::new("foo", Span::call_site())),
+            "other" => Value::from_ident(Ident::new("bar", Span::call_site())),
+        );
+        assert_eq!(format_string(input, &substitutions).0, expected);
+    }
+
+    #[rstest]
+    #[case::no_placeholders("Hello, world!", &[])]
+    #[case::single("Hello, % name %!", &[(7, 15)])]
+    #[case::multiple("%name%, %other%", &[(0, 6), (8, 15)])]
+    #[case::escape_before("100%% %name%", &[(6, 12)])]
+    #[case::undefined_skipped("% undefined % %name%", &[(14, 20)])]
+    #[case::unicode("Привет, %name%", &[(14, 20)])]
+    fn offsets(#[case] input: &str, #[case] expected: &[(usize, usize)]) {
+        let substitutions = make_substitutions!(
+            "name" => Value::from_ident(Ident::new("foo", Span::call_site())),
+            "other" => Value::from_ident(Ident::new("bar", Span::call_site())),
+        );
+        let (_, offsets) = format_string(input, &substitutions);
+        let expected = expected
+            .iter()
+            .map(|&(start, end)| start..end)
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, expected);
+    }
+}
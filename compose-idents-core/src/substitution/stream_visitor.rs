@@ -230,6 +230,12 @@ impl VisitorCtx {
     fn current_token(&self) -> Option<&TokenTree> {
         self.stack.back().and_then(|(i, tokens)| tokens.get(*i))
     }
+    /// Returns the token preceding the current one within the current group.
+    pub fn previous_token(&self) -> Option<&TokenTree> {
+        self.stack
+            .back()
+            .and_then(|(i, tokens)| i.checked_sub(1).and_then(|i| tokens.get(i)))
+    }
     /// Replaces the current token with a sequence of tokens provided in form of a [`TokenStream`].
     ///
     /// # Notes
@@ -314,26 +320,33 @@ impl VisitorCtx {
     }
 
     /// Reconstructs the whole `TokenStream` from the nested groups at the current traversal state.
+    ///
+    /// # Notes
+    ///
+    /// - Works iteratively from the innermost group outwards, so that the depth of nesting isn't
+    ///   limited by the call stack.
+    /// - Only the groups on the path to the current token are rebuilt, the rest of the tokens are
+    ///   shared with the stack.
     pub fn current_stream(&self) -> TokenStream {
-        match self.stack.len() {
-            0 => return TokenStream::new(),
-            1 => {
-                let (_, tokens) = self.stack[0].clone();
-                return tokens.into_iter().collect();
-            }
-            _ => {}
+        let mut levels = self.stack.iter().rev();
+        let Some((_, innermost)) = levels.next() else {
+            return TokenStream::new();
         };
 
-        let mut iter = self.stack.iter().cloned().rev();
-
-        let back = iter.next().unwrap().1;
-
-        let folded = iter.fold(back, |acc, (i, mut token_vec)| {
-            Self::fold_group(&acc, &mut token_vec, i);
-            token_vec.into_iter().collect::<Vec<_>>()
-        });
-
-        folded.into_iter().collect()
+        let mut stream = innermost.iter().cloned().collect::<TokenStream>();
+        for (i, tokens) in levels {
+            let mut folded = TokenStream::new();
+            folded.extend(tokens[..*i].iter().cloned());
+            let TokenTree::Group(original_group) = &tokens[*i] else {
+                panic!("Expected a group at index {}, found: {:?}", i, tokens[*i]);
+            };
+            let mut new_group = Group::new(original_group.delimiter(), stream);
+            new_group.set_span(original_group.span());
+            folded.extend(std::iter::once(TokenTree::Group(new_group)));
+            folded.extend(tokens[*i + 1..].iter().cloned());
+            stream = folded;
+        }
+        stream
     }
 }
 
@@ -427,16 +440,19 @@ mod tests {
         AfterReplace,
     }
 
+    /// An optional callback invoked by [`TestVisitor`] for a specific event.
+    type Callback<T> = Option<Box<dyn FnMut(&T) -> Result<StreamVisitorAction, Error>>>;
+
     /// A test visitor compatible with [`StreamWalker`] that logs events and allows to optionally
     /// pass callbacks for specific events.
     struct TestVisitor {
         log: Vec<LogEvent>,
-        on_ident: Option<Box<dyn FnMut(&Ident) -> Result<StreamVisitorAction, Error>>>,
-        on_punct: Option<Box<dyn FnMut(&Punct) -> Result<StreamVisitorAction, Error>>>,
-        on_literal: Option<Box<dyn FnMut(&Literal) -> Result<StreamVisitorAction, Error>>>,
-        on_visit_group: Option<Box<dyn FnMut(&Group) -> Result<StreamVisitorAction, Error>>>,
-        on_enter_group: Option<Box<dyn FnMut(&[TokenTree]) -> Result<StreamVisitorAction, Error>>>,
-        on_exit_group: Option<Box<dyn FnMut(&[TokenTree]) -> Result<StreamVisitorAction, Error>>>,
+        on_ident: Callback<Ident>,
+        on_punct: Callback<Punct>,
+        on_literal: Callback<Literal>,
+        on_visit_group: Callback<Group>,
+        on_enter_group: Callback<[TokenTree]>,
+        on_exit_group: Callback<[TokenTree]>,
     }
 
     impl TestVisitor {
@@ -666,4 +682,63 @@ mod tests {
         let err = walker.walk(input).expect_err("expected error");
         assert!(matches!(err, Error::InternalError(_)));
     }
+
+    /// A visitor that replaces every `a` ident with `b` and reconstructs the whole stream after
+    /// each replacement - the same way validation does it.
+    struct ReconstructingVisitor {
+        num_visits: usize,
+        num_replacements: usize,
+    }
+
+    impl StreamVisitor for ReconstructingVisitor {
+        fn visit_ident_mut(
+            &mut self,
+            _ctx: &super::VisitorCtx,
+            ident: &Ident,
+        ) -> Result<StreamVisitorAction, Error> {
+            self.num_visits += 1;
+            if ident == "a" {
+                Ok(StreamVisitorAction::Replace(quote!(b)))
+            } else {
+                Ok(StreamVisitorAction::Continue)
+            }
+        }
+        fn after_replace_mut(&mut self, ctx: &super::VisitorCtx) -> Result<(), Error> {
+            let _ = ctx.current_stream();
+            self.num_replacements += 1;
+            Ok(())
+        }
+    }
+
+    /// Deeply nested groups must be handled without overflowing the stack and without walking
+    /// the reconstructed groups again.
+    #[test]
+    fn deep_nesting_stress() {
+        const DEPTH: usize = 2000;
+
+        let mut input = quote!(a);
+        for _ in 0..DEPTH {
+            input = quote!(a + (#input));
+        }
+
+        let mut visitor = ReconstructingVisitor {
+            num_visits: 0,
+            num_replacements: 0,
+        };
+        let mut walker = StreamWalker::new(&mut visitor);
+        let mut actual = walker.walk(input).unwrap();
+
+        assert_eq!(visitor.num_visits, DEPTH + 1);
+        assert_eq!(visitor.num_replacements, DEPTH + 1);
+        for _ in 0..DEPTH {
+            let tokens = actual.into_iter().collect::<Vec<_>>();
+            assert_eq!(tokens.len(), 3);
+            assert_eq!(tokens[0].to_string(), "b");
+            let TokenTree::Group(group) = &tokens[2] else {
+                panic!("Expected a group, found: {:?}", tokens[2]);
+            };
+            actual = group.stream();
+        }
+        assert_eq!(actual.to_string(), "b");
+    }
 }
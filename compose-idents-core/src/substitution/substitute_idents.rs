@@ -0,0 +1,463 @@
+//! Provides [`substitute_idents`] - a helper that performs alias-substitution within an AST node
+//! of an arbitrary type while using [`StreamVisitor`] internally.
+
+use crate::ast::Value;
+use crate::ast::ValueKind;
+use crate::error::Error;
+use crate::strings::is_valid_ident;
+use crate::substitution::{
+    format_string, StreamVisitor, StreamVisitorAction, StreamWalker, VisitorCtx,
+};
+use crate::util::log::debug;
+use proc_macro2::{Delimiter, Group, Ident, Literal, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::rc::Rc;
+use syn::ext::IdentExt;
+use syn::parse::Parse;
+use syn::LitStr;
+
+/// A visitor compatible with [`StreamWalker`] that substitutes identifiers and formats
+/// string literals.
+///
+/// If validation is enabled - the stream is checked to be parseable as `N` after each replacement.
+struct SubstituteIdentsVisitor<'a, N: Parse> {
+    substitutions: &'a BTreeMap<String, Rc<Value>>,
+    validate: bool,
+    error_data: Option<(String, String, Span)>,
+    node_type: PhantomData<N>,
+}
+
+impl<'a, N: Parse> SubstituteIdentsVisitor<'a, N> {
+    pub fn new(substitutions: &'a BTreeMap<String, Rc<Value>>, validate: bool) -> Self {
+        Self {
+            substitutions,
+            validate,
+            error_data: None,
+            node_type: PhantomData,
+        }
+    }
+
+    /// Looks up an alias used as an outer attribute (`#[alias]`) that is bound to an attribute
+    /// or to a list of attributes.
+    fn attr_alias(&self, ctx: &VisitorCtx, group: &Group) -> Option<(Ident, &'a Value)> {
+        if group.delimiter() != Delimiter::Bracket {
+            return None;
+        }
+        if !matches!(ctx.previous_token(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#')
+        {
+            return None;
+        }
+        let mut tokens = group.stream().into_iter();
+        let (Some(TokenTree::Ident(ident)), None) = (tokens.next(), tokens.next()) else {
+            return None;
+        };
+        let value = self.substitutions.get(&alias_name(&ident)?)?;
+        let is_attr = match value.kind() {
+            ValueKind::Attr(_) => true,
+            ValueKind::List(items) => {
+                !items.is_empty()
+                    && items
+                        .iter()
+                        .all(|item| matches!(item.kind(), ValueKind::Attr(_)))
+            }
+            _ => false,
+        };
+        is_attr.then_some((ident, value.as_ref()))
+    }
+}
+
+/// Returns the name of the alias an identifier could reference.
+///
+/// `r#foo` and `foo` reference the same alias, but keywords (`fn`) could only be referenced by
+/// their raw form (`r#fn`).
+fn alias_name(ident: &Ident) -> Option<String> {
+    if !is_valid_ident(&ident.to_string()) {
+        return None;
+    }
+    Some(ident.unraw().to_string())
+}
+
+/// Locates a fragment of a string literal by its byte range within the value of the literal.
+///
+/// Sub-spans are only computed when built with `--cfg proc_macro_span` (nightly Rust). Otherwise,
+/// or if the fragment can't be located because the literal contains escapes, falls back to the
+/// span of the whole literal.
+#[cfg(proc_macro_span)]
+fn literal_subspan(literal: &Literal, value: &str, range: Range<usize>) -> Span {
+    let repr = literal.to_string();
+    // The value starts after the opening quote - both of plain and raw (`r#"..."#`) literals.
+    let Some(start) = repr.find('"').map(|quote| quote + 1) else {
+        return literal.span();
+    };
+    if repr.get(start..start + value.len()) != Some(value) {
+        return literal.span();
+    }
+    literal
+        .subspan(start + range.start..start + range.end)
+        .unwrap_or_else(|| literal.span())
+}
+
+#[cfg(not(proc_macro_span))]
+fn literal_subspan(literal: &Literal, _value: &str, _range: Range<usize>) -> Span {
+    literal.span()
+}
+
+/// Makes the tokens an alias occurrence is replaced with.
+///
+/// A substituted identifier is located at the alias occurrence while keeping the hygiene of the
+/// evaluated value - that way IDEs map generated items back to the code block they are defined in.
+///
+/// An alias bound to a lifetime and used as one (`'alias`) is replaced only by the identifier of
+/// the lifetime, since the apostrophe is already in place.
+fn make_substitution(value: &Value, occurrence: &Ident, is_lifetime: bool) -> TokenStream {
+    match value.kind() {
+        ValueKind::Ident(ident) => {
+            let mut ident = ident.clone();
+            ident.set_span(ident.span().located_at(occurrence.span()));
+            ident.into_token_stream()
+        }
+        ValueKind::Lifetime(lifetime) if is_lifetime => {
+            let mut ident = lifetime.ident.clone();
+            ident.set_span(ident.span().located_at(occurrence.span()));
+            ident.into_token_stream()
+        }
+        _ => value.to_token_stream(),
+    }
+}
+
+impl<N: Parse> StreamVisitor for SubstituteIdentsVisitor<'_, N> {
+    fn visit_ident_mut(
+        &mut self,
+        ctx: &VisitorCtx,
+        ident: &Ident,
+    ) -> Result<StreamVisitorAction, Error> {
+        // `$crate` and metavariables of `macro_rules!` defined within the block are never aliases.
+        if matches!(ctx.previous_token(), Some(TokenTree::Punct(punct)) if punct.as_char() == '$') {
+            return Ok(StreamVisitorAction::Continue);
+        }
+        if let Some(value) = alias_name(ident).and_then(|name| self.substitutions.get(&name)) {
+            let is_lifetime = matches!(
+                ctx.previous_token(),
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '\''
+            );
+            let substitution = make_substitution(value, ident, is_lifetime);
+            self.error_data = Some((
+                ident.to_string(),
+                substitution.clone().to_string(),
+                ident.span(),
+            ));
+
+            Ok(StreamVisitorAction::Replace(substitution))
+        } else {
+            Ok(StreamVisitorAction::Continue)
+        }
+    }
+    fn visit_group_mut(
+        &mut self,
+        ctx: &VisitorCtx,
+        group: &Group,
+    ) -> Result<StreamVisitorAction, Error> {
+        let Some((ident, value)) = self.attr_alias(ctx, group) else {
+            return Ok(StreamVisitorAction::Continue);
+        };
+        let attrs = match value.kind() {
+            ValueKind::List(items) => items
+                .iter()
+                .map(|item| item.to_token_stream())
+                .collect::<TokenStream>(),
+            _ => value.to_token_stream(),
+        };
+        self.error_data = Some((ident.to_string(), attrs.to_string(), ident.span()));
+        // The `#` preceding the group is kept in place, so it's dropped from the substitution.
+        let substitution = attrs.into_iter().skip(1).collect::<TokenStream>();
+
+        Ok(StreamVisitorAction::Replace(substitution))
+    }
+    fn visit_literal_mut(
+        &mut self,
+        _: &VisitorCtx,
+        literal: &Literal,
+    ) -> Result<StreamVisitorAction, Error> {
+        let Ok(lit_str) = syn::parse2::<LitStr>(literal.into_token_stream()) else {
+            return Ok(StreamVisitorAction::Continue);
+        };
+        let value = lit_str.value();
+        if !value.contains('%') {
+            return Ok(StreamVisitorAction::Continue);
+        }
+        let (formatted, offsets) = format_string(value.as_str(), self.substitutions);
+        let span = offsets.first().map_or_else(
+            || literal.span(),
+            |range| literal_subspan(literal, &value, range.clone()),
+        );
+        let lit_str = LitStr::new(&formatted, lit_str.span());
+        self.error_data = Some((
+            literal.to_string(),
+            lit_str.to_token_stream().to_string(),
+            span,
+        ));
+
+        Ok(StreamVisitorAction::Replace(lit_str.to_token_stream()))
+    }
+    fn after_replace_mut(&mut self, ctx: &VisitorCtx) -> Result<(), Error> {
+        if !self.validate {
+            return Ok(());
+        }
+        let stream = ctx.current_stream();
+        match syn::parse2::<N>(stream.clone()) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                debug!("Error validating stream after token replacement: {}", err);
+                let error_data = match self.error_data.take() {
+                    Some(err) => err,
+                    None => {
+                        panic!("Error data is always expected to be set at this point.");
+                    }
+                };
+                Err(Error::SubstitutionError(
+                    error_data.0,
+                    error_data.1,
+                    err,
+                    error_data.2,
+                ))
+            }
+        }
+    }
+}
+
+/// Substitutes identifiers within the provided AST node's token-stream representation and returns a
+/// new node based on the resulting token-stream.
+pub fn substitute_idents<N: ToTokens + Parse>(
+    node: &N,
+    substitutions: &BTreeMap<String, Rc<Value>>,
+) -> Result<N, Error> {
+    let mut visitor = SubstituteIdentsVisitor::<N>::new(substitutions, true);
+    let mut walker = StreamWalker::new(&mut visitor);
+    let stream = node.to_token_stream();
+    let new_stream = walker.walk(stream)?;
+
+    match syn::parse2::<N>(new_stream.clone()) {
+        Ok(new_node) => Ok(new_node),
+        Err(err) => {
+            unreachable!(
+                "Stream is expected to be valid after all token replacements for node \"{}\" of type \"{}\":\n{}\n{}",
+                node.to_token_stream(),
+                type_name::<N>(),
+                new_stream,
+                err,
+            );
+        }
+    }
+}
+
+/// Substitutes identifiers within the provided AST node in a single pass over its token-stream
+/// representation.
+///
+/// Unlike [`substitute_idents`] it doesn't validate the stream after each replacement - only the
+/// final stream is parsed back. Returns `None` if the result isn't a valid node, in which case
+/// [`substitute_idents`] should be used on the sub-nodes to attribute the error precisely.
+pub fn substitute_idents_single_pass<N: ToTokens + Parse>(
+    node: &N,
+    substitutions: &BTreeMap<String, Rc<Value>>,
+) -> Result<Option<N>, Error> {
+    let mut visitor = SubstituteIdentsVisitor::<N>::new(substitutions, false);
+    let mut walker = StreamWalker::new(&mut visitor);
+    let new_stream = walker.walk(node.to_token_stream())?;
+
+    Ok(syn::parse2::<N>(new_stream).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test::make_substitutions;
+    use super::{literal_subspan, substitute_idents, SubstituteIdentsVisitor};
+    use crate::ast::Value;
+    use crate::error::Error;
+    use crate::substitution::{StreamVisitor, StreamVisitorAction, VisitorCtx};
+    use proc_macro2::{Ident, Literal, Span};
+    use quote::ToTokens;
+    use rstest::rstest;
+    use std::collections::{BTreeMap, VecDeque};
+    use std::rc::Rc;
+    use syn::parse_quote;
+
+    /// Various token substitution cases.
+    #[rstest]
+    #[case::substituting_single_token(
+        parse_quote!{{
+            fn foo() -> u32 { 1 }
+        }},
+        parse_quote!{{
+            fn bar() -> u32 { 1 }
+        }},
+        make_substitutions!(
+            "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
+        ),
+    )]
+    #[case::substituting_multiple_tokens(
+        parse_quote!{{
+            let foo = 1;
+            let bar = foo + 1;
+        }},
+        parse_quote!{{
+            let baz = 1;
+            let bar = baz + 1;
+        }},
+        make_substitutions!(
+            "foo" => Value::from_ident(Ident::new("baz", Span::call_site())),
+        ),
+    )]
+    #[case::substituting_with_multiple_tokens(
+        parse_quote!{{
+            fn foo() -> T { 1 }
+        }},
+        parse_quote!{{
+            fn foo() -> Result<u32, String> { 1 }
+        }},
+        make_substitutions!(
+            "T" => Value::from_type(syn::parse_str::<syn::Type>("Result<u32, String>").unwrap()),
+        ),
+    )]
+    #[case::string_formatting(
+        parse_quote!{{
+            fn foo() -> &str { "Hello, % name %!" }
+        }},
+        parse_quote!{{
+            fn foo() -> &str { "Hello, World!" }
+        }},
+        make_substitutions!(
+            "name" => Value::from_ident(Ident::new("World", Span::call_site())),
+        ),
+    )]
+    #[case::lifetime(
+        parse_quote!{{
+            fn f<'lt>(x: &'lt str) -> Cow<lt, str> { x.into() }
+        }},
+        parse_quote!{{
+            fn f<'a>(x: &'a str) -> Cow<'a, str> { x.into() }
+        }},
+        make_substitutions!(
+            "lt" => Value::from_lifetime(syn::parse_str::<syn::Lifetime>("'a").unwrap()),
+        ),
+    )]
+    #[case::dollar_prefixed_idents(
+        parse_quote!{{
+            macro_rules! m { ($foo:ident) => { $crate::foo!($foo) } }
+        }},
+        parse_quote!{{
+            macro_rules! m { ($foo:ident) => { $crate::bar!($foo) } }
+        }},
+        make_substitutions!(
+            "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
+            "crate" => Value::from_ident(Ident::new("baz", Span::call_site())),
+        ),
+    )]
+    fn substitution(
+        #[case] input: syn::Block,
+        #[case] expected: syn::Block,
+        #[case] substitutions: BTreeMap<String, Rc<Value>>,
+    ) {
+        let result = substitute_idents(&input, &substitutions);
+        assert!(result.is_ok());
+
+        let actual = result.unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    /// The span points at the placeholder unless the literal contains escapes.
+    #[cfg(proc_macro_span)]
+    #[rstest]
+    #[case::plain(r#""Hello, % name %!""#, 8..16)]
+    #[case::raw(r###"r#"Hello, % name %!"#"###, 10..18)]
+    #[case::escapes_fallback(r#""\tHello, % name %!""#, 0..20)]
+    fn literal_subspan_columns(#[case] literal: &str, #[case] expected: std::ops::Range<usize>) {
+        let literal = syn::parse_str::<proc_macro2::Literal>(literal).unwrap();
+        let value = syn::parse2::<syn::LitStr>(literal.to_token_stream())
+            .unwrap()
+            .value();
+        let start = value.find('%').unwrap();
+        let span = literal_subspan(&literal, &value, start..start + 8);
+        assert_eq!(span.start().column..span.end().column, expected);
+    }
+
+    /// Without sub-span support a fragment is located at the whole literal.
+    #[cfg(not(proc_macro_span))]
+    #[test]
+    fn literal_subspan_fallback() {
+        let literal = syn::parse_str::<proc_macro2::Literal>(r#""Hello, % name %!""#).unwrap();
+        let span = literal_subspan(&literal, "Hello, % name %!", 7..15);
+        assert_eq!(span.start().column..span.end().column, 0..18);
+    }
+
+    /// Substitution producing invalid AST node should yield an error.
+    #[test]
+    fn substitution_yields_error_on_invalid_ast_node() {
+        let input_fn: syn::ItemFn = parse_quote! {
+            fn f() -> T { 0 }
+        };
+        let type_: syn::LitInt = parse_quote!(123);
+
+        let value = Value::from_lit_int(type_);
+        let subs = make_substitutions!(
+            "T" => value.clone(),
+        );
+
+        let result = substitute_idents(&input_fn, &subs);
+        assert!(result.is_err());
+
+        let err = result.unwrap_err();
+
+        assert!(matches!(err, Error::SubstitutionError(_, _, _, _)));
+
+        let Error::SubstitutionError(actual_original, actual_replacement, _, _) = err else {
+            unreachable!()
+        };
+
+        assert_eq!(actual_original, "T");
+        assert_eq!(actual_replacement, value.to_token_stream().to_string());
+    }
+
+    /// Literals without placeholders are left as they are without being formatted.
+    #[test]
+    fn placeholder_free_literals() {
+        let literal = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. ".repeat(50);
+        let stmts = (0..200)
+            .map(|i| format!("static S_{}: &str = {:?};", i, literal))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let block = syn::parse_str::<syn::Block>(&format!("{{ {} }}", stmts)).unwrap();
+        let substitutions = make_substitutions!(
+            "foo" => Value::from_ident(Ident::new("bar", Span::call_site())),
+        );
+
+        let mut visitor = SubstituteIdentsVisitor::<syn::Block>::new(&substitutions, true);
+        let ctx = VisitorCtx::new(VecDeque::new());
+        let mut num_literals = 0;
+        for stmt in block.stmts.iter() {
+            let syn::Stmt::Item(syn::Item::Static(item)) = stmt else {
+                unreachable!()
+            };
+            let syn::Expr::Lit(syn::ExprLit { lit, .. }) = item.expr.as_ref() else {
+                unreachable!()
+            };
+            let literal = syn::parse2::<Literal>(lit.to_token_stream()).unwrap();
+            let action = visitor.visit_literal_mut(&ctx, &literal).unwrap();
+            assert!(matches!(action, StreamVisitorAction::Continue));
+            num_literals += 1;
+        }
+        assert_eq!(num_literals, 200);
+        let placeholder = Literal::string("% foo %");
+        let action = visitor.visit_literal_mut(&ctx, &placeholder).unwrap();
+        assert!(matches!(action, StreamVisitorAction::Replace(_)));
+
+        let result = substitute_idents(&block, &substitutions).unwrap();
+        assert_eq!(
+            result.to_token_stream().to_string(),
+            block.to_token_stream().to_string()
+        );
+    }
+}
@@ -4,8 +4,8 @@
 macro_rules! make_substitutions {
     ($($key:expr => $value:expr),* $(,)*) => {
         {
-            HashMap::from([
-                $(($key.to_string(), Rc::new($value)))*
+            BTreeMap::from([
+                $(($key.to_string(), Rc::new($value))),*
             ])
         }
     }
@@ -0,0 +1,94 @@
+//! Helpers for accessing attributes of syntactic elements of the generated code.
+
+use syn::visit_mut::VisitMut;
+use syn::{Attribute, Block, Item, Stmt};
+
+/// Argument of the marker attribute that keeps an item outside of the scope of
+/// `options(scoped)` - `#[compose(export)]`.
+const EXPORT_MARKER: &str = "export";
+
+/// Returns the attributes of an item unless it is a verbatim one.
+pub fn item_attrs_mut(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    use Item::*;
+
+    match item {
+        Const(item) => Some(&mut item.attrs),
+        Enum(item) => Some(&mut item.attrs),
+        ExternCrate(item) => Some(&mut item.attrs),
+        Fn(item) => Some(&mut item.attrs),
+        ForeignMod(item) => Some(&mut item.attrs),
+        Impl(item) => Some(&mut item.attrs),
+        Macro(item) => Some(&mut item.attrs),
+        Mod(item) => Some(&mut item.attrs),
+        Static(item) => Some(&mut item.attrs),
+        Struct(item) => Some(&mut item.attrs),
+        Trait(item) => Some(&mut item.attrs),
+        TraitAlias(item) => Some(&mut item.attrs),
+        Type(item) => Some(&mut item.attrs),
+        Union(item) => Some(&mut item.attrs),
+        Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+/// Returns the attributes of a statement - expressions can't have attributes on stable Rust.
+pub fn stmt_attrs_mut(stmt: &mut Stmt) -> Option<&mut Vec<Attribute>> {
+    match stmt {
+        Stmt::Item(item) => item_attrs_mut(item),
+        Stmt::Local(local) => Some(&mut local.attrs),
+        Stmt::Macro(stmt_macro) => Some(&mut stmt_macro.attrs),
+        Stmt::Expr(_, _) => None,
+    }
+}
+
+/// Whether the attribute is the `#[compose(export)]` marker.
+fn is_export_marker(attr: &Attribute) -> bool {
+    attr.path().is_ident("compose")
+        && attr
+            .parse_args::<syn::Ident>()
+            .is_ok_and(|arg| arg == EXPORT_MARKER)
+}
+
+/// Removes the `#[compose(export)]` marker from the statement returning whether it was present.
+pub fn take_export_marker(stmt: &mut Stmt) -> bool {
+    let Some(attrs) = stmt_attrs_mut(stmt) else {
+        return false;
+    };
+    let len = attrs.len();
+    attrs.retain(|attr| !is_export_marker(attr));
+    attrs.len() != len
+}
+
+/// Stamps the attributes of `apply_attrs(...)` directive onto the top-level items of a block -
+/// an attribute is skipped if the item already has an identical one.
+pub struct ApplyAttrsVisitor<'a> {
+    attrs: &'a [Attribute],
+}
+
+impl<'a> ApplyAttrsVisitor<'a> {
+    pub fn new(attrs: &'a [Attribute]) -> Self {
+        Self { attrs }
+    }
+}
+
+impl VisitMut for ApplyAttrsVisitor<'_> {
+    fn visit_block_mut(&mut self, node: &mut Block) {
+        for stmt in &mut node.stmts {
+            if let Stmt::Item(item) = stmt {
+                self.visit_item_mut(item);
+            }
+        }
+    }
+
+    /// Doesn't descend into the item - the nested items are left as they are.
+    fn visit_item_mut(&mut self, node: &mut Item) {
+        let Some(attrs) = item_attrs_mut(node) else {
+            return;
+        };
+        for attr in self.attrs {
+            if !attrs.contains(attr) {
+                attrs.push(attr.clone());
+            }
+        }
+    }
+}
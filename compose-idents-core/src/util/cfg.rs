@@ -0,0 +1,137 @@
+//! Evaluation of `cfg`-like predicates at macro expansion time.
+//!
+//! Proc-macros don't have access to the configuration of the crate being compiled, so predicates
+//! are evaluated against the environment variables visible to the proc-macro host:
+//!
+//! - `feature = "foo-bar"` checks `CARGO_FEATURE_FOO_BAR`.
+//! - `test`, `debug_assertions` check `CARGO_CFG_TEST` and `CARGO_CFG_DEBUG_ASSERTIONS`.
+
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, ExprLit, Lit, Meta, Token};
+
+/// Names of the configuration options supported in addition to `feature = "..."`.
+pub const CFG_OPTION_NAMES: &[&str] = &["test", "debug_assertions"];
+
+/// Makes the name of the environment variable that signals a `cfg`-option.
+fn make_env_var_name(prefix: &str, name: &str) -> String {
+    format!("{}{}", prefix, name.to_uppercase().replace('-', "_"))
+}
+
+/// Looks up environment variables of the current process.
+pub fn env_lookup(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Evaluates a predicate of the form accepted by `#[cfg(...)]` - `feature = "..."`, `test`,
+/// `debug_assertions` and their combinations with `all(...)`, `any(...)` and `not(...)`.
+///
+/// `lookup` resolves an environment variable by name.
+pub fn eval_cfg_predicate(
+    meta: &Meta,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> syn::Result<bool> {
+    match meta {
+        Meta::Path(path) => {
+            let name = path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .filter(|name| CFG_OPTION_NAMES.contains(&name.as_str()));
+            let Some(name) = name else {
+                return Err(syn::Error::new(
+                    path.span(),
+                    format!(
+                        "Unsupported cfg option, expected one of: feature = \"...\", {}",
+                        CFG_OPTION_NAMES.join(", "),
+                    ),
+                ));
+            };
+            Ok(lookup(&make_env_var_name("CARGO_CFG_", &name)).is_some())
+        }
+        Meta::NameValue(name_value) if name_value.path.is_ident("feature") => {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Str(feature),
+                ..
+            }) = &name_value.value
+            else {
+                return Err(syn::Error::new(
+                    name_value.value.span(),
+                    "Expected a feature name as a string literal",
+                ));
+            };
+            Ok(lookup(&make_env_var_name("CARGO_FEATURE_", &feature.value())).is_some())
+        }
+        Meta::List(list)
+            if ["all", "any", "not"]
+                .iter()
+                .any(|op| list.path.is_ident(op)) =>
+        {
+            let predicates =
+                list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+            let results = predicates
+                .iter()
+                .map(|predicate| eval_cfg_predicate(predicate, lookup))
+                .collect::<syn::Result<Vec<_>>>()?;
+            if list.path.is_ident("all") {
+                Ok(results.into_iter().all(|result| result))
+            } else if list.path.is_ident("any") {
+                Ok(results.into_iter().any(|result| result))
+            } else {
+                let [result] = results.as_slice() else {
+                    return Err(syn::Error::new(
+                        list.span(),
+                        "not(...) expects exactly one predicate",
+                    ));
+                };
+                Ok(!result)
+            }
+        }
+        _ => Err(syn::Error::new(meta.span(), "Unsupported cfg predicate")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::eval_cfg_predicate;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use syn::Meta;
+
+    #[rstest]
+    #[case::feature_enabled("feature = \"gpu\"", true)]
+    #[case::feature_disabled("feature = \"cpu\"", false)]
+    #[case::feature_dashes("feature = \"fast-math\"", true)]
+    #[case::debug_assertions("debug_assertions", true)]
+    #[case::test("test", false)]
+    #[case::not("not(test)", true)]
+    #[case::all("all(feature = \"gpu\", debug_assertions)", true)]
+    #[case::all_partial("all(feature = \"gpu\", test)", false)]
+    #[case::all_empty("all()", true)]
+    #[case::any("any(test, feature = \"gpu\")", true)]
+    #[case::any_empty("any()", false)]
+    #[case::nested(
+        "all(not(any(test, feature = \"cpu\")), feature = \"fast-math\")",
+        true
+    )]
+    fn predicate_evaluation(#[case] input: &str, #[case] expected: bool) {
+        let env = HashSet::from([
+            "CARGO_FEATURE_GPU",
+            "CARGO_FEATURE_FAST_MATH",
+            "CARGO_CFG_DEBUG_ASSERTIONS",
+        ]);
+        let lookup = |name: &str| env.contains(name).then(|| "1".to_string());
+        let meta = syn::parse_str::<Meta>(input).unwrap();
+        assert_eq!(eval_cfg_predicate(&meta, &lookup).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::unknown_option("unix")]
+    #[case::unknown_name_value("target_os = \"linux\"")]
+    #[case::non_str_feature("feature = gpu")]
+    #[case::unknown_list("some(test)")]
+    #[case::not_many("not(test, debug_assertions)")]
+    fn predicate_evaluation_errors(#[case] input: &str) {
+        let meta = syn::parse_str::<Meta>(input).unwrap();
+        assert!(eval_cfg_predicate(&meta, &|_| None).is_err());
+    }
+}
@@ -0,0 +1,567 @@
+//! Implements the deprecation mechanism.
+
+use crate::util::attrs::item_attrs_mut;
+use proc_macro2::Span;
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use syn::visit_mut::VisitMut;
+use syn::{
+    parse_quote, visit_mut, Attribute, Block, Expr, Field, File, ForeignItem, Item, Stmt,
+    TraitItem, Variant,
+};
+
+/// Identifier of the deprecation warning about semicolon separators.
+pub const SEMICOLON_SEPARATOR_WARNING: &str = "semicolon_separator";
+/// Identifier of the warning about user-defined aliases shadowing the loop index aliases.
+pub const INDEX_ALIAS_SHADOWING_WARNING: &str = "index_alias_shadowing";
+/// Identifier of the warning about duplicate values removed from a `unique(...)` loop source.
+pub const DUPLICATE_LOOP_VALUES_WARNING: &str = "duplicate_loop_values";
+
+/// Stable identifiers of all the deprecation warnings - could be used to suppress them with
+/// `options(allow_deprecated(...))`.
+pub const DEPRECATION_WARNING_IDS: &[&str] = &[
+    SEMICOLON_SEPARATOR_WARNING,
+    INDEX_ALIAS_SHADOWING_WARNING,
+    DUPLICATE_LOOP_VALUES_WARNING,
+];
+
+thread_local! {
+    static GLOBAL_DEPRECATION_SERVICE: RefCell<Option<Rc<RefCell<DeprecationService>>>> = const { RefCell::new(None) };
+}
+
+/// Deprecation warning - could be used to warn user about usage of deprecated functionality while
+/// still preserving backwards-compatibility.
+///
+/// Warnings without a `since` version are notices about valid, but likely unintended usage - they
+/// are never turned into errors.
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct DeprecationWarning {
+    id: String,
+    note: String,
+    since: Option<String>,
+}
+
+impl DeprecationWarning {
+    pub fn new(id: String, note: String, since: String) -> Self {
+        Self {
+            id,
+            note,
+            since: Some(since),
+        }
+    }
+
+    /// Makes a notice - a warning that isn't related to any deprecated functionality.
+    pub fn new_notice(id: String, note: String) -> Self {
+        Self {
+            id,
+            note,
+            since: None,
+        }
+    }
+
+    /// Stable identifier of the warning.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Whether the warning could be turned into an error - only deprecations are.
+    pub fn is_deniable(&self) -> bool {
+        self.since.is_some()
+    }
+
+    pub(crate) fn with_prefix(&self, prefix: &str) -> Self {
+        let DeprecationWarning { id, note, since } = self;
+        DeprecationWarning {
+            id: id.clone(),
+            note: format!("{}{}", prefix, note),
+            since: since.clone(),
+        }
+    }
+
+    /// Turns the warning into a hard error pointing at the deprecated syntax.
+    pub(crate) fn to_error(&self, prefix: &str, span: Span) -> syn::Error {
+        syn::Error::new(span, self.with_prefix(prefix).note)
+    }
+
+    pub(crate) fn to_attribute(&self) -> Attribute {
+        let DeprecationWarning { note, since, .. } = self;
+        match since {
+            Some(since) => parse_quote! {
+                #[deprecated(
+                    since=#since,
+                    note=#note,
+                )]
+            },
+            None => parse_quote! {
+                #[deprecated(
+                    note=#note,
+                )]
+            },
+        }
+    }
+}
+
+/// Processes the code block tries to add deprecations to existing syntactic elements.
+pub struct DeprecationWarningVisitor {
+    warnings: Vec<DeprecationWarning>,
+    prefix: String,
+}
+
+impl DeprecationWarningVisitor {
+    pub fn new(mut warnings: Vec<DeprecationWarning>, prefix: String) -> Self {
+        warnings.reverse();
+
+        Self { warnings, prefix }
+    }
+
+    /// Takes the warnings that couldn't be attached to any of the syntactic elements.
+    pub fn into_remaining(self) -> Vec<DeprecationWarning> {
+        let mut warnings = self.warnings;
+        warnings.reverse();
+        warnings
+    }
+
+    /// Try to place the deprecation attribute into the given attribute list.
+    fn process_deprecations(&mut self, attrs: &mut Vec<Attribute>) {
+        if self.warnings.is_empty() {
+            return;
+        }
+        for attr in attrs.iter() {
+            if attr.path().is_ident("deprecated") {
+                return;
+            }
+        }
+        let warning = &self.warnings.pop().unwrap();
+        let attr = warning.with_prefix(&self.prefix).to_attribute();
+        attrs.push(attr);
+    }
+}
+
+impl VisitMut for DeprecationWarningVisitor {
+    fn visit_field_mut(&mut self, node: &mut Field) {
+        self.process_deprecations(&mut node.attrs);
+        visit_mut::visit_field_mut(self, node);
+    }
+
+    fn visit_file_mut(&mut self, node: &mut File) {
+        self.process_deprecations(&mut node.attrs);
+        visit_mut::visit_file_mut(self, node);
+    }
+
+    fn visit_foreign_item_mut(&mut self, node: &mut ForeignItem) {
+        use ForeignItem::*;
+
+        match node {
+            Fn(item) => self.process_deprecations(&mut item.attrs),
+            Static(item) => self.process_deprecations(&mut item.attrs),
+            Type(item) => self.process_deprecations(&mut item.attrs),
+            Macro(item) => self.process_deprecations(&mut item.attrs),
+            _ => {}
+        }
+        visit_mut::visit_foreign_item_mut(self, node);
+    }
+
+    fn visit_item_mut(&mut self, node: &mut Item) {
+        if let Some(attrs) = item_attrs_mut(node) {
+            self.process_deprecations(attrs);
+        }
+        visit_mut::visit_item_mut(self, node);
+    }
+
+    fn visit_trait_item_mut(&mut self, node: &mut TraitItem) {
+        use TraitItem::*;
+
+        match node {
+            Const(item) => self.process_deprecations(&mut item.attrs),
+            Fn(item) => self.process_deprecations(&mut item.attrs),
+            Type(item) => self.process_deprecations(&mut item.attrs),
+            Macro(item) => self.process_deprecations(&mut item.attrs),
+            _ => {}
+        }
+        visit_mut::visit_trait_item_mut(self, node);
+    }
+
+    fn visit_variant_mut(&mut self, node: &mut Variant) {
+        self.process_deprecations(&mut node.attrs);
+        visit_mut::visit_variant_mut(self, node);
+    }
+}
+
+/// A utility for collecting and publishing deprecation warnings during macro expansion.
+///
+/// It attaches `#[deprecated(...)]` attributes to the generated code (when it's possible) -
+/// this way it makes it not necessary to create additional syntactic elements and pollute
+/// the generated code.
+///
+/// ## Usage
+///
+/// The main usage pattern is through a thread-local singleton accessed via scoped handles
+/// [`DeprecationServiceScope`], which serves as a facade for places in the code where the
+/// service instance can't be passed normally through arguments. The service must be
+/// initialized with a prefix at the macro entrypoint before use by setting the global:
+///
+/// ```rust,ignore
+/// let service = DeprecationService::new_rc("compose!: ");
+/// DeprecationService::maybe_set_global(service);
+/// let scope = DeprecationService::scoped();
+/// scope.add_semicolon_separator_warning(span, false)?;
+/// scope.emit(&mut generated_block);
+/// ```
+///
+/// ## Denying deprecations
+///
+/// With the `deny-deprecated` cargo feature enabled (or when the caller passes `deny = true`,
+/// e.g. because of `options(deny_deprecated)`) warnings are turned into hard errors with the same
+/// message pointing at the deprecated syntax.
+pub struct DeprecationService {
+    warnings: BTreeSet<DeprecationWarning>,
+    /// Identifiers of the warnings suppressed within the current macro invocation.
+    allowed: BTreeSet<String>,
+    borrowed: usize,
+    prefix: String,
+    deny: bool,
+}
+
+impl DeprecationService {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            warnings: BTreeSet::new(),
+            allowed: BTreeSet::new(),
+            borrowed: 0,
+            prefix: prefix.into(),
+            deny: cfg!(feature = "deny-deprecated"),
+        }
+    }
+
+    pub fn new_rc(prefix: impl Into<String>) -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self::new(prefix)))
+    }
+
+    pub fn add_warning(&mut self, warning: DeprecationWarning) {
+        if self.allowed.contains(warning.id()) {
+            return;
+        }
+        self.warnings.insert(warning);
+    }
+
+    /// Suppresses the warnings with the given identifiers - including the already added ones.
+    pub fn allow(&mut self, ids: &[String]) {
+        self.allowed.extend(ids.iter().cloned());
+        let allowed = &self.allowed;
+        self.warnings
+            .retain(|warning| !allowed.contains(warning.id()));
+    }
+
+    pub fn make_semicolon_separator_warning() -> DeprecationWarning {
+        DeprecationWarning::new(
+            SEMICOLON_SEPARATOR_WARNING.to_string(),
+            "Using semicolons as separators is deprecated, use commas instead".to_string(),
+            "0.0.5".to_string(),
+        )
+    }
+
+    pub fn make_index_alias_shadowing_warning(alias: &str) -> DeprecationWarning {
+        DeprecationWarning::new_notice(
+            INDEX_ALIAS_SHADOWING_WARNING.to_string(),
+            format!(
+                "Alias {} shadows the builtin loop index alias, consider renaming it",
+                alias
+            ),
+        )
+    }
+
+    pub fn make_duplicate_loop_values_warning(values: &[String]) -> DeprecationWarning {
+        DeprecationWarning::new_notice(
+            DUPLICATE_LOOP_VALUES_WARNING.to_string(),
+            format!(
+                "Duplicate loop values were removed by unique(...): {}",
+                values.join(", ")
+            ),
+        )
+    }
+
+    /// Adds the warning or fails with an error at `span` if deprecations are denied and the warning
+    /// is deniable.
+    pub fn add_warning_at(
+        &mut self,
+        warning: DeprecationWarning,
+        span: Span,
+        deny: bool,
+    ) -> syn::Result<()> {
+        if self.allowed.contains(warning.id()) {
+            return Ok(());
+        }
+        if (self.deny || deny) && warning.is_deniable() {
+            return Err(warning.to_error(&self.prefix, span));
+        }
+        self.add_warning(warning);
+        Ok(())
+    }
+
+    pub fn add_semicolon_separator_warning(&mut self, span: Span, deny: bool) -> syn::Result<()> {
+        self.add_warning_at(Self::make_semicolon_separator_warning(), span, deny)
+    }
+
+    pub fn add_index_alias_shadowing_warning(&mut self, alias: &str) {
+        self.add_warning(Self::make_index_alias_shadowing_warning(alias));
+    }
+
+    pub fn add_duplicate_loop_values_warning(&mut self, values: &[String]) {
+        self.add_warning(Self::make_duplicate_loop_values_warning(values));
+    }
+
+    pub fn clear(&mut self) {
+        self.warnings.clear();
+        self.allowed.clear();
+    }
+
+    pub fn emit(&self, block: &mut Block) {
+        if self.warnings.is_empty() {
+            return;
+        }
+        let mut deprecation_visitor = DeprecationWarningVisitor::new(
+            self.warnings.iter().cloned().collect(),
+            self.prefix.clone(),
+        );
+        deprecation_visitor.visit_block_mut(block);
+
+        let remaining = deprecation_visitor.into_remaining();
+        if !remaining.is_empty() {
+            self.emit_fallback(block, remaining);
+        }
+    }
+
+    /// Emits warnings that couldn't be attached to the existing syntactic elements through usages
+    /// of hidden deprecated constants (scoped within anonymous constants to avoid name clashes).
+    ///
+    /// If the block ends with an expression - the constants are placed into a block-expression
+    /// wrapping it, so that the output remains usable in expression position. Otherwise they are
+    /// appended to the end of the block.
+    fn emit_fallback(&self, block: &mut Block, warnings: Vec<DeprecationWarning>) {
+        let stmts = warnings
+            .iter()
+            .map(|warning| {
+                let attr = warning.with_prefix(&self.prefix).to_attribute();
+                parse_quote! {
+                    const _: () = {
+                        #[doc(hidden)]
+                        #attr
+                        const __COMPOSE_IDENTS_DEPRECATION: () = ();
+                        __COMPOSE_IDENTS_DEPRECATION
+                    };
+                }
+            })
+            .collect::<Vec<Stmt>>();
+
+        match block.stmts.pop() {
+            Some(Stmt::Expr(expr, None)) => {
+                let expr: Expr = parse_quote! {
+                    {
+                        #(#stmts)*
+                        #expr
+                    }
+                };
+                block.stmts.push(Stmt::Expr(expr, None));
+            }
+            last => {
+                block.stmts.extend(last);
+                block.stmts.extend(stmts);
+            }
+        }
+    }
+
+    /// Sets the global service unless it is already set.
+    pub fn maybe_set_global(service: Rc<RefCell<DeprecationService>>) {
+        GLOBAL_DEPRECATION_SERVICE.with(|cell| {
+            cell.borrow_mut().get_or_insert(service);
+        });
+    }
+
+    /// Replaces the global service returning the previous one.
+    pub fn replace_global(
+        service: Option<Rc<RefCell<DeprecationService>>>,
+    ) -> Option<Rc<RefCell<DeprecationService>>> {
+        GLOBAL_DEPRECATION_SERVICE.with(|cell| cell.replace(service))
+    }
+
+    pub fn get_global() -> Option<Rc<RefCell<DeprecationService>>> {
+        GLOBAL_DEPRECATION_SERVICE.with(|cell| cell.borrow().clone())
+    }
+
+    pub fn scoped() -> DeprecationServiceScope {
+        let service = Self::get_global()
+            .expect("DeprecationService is not initialized. Call maybe_set_global() first");
+        service.borrow_mut().borrowed += 1;
+        DeprecationServiceScope {}
+    }
+}
+
+pub struct DeprecationServiceScope;
+
+impl DeprecationServiceScope {
+    pub fn add_semicolon_separator_warning(&self, span: Span, deny: bool) -> syn::Result<()> {
+        match DeprecationService::get_global() {
+            Some(service) => service
+                .borrow_mut()
+                .add_semicolon_separator_warning(span, deny),
+            None => Ok(()),
+        }
+    }
+
+    pub fn add_index_alias_shadowing_warning(&self, alias: &str) {
+        if let Some(service) = DeprecationService::get_global() {
+            service
+                .borrow_mut()
+                .add_index_alias_shadowing_warning(alias);
+        }
+    }
+
+    pub fn add_duplicate_loop_values_warning(&self, values: &[String]) {
+        if let Some(service) = DeprecationService::get_global() {
+            service
+                .borrow_mut()
+                .add_duplicate_loop_values_warning(values);
+        }
+    }
+
+    pub fn allow(&self, ids: &[String]) {
+        if let Some(service) = DeprecationService::get_global() {
+            service.borrow_mut().allow(ids);
+        }
+    }
+
+    pub fn emit(&self, block: &mut Block) {
+        if let Some(service) = DeprecationService::get_global() {
+            service.borrow().emit(block);
+        }
+    }
+}
+
+impl Drop for DeprecationServiceScope {
+    fn drop(&mut self) {
+        if let Some(service) = DeprecationService::get_global() {
+            let mut service_ = service.borrow_mut();
+            service_.borrowed -= 1;
+            if service_.borrowed == 0 {
+                service_.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DeprecationService, DeprecationWarning, SEMICOLON_SEPARATOR_WARNING};
+    use proc_macro2::Span;
+    use quote::ToTokens;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::warn(false)]
+    #[case::deny(true)]
+    fn add_warning_at(#[case] deny: bool) {
+        let mut service = DeprecationService::new("compose!: ");
+        let result = service.add_semicolon_separator_warning(Span::call_site(), deny);
+        if deny || cfg!(feature = "deny-deprecated") {
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "compose!: Using semicolons as separators is deprecated, use commas instead",
+            );
+            assert!(service.warnings.is_empty());
+        } else {
+            assert!(result.is_ok());
+            assert_eq!(service.warnings.len(), 1);
+        }
+    }
+
+    /// Notices are never turned into errors and are attached without the `since` version.
+    #[test]
+    fn add_notice_at() {
+        let mut service = DeprecationService::new("compose!: ");
+        let notice = DeprecationService::make_index_alias_shadowing_warning("__index__");
+        assert!(!notice.is_deniable());
+        assert!(service
+            .add_warning_at(notice, Span::call_site(), true)
+            .is_ok());
+        assert_eq!(service.warnings.len(), 1);
+
+        let mut block = syn::parse_str::<syn::Block>("{ fn foo() {} }").unwrap();
+        service.emit(&mut block);
+        let tokens = block.to_token_stream().to_string();
+        assert!(tokens.contains("compose!: Alias __index__ shadows the builtin loop index alias"));
+        assert!(!tokens.contains("since"));
+    }
+
+    /// Counts usages of the deprecated constants generated by the fallback.
+    fn count_fallback_consts(block: &syn::Block) -> usize {
+        let tokens = block.to_token_stream().to_string();
+        tokens
+            .matches("const __COMPOSE_IDENTS_DEPRECATION : () = () ; __COMPOSE_IDENTS_DEPRECATION")
+            .count()
+    }
+
+    #[rstest]
+    #[case::item("{ fn foo() {} }", 0)]
+    #[case::expression_only("{ foo() }", 1)]
+    #[case::statements_only("{ let a = 1; foo(a); }", 1)]
+    #[case::already_deprecated("{ #[deprecated] fn foo() {} }", 1)]
+    fn emit(#[case] block: &str, #[case] expected_count: usize) {
+        let mut service = DeprecationService::new("compose!: ");
+        service.add_warning(DeprecationService::make_semicolon_separator_warning());
+        let mut block = syn::parse_str::<syn::Block>(block).unwrap();
+        service.emit(&mut block);
+
+        let tokens = block.to_token_stream().to_string();
+        assert!(tokens.contains(
+            "compose!: Using semicolons as separators is deprecated, use commas instead"
+        ));
+        assert_eq!(count_fallback_consts(&block), expected_count);
+    }
+
+    #[test]
+    fn emit_expression_only_keeps_expression() {
+        let mut service = DeprecationService::new("compose!: ");
+        service.add_warning(DeprecationService::make_semicolon_separator_warning());
+        let mut block = syn::parse_str::<syn::Block>("{ foo() }").unwrap();
+        service.emit(&mut block);
+
+        let [syn::Stmt::Expr(syn::Expr::Block(expr), None)] = block.stmts.as_slice() else {
+            panic!("Expected a single block-expression, got: {:?}", block.stmts);
+        };
+        assert!(matches!(
+            expr.block.stmts.last(),
+            Some(syn::Stmt::Expr(syn::Expr::Call(_), None))
+        ));
+    }
+
+    #[test]
+    fn allow() {
+        let other = DeprecationWarning::new(
+            "other".to_string(),
+            "Other feature is deprecated".to_string(),
+            "0.0.1".to_string(),
+        );
+        let mut service = DeprecationService::new("compose!: ");
+        service.add_warning(DeprecationService::make_semicolon_separator_warning());
+        service.add_warning(other);
+        service.allow(&[SEMICOLON_SEPARATOR_WARNING.to_string()]);
+
+        // Suppressed warnings are neither added again nor turned into errors.
+        service.add_warning(DeprecationService::make_semicolon_separator_warning());
+        assert!(service
+            .add_semicolon_separator_warning(Span::call_site(), true)
+            .is_ok());
+
+        let mut block = syn::parse_str::<syn::Block>("{ fn foo() {} fn bar() {} }").unwrap();
+        service.emit(&mut block);
+        let tokens = block.to_token_stream().to_string();
+        assert!(tokens.contains("compose!: Other feature is deprecated"));
+        assert!(!tokens.contains("semicolons"));
+
+        service.clear();
+        service.add_warning(DeprecationService::make_semicolon_separator_warning());
+        assert_eq!(service.warnings.len(), 1);
+    }
+}
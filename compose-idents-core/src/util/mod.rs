@@ -1,10 +1,17 @@
+pub mod attrs;
+pub mod cfg;
 pub mod combined;
 #[allow(unused_imports)]
 pub mod cross_product;
 pub mod deprecation;
 #[allow(unused_imports, unused_macros)]
 pub mod log;
+#[cfg(feature = "pretty")]
+pub mod pretty;
+pub mod stable_hash;
 pub mod terminated;
 pub mod token_distance;
 pub mod token_stream;
+#[cfg(feature = "trace")]
+pub mod trace;
 pub mod unique_id;
@@ -0,0 +1,60 @@
+//! Pretty-printing of the expanded code enabled by `options(pretty)` and used by
+//! [`crate::expand_compose_pretty`].
+use crate::error::Error;
+use proc_macro2::TokenStream;
+
+/// Formats the expansion of a single block-rewrite - it must consist of items.
+pub fn unparse_items(stream: TokenStream) -> Result<String, Error> {
+    let file = syn::parse2::<syn::File>(stream).map_err(|err| {
+        Error::EvalError(
+            format!(
+                "Formatted output requires the code block to consist of items: {}",
+                err
+            ),
+            err.span(),
+        )
+    })?;
+    Ok(prettyplease::unparse(&file))
+}
+
+/// Makes a comment separating expansions of different loop combinations.
+///
+/// `variables` are the names and the values of the loop variables of the combination.
+pub fn make_separator(combination: usize, variables: &[(String, String)]) -> String {
+    let variables = variables
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "// --- combination {} ({}) ---\n",
+        combination + 1,
+        variables
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{make_separator, unparse_items};
+    use quote::quote;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::single(&[("x", "a")], "// --- combination 1 (x=a) ---\n")]
+    #[case::multiple(&[("x", "a"), ("ty", "u8")], "// --- combination 1 (x=a, ty=u8) ---\n")]
+    fn separator(#[case] variables: &[(&str, &str)], #[case] expected: &str) {
+        let variables = variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(make_separator(0, &variables), expected);
+    }
+
+    #[test]
+    fn unparse_non_items() {
+        let err = unparse_items(quote! { let x = 1; }).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "EvalError: Formatted output requires the code block to consist of items"
+        ));
+    }
+}
@@ -0,0 +1,93 @@
+//! Hashing with a fixed algorithm.
+//!
+//! Unlike [`std::collections::hash_map::DefaultHasher`], the algorithm of which could change
+//! between Rust releases, the output of [`StableHasher`] is pinned - so the identifiers generated
+//! from hashes stay the same after a toolchain upgrade.
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a hasher with the finalizer of SplitMix64 applied to the result, so that short
+/// inputs affect all the bits of the hash.
+///
+/// The values are fed explicitly in a platform-independent form instead of going through
+/// [`std::hash::Hash`], whose implementations aren't guaranteed to be stable either.
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StableHasher {
+    pub fn new() -> Self {
+        Self {
+            state: FNV_OFFSET_BASIS,
+        }
+    }
+
+    /// Feeds the bytes into the hasher.
+    pub fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Feeds the integer into the hasher as little-endian bytes.
+    pub fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    /// Feeds the string into the hasher - it is terminated, so that the consecutive strings
+    /// `"ab", "c"` and `"a", "bc"` produce different hashes.
+    pub fn write_str(&mut self, value: &str) {
+        self.write(value.as_bytes());
+        self.write(&[0xff]);
+    }
+
+    /// Returns the hash of the values fed so far.
+    pub fn finish(&self) -> u64 {
+        let mut hash = self.state;
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+        hash ^ (hash >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StableHasher;
+
+    fn hash_str(value: &str) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_str(value);
+        hasher.finish()
+    }
+
+    /// The hashes must never change - the identifiers generated by users depend on them.
+    #[test]
+    fn pinned_values() {
+        assert_eq!(StableHasher::new().finish(), 17665956581633026203);
+        assert_eq!(hash_str("foo"), 15762442136122190272);
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(42);
+        hasher.write_str("foo");
+        assert_eq!(hasher.finish(), 11900177801265368001);
+    }
+
+    #[test]
+    fn terminated_strings() {
+        let hash = |values: &[&str]| {
+            let mut hasher = StableHasher::new();
+            values.iter().for_each(|value| hasher.write_str(value));
+            hasher.finish()
+        };
+        assert_ne!(hash(&["ab", "c"]), hash(&["a", "bc"]));
+        assert_ne!(hash_str("foo"), hash_str("bar"));
+    }
+}
@@ -0,0 +1,171 @@
+use syn::buffer::Cursor;
+
+/// Returns the number of tokens between `start` and `end`.
+///
+/// # Notes
+///
+/// - The function works recursively - counting tokens even inside token groups.
+#[allow(dead_code)]
+pub fn token_distance<'a>(start: &Cursor<'a>, end: &Cursor<'a>) -> usize {
+    let mut start = *start;
+    let mut end = *end;
+
+    // Ensure `start` is always before `end`.
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+
+    // Groups are traversed iteratively using an explicit stack of the cursors pointing past them,
+    // so that the depth of nesting isn't limited by the call stack.
+    let mut total = 0;
+    let mut stack = Vec::new();
+    let mut current = start;
+    let mut current_end = end;
+    loop {
+        if current < current_end {
+            if let Some((inside, _delim, _span, after)) = current.any_group() {
+                if end < after {
+                    // `end` is inside of the group - no need to return to the outer level.
+                    current = inside;
+                    current_end = end;
+                } else {
+                    stack.push(after);
+                    current = inside;
+                    current_end = after;
+                }
+                continue;
+            }
+            if let Some((_, next)) = current.token_tree() {
+                total += 1;
+                current = next;
+                continue;
+            }
+        }
+        match stack.pop() {
+            Some(after) => {
+                current = after;
+                current_end = stack.last().copied().unwrap_or(end);
+            }
+            None => break,
+        }
+    }
+    total
+}
+
+/// Returns the number of top-level token trees between `start` and `end`.
+///
+/// # Notes
+///
+/// - Both cursors are expected to point to the same level of nesting, groups are counted as
+///   single tokens without descending into them.
+/// - For such cursors the result is ordered the same way as [`token_distance`], but is much
+///   cheaper to compute for inputs containing large groups.
+pub fn top_level_token_distance<'a>(start: &Cursor<'a>, end: &Cursor<'a>) -> usize {
+    let mut start = *start;
+    let mut end = *end;
+
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+
+    let mut total = 0;
+    while start < end {
+        match start.token_tree() {
+            Some((_, next)) => {
+                total += 1;
+                start = next;
+            }
+            None => break,
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proc_macro2::TokenStream;
+    use syn::buffer::TokenBuffer;
+    use syn::parse_str;
+
+    /// Advances the cursor to the first occurrence of a token with the given string representation.
+    fn advance_cursor<'a>(cursor: Cursor<'a>, target: &str) -> Cursor<'a> {
+        let mut stack = Vec::new();
+        let mut c = cursor;
+        loop {
+            if let Some((inside, _, _, after)) = c.any_group() {
+                stack.push(after);
+                c = inside;
+                continue;
+            }
+
+            match c.token_tree() {
+                Some((tt, next)) => {
+                    if tt.to_string() == target {
+                        return c;
+                    }
+                    c = next;
+                }
+                None => match stack.pop() {
+                    Some(after) => c = after,
+                    None => return cursor,
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn token_distance_forward() {
+        let stream: TokenStream = parse_str("a + b - c * d").unwrap();
+        let buf = TokenBuffer::new2(stream);
+        let start = buf.begin();
+        let end = advance_cursor(start, "c");
+        assert_eq!(token_distance(&start, &end), 4);
+    }
+
+    #[test]
+    fn token_distance_backward() {
+        let stream: TokenStream = parse_str("x * y / z").unwrap();
+        let buf = TokenBuffer::new2(stream);
+        let start = buf.begin();
+        let end = advance_cursor(start, "/");
+        assert_eq!(token_distance(&start, &end), token_distance(&end, &start));
+    }
+
+    #[test]
+    fn token_distance_recursive() {
+        let stream: TokenStream = parse_str("a + (b * c) - d").unwrap();
+        let buf = TokenBuffer::new2(stream);
+        let start = buf.begin();
+        let end = advance_cursor(start, "c");
+        assert_eq!(token_distance(&start, &end), 4);
+    }
+
+    #[test]
+    fn top_level_token_distance_skips_groups() {
+        let stream: TokenStream = parse_str("a + (b * c) - d").unwrap();
+        let buf = TokenBuffer::new2(stream);
+        let start = buf.begin();
+        let end = advance_cursor(start, "-");
+        assert_eq!(top_level_token_distance(&start, &end), 3);
+        assert_eq!(top_level_token_distance(&end, &start), 3);
+    }
+
+    #[test]
+    fn token_distance_deep_nesting() {
+        const DEPTH: usize = 2000;
+
+        let mut stream = quote::quote!(target);
+        for _ in 0..DEPTH {
+            stream = quote::quote!(a (#stream));
+        }
+        let buf = TokenBuffer::new2(stream);
+        let start = buf.begin();
+        let end = advance_cursor(start, "target");
+        assert_eq!(token_distance(&start, &end), DEPTH);
+        let (_, group) = start.token_tree().unwrap();
+        let (_, eof) = group.token_tree().unwrap();
+        assert!(eof.eof());
+        assert_eq!(token_distance(&start, &eof), DEPTH + 1);
+    }
+}
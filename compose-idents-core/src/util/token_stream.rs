@@ -0,0 +1,171 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use std::iter::FromIterator;
+
+pub trait TokenStreamExt {
+    fn into_vec(self) -> Vec<TokenTree>;
+    fn to_vec(&self) -> Vec<TokenTree>;
+}
+
+impl TokenStreamExt for TokenStream {
+    fn into_vec(self) -> Vec<TokenTree> {
+        self.into_iter().collect()
+    }
+    fn to_vec(&self) -> Vec<TokenTree> {
+        self.clone().into_vec()
+    }
+}
+
+pub trait TokenVecExt {
+    fn into_token_stream(self) -> TokenStream;
+    fn to_token_stream(&self) -> TokenStream;
+}
+
+impl TokenVecExt for Vec<TokenTree> {
+    fn into_token_stream(self) -> TokenStream {
+        TokenStream::from_iter(self)
+    }
+
+    fn to_token_stream(&self) -> TokenStream {
+        self.clone().into_token_stream()
+    }
+}
+
+impl TokenVecExt for &[TokenTree] {
+    fn into_token_stream(self) -> TokenStream {
+        TokenStream::from_iter(self.iter().cloned())
+    }
+
+    fn to_token_stream(&self) -> TokenStream {
+        TokenStream::from_iter(self.iter().cloned())
+    }
+}
+
+/// Canonical form of a token-tree that doesn't depend on spacing and provenance of the tokens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonicalToken {
+    Ident(String),
+    /// Punctuation character - joint-ness is ignored.
+    Punct(char),
+    Literal(String),
+    Group(Delimiter, Vec<CanonicalToken>),
+}
+
+/// Converts a token-stream into its canonical form suitable for comparisons.
+///
+/// Invisible groups (produced for example by `macro_rules` fragments) are flattened since they
+/// are equivalent to their contents.
+pub fn canonicalize(stream: &TokenStream) -> Vec<CanonicalToken> {
+    let mut tokens = Vec::new();
+    for tt in stream.clone() {
+        match tt {
+            TokenTree::Ident(ident) => tokens.push(CanonicalToken::Ident(ident.to_string())),
+            TokenTree::Punct(punct) => tokens.push(CanonicalToken::Punct(punct.as_char())),
+            TokenTree::Literal(literal) => {
+                tokens.push(CanonicalToken::Literal(literal.to_string()))
+            }
+            TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+                tokens.extend(canonicalize(&group.stream()))
+            }
+            TokenTree::Group(group) => tokens.push(CanonicalToken::Group(
+                group.delimiter(),
+                canonicalize(&group.stream()),
+            )),
+        }
+    }
+    tokens
+}
+
+/// Checks whether two token-streams consist of the same tokens regardless of their spacing.
+pub fn token_streams_eq(a: &TokenStream, b: &TokenStream) -> bool {
+    canonicalize(a) == canonicalize(b)
+}
+
+/// Renders a token-stream in its canonical form - token-streams consisting of the same tokens are
+/// rendered the same way regardless of their spacing.
+pub fn canonical_string(stream: &TokenStream) -> String {
+    fn render(tokens: &[CanonicalToken]) -> String {
+        tokens
+            .iter()
+            .map(|token| match token {
+                CanonicalToken::Ident(string) | CanonicalToken::Literal(string) => string.clone(),
+                CanonicalToken::Punct(punct) => punct.to_string(),
+                CanonicalToken::Group(delimiter, tokens) => {
+                    let (open, close) = match delimiter {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Brace => ("{", "}"),
+                        Delimiter::Bracket => ("[", "]"),
+                        Delimiter::None => ("", ""),
+                    };
+                    format!("{}{}{}", open, render(tokens), close)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+    render(&canonicalize(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonical_string, token_streams_eq};
+    use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+    use quote::quote;
+    use std::str::FromStr;
+
+    macro_rules! quote_vec_of {
+        ($t:ty) => {
+            quote!(Vec<$t>)
+        };
+    }
+
+    fn parse(s: &str) -> TokenStream {
+        TokenStream::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn spacing_is_ignored() {
+        assert!(token_streams_eq(&quote!(Vec<u8>), &parse("Vec < u8 >")));
+        assert!(token_streams_eq(&parse("a::b"), &parse("a : : b")));
+        assert!(token_streams_eq(
+            &syn::parse_str::<syn::Type>("Result<u32, String>")
+                .map(|type_| quote!(#type_))
+                .unwrap(),
+            &quote!(Result<u32, String>),
+        ));
+    }
+
+    #[test]
+    fn macro_rules_fragment() {
+        assert!(token_streams_eq(&quote_vec_of!(u8), &parse("Vec<u8>")));
+    }
+
+    #[test]
+    fn invisible_groups_are_flattened() {
+        let inner = Group::new(Delimiter::None, quote!(u8));
+        let stream = quote!(Vec<#inner>);
+        assert!(matches!(
+            stream.clone().into_iter().nth(2),
+            Some(TokenTree::Group(_))
+        ));
+        assert!(token_streams_eq(&stream, &quote!(Vec<u8>)));
+    }
+
+    #[test]
+    fn canonical_strings() {
+        let inner = Group::new(Delimiter::None, quote!(u8));
+        let expected = canonical_string(&quote!(Vec<u8>));
+        assert_eq!(canonical_string(&parse("Vec < u8 >")), expected);
+        assert_eq!(canonical_string(&quote_vec_of!(u8)), expected);
+        assert_eq!(canonical_string(&quote!(Vec<#inner>)), expected);
+        assert_ne!(canonical_string(&quote!(Vec<u16>)), expected);
+        assert_eq!(canonical_string(&parse("f( a,b )")), "f (a , b)");
+    }
+
+    #[test]
+    fn different_tokens() {
+        assert!(!token_streams_eq(&quote!(Vec<u8>), &quote!(Vec<u16>)));
+        assert!(!token_streams_eq(&quote!((a)), &quote!([a])));
+        assert!(!token_streams_eq(&quote!("a"), &quote!(a)));
+        assert!(!token_streams_eq(&quote!(a b), &quote!(a)));
+    }
+}
@@ -0,0 +1,119 @@
+//! Machine-readable trace of macro expansions for external tooling.
+//!
+//! Tracing is enabled by `COMPOSE_IDENTS_TRACE=/path/to/dir` environment variable or by
+//! `options(trace)`. Every expanded block-rewrite appends one JSON line to `trace.jsonl` inside
+//! the directory:
+//!
+//! ```plain,ignore
+//! {"aliases":{"name":"foo","suffix":"bar"},"combination":0,"combinations":2,"docs":{"name":"Docs."},"invocation_id":1,"private":["suffix"]}
+//! ```
+//!
+//! `aliases` holds all the aliases defined by the invocation - including the ones unused by the
+//! blocks and the private ones, which are listed in `private`. `docs` holds the doc-comments of
+//! the documented aliases among `aliases`.
+use crate::ast::Value;
+use crate::error::Error;
+use proc_macro2::Span;
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Environment variable specifying the directory the trace is written to.
+pub const TRACE_DIR_ENV_VAR: &str = "COMPOSE_IDENTS_TRACE";
+/// Name of the trace file inside the trace directory.
+pub const TRACE_FILE_NAME: &str = "trace.jsonl";
+
+/// Resolves the trace directory - the one from [`TRACE_DIR_ENV_VAR`] or, if tracing is forced
+/// with `options(trace)`, a `compose-idents` directory inside the system temporary directory.
+pub fn trace_dir(lookup: &dyn Fn(&str) -> Option<String>, forced: bool) -> Option<PathBuf> {
+    match lookup(TRACE_DIR_ENV_VAR).filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None if forced => Some(std::env::temp_dir().join("compose-idents")),
+        None => None,
+    }
+}
+
+/// A single traced expansion of a block-rewrite.
+pub struct TraceRecord<'a> {
+    pub invocation_id: u64,
+    pub combination: usize,
+    pub combinations: usize,
+    /// Values of the aliases along with the flags of the private ones.
+    pub aliases: &'a BTreeMap<String, (Rc<Value>, bool)>,
+    pub docs: BTreeMap<String, String>,
+}
+
+impl TraceRecord<'_> {
+    /// Renders the record as a single JSON line (including the trailing newline).
+    pub fn to_json_line(&self) -> String {
+        let aliases = self
+            .aliases
+            .iter()
+            .map(|(alias, (value, _))| (alias.clone(), serde_json::Value::from(value.to_string())))
+            .collect::<serde_json::Map<_, _>>();
+        let private = self
+            .aliases
+            .iter()
+            .filter(|(_, (_, is_private))| *is_private)
+            .map(|(alias, _)| alias.clone())
+            .collect::<Vec<_>>();
+        let record = serde_json::json!({
+            "invocation_id": self.invocation_id,
+            "combination": self.combination,
+            "combinations": self.combinations,
+            "aliases": aliases,
+            "docs": self.docs,
+            "private": private,
+        });
+        format!("{}\n", record)
+    }
+
+    /// Appends the record to the trace file inside `dir`.
+    ///
+    /// The file is opened in append mode and the line is written with a single call, so records
+    /// of concurrently running compiler processes don't interleave.
+    pub fn write(&self, dir: &Path) -> Result<(), Error> {
+        let path = dir.join(TRACE_FILE_NAME);
+        let make_error = |err: std::io::Error| {
+            Error::EvalError(
+                format!("Failed to write trace to {}: {}", path.display(), err),
+                Span::call_site(),
+            )
+        };
+        std::fs::create_dir_all(dir).map_err(make_error)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(make_error)?;
+        file.write_all(self.to_json_line().as_bytes())
+            .map_err(make_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{trace_dir, TRACE_DIR_ENV_VAR};
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    #[rstest]
+    #[case::env_var(Some("/tmp/trace"), false, Some(PathBuf::from("/tmp/trace")))]
+    #[case::env_var_forced(Some("/tmp/trace"), true, Some(PathBuf::from("/tmp/trace")))]
+    #[case::forced(None, true, Some(std::env::temp_dir().join("compose-idents")))]
+    #[case::empty_env_var(Some(""), false, None)]
+    #[case::disabled(None, false, None)]
+    fn resolving_dir(
+        #[case] env_value: Option<&str>,
+        #[case] forced: bool,
+        #[case] expected: Option<PathBuf>,
+    ) {
+        let lookup = |name: &str| {
+            assert_eq!(name, TRACE_DIR_ENV_VAR);
+            env_value.map(str::to_string)
+        };
+        assert_eq!(trace_dir(&lookup, forced), expected);
+    }
+}
@@ -0,0 +1,109 @@
+//! Declarative fallback of [compose-idents](https://crates.io/crates/compose-idents).
+//!
+//! [`compose_simple!`] supports the simplest case of `compose!` - a single alias of an identifier
+//! used in a code block. It is written with `macro_rules!`, so the crate has no dependencies and
+//! no procedural macros to compile.
+//!
+//! The syntax is a strict subset of the `compose!` syntax, so migrating to `compose!` is a change
+//! of the macro name. Declarative macros can't make new identifiers out of parts on stable Rust,
+//! so the value of the alias must be an identifier spelled out in full - `compose!` is needed for
+//! anything else:
+//!
+//! - `concat()` and other functions.
+//! - Loops.
+//! - Multiple aliases.
+//! - `options(...)`.
+//! - `% alias %` placeholders in string literals.
+#![no_std]
+
+/// Replaces the alias with the identifier in the code block.
+///
+/// Accepts `alias = value, { ... }` - a single alias defined as an identifier, followed by the
+/// code block. Occurrences of the alias are replaced everywhere in the code block, including the
+/// nested groups, but not inside string literals.
+///
+/// The code block is processed token by token with the macro recursion, so blocks longer than
+/// about a hundred tokens need a higher `#![recursion_limit]` of the crate.
+///
+/// # Example
+///
+/// ```rust
+/// use compose_idents_simple::compose_simple;
+///
+/// compose_simple!(
+///     getter = get_value,
+///     {
+///         fn getter() -> u32 { 42 }
+///     },
+/// );
+///
+/// assert_eq!(get_value(), 42);
+/// ```
+#[macro_export]
+macro_rules! compose_simple {
+    ($alias:ident = $value:ident, { $($block:tt)* } $(,)?) => {
+        $crate::__compose_simple_define! { ($) $alias $value { $($block)* } }
+    };
+}
+
+/// Defines a substituting macro matching the alias literally and invokes it on the code block.
+///
+/// `macro_rules!` can't compare a token with a captured identifier, so the alias is baked into the
+/// patterns of a nested macro. `$d` is the `$` token - a nested macro can't spell its own
+/// metavariables otherwise.
+///
+/// The nested macro walks the tokens accumulating the output in the first bracket. On entering a
+/// group the output so far, the delimiter of the group and the tokens following it are pushed to
+/// the stack in the second bracket - they are popped once the content of the group is exhausted.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __compose_simple_define {
+    (($d:tt) $alias:ident $value:ident { $($block:tt)* }) => {
+        macro_rules! __compose_simple_substitute {
+            ([$d($d out:tt)*] []) => {
+                $d($d out)*
+            };
+            ([$d($d out:tt)*] [$d($d stack:tt)*] $alias $d($d rest:tt)*) => {
+                __compose_simple_substitute! {
+                    [$d($d out)* $value] [$d($d stack)*] $d($d rest)*
+                }
+            };
+            ([$d($d out:tt)*] [$d($d stack:tt)*] ($d($d inner:tt)*) $d($d rest:tt)*) => {
+                __compose_simple_substitute! {
+                    [] [[$d($d out)*] () [$d($d rest)*] $d($d stack)*] $d($d inner)*
+                }
+            };
+            ([$d($d out:tt)*] [$d($d stack:tt)*] [$d($d inner:tt)*] $d($d rest:tt)*) => {
+                __compose_simple_substitute! {
+                    [] [[$d($d out)*] [] [$d($d rest)*] $d($d stack)*] $d($d inner)*
+                }
+            };
+            ([$d($d out:tt)*] [$d($d stack:tt)*] {$d($d inner:tt)*} $d($d rest:tt)*) => {
+                __compose_simple_substitute! {
+                    [] [[$d($d out)*] {} [$d($d rest)*] $d($d stack)*] $d($d inner)*
+                }
+            };
+            ([$d($d out:tt)*] [$d($d stack:tt)*] $d token:tt $d($d rest:tt)*) => {
+                __compose_simple_substitute! {
+                    [$d($d out)* $d token] [$d($d stack)*] $d($d rest)*
+                }
+            };
+            ([$d($d out:tt)*] [[$d($d prev:tt)*] () [$d($d rest:tt)*] $d($d stack:tt)*]) => {
+                __compose_simple_substitute! {
+                    [$d($d prev)* ($d($d out)*)] [$d($d stack)*] $d($d rest)*
+                }
+            };
+            ([$d($d out:tt)*] [[$d($d prev:tt)*] [] [$d($d rest:tt)*] $d($d stack:tt)*]) => {
+                __compose_simple_substitute! {
+                    [$d($d prev)* [$d($d out)*]] [$d($d stack)*] $d($d rest)*
+                }
+            };
+            ([$d($d out:tt)*] [[$d($d prev:tt)*] {} [$d($d rest:tt)*] $d($d stack:tt)*]) => {
+                __compose_simple_substitute! {
+                    [$d($d prev)* {$d($d out)*}] [$d($d stack)*] $d($d rest)*
+                }
+            };
+        }
+        __compose_simple_substitute! { [] [] $($block)* }
+    };
+}
@@ -0,0 +1,26 @@
+//! An example of a custom attribute macro built on top of `compose-idents-core`.
+//!
+//! `#[for_each_int(...)]` repeats the annotated item for every unsigned integer type, exposing the
+//! type as the `int` loop variable, so that the macro arguments only need to define the aliases:
+//!
+//! ```rust,ignore
+//! #[for_each_int(max_fn = concat(max_, int))]
+//! fn max_fn() -> int {
+//!     int::MAX
+//! }
+//! ```
+use compose_idents_core::{expand, Session};
+use proc_macro::TokenStream;
+use quote::quote;
+
+#[proc_macro_attribute]
+pub fn for_each_int(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let spec = quote!(for int in [u8, u16, u32, u64, u128] #attr);
+    let result = Session::from_input("for_each_int!: ", &format!("{} {}", spec, item))
+        .and_then(|session| expand(&session, spec, item.into()));
+    match result {
+        Ok(expanded) => expanded.into(),
+        Err(err) => syn::Error::from(err).into_compile_error().into(),
+    }
+}
@@ -0,0 +1,27 @@
+use custom_attribute::for_each_int;
+
+#[for_each_int(max_fn = concat(max_, int))]
+fn max_fn() -> int {
+    int::MAX
+}
+
+#[for_each_int(Wrapper = concat(Wrapper, upper(int)))]
+struct Wrapper(int);
+
+#[test]
+fn functions() {
+    assert_eq!(max_u8(), u8::MAX);
+    assert_eq!(max_u16(), u16::MAX);
+    assert_eq!(max_u32(), u32::MAX);
+    assert_eq!(max_u64(), u64::MAX);
+    assert_eq!(max_u128(), u128::MAX);
+}
+
+#[test]
+fn structs() {
+    assert_eq!(WrapperU8(1).0, 1_u8);
+    assert_eq!(WrapperU16(1).0, 1_u16);
+    assert_eq!(WrapperU32(1).0, 1_u32);
+    assert_eq!(WrapperU64(1).0, 1_u64);
+    assert_eq!(WrapperU128(1).0, 1_u128);
+}
@@ -0,0 +1,15 @@
+use compose_idents::compose;
+
+struct UserId;
+
+compose!(
+    /// Name of the type in the wire format.
+    wire = snake_case(UserId),
+    {
+        impl UserId {
+            const WIRE_NAME: &'static str = "% wire %";
+        }
+    }
+);
+
+assert_eq!(UserId::WIRE_NAME, "user_id");
@@ -0,0 +1,11 @@
+use compose_idents::compose;
+
+compose!(
+    for ty in [Foo, Bar]
+    apply_attrs(#[derive(Debug)], #[doc = "Generated % ty %."]),
+    {
+        pub struct ty;
+    },
+);
+
+assert_eq!(format!("{:?}", Foo), "Foo");
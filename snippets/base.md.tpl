@@ -35,6 +35,12 @@ and [`concat_idents!`][1] macro from the nightly Rust, which is limited in capab
 
   Unique identifiers can be deterministically generated by using the `hash()` function, which is seeded uniquely
   for each invocation of the macro. This might be useful for generating unique global variables.
+- **Reusable engine**
+
+  The expansion pipeline, the string functions (case conversion, `normalize()`) and the identifier validation rules
+  are available as a regular library in the [compose-idents-core](https://crates.io/crates/compose-idents-core)
+  crate - for use in build scripts and for building custom procedural macros on top of it (see
+  `examples/custom-attribute` in the repository).
 
 {{ $h1 }} Usage
 
@@ -152,11 +158,14 @@ test_frobnicate_static_str();
 
 - As a general rule old functionality is not removed abruptly, but rather deprecated first and removed after
   a few releases. This applies to pre-1.0.0 releases as well.
-- Deprecation works through injection of `#[deprecated]` attributes to existing syntactic elements of generated code.
+- Deprecation works through injection of `#[deprecated]` attributes to existing syntactic elements of generated code
+  (or to a hidden constant used by the generated code if there are no suitable elements).
   It triggers deprecation warnings at compile time with text like this:
   ```text,ignore
   compose!: Feature XXX is deprecated, syntax `compose!(...)` is considered obsolete, please use...
   ```
+- Deprecation warnings could be turned into compile errors pointing at the deprecated syntax - for the whole crate
+  with the `deny-deprecated` cargo feature, or for a single invocation with `options(deny_deprecated)`.
 - Removal of a feature without a deprecation process is only possible in pre-1.0.0 releases and in such a case an
   explicit warning is issued in the changelog and the release notes.
 - A deprecated feature is kept for a reasonably long time, or until backwards-compatibility can't be maintained anymore,
@@ -238,6 +247,7 @@ compose_idents!(
 1. Wrap comma-separated arguments in `concat( … )`.
 2. Or use the appropriate function (`upper()`, `lower()`, etc.) directly when only one argument is present.
 3. Or Use the argument itself if no transformation is needed.
+4. Or juxtapose the arguments if they are identifiers, underscores or integers: `my_fn = foo _ bar`.
 
 {{ $h3 }} [≤ 0.2.2 → 0.3.0]: Macro rename compose_idents! → compose!
 
@@ -0,0 +1,14 @@
+use compose_idents::compose;
+
+struct GpuBackend;
+struct CpuBackend;
+
+compose!(
+    backend = cfg(feature = "gpu", GpuBackend, CpuBackend),
+    {
+        type Backend = backend;
+    }
+);
+
+// `CARGO_FEATURE_GPU` isn't set, so the `else` branch is selected.
+assert!(std::any::type_name::<Backend>().ends_with("CpuBackend"));
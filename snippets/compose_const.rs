@@ -0,0 +1,10 @@
+use compose_idents::{compose_const, compose_static};
+
+compose_const!(pub concat(upper(prefix), _LIMIT): u32 = 42; prefix = max);
+compose_static!(concat(upper(name), _GREETING): &str = "Hello, % name %!"; for name in [alice, bob]);
+
+fn main() {
+    assert_eq!(MAX_LIMIT, 42);
+    assert_eq!(ALICE_GREETING, "Hello, alice!");
+    assert_eq!(BOB_GREETING, "Hello, bob!");
+}
@@ -0,0 +1,10 @@
+use compose_idents::compose_expr;
+
+fn get_foo() -> u32 {
+    42
+}
+
+fn main() {
+    let value = compose_expr!(getter = concat(get_, name), name = foo, { getter() });
+    assert_eq!(value, 42);
+}
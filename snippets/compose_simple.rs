@@ -0,0 +1,12 @@
+use compose_idents::compose;
+use compose_idents_simple::compose_simple;
+
+// Only the name of the macro differs.
+compose_simple!(getter = get_foo, {
+    fn getter() -> u32 { 1 }
+});
+compose!(getter = get_bar, {
+    fn getter() -> u32 { 2 }
+});
+
+assert_eq!(get_foo() + get_bar(), 3);
@@ -0,0 +1,14 @@
+use compose_idents::compose_test;
+
+compose_test!(
+    for (name, input, expected) in [
+        (empty, "", 0),
+        (single_word, "foo", 1),
+        ("two words", "foo bar", 2),
+    ]
+    {
+        assert_eq!(input.split_whitespace().count(), expected);
+    },
+);
+
+fn main() {}
@@ -0,0 +1,19 @@
+use compose_idents::compose;
+
+trait Zero {
+    fn zero() -> Self;
+}
+
+compose!(
+    for t in [u8, u16] + [f32, f64]
+    {
+        impl Zero for t {
+            fn zero() -> Self {
+                0 as t
+            }
+        }
+    }
+);
+
+assert_eq!(u16::zero(), 0);
+assert_eq!(f64::zero(), 0.0);
@@ -0,0 +1,11 @@
+use compose_idents::compose;
+
+compose!(
+    for (i, (name, ty)) in enumerate([(SMALL, u8), (LARGE, u64)])
+    {
+        const name: ty = i;
+    }
+);
+
+assert_eq!(SMALL, 0u8);
+assert_eq!(LARGE, 1u64);
@@ -0,0 +1,9 @@
+use compose_idents::compose_item;
+
+#[compose_item(options(expand = after), name = concat(get_, foo))]
+#[inline]
+fn name() -> u32 {
+    1
+}
+
+assert_eq!(get_foo(), 1);
@@ -0,0 +1,13 @@
+use compose_idents::compose;
+
+compose!(
+    for variant in [Foo, Bar]
+
+    field = concat(variant, _, __index__),
+    {
+        const field: usize = __index1__;
+    }
+);
+
+assert_eq!(Foo_0, 1);
+assert_eq!(Bar_1, 2);
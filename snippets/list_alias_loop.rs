@@ -0,0 +1,12 @@
+use compose_idents::compose;
+
+compose!(
+    for w in widths
+    widths = 1..=8 as list,
+    name = concat(Int, w),
+    {
+        struct name([u8; w]);
+    }
+);
+
+assert_eq!(std::mem::size_of::<Int8>(), 8);
@@ -0,0 +1,15 @@
+use compose_idents::compose;
+
+compose!(
+    for a in [u8, u16, u32]
+    for b in [u8, u16, u32] if ne(a, b)
+    name = concat(a, _to_, b),
+    {
+        fn name(value: a) -> b {
+            value as b
+        }
+    }
+);
+
+assert_eq!(u8_to_u32(255), 255u32);
+assert_eq!(u32_to_u8(256), 0u8);
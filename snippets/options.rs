@@ -0,0 +1,13 @@
+use compose_idents::compose;
+
+compose!(
+    options(strict, max_combinations = 100),
+    alias = concat(foo, _, bar),
+    {
+        fn alias() -> u32 {
+            1
+        }
+    }
+);
+
+assert_eq!(foo_bar(), 1);
@@ -0,0 +1,15 @@
+use compose_idents::compose;
+
+compose!(
+    #[inline]
+    #[allow(non_snake_case)]
+    for ty in [Foo, Bar]
+    name = concat(new_, ty),
+    {
+        fn name() -> u32 {
+            1
+        }
+    },
+);
+
+assert_eq!(new_Foo() + new_Bar(), 2);
@@ -0,0 +1,19 @@
+use compose_idents::compose;
+
+struct Config {
+    base: u32,
+}
+
+compose!(
+    let base = snake_case(RequestTimeout), // `base` identifiers in the code block are left untouched
+    getter = concat(get_, base),
+    {
+        impl Config {
+            fn getter(&self) -> u32 {
+                self.base
+            }
+        }
+    }
+);
+
+assert_eq!(Config { base: 30 }.get_request_timeout(), 30);
@@ -29,6 +29,79 @@ the block:
 {{ file.Read "snippets/code_repetition.rs" -}}
 ```
 
+The list of values could also be enclosed in `{...}` or `(...)` instead of `[...]` - the delimiters are
+interchangeable, e.g. `for x in {foo, bar}` is the same as `for x in [foo, bar]`. Tuple values are always enclosed in
+`(...)`.
+
+Loops could also be defined with a substitution table - a header row of loop variables followed by rows of their
+values separated by semicolons. Values containing multiple tokens are enclosed in `[...]` (or `{...}`). It is the same
+syntax as used by the `duplicate` crate, which makes porting its invocations simple:
+```rust
+{{ file.Read "snippets/substitution_table.rs" -}}
+```
+
+The table above is equivalent to `for (int_type, max_value) in [(u8, 255), (u16, 65_535)]`.
+
+With the `serde` cargo feature enabled, loop values could also be read from an inline TOML or JSON document with
+`toml("...")` and `json("...")`. A TOML document must contain a single array of tables and a JSON document must be an
+array of objects. Each row becomes a loop value - its fields are mapped to the loop variables by name and string fields
+are parsed just like any other values:
+```rust
+{{ file.Read "snippets/toml_loop.rs" -}}
+```
+
+All fields must be strings, integers or booleans. Fields that don't correspond to any loop variable are ignored.
+
+A loop could also iterate over an alias bound to a list - `for w in widths`. The loops are expanded before the aliases
+are evaluated, so the alias must be bound either to a list literal (`names = [foo, bar,]`) or to a range of integers
+cast to a list (`widths = 1..=8 as list`). The same alias could be the source of multiple loops and is still usable as
+a regular one, e.g. with `len(widths)`. Referencing an undefined alias results in an `UndefinedNameError`:
+```rust
+{{ file.Read "snippets/list_alias_loop.rs" -}}
+```
+
+A loop could also iterate over the fragments of a string literal split by a separator - `split("<string>",
+"<separator>")`. The fragments are string literals, empty fragments (between consecutive separators) are preserved and
+an empty separator splits the string into characters:
+```rust
+{{ file.Read "snippets/split.rs" -}}
+```
+
+Nested loops iterate over every combination of their values. To iterate over multiple lists of the same length in
+lockstep they could be zipped instead - `zip(...)` pairs the elements of the lists (or aliases bound to lists)
+positionally into tuples. The elements could be tuples themselves, and lists of different lengths are an error:
+```rust
+{{ file.Read "snippets/zip.rs" -}}
+```
+
+Multiple lists (including aliases, `zip(...)`, `split(...)` and `enumerate(...)`) could be concatenated with `+` to
+iterate over their union while keeping them separate. Values of the lists could differ in shape only if the loop
+variable isn't a tuple:
+```rust
+{{ file.Read "snippets/concat_lists.rs" -}}
+```
+
+The elements of a list could be paired with their 0-based indices with `enumerate(...)` - unlike `__index__` the index
+is bound to a loop variable of choice. Tuple elements end up nested - `enumerate([(a, b), ...])` yields `(i, (a, b))`:
+```rust
+{{ file.Read "snippets/enumerate.rs" -}}
+```
+
+Duplicate values of a list could be removed with `unique(...)` - the values are compared by their tokens regardless
+of the spacing and the first occurrences are kept. Removed duplicates are reported with a `duplicate_loop_values`
+warning, which could be suppressed with `allow_deprecated(...)` when the duplicates are expected:
+```rust
+{{ file.Read "snippets/unique.rs" -}}
+```
+
+Combinations of loop values could be skipped with a filter - `if <expr>` after the list of values. The condition is
+evaluated per combination and must produce a `bool`. It could call functions and reference the loop variables of the
+current combination (but not the other aliases). A filter that is always `false` yields no output at all - just like
+an empty list of values:
+```rust
+{{ file.Read "snippets/loop_filter.rs" -}}
+```
+
 {{ $h1 }} Attribute macro form
 
 `#[compose_item(...)]` is an attribute macro equivalent to `compose! { ... }`, except it treats the annotated item as
@@ -37,6 +110,33 @@ the code block. Otherwise, it works the same way:
 {{ file.Read "snippets/compose_item.rs" -}}
 ```
 
+{{ $h1 }} Parametrized tests
+
+`compose_test!` generates a `#[test]` function out of each combination of loop values. It accepts the same input as
+`compose!`, but wraps the code block into a function named `test_<name>`, where `<name>` is the normalized first
+element of the loop variable (the first elements of the variables of multiple loops are joined with `_`). The rest of
+the elements are available as aliases within the block:
+```rust
+{{ file.Read "snippets/compose_test.rs" -}}
+```
+
+{{ $h1 }} Constants and statics
+
+`compose_const!` and `compose_static!` declare a single `const` or `static` item named by an alias expression. They
+accept `NAME_EXPR: Type = value;` followed by the usual loops and alias definitions:
+```rust
+{{ file.Read "snippets/compose_const.rs" -}}
+```
+
+{{ $h1 }} Expressions
+
+`compose_expr!` accepts the same input as `compose!`, but the block must consist of a single expression - so the macro
+could be used in expression position, e.g. on the right-hand side of a `let` or inside an argument list. Loops aren't
+supported since an expression can't expand to multiple values:
+```rust
+{{ file.Read "snippets/compose_expr.rs" -}}
+```
+
 {{ $h1 }} Functions
 
 Functions can be applied to the arguments used for the alias definitions:
@@ -68,6 +168,9 @@ normalizes the result into a valid identifier. Unlike `normalize()`, which opera
 {{ file.Read "snippets/normalize2.rs" -}}
 ```
 
+Both functions replace the characters that can't be a part of an identifier with underscores following the rules of
+Rust - non-ASCII letters are kept (`normalize("größe wert")` is `größe_wert`).
+
 {{ $h1 }} String formatting
 
 Aliases could be used in string formatting with `% alias %` syntax. This is useful for generating doc-attributes:
@@ -93,6 +196,23 @@ This example roughly expands to this:
 {{ file.Read "snippets/hash_expansion.rs" -}}
 ```
 
+The identifiers generated by `hash()` are prefixed with `__` - another prefix could be given either per call with
+`hash(input, "gen_")` or for the whole invocation with `options(hash_prefix = "gen_")`. The prefix must be a valid start
+of an identifier.
+
+To make two separate invocations produce the same identifier, `hash()` also accepts an explicit seed used instead of
+the seed of the invocation - an integer as the second argument (`hash(input, 42)`) or an integer or a string following
+the prefix (`hash(input, "gen_", "my_seed")`). Such output stays the same across invocations, builds and Rust releases
+and doesn't depend on the spacing of the tokens. Note that the second string argument of `hash()` is always the prefix,
+never the seed.
+
+By default the outputs of `hash()` differ between compilations. For reproducible builds the seed could be pinned with
+`COMPOSE_IDENTS_SEED` environment variable (an unsigned integer) - then the output depends only on the seed and on the
+input of the macro invocation. Note that Cargo doesn't rebuild dependent crates when the variable changes.
+
+Apart from `hash()` the output of the macro is always deterministic - the generated items follow the order of the loop
+combinations and the same input always expands to the same tokens, including the attached deprecation attributes.
+
 {{ $h1 }} Concatenating multiple arguments
 
 The `concat()` function takes multiple arguments and concatenates them together. It provides explicit concatenation
@@ -102,8 +222,57 @@ that can be either nested within other function calls or to aggregate results of
 {{ file.Read "snippets/concat.rs" -}}
 ```
 
+{{ $h1 }} Declarative fallback
+
+For the simplest case - a single alias of an identifier used in a code block - the
+[compose-idents-simple](https://crates.io/crates/compose-idents-simple) crate provides `compose_simple!`, a
+`macro_rules!` macro without any dependencies. Its syntax is a strict subset of the `compose!` syntax, so migrating to
+`compose!` is a change of the macro name:
+
+```rust
+{{ file.Read "snippets/compose_simple.rs" -}}
+```
+
+Declarative macros can't make new identifiers out of parts on stable Rust, so the value of the alias must be an
+identifier spelled out in full. `compose!` is needed for anything else - `concat()` and other functions, loops,
+multiple aliases, options or `% alias %` placeholders in string literals. The code block of `compose_simple!` is
+processed token by token with the macro recursion, so blocks longer than about a hundred tokens need a higher
+`#![recursion_limit]` of the crate.
+
 {{ $h1 }} Syntax
 
+Trailing commas are accepted everywhere in the macro arguments - after the last alias, loop value, tuple item, function
+argument and option, after loop headers and after the code block.
+
+The code block of `compose!` goes after the loops and the aliases, but for long specs it could also be put before
+them - `compose!({ ... }, for x in [foo, bar] name = concat(x, _fn))` is the same as
+`compose!(for x in [foo, bar] name = concat(x, _fn), { ... })`.
+
+The loops usually go before the aliases, but they could also follow them or be interleaved with them - all the loops
+are gathered together regardless of where they appear, so `name = concat(x, _fn), for x in [foo, bar] { ... }` is the
+same as `for x in [foo, bar] name = concat(x, _fn), { ... }`. `options(...)` must still go before the aliases.
+
+`compose!` could also take multiple blocks - `compose!(spec..., { ... }, { ... })`. The spec is evaluated once per
+combination of loop values and applied to each of the blocks in order, so the values of functions like `hash()` agree
+between the blocks. It is handy when parts of the generated code have to go to different places - e.g. the tests
+into a separate module.
+
+Outer attributes at the start of the `compose!` invocation are attached to every item (or statement) generated from
+the blocks, in front of the item's own attributes. They are attached to each item separately rather than wrapping the
+output, so `#[cfg(...)]` removes the items themselves. Attributes can't be attached to expressions, so the blocks
+must consist of items and statements. A doc-comment ends the attributes, since it documents the first alias:
+
+```rust
+{{ file.Read "snippets/outer_attrs.rs" -}}
+```
+
+The output of the macro could contain other invocations of it, including ones produced by other macros - each of
+them is expanded separately by the compiler and knows nothing about the invocation it came from, so recursion through
+them is only stopped by the compiler's `recursion_limit`. Procedural macros built on top of `compose-idents-core`
+could nest expansions within each other (e.g. by expanding a specification while handling their own) - such
+expansions fail with an error once they are nested more than 32 levels deep. The limit could be changed with
+`COMPOSE_IDENTS_MAX_DEPTH` environment variable (a positive integer).
+
 {{ $h2 }} Expressions
 
 Expressions consist of values (`foo`, `Foo::Bar`, `1 + 1`, `"bar"`, `123`, etc) and
@@ -120,6 +289,10 @@ them. For explicit casting, see functions described in the "Functions" → "Type
 
 Examples of values of different types could be found in the "Types" section.
 
+Values passed from `macro_rules!` fragments (`$ty`, `$ident`, `$expr`, etc) are detected the same way as if they were
+written literally. Identifiers prefixed with `$` in the code block (`$crate`, metavariables of `macro_rules!` defined
+in it) are never substituted.
+
 {{ $h4 }} String formatting
 
 String literals could be formatted using `% alias %` syntax. This is especially useful for generating doc-attributes.
@@ -141,6 +314,38 @@ but if it's not possible - use `raw()` function to fence the complex argument.
 Functions could be overloaded and have multiple signatures. For example `concat(...)` could work for strings, integers
 and for arbitrary tokens as well. All overloads are listed in the "Functions" section.
 
+Variadic parameters are denoted with `...` - `T...+` accepts one or more arguments of type `T`.
+
+{{ $h3 }} Casts
+
+Any expression could be cast to a particular type using `<expr> as <type>` syntax (see the "Types" section for the
+type names), which is a shorthand for calling the corresponding casting function - `foo as str` is equivalent to
+`to_str(foo)`. The cast applies to the whole preceding expression: `upper(foo) as str` is `to_str(upper(foo))`.
+
+A range of integers with literal bounds is a valid Rust expression, so it is an `expr` value by default. It becomes a
+list of integers only when cast explicitly - `1..=8 as list` is `[1, 2, 3, 4, 5, 6, 7, 8]` (`to_list(1..=8)`).
+Without the cast `len()` of a range is an error instead of the number of its characters, and `index()` doesn't
+accept it.
+
+Casts to types other than the supported ones (like `x as u32`) are treated as regular Rust expressions.
+
+{{ $h3 }} Conditional expressions
+
+`cfg(<predicate>, <then>, <else>)` selects one of the expressions depending on the predicate:
+
+```rust
+{{ file.Read "snippets/cfg_expr.rs" -}}
+```
+
+The predicate supports a subset of the `#[cfg(...)]` syntax - `feature = "..."`, `test`, `debug_assertions` and their
+combinations with `all(...)`, `any(...)` and `not(...)`. Only the selected expression is evaluated.
+
+Proc-macros can't see the configuration of the crate being compiled, so the predicate is evaluated against the
+environment variables of the compiler process at expansion time: `feature = "foo-bar"` checks `CARGO_FEATURE_FOO_BAR`,
+`test` and `debug_assertions` check `CARGO_CFG_TEST` and `CARGO_CFG_DEBUG_ASSERTIONS`. Cargo sets these variables only
+for build scripts, so a crate has to forward them with `cargo:rustc-env=...` from its `build.rs`. It reflects the
+view of the current crate only - features of other crates are not visible.
+
 {{ $h2 }} Aliases
 
 An alias is an identifier assigned an arbitrary expression: `alias = <expr>`. Alias-definitions are separated by commas.
@@ -160,27 +365,264 @@ alias4 = concat(Result<, raw(u32,), String>),
 alias5 = concat(my, _, fn, _, normalize(My::Enum)),
 ```
 
+A sequence of identifiers, underscores and integers separated by whitespace is a shorthand for `concat(...)` of them:
+`alias = prefix _ name _suffix` is the same as `alias = concat(prefix, _, name, _suffix)`. The shorthand only applies
+to two or more such atoms that don't form a single value (a type like `dyn Foo` or a path like `foo::bar`), keywords
+(`pub async`) aren't concatenated, and values annotated as `tokens` are kept as is.
+
+Aliases could be named with raw identifiers - `r#type = to_type("u8")`. Just like in Rust `r#foo` and `foo` are the same
+name, so they are redefinitions of each other and both spellings are substituted in the code block - except for
+keywords, which are only substituted in their raw form (`r#type`, but not `type`). String formatting placeholders
+accept both spellings: `% r#type %` and `% type %`.
+
+Functions producing identifiers (`concat()`, `normalize()`, `to_ident()`, etc.) escape keywords as raw identifiers -
+`concat(ma, tch)` yields `r#match`. The keywords that can't be raw identifiers (`crate`, `self`, `super` and `Self`)
+produce an error instead. The `r#` prefix could be stripped off with `unraw()`.
+
+An alias could expand to a multi-segment path anywhere a path is accepted - including use-declarations. With
+`backend = foo::bar` the declaration `use backends::backend::{Handler, Config};` becomes
+`use backends::foo::bar::{Handler, Config};`, the same applies to glob (`use backend::*;`), renamed
+(`use backend as b;`) and nested use-trees.
+
 {{ $h3 }} Alias re-use
 
-Aliases could be re-used in subsequent (but not preceding) definitions of other aliases:
+Aliases could be re-used in definitions of other aliases - regardless of the order of the definitions:
 
 ```plain,ignore
 alias1 = foo,
 alias2 = concat(alias1, _, bar), // alias1 is re-used here
+alias3 = concat(alias4, _, baz), // alias4 is re-used before its definition
+alias4 = upper(alias1),
 ```
 
+Aliases are evaluated in the order of their references to each other, otherwise keeping the order of the definitions.
+Aliases referencing each other in a cycle (`a = upper(b), b = lower(a)`) cause an error. A reference of an alias to its
+own name is not re-use - the name is taken literally, e.g. `Wrapper = concat(Wrapper, Int)` is `WrapperInt`.
+
+Only aliases that are used in the code block (directly, in string formatting placeholders or through other used
+aliases) are evaluated - so an unused alias is never evaluated and can't cause an evaluation error.
+
+{{ $h3 }} Private aliases
+
+Aliases defined with `let` are private - they could be re-used by other aliases, but aren't substituted into the
+code block. It is useful for intermediate values which names could clash with identifiers in the code block:
+
+```rust
+{{ file.Read "snippets/private_aliases.rs" -}}
+```
+
+{{ $h3 }} Documenting aliases
+
+Alias definitions could be preceded by doc-comments (`/// ...` or `#[doc = "..."]`) explaining their purpose. The
+doc-comments don't affect the expansion, but are included into the expansion trace (see "Expansion trace"):
+
+```rust
+{{ file.Read "snippets/alias_docs.rs" -}}
+```
+
+{{ $h3 }} Tuple destructuring
+
+Multiple aliases could be defined at once by destructuring a tuple of values - the same way as in loops. Tuples
+could be nested, but the shape of the aliases must match the shape of the values:
+
+```rust
+{{ file.Read "snippets/tuple_destructuring.rs" -}}
+```
+
+Values that aren't needed could be discarded with `_` placeholders - both here and in loops, e.g.
+`for (name, _, default) in [...]`. A placeholder in place of a nested tuple discards the whole tuple, and the
+placeholders aren't substituted in the code block.
+
+{{ $h3 }} Builtin aliases
+
+The following aliases are always available in the code block (including string formatting placeholders) unless
+shadowed by user-defined aliases of the same name:
+
+- `__invocation_id` - an integer uniquely identifying the macro invocation.
+- `__seed` - the random seed of the macro invocation used by functions like `hash()`.
+
+Within loops two more aliases hold the position of the current combination of loop values (in the order of the
+expansion, combinations skipped by loop filters aren't counted) - `__index__` counts from zero and `__index1__` from
+one. They could be used by other aliases as well:
+
+```rust
+{{ file.Read "snippets/index_aliases.rs" -}}
+```
+
+Shadowing them by user-defined aliases (including loop aliases) is reported with a warning - it is a notice rather
+than a deprecation, so `deny_deprecated` doesn't turn it into an error.
+
+{{ $h3 }} Spans of substituted identifiers
+
+When an alias evaluates to an identifier, the substituted identifier is located at the place where the alias is used
+in the code block (while keeping the hygiene of the evaluated value). This way compiler diagnostics and IDE features like
+go-to-definition point to the code block - for example, to `fn getter()` for a function generated from
+`fn getter() {...}` - rather than to the alias definition. Values consisting of multiple tokens keep their original
+spans.
+
+{{ $h3 }} Type annotations
+
+The type of an alias could be specified explicitly with `alias: <type> = <expr>` (see the "Types" section for the
+type names). For values the annotation forces how they are interpreted - `alias: type = [u8; 4]` and
+`alias: expr = [u8; 4]` yield values of different types. For function calls overloads producing exactly the annotated
+type are preferred:
+
+```rust
+{{ file.Read "snippets/type_annotations.rs" -}}
+```
+
+String literals annotated as `path`, `type` or `expr` are parsed by their content - `alias: type = "Result<T, E>"`
+yields the `Result<T, E>` type.
+
+Parentheses around a value annotated as `tokens` only group it - they are stripped, so comma-containing tokens could be
+passed through: `alias: tokens = (A, B)` yields `A, B`. Without the annotation `(A, B)` remains a tuple type.
+
+It is an error if the value can't be interpreted as or coerced to the annotated type.
+
+{{ $h2 }} Options
+
+Behavior of a macro invocation could be adjusted with an `options(...)` directive placed at the start of the alias
+spec (after the loops, if any). It contains comma-separated flags, `key = value` pairs and lists of items - `key(item, ...)`:
+
+```rust
+{{ file.Read "snippets/options.rs" -}}
+```
+
+| Option                        | Description                                                                        |
+|-------------------------------|------------------------------------------------------------------------------------|
+| `strict`                      | Evaluates all aliases - including the ones that are not used by the code block.    |
+| `max_combinations = <n>`      | Fails if the loops yield more than `n` combinations (10000 by default).            |
+| `deny_deprecated`             | Turns usage of deprecated syntax into compile errors instead of warnings.          |
+| `allow_global_state`          | Allows functions relying on state shared between invocations (`global_counter()`). |
+| `allow_deprecated(<id>, ...)` | Suppresses the deprecation warnings with the given identifiers (see below).        |
+| `trace`                       | Writes the expansion trace (see below). Requires `trace` cargo feature.            |
+| `pretty`                      | Formats the expanded code (see below). Requires `pretty` cargo feature.            |
+| `expand = before \| after`    | Order of `#[compose_item]` expansion relative to other attribute macros (see below). |
+| `hash_prefix = "<prefix>"`    | Prefix of the identifiers generated by `hash()` instead of `__`.                   |
+| `scoped`                      | Wraps the generated items into an anonymous const scope (see below).               |
+
+Flags could also be set explicitly - `strict = false`. Unknown and repeated options are errors.
+
+Identifiers of the deprecation warnings accepted by `allow_deprecated(...)`:
+
+| Identifier              | Deprecated syntax                                            |
+|-------------------------|--------------------------------------------------------------|
+| `semicolon_separator`   | Semicolons as separators of alias definitions.               |
+| `index_alias_shadowing` | User-defined aliases shadowing `__index__` and `__index1__`. |
+| `duplicate_loop_values` | Duplicate values removed from a list by `unique(...)`.       |
+
+{{ $h3 }} Expansion order
+
+Attribute macros are expanded from the outermost to the innermost one, and each of them receives the item along with
+the attributes that are not expanded yet. So by default (`expand = before`) `#[compose_item]` substitutes the aliases
+everywhere in the item, including the arguments of the attributes following it, and emits them untouched - the macros
+behind them see the code with the aliases substituted.
+
+With `expand = after` `#[compose_item]` instead re-emits itself beneath the other outer attributes of the item, so it
+is expanded after them - they see the code as it was written, and the aliases are substituted in their output:
+
+```rust
+{{ file.Read "snippets/expand_after.rs" -}}
+```
+
+Notes:
+
+- The re-emitted attribute is referenced as `::compose_idents::compose_item`, so the crate must not be renamed in
+  `Cargo.toml`.
+- Only the item carrying the attribute is processed - items generated by the other macros next to it are not. For that
+  reason deferring after `#[derive(...)]` is an error.
+- The option is only supported by `#[compose_item]`.
+
+{{ $h3 }} Scoped output
+
+Helper items generated by an invocation could collide with the items of the surrounding module or of other
+invocations. With `options(scoped)` the items of each loop combination are wrapped into an anonymous
+`const _: () = { ... };` scope the way many derive macros do, so their names don't leak. Impl blocks and the items
+marked with `#[compose(export)]` are left outside of the scope - they remain visible, but can't reference the scoped
+items. The exception are impl blocks of the scoped types and traits - they stay in the scope next to them (impls are
+effective regardless of the scope):
+
+```rust
+{{ file.Read "snippets/scoped.rs" -}}
+```
+
+The `#[compose(export)]` marker is removed from the output even without the option. The blocks of a scoped invocation
+must consist of items.
+
+{{ $h3 }} Applying attributes
+
+Attributes that every generated item needs (but that don't belong to the code block itself) could be listed in an
+`apply_attrs(...)` directive placed next to `options(...)`. They are appended to each top-level item of the generated
+code unless the item already has an identical attribute; nested items aren't affected. Placeholders in string literals
+of the attributes are expanded per loop combination:
+
+```rust
+{{ file.Read "snippets/apply_attrs.rs" -}}
+```
+
+{{ $h3 }} Conditional generation
+
+A `when(<expr>)` directive placed next to `options(...)` generates the code only for the combinations of loop values
+the condition evaluates to `true` for. Unlike a loop filter, the condition is evaluated after the aliases, so it could
+reference any of them - including the private ones. Without loops it switches the whole invocation on and off:
+
+```rust
+{{ file.Read "snippets/when.rs" -}}
+```
+
+{{ $h3 }} Expansion trace
+
+With the `trace` cargo feature enabled, each invocation could report how it was expanded - for consumption by linters
+and other tooling. Tracing is enabled for all invocations by the `COMPOSE_IDENTS_TRACE=/path/to/dir` environment
+variable, or for a single invocation by `options(trace)` (if the variable isn't set, the trace goes to the
+`compose-idents` directory inside the system temporary directory). Every expanded code block appends a JSON line to
+`trace.jsonl` inside the directory:
+
+```json
+{"aliases":{"__invocation_id":"7","__seed":"1234","p":"get_a","x":"a"},"combination":0,"combinations":2,"docs":{"x":"Docs."},"invocation_id":7,"private":["p"]}
+```
+
+`combination` is the index of the loop combination and `combinations` is their total number. Values of the aliases are
+rendered as strings. All the defined aliases are traced - including the ones unused by the code blocks (those failing
+to evaluate are left out) and the private ones, which are listed in `private`. `docs` holds the doc-comments of the
+documented aliases (see "Documenting aliases").
+
+{{ $h3 }} Formatted output
+
+With the `pretty` cargo feature enabled, `options(pretty)` formats the expansion of each loop combination with
+`prettyplease` and precedes it with a comment listing the values of the loop variables:
+
+```plain,ignore
+// --- combination 1 (x=a, ty=u8) ---
+struct a_u8(u8);
+// --- combination 2 (x=b, ty=u16) ---
+struct b_u16(u16);
+```
+
+The code block must consist of items. Note that a macro passes tokens to the compiler - comments and whitespace are
+discarded, and tools like `cargo expand` print the tokens with their own formatting. The formatted code is tokenized
+again, so its tokens point at the macro invocation instead of the code block in error messages. For snapshot tests the
+`pretty` feature of `compose-idents-core` provides `expand_compose_pretty()` returning the formatted code as a string.
+
 {{ $h2 }} Types
 
-| Type     | Example                              | Description                                                                                                                                                                                  |
-|----------|--------------------------------------|----------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
-| `ident`  | `foo`                                | Identifier type.                                                                                                                                                                             |
-| `type`   | `Result<u32, Error>`                 | Type type.                                                                                                                                                                                   |
-| `path`   | `foo::bar`                           | Path type.                                                                                                                                                                                   |
-| `expr`   | `2 + 2`, `if c { 1 } else { 0 }`     | Expression type.                                                                                                                                                                             |
-| `str`    | `"foo"`                              | Literal string type.                                                                                                                                                                         |
-| `int`    | `123`                                | Literal integer type.                                                                                                                                                                        |
-| `tokens` | `mod foo { fn bar() -> u32 { 0 } }`  | Arbitrary evaluated token-sequence. If used as a function argument - terminated by a comma, and if it contains expressions - they are evaluated.                                             |
-| `raw`    | `mod foo { fn bar() -> u32 { 0 } }`  | Raw unevaluated token-sequence. Only used as a type of a single input argument - doesn't respect any delimiters, if contains expressions - they are treated as raw tokens and not evaluated. |
+| Type       | Example                             | Description                                                                                                                                                                                                                                                                                       |
+|------------|-------------------------------------|---------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
+| `ident`    | `foo`                               | Identifier type.                                                                                                                                                                                                                                                                                  |
+| `type`     | `Result<u32, Error>`                | Type type.                                                                                                                                                                                                                                                                                        |
+| `path`     | `foo::bar`                          | Path type.                                                                                                                                                                                                                                                                                        |
+| `expr`     | `2 + 2`, `if c { 1 } else { 0 }`    | Expression type.                                                                                                                                                                                                                                                                                  |
+| `str`      | `"foo"`                             | Literal string type.                                                                                                                                                                                                                                                                              |
+| `byte_str` | `b"foo"`                            | Literal byte string type.                                                                                                                                                                                                                                                                         |
+| `int`      | `123`, `-1`                         | Literal integer type. A leading minus is a part of the literal (`-1`).                                                                                                                                                                                                                            |
+| `float`    | `1.5`, `2.5f32`                     | Literal float type. Suffixes are preserved when the value is substituted or formatted.                                                                                                                                                                                                            |
+| `char`     | `'a'`, `'\n'`                       | Literal char type. Rendered without quotes in string formatting.                                                                                                                                                                                                                                  |
+| `bool`     | `true`, `false`                     | Literal boolean type.                                                                                                                                                                                                                                                                             |
+| `lifetime` | `'a`, `'static`                     | Lifetime. An alias bound to a lifetime could be used both as `alias` and as `'alias`.                                                                                                                                                                                                             |
+| `tokens`   | `mod foo { fn bar() -> u32 { 0 } }` | Arbitrary evaluated token-sequence. If used as a function argument - terminated by a comma, and if it contains expressions - they are evaluated.                                                                                                                                                  |
+| `raw`      | `mod foo { fn bar() -> u32 { 0 } }` | Raw unevaluated token-sequence. Only used as a type of a single input argument - doesn't respect any delimiters, if contains expressions - they are treated as raw tokens and not evaluated.                                                                                                      |
+| `attr`     | `#[derive(Debug, Clone)]`           | Outer attribute. An alias bound to an attribute (or to a list of them) and used as an attribute - `#[alias]` - is replaced by the bound attribute(s). Consecutive attributes form a list.                                                                                                         |
+| `[T]`      | `[foo, bar]`, `[[1, 2], [3, 4]]`    | List of values of type `T`. A list literal requires at least one comma (`[foo,]`), otherwise it's parsed as a type or an expression - except for items of a list (`[[a, b], [c]]`) and arguments where a list is expected (`len([foo])`). Items of different types are cast to their common type. |
 
 {{ $h3 }} Coercion rules
 
@@ -192,7 +634,11 @@ all possible type conversion directions, only the most useful ones and the ones
 | `ident` | `path`   | Identifier to path (e.g., `foo` → `foo`)       |
 | `ident` | `type`   | Identifier to type (e.g., `u32` → `u32`)       |
 | `ident` | `expr`   | Identifier to expression (e.g., `foo` → `foo`) |
-| any     | `tokens` | Any value to tokens                            |
+| `float` | `expr`   | Float to expression (e.g., `1.5` → `1.5`)      |
+| `bool`  | `ident`  | Boolean to identifier (e.g., `true` → `true`)  |
+| `char`  | `str`    | Char to string (e.g., `'a'` → `"a"`)           |
+| any     | `tokens` | Any value to tokens (except lists)             |
+| `[T]`   | `[U]`    | List to list if `T` coerces to `U`             |
 
 {{ $h2 }} Functions
 
@@ -200,60 +646,156 @@ all possible type conversion directions, only the most useful ones and the ones
 
 Functions that change the case or style.
 
-| Function                      | Description                                 | Example                  | Example Result |
-|-------------------------------|---------------------------------------------|--------------------------|----------------|
-| `upper(str) -> str`           | Converts the string argument to UPPER case. | `upper("foo")`           | `"FOO"`        |
-| `upper(ident) -> ident`       | Converts the ident argument to UPPER case.  | `upper(foo)`             | `FOO`          |
-| `lower(str) -> str`           | Converts the string argument to lower case. | `lower("FOO")`           | `"foo"`        |
-| `lower(ident) -> ident`       | Converts the ident argument to lower case.  | `lower(FOO)`             | `foo`          |
-| `snake_case(str) -> str`      | Converts the string argument to snake_case. | `snake_case("FooBar")`   | `"foo_bar"`    |
-| `snake_case(ident) -> ident`  | Converts the ident argument to snake_case.  | `snake_case(FooBar)`     | `foo_bar`      |
-| `camel_case(str) -> str`      | Converts the string argument to camelCase.  | `camel_case("foo_bar")`  | `"fooBar"`     |
-| `camel_case(ident) -> ident`  | Converts the ident argument to camelCase.   | `camel_case(foo_bar)`    | `fooBar`       |
-| `pascal_case(str) -> str`     | Converts the string argument to PascalCase. | `pascal_case("foo_bar")` | `"FooBar"`     |
-| `pascal_case(ident) -> ident` | Converts the ident argument to PascalCase.  | `pascal_case(foo_bar)`   | `FooBar`       |
+| Function                            | Description                                                 | Example                          | Example Result |
+|-------------------------------------|-------------------------------------------------------------|----------------------------------|----------------|
+| `upper(str) -> str`                 | Converts the string argument to UPPER case.                 | `upper("foo")`                   | `"FOO"`        |
+| `upper(ident) -> ident`             | Converts the ident argument to UPPER case.                  | `upper(foo)`                     | `FOO`          |
+| `upper(path) -> path`               | Converts the last segment of the path to UPPER case.        | `upper(foo::Bar)`                | `foo::BAR`     |
+| `lower(str) -> str`                 | Converts the string argument to lower case.                 | `lower("FOO")`                   | `"foo"`        |
+| `lower(ident) -> ident`             | Converts the ident argument to lower case.                  | `lower(FOO)`                     | `foo`          |
+| `lower(path) -> path`               | Converts the last segment of the path to lower case.        | `lower(foo::BAR)`                | `foo::bar`     |
+| `snake_case(str) -> str`            | Converts the string argument to snake_case.                 | `snake_case("FooBar")`           | `"foo_bar"`    |
+| `snake_case(ident) -> ident`        | Converts the ident argument to snake_case.                  | `snake_case(FooBar)`             | `foo_bar`      |
+| `snake_case(path) -> path`          | Converts the last segment of the path to snake_case.        | `snake_case(foo::FooBar)`        | `foo::foo_bar` |
+| `shouty_snake_case(str) -> str`     | Converts the string argument to SHOUTY_SNAKE_CASE.          | `shouty_snake_case("FooBar")`    | `"FOO_BAR"`    |
+| `shouty_snake_case(ident) -> ident` | Converts the ident argument to SHOUTY_SNAKE_CASE.           | `shouty_snake_case(FooBar)`      | `FOO_BAR`      |
+| `shouty_snake_case(path) -> path`   | Converts the last segment of the path to SHOUTY_SNAKE_CASE. | `shouty_snake_case(foo::FooBar)` | `foo::FOO_BAR` |
+| `kebab_case(str) -> str`            | Converts the string argument to kebab-case.                 | `kebab_case("FooBar")`           | `"foo-bar"`    |
+| `kebab_case(ident) -> str`          | Converts the ident argument to a kebab-case string.         | `kebab_case(FooBar)`             | `"foo-bar"`    |
+| `title_case(str) -> str`            | Converts the string argument to Title Case.                 | `title_case("foo_bar")`          | `"Foo Bar"`    |
+| `title_case(ident) -> str`          | Converts the ident argument to a Title Case string.         | `title_case(foo_bar)`            | `"Foo Bar"`    |
+| `camel_case(str) -> str`            | Converts the string argument to camelCase.                  | `camel_case("foo_bar")`          | `"fooBar"`     |
+| `camel_case(ident) -> ident`        | Converts the ident argument to camelCase.                   | `camel_case(foo_bar)`            | `fooBar`       |
+| `camel_case(path) -> path`          | Converts the last segment of the path to camelCase.         | `camel_case(foo::foo_bar)`       | `foo::fooBar`  |
+| `pascal_case(str) -> str`           | Converts the string argument to PascalCase.                 | `pascal_case("foo_bar")`         | `"FooBar"`     |
+| `pascal_case(ident) -> ident`       | Converts the ident argument to PascalCase.                  | `pascal_case(foo_bar)`           | `FooBar`       |
+| `pascal_case(path) -> path`         | Converts the last segment of the path to PascalCase.        | `pascal_case(foo::foo_bar)`      | `foo::FooBar`  |
+| `capitalize(str) -> str`            | Uppercases the first character of the string argument.      | `capitalize("fooBar")`           | `"FooBar"`     |
+| `capitalize(ident) -> ident`        | Uppercases the first character of the ident argument.       | `capitalize(someHTTP)`           | `SomeHTTP`     |
+| `capitalize(path) -> path`          | Uppercases the first character of the last path segment.    | `capitalize(foo::bar)`           | `foo::Bar`     |
+| `decapitalize(str) -> str`          | Lowercases the first character of the string argument.      | `decapitalize("FooBar")`         | `"fooBar"`     |
+| `decapitalize(ident) -> ident`      | Lowercases the first character of the ident argument.       | `decapitalize(HTTPFoo)`          | `hTTPFoo`      |
+| `decapitalize(path) -> path`        | Lowercases the first character of the last path segment.    | `decapitalize(foo::Bar)`         | `foo::bar`     |
+
+Non-ASCII identifiers (`größe`, `μέγεθος`, `名前`) are supported. `upper()`, `lower()`, `capitalize()` and
+`decapitalize()` follow the Unicode case mapping. The other functions split words the way it's done in cased
+alphabets, so scripts without case are kept as they are, and inputs with identifier characters that are neither
+letters nor digits (e.g. the combining marks of Devanagari) are returned unchanged. If the result is not a valid
+identifier (e.g. `upper(r#type)`), an error is reported.
 
 {{ $h3 }} Token manipulation
 
 General purpose functions that perform useful operations on tokens.
 
-| Function                            | Description                                                                    | Example                                 | Example Result        |
-|-------------------------------------|--------------------------------------------------------------------------------|-----------------------------------------|-----------------------|
-| `normalize(raw) -> ident`           | Transforms raw input into a valid Rust identifier.                             | `normalize(&'static str)`               | `static_str`          |
-| `normalize2(ident) -> ident`        | Evaluates the ident and transforms it to a valid identifier.                   | `normalize2(FooBar)`                    | `FooBar`              |
-| `normalize2(str) -> ident`          | Evaluates the string literal and transforms it to a valid identifier.          | `normalize2("&'static str")`            | `static_str`          |
-| `normalize2(int) -> ident`          | Evaluates the integer literal and transforms it to a valid identifier.         | `normalize2(123)`                       | `_123`                |
-| `normalize2(path) -> ident`         | Evaluates the path and transforms it to a valid identifier.                    | `normalize2(Foo::Bar)`                  | `Foo_Bar`             |
-| `normalize2(type) -> ident`         | Evaluates the type and transforms it to a valid identifier.                    | `normalize2(&'static str)`              | `static_str`          |
-| `normalize2(expr) -> ident`         | Evaluates the expression and transforms it to a valid identifier.              | `normalize2(1 + 2)`                     | `_1_2`                |
-| `normalize2(tokens) -> ident`       | Evaluates tokens and transforms them to a valid identifier.                    | `normalize2(raw(Result<u32, String>))`  | `Result_u32_String`   |
-| `concat(ident...) -> ident`         | Concatenates multiple idents into a single identifier.                         | `concat(foo, _, bar)`                   | `foo_bar`             |
-| `concat(ident, tokens...) -> ident` | Concatenates an ident and follow-up tokens arguments into a single identifier. | `concat(prefix, _, 123)`                | `prefix_123`          |
-| `concat(str...) -> str`             | Concatenates multiple strings into a single string.                            | `concat("foo", "_", "bar")`             | `"foo_bar"`           |
-| `concat(int...) -> int`             | Concatenates multiple integers into a single integer.                          | `concat(1, 2, 3)`                       | `123`                 |
-| `concat(tokens...) -> tokens`       | Concatenates multiple tokens arguments into a single tokens value.             | `concat(Result<, raw(u32,), String, >)` | `Result<u32, String>` |
+| Function                             | Description                                                                        | Example                                     | Example Result        |
+|--------------------------------------|------------------------------------------------------------------------------------|---------------------------------------------|-----------------------|
+| `normalize(raw) -> ident`            | Transforms raw input into a valid Rust identifier.                                 | `normalize(&'static str)`                   | `static_str`          |
+| `normalize2(ident) -> ident`         | Evaluates the ident and transforms it to a valid identifier.                       | `normalize2(FooBar)`                        | `FooBar`              |
+| `normalize2(str) -> ident`           | Evaluates the string literal and transforms it to a valid identifier.              | `normalize2("&'static str")`                | `static_str`          |
+| `normalize2(int) -> ident`           | Evaluates the integer literal and transforms it to a valid identifier.             | `normalize2(123)`                           | `_123`                |
+| `normalize2(path) -> ident`          | Evaluates the path and transforms it to a valid identifier.                        | `normalize2(Foo::Bar)`                      | `Foo_Bar`             |
+| `normalize2(type) -> ident`          | Evaluates the type and transforms it to a valid identifier.                        | `normalize2(&'static str)`                  | `static_str`          |
+| `normalize2(expr) -> ident`          | Evaluates the expression and transforms it to a valid identifier.                  | `normalize2(1 + 2)`                         | `_1_2`                |
+| `normalize2(tokens) -> ident`        | Evaluates tokens and transforms them to a valid identifier.                        | `normalize2(raw(Result<u32, String>))`      | `Result_u32_String`   |
+| `unraw(ident) -> ident`              | Strips the `r#` prefix off a raw identifier.                                       | `unraw(r#type)`                             | `type`                |
+| `concat(ident...+) -> ident`         | Concatenates multiple idents into a single identifier.                             | `concat(foo, _, bar)`                       | `foo_bar`             |
+| `concat(ident, tokens...+) -> ident` | Concatenates an ident and follow-ups (strings by content) into an identifier.      | `concat(prefix, "_", 123)`                  | `prefix_123`          |
+| `concat(str...+) -> str`             | Concatenates multiple strings into a single string.                                | `concat("foo", "_", "bar")`                 | `"foo_bar"`           |
+| `concat(str, tokens...+) -> str`     | Concatenates a string and follow-up arguments into a single string.                | `concat("foo_", 1)`                         | `"foo_1"`             |
+| `concat(int...+) -> int`             | Concatenates multiple integers into a single integer.                              | `concat(1, 2, 3)`                           | `123`                 |
+| `concat(tokens...+) -> tokens`       | Concatenates multiple tokens arguments into a single tokens value.                 | `concat(Result<, raw(u32,), String, >)`     | `Result<u32, String>` |
+| `join(str, ident...+) -> ident`      | Joins idents with a separator of identifier characters into a single identifier.   | `join("_", a, b, c)`                        | `a_b_c`               |
+| `join(str, tokens...+) -> str`       | Joins follow-up arguments (strings by content) with the separator into a string.   | `join("::", foo, bar)`                      | `"foo::bar"`          |
+| `replace(str, str, str) -> str`      | Replaces all occurrences of the second argument with the third one.                | `replace("foo_bar", "_", "-")`              | `"foo-bar"`           |
+| `replace(ident, str, str) -> ident`  | Replaces all occurrences within the ident - the result must be a valid identifier. | `replace(AsyncReader, "Async", "Blocking")` | `BlockingReader`      |
+| `strip_prefix(str, str) -> str`      | Strips the prefix off the string - it's returned unchanged if there is no prefix.  | `strip_prefix("tcp_stream", "tcp_")`        | `"stream"`            |
+| `strip_prefix(ident, str) -> ident`  | Strips the prefix off the ident - it's returned unchanged if there is no prefix.   | `strip_prefix(tcp_stream, "tcp_")`          | `stream`              |
+| `strip_suffix(str, str) -> str`      | Strips the suffix off the string - it's returned unchanged if there is no suffix.  | `strip_suffix("stream_tcp", "_tcp")`        | `"stream"`            |
+| `strip_suffix(ident, str) -> ident`  | Strips the suffix off the ident - it's returned unchanged if there is no suffix.   | `strip_suffix(stream_tcp, "_tcp")`          | `stream`              |
+| `hex(int) -> str`                    | Formats the integer in hexadecimal (lower case, without a prefix).                 | `hex(255)`                                  | `"ff"`                |
+| `oct(int) -> str`                    | Formats the integer in octal (without a prefix).                                   | `oct(8)`                                    | `"10"`                |
+| `bin(int) -> str`                    | Formats the integer in binary (without a prefix).                                  | `bin(5)`                                    | `"101"`               |
+| `repeat(str, int) -> str`            | Repeats the string the given number of times.                                      | `repeat("X", 4)`                            | `"XXXX"`              |
+| `repeat(ident, int) -> ident`        | Repeats the ident the given number of times.                                       | `repeat(ab, 3)`                             | `ababab`              |
+| `repeat(tokens, int) -> tokens`      | Concatenates the token stream with itself the given number of times.               | `repeat(raw(u8,), 3)`                       | `u8, u8, u8,`         |
+| `len(str) -> int`                    | Counts the characters of the string.                                               | `len("hello")`                              | `5`                   |
+| `len(ident) -> int`                  | Counts the characters of the ident.                                                | `len(foo)`                                  | `3`                   |
+| `len(tokens) -> int`                 | Counts the characters of the string form of the tokens - ranges are rejected.      | `len(raw(Vec<u8>))`                         | `10`                  |
+| `select(tokens, T, T) -> T`          | Returns the second argument if the first is `true`, the third if it's `false`.     | `select(true, raw(mut), raw())`             | `mut`                 |
+| `starts_with(str, str) -> bool`      | Checks whether the string starts with the other one - `true` or `false`.           | `starts_with("async_read", "async_")`       | `true`                |
+| `starts_with(ident, str) -> bool`    | Checks whether the ident starts with the string - `true` or `false`.               | `starts_with(async_read, "async_")`         | `true`                |
+| `ends_with(str, str) -> bool`        | Checks whether the string ends with the other one - `true` or `false`.             | `ends_with("read_async", "_async")`         | `true`                |
+| `ends_with(ident, str) -> bool`      | Checks whether the ident ends with the string - `true` or `false`.                 | `ends_with(read_async, "_async")`           | `true`                |
+| `contains(str, str) -> bool`         | Checks whether the string contains the other one - `true` or `false`.              | `contains("read_impl", "async")`            | `false`               |
+| `contains(ident, str) -> bool`       | Checks whether the ident contains the string - `true` or `false`.                  | `contains(read_impl, "async")`              | `false`               |
+| `eq(tokens, tokens) -> bool`         | Checks whether the values consist of the same tokens - `true` or `false`.          | `eq(u8, u16)`                               | `false`               |
+| `ne(tokens, tokens) -> bool`         | Checks whether the values consist of different tokens - `true` or `false`.         | `ne(u8, u16)`                               | `true`                |
+| `last_segment(path) -> ident`        | Reads the ident of the last segment of the path without generic arguments.         | `last_segment(std::vec::Vec<u8>)`           | `Vec`                 |
+| `last_segment(type) -> ident`        | Same as `last_segment(path)`, but for a type - only path types are accepted.       | `last_segment(Option<u8>)`                  | `Option`              |
+| `strip_generics(path) -> path`       | Removes generic arguments from the path.                                           | `strip_generics(std::vec::Vec<u8>)`         | `std::vec::Vec`       |
+| `strip_generics(type) -> type`       | Removes generic arguments from the type, including the nested ones.                | `strip_generics(HashMap<K, Vec<V>>)`        | `HashMap`             |
+| `strip_ref(type) -> type`            | Peels references off the type - other types are returned unchanged.                | `strip_ref(&'a mut Foo)`                    | `Foo`                 |
 
 {{ $h3 }} Special purpose
 
 Functions for special use cases.
 
-| Function                | Description                                                                    | Example           | Example Result |
-|-------------------------|--------------------------------------------------------------------------------|-------------------|----------------|
-| `hash(str) -> str`      | Hashes the string deterministically within a single macro invocation.          | `hash("input")`   | `"12345678"`   |
-| `hash(ident) -> ident`  | Hashes the ident deterministically within a single macro invocation.           | `hash(input)`     | `__12345678`   |
-| `hash(tokens) -> ident` | Hashes the tokens argument deterministically within a single macro invocation. | `hash(foo + bar)` | `__87654321`   |
+| Function                                 | Description                                                                               | Example                              | Example Result |
+|------------------------------------------|-------------------------------------------------------------------------------------------|--------------------------------------|----------------|
+| `hash(str) -> str`                       | Hashes the string deterministically within a single macro invocation.                     | `hash("input")`                      | `"12345678"`   |
+| `hash(ident) -> ident`                   | Hashes the ident deterministically within a single macro invocation.                      | `hash(input)`                        | `__12345678`   |
+| `hash(tokens) -> ident`                  | Hashes the tokens argument deterministically within a single macro invocation.            | `hash(foo + bar)`                    | `__87654321`   |
+| `hash(ident, str) -> ident`              | Same as `hash(ident)`, but the hash is preceded by the given prefix.                      | `hash(input, "gen_")`                | `gen_12345678` |
+| `hash(tokens, str) -> ident`             | Same as `hash(tokens)`, but the hash is preceded by the given prefix.                     | `hash(a + b, "gen_")`                | `gen_87654321` |
+| `hash(str, int) -> str`                  | Same as `hash(str)`, but seeded with the given integer instead of the invocation seed.    | `hash("input", 42)`                  | `"12345678"`   |
+| `hash(tokens, int) -> ident`             | Same as `hash(tokens)`, but seeded with the given integer instead of the invocation seed. | `hash(input, 42)`                    | `__12345678`   |
+| `hash(tokens, str, int) -> ident`        | Same as `hash(tokens, str)`, but seeded with the given integer.                           | `hash(input, "gen_", 42)`            | `gen_12345678` |
+| `hash(tokens, str, str) -> ident`        | Same as `hash(tokens, str)`, but seeded with the given string.                            | `hash(input, "gen_", "seed")`        | `gen_12345678` |
+| `short_hash(str, int) -> str`            | Same as `hash(str)`, but hex-encoded and truncated to the given length (1 to 16).         | `short_hash("input", 6)`             | `"1a2b3c"`     |
+| `short_hash(tokens, int) -> ident`       | Same as `hash(tokens)`, but hex-encoded and truncated to the given length (1 to 16).      | `short_hash(input, 6)`               | `__1a2b3c`     |
+| `global_counter(tokens) -> int`          | Returns the next value of a counter shared between macro invocations.                     | `global_counter(msg)`                | `0`            |
+| `env(str) -> str`                        | Reads the environment variable at expansion time - a missing variable is an error.        | `env("PROFILE")`                     | `"debug"`      |
+| `env(str, str) -> str`                   | Same as `env(str)`, but falls back to the second argument if the variable is missing.     | `env("PROFILE", "dev")`              | `"debug"`      |
+
+**Caveats of `global_counter()`**: the counter lives in the memory of the compiler process, so it is shared only by the
+invocations expanded by the same process in the order chosen by the compiler. Incremental compilation may re-expand
+only a part of the invocations and parallel compilation may distribute them between processes - in these cases the
+numbering is not guaranteed to be sequential or stable. That's why it requires an explicit
+`options(allow_global_state)` acknowledgement.
+
+**Caveats of `env()`**: environment variables are read from the compiler process at expansion time and Cargo doesn't
+track them for procedural macros - changing a variable doesn't trigger re-expansion on its own.
+
+{{ $h3 }} List manipulation
+
+Functions for working with lists.
+
+| Function                 | Description                                                     | Example                       | Example Result |
+|--------------------------|-----------------------------------------------------------------|-------------------------------|----------------|
+| `index([T], int) -> T`   | Reads the list item at the given (zero-based) index.            | `index([foo, bar], 1)`        | `bar`          |
+| `len([tokens]) -> int`   | Computes the length of a list.                                  | `len([foo, bar])`             | `2`            |
+| `len([[tokens]]) -> int` | Computes the length of a list of lists.                         | `len([[foo, bar], [baz, 1]])` | `2`            |
 
 {{ $h3 }} Type casting
 
 These functions are useful whenever you need to explicitly cast an arbitrary value to a particular type.
 
-| Function                      | Description                                                                                             | Example                           | Example Result       |
-|-------------------------------|---------------------------------------------------------------------------------------------------------|-----------------------------------|----------------------|
-| `raw(raw) -> tokens`          | Converts raw unevaluated input to a tokens value. Useful for noisy inputs that contain separators, etc. | `raw(Result<u32, Error>)`         | `Result<u32, Error>` |
-| `to_ident(tokens) -> ident`   | Converts the tokens argument to an identifier.                                                          | `to_ident(lower("FOO"))`          | `foo`                |
-| `to_path(tokens) -> path`     | Converts the tokens argument to a path.                                                                 | `to_path(concat(std, ::, vec))`   | `std::vec`           |
-| `to_type(tokens) -> type`     | Converts the tokens argument to a type.                                                                 | `to_type(concat(Vec, <, u32, >))` | `Vec<u32>`           |
-| `to_expr(tokens) -> expr`     | Converts the tokens argument to an expression.                                                          | `to_expr(concat(1, +, 2))`        | `1 + 2`              |
-| `to_str(tokens) -> str`       | Converts the tokens argument to a string.                                                               | `to_str(foo)`                     | `"foo"`              |
-| `to_int(tokens) -> int`       | Converts the tokens argument to an integer.                                                             | `to_int(concat(4, 2))`            | `42`                 |
-| `to_tokens(tokens) -> tokens` | Identity function for tokens - useful for converting any value to tokens.                               | `to_tokens(foo)`                  | `foo`                |
+| Function                             | Description                                                                                             | Example                           | Example Result       |
+|--------------------------------------|---------------------------------------------------------------------------------------------------------|-----------------------------------|----------------------|
+| `raw(raw) -> tokens`                 | Converts raw unevaluated input to a tokens value. Useful for noisy inputs that contain separators, etc. | `raw(Result<u32, Error>)`         | `Result<u32, Error>` |
+| `to_ident(tokens) -> ident`          | Converts the tokens argument to an identifier. A leading digit gets prefixed with `_`.                  | `to_ident(42)`                    | `_42`                |
+| `to_path(tokens) -> path`            | Converts the tokens argument to a path. String literals are parsed by their content.                    | `to_path(concat(std, ::, vec))`   | `std::vec`           |
+| `to_type(tokens) -> type`            | Converts the tokens argument to a type. String literals are parsed by their content.                    | `to_type(concat(Vec, <, u32, >))` | `Vec<u32>`           |
+| `to_expr(tokens) -> expr`            | Converts the tokens argument to an expression. String literals are parsed by their content.             | `to_expr(concat(1, +, 2))`        | `1 + 2`              |
+| `to_str(tokens) -> str`              | Converts the tokens argument to a string.                                                               | `to_str(foo)`                     | `"foo"`              |
+| `to_byte_str(str) -> byte_str`       | Converts the string to a byte string.                                                                   | `to_byte_str("foo")`              | `b"foo"`             |
+| `to_byte_str(str, bool) -> byte_str` | Same as `to_byte_str(str)`, but appends a trailing NUL if the flag is `true`.                           | `to_byte_str("foo", true)`        | `b"foo\0"`           |
+| `to_byte_str(tokens) -> byte_str`    | Converts the tokens argument to a byte string.                                                          | `to_byte_str(raw(b"foo"))`        | `b"foo"`             |
+| `to_int(tokens) -> int`              | Converts the tokens argument to an integer.                                                             | `to_int(concat(4, 2))`            | `42`                 |
+| `to_float(tokens) -> float`          | Converts the tokens argument to a float. An integer without a suffix gets a zero fraction - `3.0`.      | `to_float(raw(1.5))`              | `1.5`                |
+| `to_char(tokens) -> char`            | Converts the tokens argument to a char.                                                                 | `to_char(raw('a'))`               | `'a'`                |
+| `to_bool(tokens) -> bool`            | Converts the tokens argument to a boolean.                                                              | `to_bool(raw(true))`              | `true`               |
+| `to_lifetime(tokens) -> lifetime`    | Converts the tokens argument to a lifetime - an identifier becomes a lifetime.                          | `to_lifetime(concat(a, b))`       | `'ab`                |
+| `to_attr(tokens) -> attr`            | Converts the tokens argument to an attribute.                                                           | `to_attr(raw(#[inline]))`         | `#[inline]`          |
+| `to_tokens(tokens) -> tokens`        | Identity function for tokens - useful for converting any value to tokens.                               | `to_tokens(foo)`                  | `foo`                |
+| `to_list(expr) -> [int]`             | Converts a range with integer literal bounds (`a..b` or `a..=b`) to a list of integers.                 | `to_list(1..=3)`                  | `[1, 2, 3]`          |
@@ -0,0 +1,19 @@
+use compose_idents::compose;
+
+compose!(
+    for ty in [Foo, Bar]
+    options(scoped),
+    {
+        struct Helper;
+
+        #[compose(export)]
+        pub struct ty;
+
+        impl ty {
+            pub const NAME: &'static str = "% ty %";
+        }
+    },
+);
+
+assert_eq!(Foo::NAME, "Foo");
+assert_eq!(Bar::NAME, "Bar");
@@ -0,0 +1,12 @@
+use compose_idents::compose;
+
+compose!(
+    for color in split("red,green,blue", ",")
+    name = upper(to_ident(color)),
+    {
+        const name: &str = color;
+    }
+);
+
+assert_eq!(RED, "red");
+assert_eq!(BLUE, "blue");
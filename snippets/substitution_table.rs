@@ -0,0 +1,21 @@
+use compose_idents::compose_item;
+
+trait IsMax {
+    fn is_max(&self) -> bool;
+}
+
+#[compose_item(
+    substitutions [
+        int_type  max_value;
+        [u8]      [255];
+        [u16]     [65_535];
+    ]
+)]
+impl IsMax for int_type {
+    fn is_max(&self) -> bool {
+        *self == max_value
+    }
+}
+
+assert!(255u8.is_max());
+assert!(!255u16.is_max());
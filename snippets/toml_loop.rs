@@ -0,0 +1,20 @@
+use compose_idents::compose;
+
+#[cfg(feature = "serde")]
+compose!(
+    for (name, ty) in toml(r#"
+        [[rows]]
+        name = "Small"
+        ty = "u8"
+
+        [[rows]]
+        name = "Large"
+        ty = "u64"
+    "#)
+    {
+        type name = ty;
+    }
+);
+
+#[cfg(feature = "serde")]
+assert_eq!(std::mem::size_of::<Large>(), 8);
@@ -0,0 +1,14 @@
+use compose_idents::compose;
+use std::io;
+
+compose!(
+    (ok_ty, err_ty) = (String, io::Error),
+    (a, (b, c)) = (foo, (bar, concat(baz, _, qux))),
+    {
+        fn a() -> Result<ok_ty, err_ty> {
+            Ok(stringify!(b c).to_string())
+        }
+    }
+);
+
+assert_eq!(foo().unwrap(), "bar baz_qux");
@@ -0,0 +1,15 @@
+use compose_idents::compose;
+
+compose!(
+    // `concat(ident...+) -> ident` is called - `foobar`.
+    alias1 = concat(foo, bar),
+    // `concat(tokens...+) -> tokens` is called - `foo bar`.
+    alias2: tokens = concat(foo, bar),
+    {
+        fn alias1() -> &'static str {
+            stringify!(alias2)
+        }
+    }
+);
+
+assert_eq!(foobar(), "foo bar");
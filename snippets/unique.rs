@@ -0,0 +1,20 @@
+use compose_idents::compose;
+
+trait Zero {
+    fn zero() -> Self;
+}
+
+compose!(
+    for t in unique([u8, u16, u8])
+    options(allow_deprecated(duplicate_loop_values)),
+    {
+        impl Zero for t {
+            fn zero() -> Self {
+                0
+            }
+        }
+    }
+);
+
+assert_eq!(u16::zero(), 0);
+assert_eq!(u8::zero(), 0);
@@ -0,0 +1,19 @@
+use compose_idents::compose;
+
+trait Bits {
+    const BITS: u32;
+}
+
+struct Unit;
+
+compose!(
+    for (ty, bits) in [(u8, 8), (Unit, 0), (u16, 16)]
+    when(ne(bits, 0)),
+    {
+        impl Bits for ty {
+            const BITS: u32 = bits;
+        }
+    },
+);
+
+assert_eq!(<u16 as Bits>::BITS, 16);
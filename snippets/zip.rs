@@ -0,0 +1,11 @@
+use compose_idents::compose;
+
+compose!(
+    for ((name, ty), n) in zip([(SMALL, u8), (LARGE, u64)], [1, 2])
+    {
+        const name: ty = n;
+    }
+);
+
+assert_eq!(SMALL, 1u8);
+assert_eq!(LARGE, 2u64);
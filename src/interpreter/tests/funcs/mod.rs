@@ -1,18 +0,0 @@
-//! Tests for calls of built-in functions.
-mod camel_case;
-mod concat;
-mod hash;
-mod lower;
-mod normalize;
-mod normalize2;
-mod pascal_case;
-mod raw;
-mod snake_case;
-mod to_expr;
-mod to_ident;
-mod to_int;
-mod to_path;
-mod to_str;
-mod to_tokens;
-mod to_type;
-mod upper;
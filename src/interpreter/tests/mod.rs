@@ -1,7 +0,0 @@
-mod aliases;
-mod calls;
-mod funcs;
-mod loops;
-mod non_terminals;
-mod string_formatting;
-mod types;
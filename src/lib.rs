@@ -1,105 +1,48 @@
 #![allow(clippy::needless_doctest_main)]
 #![doc = include_str!("../snippets/docs.md")]
 
-mod ast;
-mod core;
-mod error;
-mod eval;
-mod expand;
-mod funcs;
-mod interpreter;
-mod parse;
-mod resolve;
-mod substitution;
-mod util;
-
-use crate::ast::{ComposeItemSpec, RawAST};
-use crate::core::Environment;
-use crate::interpreter::Interpreter;
-use crate::util::unique_id::next_unique_id;
+use compose_idents_core::{
+    expand, expand_compose, expand_compose_const, expand_compose_expr, expand_compose_static,
+    expand_compose_test, Session,
+};
 use proc_macro::TokenStream;
-use quote::quote;
-use std::rc::Rc;
-use syn::parse_macro_input;
-use util::deprecation::DeprecationService;
 
 enum InvocationType {
     Func(TokenStream),
     Attr(TokenStream, TokenStream),
+    Test(TokenStream),
+    Const(TokenStream),
+    Static(TokenStream),
+    Expr(TokenStream),
 }
 
 fn compose_core(prefix: &'static str, invocation: InvocationType) -> TokenStream {
-    let deprecation_service = DeprecationService::new_rc(prefix);
-    DeprecationService::maybe_set_global(deprecation_service);
-    let deprecation_service_scope = DeprecationService::scoped();
-
-    let environment = Rc::new(Environment::new_initialized(next_unique_id()));
-    Environment::maybe_set_global(environment.clone());
-
-    let interpreter = Interpreter::new(environment, deprecation_service_scope);
-
-    let args = match invocation {
-        InvocationType::Func(input) => parse_macro_input!(input as RawAST),
-        InvocationType::Attr(attr, item) => {
-            // Parse attribute prefix tailored for the attribute macro form
-            let spec: ComposeItemSpec = match syn::parse(attr) {
-                Ok(v) => v,
-                Err(err) => return TokenStream::from(err.into_compile_error()),
-            };
-
-            // Treat the decorated item as the block
-            let item: proc_macro2::TokenStream = item.into();
-            let block_tokens: proc_macro2::TokenStream = quote!({ #item });
-            let block: syn::Block = match syn::parse2(block_tokens) {
-                Ok(v) => v,
-                Err(err) => return TokenStream::from(err.into_compile_error()),
-            };
-
-            RawAST::from_compose_item_spec(&spec, block)
-        }
+    let input = match &invocation {
+        InvocationType::Func(input)
+        | InvocationType::Test(input)
+        | InvocationType::Const(input)
+        | InvocationType::Static(input)
+        | InvocationType::Expr(input) => input.to_string(),
+        InvocationType::Attr(attr, item) => format!("{} {}", attr, item),
     };
-    match interpreter.execute(args) {
+    let result = Session::from_input(prefix, input.as_str()).and_then(|session| match invocation {
+        InvocationType::Func(input) => expand_compose(&session, input.into()),
+        InvocationType::Attr(attr, item) => expand(
+            &session.with_attr_path(syn::parse_quote!(::compose_idents::compose_item)),
+            attr.into(),
+            item.into(),
+        ),
+        InvocationType::Test(input) => expand_compose_test(&session, input.into()),
+        InvocationType::Const(input) => expand_compose_const(&session, input.into()),
+        InvocationType::Static(input) => expand_compose_static(&session, input.into()),
+        InvocationType::Expr(input) => expand_compose_expr(&session, input.into()),
+    });
+    match result {
         Ok(ts) => ts.into(),
-        Err(err) => {
-            let syn_err: syn::Error = err.into();
-            TokenStream::from(syn_err.into_compile_error())
-        }
+        Err(err) => TokenStream::from(syn::Error::from(err).into_compile_error()),
     }
 }
 
-/// Compose identifiers from the provided parts and replace their aliases in the decorated item.
-///
-/// This attribute macro is equivalent to [`compose!`], but treats the annotated item as the
-/// code block.
-///
-/// # Example
-///
-/// ```rust
-/// use compose_idents::compose_item;
-///
-/// #[compose_item(
-///     // For-in loops could be used to generate multiple variations of the code.
-///     for (suffix, (interjection, noun)) in [
-///         (BAR, (Hello, "world")),
-///         (baz, ("Hallo", "welt")),
-///     ]
-///
-///     // A simple alias definition.
-///     my_fn = concat(foo, _, 1, _, lower(suffix)),
-///     // Many functions are overloaded support different input argument types.
-///     greeting = concat(to_str(interjection), ", ", noun, "!"),
-/// )]
-/// // String placeholders `% my_alias %` are expanded inside literals and doc attributes.
-/// #[doc = "Greets: % greeting %"]
-/// fn my_fn() -> &'static str { greeting }
-///
-/// assert_eq!(foo_1_bar(), "Hello, world!");
-/// assert_eq!(foo_1_baz(), "Hallo, welt!");
-/// ```
-///
-/// # Reference
-///
-#[doc = include_str!("../snippets/reference_h2.md")]
 #[proc_macro_attribute]
 pub fn compose_item(attr: TokenStream, item: TokenStream) -> TokenStream {
     compose_core("compose_item!: ", InvocationType::Attr(attr, item))
@@ -145,6 +88,98 @@ pub fn compose(input: TokenStream) -> TokenStream {
     compose_core("compose!: ", InvocationType::Func(input))
 }
 
+/// Generate parametrized test functions from a table of cases.
+///
+/// Accepts the same input as [`compose!`], but wraps each block into a `#[test]` function named
+/// `test_<name>`, where `<name>` is the normalized first element of the loop variable. The other
+/// elements are available as aliases within the block.
+///
+/// # Example
+///
+/// ```rust
+/// use compose_idents::compose_test;
+///
+/// compose_test!(
+///     for (name, input, expected) in [
+///         (empty, "", 0),
+///         (single_word, "foo", 3),
+///         ("two words", "foo bar", 7),
+///     ]
+///     {
+///         assert_eq!(input.len(), expected);
+///     },
+/// );
+/// ```
+///
+/// The example above generates `test_empty`, `test_single_word` and `test_two_words` functions.
+#[proc_macro]
+pub fn compose_test(input: TokenStream) -> TokenStream {
+    compose_core("compose_test!: ", InvocationType::Test(input))
+}
+
+/// Declare a constant named by an alias expression.
+///
+/// Accepts `NAME_EXPR: Type = value;` followed by the usual loops and alias definitions. The name
+/// position accepts any alias expression, the aliases are also substituted in the type and the
+/// value.
+///
+/// # Example
+///
+/// ```rust
+/// use compose_idents::compose_const;
+///
+/// compose_const!(pub concat(upper(prefix), _LIMIT): u32 = 42; prefix = max);
+///
+/// assert_eq!(MAX_LIMIT, 42);
+/// ```
+#[proc_macro]
+pub fn compose_const(input: TokenStream) -> TokenStream {
+    compose_core("compose_const!: ", InvocationType::Const(input))
+}
+
+/// Declare a static named by an alias expression.
+///
+/// Same as [`compose_const!`], but declares a `static` item.
+///
+/// # Example
+///
+/// ```rust
+/// use compose_idents::compose_static;
+///
+/// compose_static!(concat(upper(name), _GREETING): &str = "Hello, % name %!"; for name in [alice, bob]);
+///
+/// assert_eq!(ALICE_GREETING, "Hello, alice!");
+/// assert_eq!(BOB_GREETING, "Hello, bob!");
+/// ```
+#[proc_macro]
+pub fn compose_static(input: TokenStream) -> TokenStream {
+    compose_core("compose_static!: ", InvocationType::Static(input))
+}
+
+/// Compose identifiers within an expression.
+///
+/// Accepts the same input as [`compose!`], but the block must consist of a single expression and
+/// loops aren't supported - so the macro could be used in expression position, e.g. on the
+/// right-hand side of a `let` or inside an argument list.
+///
+/// # Example
+///
+/// ```rust
+/// use compose_idents::compose_expr;
+///
+/// fn get_foo() -> u32 {
+///     42
+/// }
+///
+/// let value = compose_expr!(getter = concat(get_, name), name = foo, { getter() });
+///
+/// assert_eq!(value, 42);
+/// ```
+#[proc_macro]
+pub fn compose_expr(input: TokenStream) -> TokenStream {
+    compose_core("compose_expr!: ", InvocationType::Expr(input))
+}
+
 /// Compose identifiers from the provided parts and replace their aliases in the code block.
 ///
 /// In addition to replacing identifier aliases it replaces tokens like `% alias %` in string
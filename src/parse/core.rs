@@ -1,4 +0,0 @@
-//! Shared parsing helpers and constants.
-
-/// Error message used when the user mixes separators of different kinds ("," and ";").
-pub const MIXING_SEP_ERROR: &str = r#"Mixing "," and ";" as separators is not allowed"#;
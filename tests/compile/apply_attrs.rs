@@ -0,0 +1,20 @@
+//! Attributes of `apply_attrs(...)` are stamped onto every generated item.
+use compose_idents::compose;
+
+compose!(
+    for ty in [Foo, Bar]
+    apply_attrs(#[doc = "Generated % ty %."], #[derive(Debug, Default)]),
+    wrapper = concat(ty, Wrapper),
+    {
+        pub struct ty;
+
+        // The derive isn't duplicated - otherwise the impls would conflict.
+        #[derive(Debug, Default)]
+        pub struct wrapper(pub ty);
+    },
+);
+
+fn main() {
+    let _ = format!("{:?}", FooWrapper::default());
+    let _ = format!("{:?}", Bar);
+}
@@ -0,0 +1,49 @@
+//! Outer attributes of the invocation are attached to every generated item.
+#![deny(non_snake_case, dead_code)]
+use compose_idents::compose;
+
+compose!(
+    #[allow(non_snake_case)]
+    for name in [GetFoo, GetBar]
+    value = lower(name),
+    {
+        fn name() -> &'static str {
+            stringify!(value)
+        }
+    },
+);
+
+compose!(
+    #[cfg(any())]
+    name = concat(missing_, fn),
+    {
+        fn name() -> u32 {
+            1
+        }
+
+        const MISSING: u32 = name();
+    },
+);
+
+compose!(
+    #[allow(dead_code)]
+    name = concat(unused_, fn),
+    {
+        fn name() {}
+
+        struct Unused;
+    },
+);
+
+fn missing_fn() -> u32 {
+    2
+}
+
+const MISSING: u32 = 3;
+
+fn main() {
+    assert_eq!(GetFoo(), "getfoo");
+    assert_eq!(GetBar(), "getbar");
+    assert_eq!(missing_fn(), 2);
+    assert_eq!(MISSING, 3);
+}
@@ -19,7 +19,17 @@ compose!(
     },
 );
 
+compose!(
+    // Private aliases are available to other aliases, but aren't substituted into the code block.
+    let base = snake_case(MyType),
+    getter = concat(get_, base),
+    {
+        fn getter(base: u32) -> u32 { base }
+    },
+);
+
 fn main() {
     assert_eq!(foo_1_bar(), "Hello, world!");
     assert_eq!(foo_1_baz(), "Hallo, welt!");
+    assert_eq!(get_my_type(1), 1);
 }
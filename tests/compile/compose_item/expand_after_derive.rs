@@ -0,0 +1,8 @@
+//! The code generated by a derive is never processed by a deferred `#[compose_item]`.
+use compose_idents::compose_item;
+
+#[compose_item(options(expand = after), name = Foo)]
+#[derive(Debug)]
+struct name;
+
+fn main() {}
@@ -0,0 +1,54 @@
+//! `options(expand = ...)` controls whether the other attribute macros of the item see the code
+//! with the aliases substituted (`before`, the default) or as it was written (`after`).
+use compose_idents::compose_item;
+use dummy_attribute::seen_name;
+
+mod default {
+    use super::*;
+
+    #[compose_item(name = concat(get_, foo))]
+    #[seen_name]
+    pub fn name() -> u32 {
+        1
+    }
+}
+
+mod before {
+    use super::*;
+
+    #[compose_item(options(expand = before), name = concat(get_, foo))]
+    #[seen_name]
+    pub fn name() -> u32 {
+        2
+    }
+}
+
+mod after {
+    use super::*;
+
+    #[compose_item(options(expand = after), name = concat(get_, foo))]
+    #[inline]
+    #[seen_name]
+    pub fn name() -> u32 {
+        3
+    }
+}
+
+mod after_without_attributes {
+    use super::*;
+
+    #[compose_item(options(expand = after), name = concat(get_, foo))]
+    pub fn name() -> u32 {
+        4
+    }
+}
+
+fn main() {
+    assert_eq!(default::get_foo(), 1);
+    assert_eq!(default::SEEN_NAME, "get_foo");
+    assert_eq!(before::get_foo(), 2);
+    assert_eq!(before::SEEN_NAME, "get_foo");
+    assert_eq!(after::get_foo(), 3);
+    assert_eq!(after::SEEN_NAME, "name");
+    assert_eq!(after_without_attributes::get_foo(), 4);
+}
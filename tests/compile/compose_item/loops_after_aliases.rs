@@ -0,0 +1,15 @@
+//! Loops could follow the alias definitions in `#[compose_item]` too.
+use compose_idents::compose_item;
+
+#[compose_item(
+    fn_name = concat(get_, ty),
+    for (ty, value) in [(u8, 1), (u16, 2)]
+)]
+fn fn_name() -> ty {
+    value
+}
+
+fn main() {
+    assert_eq!(get_u8(), 1);
+    assert_eq!(get_u16(), 2);
+}
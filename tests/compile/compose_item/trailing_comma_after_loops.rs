@@ -1,9 +1,13 @@
-//! A trailing comma after loops (with no alias spec) should be rejected for `#[compose_item]`.
+//! A trailing comma after loops (with no alias spec) should be allowed for `#[compose_item]`.
 use compose_idents::compose_item;
 
 #[compose_item(
     for name in [foo, bar],
 )]
-fn name() {}
+fn name() -> u32 {
+    1
+}
 
-fn main() {}
+fn main() {
+    assert_eq!(foo() + bar(), 2);
+}
@@ -0,0 +1,77 @@
+//! The subset of the syntax shared by `compose_simple!` and `compose!` expands the same way with
+//! both macros - migration between them is a change of the macro name.
+
+/// Expands the same input with both macros into `simple` and `proc_macro` modules.
+macro_rules! with_both {
+    ($($input:tt)*) => {
+        pub mod simple {
+            compose_idents_simple::compose_simple!($($input)*);
+        }
+        pub mod proc_macro {
+            compose_idents::compose!($($input)*);
+        }
+    };
+}
+
+mod function {
+    with_both!(getter = get_value, {
+        pub fn getter() -> u32 {
+            42
+        }
+    });
+}
+
+mod type_and_impl {
+    with_both!(Alias = Point, {
+        #[derive(Debug, PartialEq)]
+        pub struct Alias(pub u32, pub u32);
+
+        impl Alias {
+            pub fn new(x: u32) -> Alias {
+                Alias(x, x)
+            }
+        }
+    });
+}
+
+mod nested_groups {
+    with_both!(value = answer, {
+        pub fn value() -> [u32; 3] {
+            let closure = |x: u32| -> u32 { (x + 1) * 2 };
+            [closure(value::X), { value::X }, (value::X)]
+        }
+
+        pub mod value {
+            pub const X: u32 = 20;
+        }
+    });
+}
+
+mod trailing_comma {
+    with_both!(getter = get_value, {
+        pub fn getter() -> &'static str {
+            "getter"
+        }
+    },);
+}
+
+fn main() {
+    assert_eq!(function::simple::get_value(), 42);
+    assert_eq!(function::proc_macro::get_value(), 42);
+
+    assert_eq!(
+        type_and_impl::simple::Point::new(1),
+        type_and_impl::simple::Point(1, 1)
+    );
+    assert_eq!(
+        type_and_impl::proc_macro::Point::new(1),
+        type_and_impl::proc_macro::Point(1, 1)
+    );
+
+    assert_eq!(nested_groups::simple::answer(), [42, 20, 20]);
+    assert_eq!(nested_groups::proc_macro::answer(), [42, 20, 20]);
+
+    // Aliases aren't substituted in string literals without `% alias %` placeholders.
+    assert_eq!(trailing_comma::simple::get_value(), "getter");
+    assert_eq!(trailing_comma::proc_macro::get_value(), "getter");
+}
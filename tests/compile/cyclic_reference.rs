@@ -0,0 +1,10 @@
+//! The error message should point to the alias that closes the cycle of references.
+use compose_idents::compose;
+
+compose!(getter = concat(get_, name), name = lower(getter), {
+    fn getter() -> u32 {
+        42
+    }
+});
+
+fn main() {}
@@ -0,0 +1,25 @@
+//! `options(deny_deprecated)` should turn usage of deprecated syntax into a compile-time error.
+use compose_idents::{compose, compose_item};
+
+compose!(
+    options(deny_deprecated);
+    my_fn_1 = concat(foo, _, baz);
+    my_fn_2 = concat(spam, _, eggs); {
+    fn my_fn_1() -> u32 {
+        1
+    }
+
+    fn my_fn_2() -> u32 {
+        2
+    }
+});
+
+#[compose_item(
+    options(deny_deprecated);
+    my_fn = concat(foo, _, bar);
+)]
+fn my_fn() -> u32 {
+    3
+}
+
+fn main() {}
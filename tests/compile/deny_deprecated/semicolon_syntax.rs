@@ -0,0 +1,23 @@
+//! Under `deny-deprecated` feature semicolons as alias definition separators should result in a
+//! compile-time error pointing at the first semicolon.
+use compose_idents::compose;
+
+compose!(my_fn = concat(foo, _, bar); {
+    fn my_fn() -> u32 {
+        1
+    }
+});
+
+compose!(
+    my_fn_1 = concat(foo, _, baz);
+    my_fn_2 = concat(spam, _, eggs); {
+    fn my_fn_1() -> u32 {
+        2
+    }
+
+    fn my_fn_2() -> u32 {
+        3
+    }
+});
+
+fn main() {}
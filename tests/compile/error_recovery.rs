@@ -0,0 +1,13 @@
+//! Problems in independent items of the spec should all be reported at once.
+use compose_idents::compose;
+
+compose!(
+    first: idnet = foo,
+    second = bar,
+    third: strr = baz,
+    {
+        fn first() {}
+    }
+);
+
+fn main() {}
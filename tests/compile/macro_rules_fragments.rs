@@ -0,0 +1,82 @@
+//! Alias values coming from `macro_rules!` fragments must behave the same way as literal input.
+use compose_idents::compose;
+
+pub struct Marker;
+
+pub mod values {
+    pub const ANSWER: u32 = 42;
+}
+
+macro_rules! ident_fragment {
+    ($name:ident) => {
+        compose!(getter = concat(get_, $name), name_str = to_str($name), {
+            fn getter() -> &'static str {
+                name_str
+            }
+        });
+    };
+}
+
+macro_rules! type_fragment {
+    ($name:ident, $t:ty) => {
+        compose!(alias = $t, getter = concat(default_, $name), {
+            fn getter() -> alias {
+                <alias>::default()
+            }
+        });
+    };
+}
+
+macro_rules! expr_fragment {
+    ($name:ident, $e:expr) => {
+        compose!(value = $e, getter = concat(eval_, $name), {
+            fn getter() -> u32 {
+                value
+            }
+        });
+    };
+}
+
+macro_rules! crate_path {
+    ($name:ident) => {
+        compose!(
+            marker = $crate::Marker,
+            answer = $crate::values::ANSWER,
+            getter = concat(crate_, $name),
+            {
+                fn getter() -> (marker, u32) {
+                    ($crate::Marker, answer)
+                }
+            }
+        );
+    };
+}
+
+// Metavariables of a `macro_rules!` defined within the block are not substituted.
+compose!(value = to_str(unused), {
+    macro_rules! get_value {
+        ($value:ident) => {
+            identity(stringify!($value))
+        };
+    }
+});
+
+fn identity(value: &'static str) -> &'static str {
+    value
+}
+
+ident_fragment!(foo);
+type_fragment!(vec, Vec<u8>);
+type_fragment!(tuple, (u8, String));
+expr_fragment!(sum, 1 + 2);
+crate_path!(values);
+
+fn main() {
+    assert_eq!(get_foo(), "foo");
+    assert_eq!(default_vec(), Vec::<u8>::new());
+    assert_eq!(default_tuple(), (0, String::new()));
+    assert_eq!(eval_sum(), 3);
+    assert_eq!(get_value!(foo), "foo");
+    let (Marker, answer) = crate_values();
+    assert_eq!(answer, 42);
+}
@@ -0,0 +1,26 @@
+//! The same spec could be applied to multiple blocks - e.g. to put tests into a separate module.
+use compose_idents::compose;
+
+compose!(
+    for (ty, max) in [(u8, 255), (u16, 65_535)]
+    getter = concat(max_, ty),
+    {
+        fn getter() -> ty {
+            max
+        }
+    },
+    {
+        #[cfg(test)]
+        mod tests {
+            #[test]
+            fn getter() {
+                assert_eq!(super::getter(), max);
+            }
+        }
+    },
+);
+
+fn main() {
+    assert_eq!(max_u8(), 255);
+    assert_eq!(max_u16(), 65_535);
+}
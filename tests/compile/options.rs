@@ -0,0 +1,27 @@
+//! `options(...)` directive should be recognized by both `compose!` and `#[compose_item]`.
+use compose_idents::{compose, compose_item};
+
+compose!(
+    for suffix in [a, b]
+
+    options(strict, max_combinations = 2),
+    fn_name = concat(foo, _, suffix),
+    {
+        fn fn_name() -> u32 {
+            1
+        }
+    },
+);
+
+#[compose_item(
+    options(strict = true, max_combinations = 1),
+    fn_name = concat(bar, _, baz),
+)]
+fn fn_name() -> u32 {
+    2
+}
+
+fn main() {
+    assert_eq!(foo_a() + foo_b(), 2);
+    assert_eq!(bar_baz(), 2);
+}
@@ -0,0 +1,34 @@
+//! Ranges cast to lists are usable as loop sources and with list functions.
+use compose_idents::compose;
+
+compose!(
+    for w in widths
+    widths = 1..=4 as list,
+    count = len(widths),
+    name = concat(Bytes, w),
+    {
+        #[allow(dead_code)]
+        struct name([u8; w]);
+
+        impl name {
+            const COUNT: usize = count;
+        }
+    },
+);
+
+compose!(
+    offsets = -1..=1 as list,
+    first = index(offsets, 0),
+    {
+        const OFFSETS: [i8; 3] = offsets;
+        const FIRST: i8 = first;
+    },
+);
+
+fn main() {
+    assert_eq!(std::mem::size_of::<Bytes1>(), 1);
+    assert_eq!(std::mem::size_of::<Bytes4>(), 4);
+    assert_eq!(Bytes2::COUNT, 4);
+    assert_eq!(OFFSETS, [-1, 0, 1]);
+    assert_eq!(FIRST, -1);
+}
@@ -0,0 +1,49 @@
+//! Helper items of scoped invocations don't collide with each other and with the user code.
+use compose_idents::compose;
+
+struct Helper;
+
+compose!(
+    for (ty, value) in [(Foo, 1), (Bar, 2)]
+    options(scoped),
+    getter = concat(get_, lower(ty)),
+    {
+        struct Helper(u32);
+
+        const fn helper() -> Helper {
+            Helper(value)
+        }
+
+        const _: () = assert!(helper().0 == value);
+
+        #[compose(export)]
+        pub struct ty;
+
+        impl ty {
+            pub fn getter(&self) -> u32 {
+                value
+            }
+        }
+    },
+);
+
+// Impls of the scoped types stay in the scope along with them.
+compose!(
+    options(scoped),
+    name = Scoped,
+    {
+        struct name;
+
+        impl name {
+            const fn f() {}
+        }
+
+        const _: () = name::f();
+    },
+);
+
+fn main() {
+    let _ = Helper;
+    assert_eq!(Foo.get_foo(), 1);
+    assert_eq!(Bar.get_bar(), 2);
+}
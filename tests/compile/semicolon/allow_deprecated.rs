@@ -0,0 +1,24 @@
+//! Deprecation warnings suppressed with `options(allow_deprecated(...))` shouldn't be emitted.
+#![deny(warnings)]
+use compose_idents::{compose, compose_item};
+
+compose!(
+    options(allow_deprecated(semicolon_separator));
+    my_fn = concat(foo, _, bar); {
+    fn my_fn() -> u32 {
+        1
+    }
+});
+
+#[compose_item(
+    options(allow_deprecated(semicolon_separator));
+    my_fn = concat(foo, _, baz);
+)]
+fn my_fn() -> u32 {
+    2
+}
+
+fn main() {
+    assert_eq!(foo_bar(), 1);
+    assert_eq!(foo_baz(), 2);
+}
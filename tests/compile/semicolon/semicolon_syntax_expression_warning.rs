@@ -0,0 +1,17 @@
+//! Deprecation warnings should be emitted even if the code block contains no items that could
+//! carry the `#[deprecated]` attribute.
+#![deny(warnings)]
+use compose_idents::compose;
+
+fn foo_bar() -> u32 {
+    1
+}
+
+fn main() {
+    let value = compose!(my_fn = concat(foo, _, bar); { my_fn() });
+    assert_eq!(value, 1);
+
+    compose!(my_fn = concat(foo, _, bar); {
+        assert_eq!(my_fn(), 1);
+    });
+}
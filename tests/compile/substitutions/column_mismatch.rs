@@ -0,0 +1,18 @@
+//! A row of a substitution table with a wrong number of values should result in a compile-time
+//! error pointing at the row.
+use compose_idents::compose;
+
+compose!(
+    substitutions [
+        name  ty;
+        [foo] [u8];
+        [bar];
+    ]
+    {
+        fn name() -> ty {
+            0
+        }
+    },
+);
+
+fn main() {}
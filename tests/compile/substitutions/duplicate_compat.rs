@@ -0,0 +1,44 @@
+//! Substitution tables should accept invocations ported from the `duplicate` crate as is.
+use compose_idents::{compose, compose_item};
+
+trait IsMax {
+    fn is_max(&self) -> bool;
+}
+
+// Ported from `#[duplicate_item(...)]`.
+#[compose_item(
+    substitutions [
+        int_type  max_value;
+        [u8]      [255];
+        [u16]     [65_535];
+        [u32]     [4_294_967_295];
+    ]
+)]
+impl IsMax for int_type {
+    fn is_max(&self) -> bool {
+        *self == max_value
+    }
+}
+
+compose!(
+    substitutions [
+        name        ty;
+        [as_bytes]  [Vec<u8>];
+        [as_words]  [Vec<u16>];
+    ]
+
+    fn_name = concat(to_, name),
+    {
+        fn fn_name() -> ty {
+            Default::default()
+        }
+    },
+);
+
+fn main() {
+    assert!(255u8.is_max());
+    assert!(!1u16.is_max());
+    assert!(4_294_967_295u32.is_max());
+    assert_eq!(to_as_bytes(), Vec::<u8>::new());
+    assert_eq!(to_as_words(), Vec::<u16>::new());
+}
@@ -0,0 +1,41 @@
+//! Non-ASCII identifiers are accepted as aliases, values and arguments of the functions.
+use compose_idents::compose;
+
+compose!(
+    größe = concat(maximale_, snake_case(WertGröße)),
+    {
+        fn größe() -> &'static str {
+            "% größe %"
+        }
+    },
+);
+
+compose!(
+    for λέξη in [Όνομα, Μέγεθος]
+    μέθοδος = concat(λάβε_, lower(λέξη)),
+    {
+        fn μέθοδος() -> &'static str {
+            "% μέθοδος %"
+        }
+    },
+);
+
+compose!(
+    名前 = concat(取得_, 値),
+    型 = pascal_case(名前),
+    {
+        struct 型;
+
+        fn 名前() -> &'static str {
+            "%名前%"
+        }
+    },
+);
+
+fn main() {
+    assert_eq!(maximale_wert_größe(), "maximale_wert_größe");
+    assert_eq!(λάβε_όνομα(), "λάβε_όνομα");
+    assert_eq!(λάβε_μέγεθος(), "λάβε_μέγεθος");
+    assert_eq!(取得_値(), "取得_値");
+    let _ = 取得値;
+}
@@ -0,0 +1,34 @@
+//! A dummy attribute macro used by the tests of `compose-idents`.
+//!
+//! `#[seen_name]` keeps the annotated item as is and records its name as the macro has seen it in
+//! the `SEEN_NAME` constant of the same visibility - it shows whether the aliases were substituted before the macro ran:
+//!
+//! ```rust,ignore
+//! #[seen_name]
+//! fn name() {}
+//!
+//! assert_eq!(SEEN_NAME, "name");
+//! ```
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::Item;
+
+#[proc_macro_attribute]
+pub fn seen_name(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item as Item);
+    let (vis, name) = match &item {
+        Item::Fn(item) => (&item.vis, item.sig.ident.to_string()),
+        Item::Struct(item) => (&item.vis, item.ident.to_string()),
+        Item::Enum(item) => (&item.vis, item.ident.to_string()),
+        _ => {
+            return syn::Error::new_spanned(item, "#[seen_name] expects a fn, a struct or an enum")
+                .into_compile_error()
+                .into()
+        }
+    };
+    quote!(
+        #item
+        #vis const SEEN_NAME: &str = #name;
+    )
+    .into()
+}
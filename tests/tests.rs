@@ -5,15 +5,45 @@ fn general_tests() {
     t.pass("tests/compile/trailing_comma.rs");
     t.pass("tests/compile/nested_compose.rs");
     t.pass("tests/compile/nested_type_token_compose.rs");
+    t.pass("tests/compile/macro_rules_fragments.rs");
+    t.pass("tests/compile/options.rs");
+    t.pass("tests/compile/multiple_blocks.rs");
+    t.pass("tests/compile/attributes.rs");
+    t.pass("tests/compile/range_lists.rs");
+    t.pass("tests/compile/unicode.rs");
+    t.pass("tests/compile/scoped.rs");
+    t.pass("tests/compile/apply_attrs.rs");
 }
 
 /// Tests semicolon backwards-compatibility support.
 #[test]
 fn semicolon_tests() {
     let t = trybuild::TestCases::new();
-    t.pass("tests/compile/semicolon/semicolon_syntax.rs");
+    #[cfg(not(feature = "deny-deprecated"))]
+    {
+        t.pass("tests/compile/semicolon/semicolon_syntax.rs");
+        t.compile_fail("tests/compile/semicolon/semicolon_syntax_warning.rs");
+        t.compile_fail("tests/compile/semicolon/semicolon_syntax_expression_warning.rs");
+    }
     t.compile_fail("tests/compile/semicolon/mixed_separators.rs");
-    t.compile_fail("tests/compile/semicolon/semicolon_syntax_warning.rs");
+    t.pass("tests/compile/semicolon/allow_deprecated.rs");
+}
+
+/// Tests rejection of deprecated syntax.
+#[test]
+fn deny_deprecated_tests() {
+    let t = trybuild::TestCases::new();
+    #[cfg(feature = "deny-deprecated")]
+    t.compile_fail("tests/compile/deny_deprecated/semicolon_syntax.rs");
+    t.compile_fail("tests/compile/deny_deprecated/options.rs");
+}
+
+/// Tests substitution tables.
+#[test]
+fn substitution_tables() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/compile/substitutions/duplicate_compat.rs");
+    t.compile_fail("tests/compile/substitutions/column_mismatch.rs");
 }
 
 /// Tests error reporting.
@@ -21,6 +51,8 @@ fn semicolon_tests() {
 fn error_reporting() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/compile/error_reporting.rs");
+    t.compile_fail("tests/compile/error_recovery.rs");
+    t.compile_fail("tests/compile/cyclic_reference.rs");
 }
 
 /// Tests for [`compose_idents::compose!`] macro.
@@ -30,13 +62,24 @@ fn compose() {
     t.pass("tests/compile/compose.rs");
 }
 
+/// Tests for the shared syntax of [`compose_idents_simple::compose_simple!`] and
+/// [`compose_idents::compose!`].
+#[test]
+fn compose_simple() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/compile/compose_simple.rs");
+}
+
 /// Tests for [`compose_idents::compose_item!`] macro.
 #[test]
 fn compose_item() {
     let t = trybuild::TestCases::new();
     t.pass("tests/compile/compose_item/basic.rs");
-    t.compile_fail("tests/compile/compose_item/trailing_comma_after_loops.rs");
+    t.pass("tests/compile/compose_item/trailing_comma_after_loops.rs");
     t.pass("tests/compile/compose_item/trailing_comma_after_aliases.rs");
+    t.pass("tests/compile/compose_item/loops_after_aliases.rs");
+    t.pass("tests/compile/compose_item/expand_order.rs");
+    t.compile_fail("tests/compile/compose_item/expand_after_derive.rs");
 }
 
 /// Tests for [`compose_idents::compose_idents!`] macro.
@@ -46,3 +89,40 @@ fn compose_idents() {
     t.pass("tests/compile/concat_idents/basic.rs");
     t.compile_fail("tests/compile/concat_idents/warning.rs");
 }
+
+// Tests generated by [`compose_idents::compose_test!`] macro.
+compose_idents::compose_test!(for (name, input, expected) in [
+    (empty, "", 0),
+    (single_word, "foo", 1),
+    ("two words", "foo bar", 2),
+] {
+    let words = input.split_whitespace().count();
+    assert_eq!(words, expected);
+},);
+
+compose_idents::compose_const!(concat(upper(prefix), _LIMIT): u32 = 42; prefix = max);
+compose_idents::compose_static!(concat(upper(name), _NAME): &str = "% name %"; for name in [foo, bar]);
+
+/// Tests for [`compose_idents::compose_const!`] and [`compose_idents::compose_static!`] macros.
+#[test]
+fn compose_const() {
+    assert_eq!(MAX_LIMIT, 42);
+    assert_eq!(FOO_NAME, "foo");
+    assert_eq!(BAR_NAME, "bar");
+}
+
+fn get_foo() -> u32 {
+    42
+}
+
+/// Tests for [`compose_idents::compose_expr!`] macro.
+#[test]
+fn compose_expr() {
+    let value =
+        compose_idents::compose_expr!(getter = concat(get_, name), name = foo, { getter() });
+    assert_eq!(value, 42);
+    assert_eq!(
+        compose_idents::compose_expr!(name = upper(max), { u32::name }),
+        u32::MAX
+    );
+}